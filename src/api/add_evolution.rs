@@ -0,0 +1,71 @@
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    domain,
+    repositories::{evolution::EvolutionRepository, pokemon::Repository},
+};
+
+use super::problem::Problem;
+
+#[derive(Serialize)]
+struct Response {
+    from: u16,
+    to: u16,
+    trigger: String,
+    level: Option<u16>,
+    item: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Request {
+    to: u16,
+    trigger: String,
+    level: Option<u16>,
+    item: Option<String>,
+}
+
+pub fn serve(
+    repo: Arc<dyn Repository>,
+    evolutions: Arc<dyn EvolutionRepository>,
+    number: u16,
+    req: &rouille::Request,
+) -> Result<rouille::Response, Problem> {
+    let req = match rouille::input::json_input::<Request>(req) {
+        Ok(req) => domain::add_evolution::Request {
+            from: number,
+            to: req.to,
+            trigger: req.trigger,
+            level: req.level,
+            item: req.item,
+        },
+        Err(e) => return Err(Problem::malformed_body(e)),
+    };
+    let to = req.to;
+    match domain::add_evolution::execute(repo, evolutions, req) {
+        Ok(domain::add_evolution::Response {
+            from,
+            to,
+            trigger,
+            level,
+            item,
+        }) => Ok(rouille::Response::json(&Response {
+            from,
+            to,
+            trigger,
+            level,
+            item,
+        })),
+        Err(domain::add_evolution::Error::BadRequest(errors)) => Err(Problem::invalid(errors)),
+        Err(domain::add_evolution::Error::NotFound) => Err(Problem::not_found(format!(
+            "pokemon {} does not exist",
+            number
+        ))),
+        Err(domain::add_evolution::Error::Conflict) => Err(Problem::conflict(format!(
+            "pokemon {} already evolves from another pokemon",
+            to
+        ))),
+        Err(domain::add_evolution::Error::Unknown(e)) => Err(Problem::internal(e)),
+    }
+}
@@ -0,0 +1,45 @@
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{domain, repositories::ability::AbilityRepository};
+
+use super::problem::Problem;
+
+#[derive(Serialize)]
+struct Response {
+    name: String,
+    description: String,
+}
+
+#[derive(Deserialize)]
+struct Request {
+    name: String,
+    #[serde(default)]
+    description: String,
+}
+
+pub fn serve(
+    repo: Arc<dyn AbilityRepository>,
+    req: &rouille::Request,
+) -> Result<rouille::Response, Problem> {
+    let req = match rouille::input::json_input::<Request>(req) {
+        Ok(req) => domain::create_ability::Request {
+            name: req.name,
+            description: req.description,
+        },
+        Err(e) => return Err(Problem::malformed_body(e)),
+    };
+    let name = req.name.clone();
+    match domain::create_ability::execute(repo, req) {
+        Ok(domain::create_ability::Response { name, description }) => {
+            Ok(rouille::Response::json(&Response { name, description }))
+        }
+        Err(domain::create_ability::Error::BadRequest(errors)) => Err(Problem::invalid(errors)),
+        Err(domain::create_ability::Error::Conflict) => Err(Problem::conflict(format!(
+            "ability {:?} already exists",
+            name
+        ))),
+        Err(domain::create_ability::Error::Unknown(e)) => Err(Problem::internal(e)),
+    }
+}
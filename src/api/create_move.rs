@@ -0,0 +1,67 @@
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{domain, repositories::moves::MoveRepository};
+
+use super::problem::Problem;
+
+#[derive(Serialize)]
+struct Response {
+    name: String,
+    r#type: String,
+    category: String,
+    power: Option<u16>,
+    accuracy: Option<u16>,
+    pp: u16,
+}
+
+#[derive(Deserialize)]
+struct Request {
+    name: String,
+    r#type: String,
+    category: String,
+    power: Option<u16>,
+    accuracy: Option<u16>,
+    pp: u16,
+}
+
+pub fn serve(
+    repo: Arc<dyn MoveRepository>,
+    req: &rouille::Request,
+) -> Result<rouille::Response, Problem> {
+    let req = match rouille::input::json_input::<Request>(req) {
+        Ok(req) => domain::create_move::Request {
+            name: req.name,
+            r#type: req.r#type,
+            category: req.category,
+            power: req.power,
+            accuracy: req.accuracy,
+            pp: req.pp,
+        },
+        Err(e) => return Err(Problem::malformed_body(e)),
+    };
+    let name = req.name.clone();
+    match domain::create_move::execute(repo, req) {
+        Ok(domain::create_move::Response {
+            name,
+            r#type,
+            category,
+            power,
+            accuracy,
+            pp,
+        }) => Ok(rouille::Response::json(&Response {
+            name,
+            r#type,
+            category,
+            power,
+            accuracy,
+            pp,
+        })),
+        Err(domain::create_move::Error::BadRequest(errors)) => Err(Problem::invalid(errors)),
+        Err(domain::create_move::Error::Conflict) => {
+            Err(Problem::conflict(format!("move {:?} already exists", name)))
+        }
+        Err(domain::create_move::Error::Unknown(e)) => Err(Problem::internal(e)),
+    }
+}
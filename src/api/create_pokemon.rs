@@ -1,52 +1,68 @@
 use std::sync::Arc;
 
-use rouille::{self};
 use serde::{Deserialize, Serialize};
 
 use crate::{domain, repositories::pokemon::Repository};
 
-use super::Status;
+use super::{
+    problem::{self, Problem},
+    stats,
+};
 
 #[derive(Serialize)]
 struct Response {
     number: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    form: Option<String>,
     name: String,
     types: Vec<String>,
+    stats: Option<stats::StatsResponse>,
 }
 
 #[derive(Serialize, Deserialize)]
 struct Request {
     number: u16,
+    form: Option<String>,
     name: String,
     types: Vec<String>,
+    stats: Option<stats::Stats>,
 }
 
-pub fn serve(repo: Arc<dyn Repository>, req: &rouille::Request) -> rouille::Response {
+pub fn serve(
+    repo: Arc<dyn Repository>,
+    req: &rouille::Request,
+) -> Result<rouille::Response, Problem> {
     let req: domain::create_pokemon::Request = match rouille::input::json_input::<Request>(req) {
         Ok(req) => domain::create_pokemon::Request {
             number: req.number,
+            form: req.form,
             name: req.name,
             types: req.types,
+            stats: req.stats.map(domain::Stats::from),
         },
-        _ => return rouille::Response::from(Status::BadRequest),
+        Err(e) => return Err(Problem::malformed_body(e)),
     };
-    // rouille::Response::from(Status::InternalServerError)
+    let (number, form) = (req.number, req.form.clone());
     match domain::create_pokemon::execute(repo, req) {
         Ok(domain::create_pokemon::Response {
             number,
+            form,
             name,
             types,
-        }) => rouille::Response::json(&Response {
+            stats,
+            total,
+        }) => Ok(rouille::Response::json(&Response {
             number,
+            form,
             name,
             types,
-        }),
-        Err(domain::create_pokemon::Error::BadRequest) => {
-            rouille::Response::from(Status::BadRequest)
-        }
-        Err(domain::create_pokemon::Error::Conflict) => rouille::Response::from(Status::Conflict),
-        Err(domain::create_pokemon::Error::Unknown) => {
-            rouille::Response::from(Status::InternalServerError)
-        }
+            stats: stats::response(stats, total),
+        })),
+        Err(domain::create_pokemon::Error::BadRequest(errors)) => Err(Problem::invalid(errors)),
+        Err(domain::create_pokemon::Error::Conflict) => Err(Problem::conflict(format!(
+            "{} already exists",
+            problem::pokemon(number, form.as_deref())
+        ))),
+        Err(domain::create_pokemon::Error::Unknown(e)) => Err(Problem::internal(e)),
     }
 }
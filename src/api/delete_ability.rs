@@ -0,0 +1,18 @@
+use std::sync::Arc;
+
+use crate::{domain, repositories::ability::AbilityRepository};
+
+use super::problem::Problem;
+
+pub fn serve(repo: Arc<dyn AbilityRepository>, name: String) -> Result<rouille::Response, Problem> {
+    let req = domain::delete_ability::Request { name: name.clone() };
+    match domain::delete_ability::execute(repo, req) {
+        Ok(()) => Ok(rouille::Response::empty_204().with_status_code(200)),
+        Err(domain::delete_ability::Error::BadRequest(errors)) => Err(Problem::invalid(errors)),
+        Err(domain::delete_ability::Error::NotFound) => Err(Problem::not_found(format!(
+            "ability {:?} does not exist",
+            name
+        ))),
+        Err(domain::delete_ability::Error::Unknown(e)) => Err(Problem::internal(e)),
+    }
+}
@@ -0,0 +1,22 @@
+use std::sync::Arc;
+
+use crate::{domain, repositories::evolution::EvolutionRepository};
+
+use super::problem::Problem;
+
+pub fn serve(
+    evolutions: Arc<dyn EvolutionRepository>,
+    from: u16,
+    to: u16,
+) -> Result<rouille::Response, Problem> {
+    let req = domain::delete_evolution::Request { from, to };
+    match domain::delete_evolution::execute(evolutions, req) {
+        Ok(()) => Ok(rouille::Response::empty_204().with_status_code(200)),
+        Err(domain::delete_evolution::Error::BadRequest(errors)) => Err(Problem::invalid(errors)),
+        Err(domain::delete_evolution::Error::NotFound) => Err(Problem::not_found(format!(
+            "pokemon {} does not evolve into {}",
+            from, to
+        ))),
+        Err(domain::delete_evolution::Error::Unknown(e)) => Err(Problem::internal(e)),
+    }
+}
@@ -1,15 +1,25 @@
-use crate::api::Status;
+use crate::api::problem::{self, Problem};
 use crate::domain::delete_pokemon;
 use crate::repositories::pokemon::Repository;
-use rouille;
 use std::sync::Arc;
 
-pub fn serve(repo: Arc<dyn Repository>, number: u16) -> rouille::Response {
-    let req = delete_pokemon::Request { number };
+pub fn serve(
+    repo: Arc<dyn Repository>,
+    number: u16,
+    req: &rouille::Request,
+) -> Result<rouille::Response, Problem> {
+    let form = req.get_param("form");
+    let req = delete_pokemon::Request {
+        number,
+        form: form.clone(),
+    };
     match delete_pokemon::execute(repo, req) {
-        Ok(()) => rouille::Response::from(Status::Ok),
-        Err(delete_pokemon::Error::BadRequest) => rouille::Response::from(Status::BadRequest),
-        Err(delete_pokemon::Error::NotFound) => rouille::Response::from(Status::NotFound),
-        Err(delete_pokemon::Error::Unknown) => rouille::Response::from(Status::InternalServerError),
+        Ok(()) => Ok(rouille::Response::empty_204().with_status_code(200)),
+        Err(delete_pokemon::Error::BadRequest(errors)) => Err(Problem::invalid(errors)),
+        Err(delete_pokemon::Error::NotFound) => Err(Problem::not_found(format!(
+            "{} does not exist",
+            problem::pokemon(number, form.as_deref())
+        ))),
+        Err(delete_pokemon::Error::Unknown(e)) => Err(Problem::internal(e)),
     }
 }
@@ -0,0 +1,28 @@
+use std::sync::Arc;
+
+use serde::Serialize;
+
+use crate::{domain, repositories::ability::AbilityRepository};
+
+use super::problem::Problem;
+
+#[derive(Serialize)]
+struct Response {
+    name: String,
+    description: String,
+}
+
+pub fn serve(repo: Arc<dyn AbilityRepository>, name: String) -> Result<rouille::Response, Problem> {
+    let req = domain::fetch_ability::Request { name: name.clone() };
+    match domain::fetch_ability::execute(repo, req) {
+        Ok(domain::fetch_ability::Response { name, description }) => {
+            Ok(rouille::Response::json(&Response { name, description }))
+        }
+        Err(domain::fetch_ability::Error::BadRequest(errors)) => Err(Problem::invalid(errors)),
+        Err(domain::fetch_ability::Error::NotFound) => Err(Problem::not_found(format!(
+            "ability {:?} does not exist",
+            name
+        ))),
+        Err(domain::fetch_ability::Error::Unknown(e)) => Err(Problem::internal(e)),
+    }
+}
@@ -0,0 +1,45 @@
+use std::sync::Arc;
+
+use serde::Serialize;
+
+use crate::{
+    domain,
+    repositories::{ability::AbilityRepository, pokemon::Repository},
+};
+
+use super::problem::Problem;
+
+#[derive(Serialize)]
+struct Response {
+    number: u16,
+    name: String,
+    hidden: bool,
+}
+
+pub fn serve(
+    repo: Arc<dyn Repository>,
+    abilities: Arc<dyn AbilityRepository>,
+    name: String,
+) -> Result<rouille::Response, Problem> {
+    let req = domain::fetch_ability_holders::Request { name: name.clone() };
+    match domain::fetch_ability_holders::execute(repo, abilities, req) {
+        Ok(res) => Ok(rouille::Response::json(
+            &res.pokemons
+                .into_iter()
+                .map(|p| Response {
+                    number: p.number,
+                    name: p.name,
+                    hidden: p.hidden,
+                })
+                .collect::<Vec<Response>>(),
+        )),
+        Err(domain::fetch_ability_holders::Error::BadRequest(errors)) => {
+            Err(Problem::invalid(errors))
+        }
+        Err(domain::fetch_ability_holders::Error::NotFound) => Err(Problem::not_found(format!(
+            "ability {:?} does not exist",
+            name
+        ))),
+        Err(domain::fetch_ability_holders::Error::Unknown(e)) => Err(Problem::internal(e)),
+    }
+}
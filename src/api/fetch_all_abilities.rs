@@ -0,0 +1,27 @@
+use std::sync::Arc;
+
+use serde::Serialize;
+
+use crate::{domain, repositories::ability::AbilityRepository};
+
+use super::problem::Problem;
+
+#[derive(Serialize)]
+struct Response {
+    name: String,
+    description: String,
+}
+
+pub fn serve(repo: Arc<dyn AbilityRepository>) -> Result<rouille::Response, Problem> {
+    match domain::fetch_all_abilities::execute(repo) {
+        Ok(res) => Ok(rouille::Response::json(
+            &res.into_iter()
+                .map(|a| Response {
+                    name: a.name,
+                    description: a.description,
+                })
+                .collect::<Vec<Response>>(),
+        )),
+        Err(domain::fetch_all_abilities::Error::Unknown(e)) => Err(Problem::internal(e)),
+    }
+}
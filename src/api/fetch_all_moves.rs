@@ -0,0 +1,35 @@
+use std::sync::Arc;
+
+use serde::Serialize;
+
+use crate::{domain, repositories::moves::MoveRepository};
+
+use super::problem::Problem;
+
+#[derive(Serialize)]
+struct Response {
+    name: String,
+    r#type: String,
+    category: String,
+    power: Option<u16>,
+    accuracy: Option<u16>,
+    pp: u16,
+}
+
+pub fn serve(repo: Arc<dyn MoveRepository>) -> Result<rouille::Response, Problem> {
+    match domain::fetch_all_moves::execute(repo) {
+        Ok(res) => Ok(rouille::Response::json(
+            &res.into_iter()
+                .map(|m| Response {
+                    name: m.name,
+                    r#type: m.r#type,
+                    category: m.category,
+                    power: m.power,
+                    accuracy: m.accuracy,
+                    pp: m.pp,
+                })
+                .collect::<Vec<Response>>(),
+        )),
+        Err(domain::fetch_all_moves::Error::Unknown(e)) => Err(Problem::internal(e)),
+    }
+}
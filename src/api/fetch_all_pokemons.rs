@@ -4,28 +4,74 @@ use serde::Serialize;
 
 use crate::{domain, repositories::pokemon::Repository};
 
-use super::Status;
+use super::{problem::Problem, stats};
 
 #[derive(Serialize)]
 struct Response {
     number: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    form: Option<String>,
     name: String,
     types: Vec<String>,
+    stats: Option<stats::StatsResponse>,
 }
 
-pub fn serve(repo: Arc<dyn Repository>) -> rouille::Response {
-    match domain::fetch_all_pokemons::execute(repo) {
-        Ok(res) => rouille::Response::json(
-            &res.into_iter()
-                .map(|p| Response {
-                    number: p.number,
-                    name: p.name,
-                    types: p.types,
-                })
-                .collect::<Vec<Response>>(),
-        ),
-        Err(domain::fetch_all_pokemons::Error::Unknown) => {
-            rouille::Response::from(Status::InternalServerError)
+const QUERY_PARAMS: [&str; 6] = ["limit", "sort", "direction", "type", "name_prefix", "forms"];
+
+pub fn serve(
+    repo: Arc<dyn Repository>,
+    req: &rouille::Request,
+) -> Result<rouille::Response, Problem> {
+    let limit = match req.get_param("limit").map(|limit| limit.parse::<u16>()) {
+        Some(Ok(limit)) => Some(limit),
+        Some(Err(e)) => return Err(Problem::invalid_parameter("limit", e.to_string())),
+        None => None,
+    };
+    let query = domain::fetch_all_pokemons::Request {
+        cursor: req.get_param("cursor"),
+        limit,
+        sort: req.get_param("sort"),
+        direction: req.get_param("direction"),
+        r#type: req.get_param("type"),
+        name_prefix: req.get_param("name_prefix"),
+        forms: req.get_param("forms"),
+    };
+    match domain::fetch_all_pokemons::execute(repo, query) {
+        Ok(domain::fetch_all_pokemons::Page {
+            pokemons,
+            next_cursor,
+        }) => {
+            let res = rouille::Response::json(
+                &pokemons
+                    .into_iter()
+                    .map(|p| Response {
+                        number: p.number,
+                        form: p.form,
+                        name: p.name,
+                        types: p.types,
+                        stats: stats::response(p.stats, p.total),
+                    })
+                    .collect::<Vec<Response>>(),
+            );
+            Ok(match next_cursor {
+                Some(cursor) => res
+                    .with_additional_header("Link", next_link(req, &cursor))
+                    .with_additional_header("X-Next-Cursor", cursor),
+                None => res,
+            })
+        }
+        Err(domain::fetch_all_pokemons::Error::BadRequest(errors)) => Err(Problem::invalid(errors)),
+        Err(domain::fetch_all_pokemons::Error::Unknown(e)) => Err(Problem::internal(e)),
+    }
+}
+
+fn next_link(req: &rouille::Request, cursor: &str) -> String {
+    let mut query = form_urlencoded::Serializer::new(String::new());
+    for param in QUERY_PARAMS {
+        if let Some(value) = req.get_param(param) {
+            query.append_pair(param, &value);
         }
     }
+    query.append_pair("cursor", cursor);
+    format!("<{}?{}>; rel=\"next\"", req.url(), query.finish())
 }
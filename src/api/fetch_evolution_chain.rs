@@ -0,0 +1,66 @@
+use std::sync::Arc;
+
+use serde::Serialize;
+
+use crate::{
+    domain::{self, fetch_evolution_chain::Stage},
+    repositories::{evolution::EvolutionRepository, pokemon::Repository},
+};
+
+use super::problem::{self, Problem};
+
+#[derive(Serialize)]
+struct Response {
+    number: u16,
+    name: String,
+    trigger: Option<Trigger>,
+    evolutions: Vec<Response>,
+}
+
+#[derive(Serialize)]
+struct Trigger {
+    name: String,
+    level: Option<u16>,
+    item: Option<String>,
+}
+
+impl From<Stage> for Response {
+    fn from(stage: Stage) -> Self {
+        Self {
+            number: stage.number,
+            name: stage.name,
+            trigger: stage.trigger.map(|t| Trigger {
+                name: t.name,
+                level: t.level,
+                item: t.item,
+            }),
+            evolutions: stage.evolutions.into_iter().map(Response::from).collect(),
+        }
+    }
+}
+
+/// Every form shares the evolution chain of its species, `?form=` only has to
+/// name an existing one.
+pub fn serve(
+    repo: Arc<dyn Repository>,
+    evolutions: Arc<dyn EvolutionRepository>,
+    number: u16,
+    req: &rouille::Request,
+) -> Result<rouille::Response, Problem> {
+    let form = req.get_param("form");
+    let req = domain::fetch_evolution_chain::Request {
+        number,
+        form: form.clone(),
+    };
+    match domain::fetch_evolution_chain::execute(repo, evolutions, req) {
+        Ok(res) => Ok(rouille::Response::json(&Response::from(res.base))),
+        Err(domain::fetch_evolution_chain::Error::BadRequest(errors)) => {
+            Err(Problem::invalid(errors))
+        }
+        Err(domain::fetch_evolution_chain::Error::NotFound) => Err(Problem::not_found(format!(
+            "{} does not exist",
+            problem::pokemon(number, form.as_deref())
+        ))),
+        Err(domain::fetch_evolution_chain::Error::Unknown(e)) => Err(Problem::internal(e)),
+    }
+}
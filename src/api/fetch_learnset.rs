@@ -0,0 +1,60 @@
+use std::sync::Arc;
+
+use serde::Serialize;
+
+use crate::{
+    domain,
+    repositories::{moves::MoveRepository, pokemon::Repository},
+};
+
+use super::problem::{self, Problem};
+
+#[derive(Serialize)]
+struct Response {
+    name: String,
+    method: String,
+    level: Option<u16>,
+    r#type: String,
+    category: String,
+    power: Option<u16>,
+    accuracy: Option<u16>,
+    pp: u16,
+}
+
+/// Every form shares the learnset of its species, `?form=` only has to
+/// name an existing one.
+pub fn serve(
+    repo: Arc<dyn Repository>,
+    moves: Arc<dyn MoveRepository>,
+    number: u16,
+    req: &rouille::Request,
+) -> Result<rouille::Response, Problem> {
+    let form = req.get_param("form");
+    let req = domain::fetch_learnset::Request {
+        number,
+        form: form.clone(),
+    };
+    match domain::fetch_learnset::execute(repo, moves, req) {
+        Ok(res) => Ok(rouille::Response::json(
+            &res.moves
+                .into_iter()
+                .map(|m| Response {
+                    name: m.name,
+                    method: m.method,
+                    level: m.level,
+                    r#type: m.r#type,
+                    category: m.category,
+                    power: m.power,
+                    accuracy: m.accuracy,
+                    pp: m.pp,
+                })
+                .collect::<Vec<Response>>(),
+        )),
+        Err(domain::fetch_learnset::Error::BadRequest(errors)) => Err(Problem::invalid(errors)),
+        Err(domain::fetch_learnset::Error::NotFound) => Err(Problem::not_found(format!(
+            "{} does not exist",
+            problem::pokemon(number, form.as_deref())
+        ))),
+        Err(domain::fetch_learnset::Error::Unknown(e)) => Err(Problem::internal(e)),
+    }
+}
@@ -0,0 +1,44 @@
+use std::sync::Arc;
+
+use serde::Serialize;
+
+use crate::{domain, repositories::moves::MoveRepository};
+
+use super::problem::Problem;
+
+#[derive(Serialize)]
+struct Response {
+    name: String,
+    r#type: String,
+    category: String,
+    power: Option<u16>,
+    accuracy: Option<u16>,
+    pp: u16,
+}
+
+pub fn serve(repo: Arc<dyn MoveRepository>, name: String) -> Result<rouille::Response, Problem> {
+    let req = domain::fetch_move::Request { name: name.clone() };
+    match domain::fetch_move::execute(repo, req) {
+        Ok(domain::fetch_move::Response {
+            name,
+            r#type,
+            category,
+            power,
+            accuracy,
+            pp,
+        }) => Ok(rouille::Response::json(&Response {
+            name,
+            r#type,
+            category,
+            power,
+            accuracy,
+            pp,
+        })),
+        Err(domain::fetch_move::Error::BadRequest(errors)) => Err(Problem::invalid(errors)),
+        Err(domain::fetch_move::Error::NotFound) => Err(Problem::not_found(format!(
+            "move {:?} does not exist",
+            name
+        ))),
+        Err(domain::fetch_move::Error::Unknown(e)) => Err(Problem::internal(e)),
+    }
+}
@@ -0,0 +1,47 @@
+use std::sync::Arc;
+
+use serde::Serialize;
+
+use crate::{
+    domain,
+    repositories::{moves::MoveRepository, pokemon::Repository},
+};
+
+use super::problem::Problem;
+
+#[derive(Serialize)]
+struct Response {
+    number: u16,
+    name: String,
+    method: String,
+    level: Option<u16>,
+}
+
+pub fn serve(
+    repo: Arc<dyn Repository>,
+    moves: Arc<dyn MoveRepository>,
+    name: String,
+) -> Result<rouille::Response, Problem> {
+    let req = domain::fetch_move_learners::Request { name: name.clone() };
+    match domain::fetch_move_learners::execute(repo, moves, req) {
+        Ok(res) => Ok(rouille::Response::json(
+            &res.pokemons
+                .into_iter()
+                .map(|p| Response {
+                    number: p.number,
+                    name: p.name,
+                    method: p.method,
+                    level: p.level,
+                })
+                .collect::<Vec<Response>>(),
+        )),
+        Err(domain::fetch_move_learners::Error::BadRequest(errors)) => {
+            Err(Problem::invalid(errors))
+        }
+        Err(domain::fetch_move_learners::Error::NotFound) => Err(Problem::not_found(format!(
+            "move {:?} does not exist",
+            name
+        ))),
+        Err(domain::fetch_move_learners::Error::Unknown(e)) => Err(Problem::internal(e)),
+    }
+}
@@ -4,38 +4,51 @@ use serde::Serialize;
 
 use crate::{domain, repositories::pokemon::Repository};
 
-use super::Status;
-
-#[derive(Serialize)]
-struct Request {
-    number: u16,
-}
+use super::{
+    problem::{self, Problem},
+    stats,
+};
 
 #[derive(Serialize)]
 struct Response {
     number: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    form: Option<String>,
     name: String,
     types: Vec<String>,
+    stats: Option<stats::StatsResponse>,
 }
 
-pub fn serve(repo: Arc<dyn Repository>, number: u16) -> rouille::Response {
-    let req = domain::fetch_pokemon::Request { number };
+pub fn serve(
+    repo: Arc<dyn Repository>,
+    number: u16,
+    req: &rouille::Request,
+) -> Result<rouille::Response, Problem> {
+    let form = req.get_param("form");
+    let req = domain::fetch_pokemon::Request {
+        number,
+        form: form.clone(),
+    };
     match domain::fetch_pokemon::execute(repo, req) {
         Ok(domain::fetch_pokemon::Response {
             number,
+            form,
             name,
             types,
-        }) => rouille::Response::json(&Response {
+            stats,
+            total,
+        }) => Ok(rouille::Response::json(&Response {
             number,
+            form,
             name,
             types,
-        }),
-        Err(domain::fetch_pokemon::Error::BadRequest) => {
-            rouille::Response::from(Status::BadRequest)
-        }
-        Err(domain::fetch_pokemon::Error::NotFound) => rouille::Response::from(Status::NotFound),
-        Err(domain::fetch_pokemon::Error::Unknown) => {
-            rouille::Response::from(Status::InternalServerError)
-        }
+            stats: stats::response(stats, total),
+        })),
+        Err(domain::fetch_pokemon::Error::BadRequest(errors)) => Err(Problem::invalid(errors)),
+        Err(domain::fetch_pokemon::Error::NotFound) => Err(Problem::not_found(format!(
+            "{} does not exist",
+            problem::pokemon(number, form.as_deref())
+        ))),
+        Err(domain::fetch_pokemon::Error::Unknown(e)) => Err(Problem::internal(e)),
     }
 }
@@ -0,0 +1,52 @@
+use std::sync::Arc;
+
+use serde::Serialize;
+
+use crate::{
+    domain,
+    repositories::{ability::AbilityRepository, pokemon::Repository},
+};
+
+use super::problem::{self, Problem};
+
+#[derive(Serialize)]
+struct Response {
+    name: String,
+    description: String,
+    hidden: bool,
+}
+
+/// Every form shares the abilities of its species, `?form=` only has to
+/// name an existing one.
+pub fn serve(
+    repo: Arc<dyn Repository>,
+    abilities: Arc<dyn AbilityRepository>,
+    number: u16,
+    req: &rouille::Request,
+) -> Result<rouille::Response, Problem> {
+    let form = req.get_param("form");
+    let req = domain::fetch_pokemon_abilities::Request {
+        number,
+        form: form.clone(),
+    };
+    match domain::fetch_pokemon_abilities::execute(repo, abilities, req) {
+        Ok(res) => Ok(rouille::Response::json(
+            &res.abilities
+                .into_iter()
+                .map(|a| Response {
+                    name: a.name,
+                    description: a.description,
+                    hidden: a.hidden,
+                })
+                .collect::<Vec<Response>>(),
+        )),
+        Err(domain::fetch_pokemon_abilities::Error::BadRequest(errors)) => {
+            Err(Problem::invalid(errors))
+        }
+        Err(domain::fetch_pokemon_abilities::Error::NotFound) => Err(Problem::not_found(format!(
+            "{} does not exist",
+            problem::pokemon(number, form.as_deref())
+        ))),
+        Err(domain::fetch_pokemon_abilities::Error::Unknown(e)) => Err(Problem::internal(e)),
+    }
+}
@@ -0,0 +1,116 @@
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{domain, repositories::pokemon::Repository};
+
+use super::{
+    problem::{self, Problem},
+    stats::Stats,
+};
+
+#[derive(Deserialize)]
+struct Row {
+    number: u16,
+    form: Option<String>,
+    name: String,
+    types: Vec<String>,
+    stats: Option<Stats>,
+}
+
+#[derive(Serialize)]
+struct Response {
+    created: Vec<PokemonKey>,
+    overwritten: Vec<PokemonKey>,
+    skipped: Vec<PokemonKey>,
+}
+
+#[derive(Serialize)]
+struct PokemonKey {
+    number: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    form: Option<String>,
+}
+
+impl From<domain::import_pokemons::PokemonKey> for PokemonKey {
+    fn from(key: domain::import_pokemons::PokemonKey) -> Self {
+        Self {
+            number: key.number,
+            form: key.form,
+        }
+    }
+}
+
+fn keys(keys: Vec<domain::import_pokemons::PokemonKey>) -> Vec<PokemonKey> {
+    keys.into_iter().map(PokemonKey::from).collect()
+}
+
+pub fn serve(
+    repo: Arc<dyn Repository>,
+    req: &rouille::Request,
+) -> Result<rouille::Response, Problem> {
+    let policy = match req.get_param("on_conflict").as_deref() {
+        None | Some("fail") => domain::import_pokemons::Policy::Fail,
+        Some("skip") => domain::import_pokemons::Policy::Skip,
+        Some("overwrite") => domain::import_pokemons::Policy::Overwrite,
+        Some(policy) => {
+            return Err(Problem::invalid_parameter(
+                "on_conflict",
+                format!(
+                    "must be \"fail\", \"skip\" or \"overwrite\", got {:?}",
+                    policy
+                ),
+            ))
+        }
+    };
+    let rows = match rouille::input::json_input::<Vec<Row>>(req) {
+        Ok(rows) => rows
+            .into_iter()
+            .map(|row| domain::import_pokemons::Row {
+                number: row.number,
+                form: row.form,
+                name: row.name,
+                types: row.types,
+                stats: row.stats.map(domain::Stats::from),
+            })
+            .collect(),
+        Err(e) => return Err(Problem::malformed_body(e)),
+    };
+    match domain::import_pokemons::execute(repo, domain::import_pokemons::Request { rows, policy })
+    {
+        Ok(res) => Ok(rouille::Response::json(&Response {
+            created: keys(res.created),
+            overwritten: keys(res.overwritten),
+            skipped: keys(res.skipped),
+        })),
+        Err(domain::import_pokemons::Error::BadRequest(errors)) => Err(rows_problem(400, errors)),
+        Err(domain::import_pokemons::Error::Conflict(errors)) => Err(rows_problem(409, errors)),
+        Err(domain::import_pokemons::Error::Unknown(e)) => Err(Problem::internal(e)),
+    }
+}
+
+/// Lists the rejected rows, the invalid fields being located by their index
+/// in the request array.
+fn rows_problem(status: u16, errors: Vec<domain::import_pokemons::RowError>) -> Problem {
+    let mut fields = vec![];
+    let rows = errors
+        .into_iter()
+        .map(|error| problem::Row {
+            row: error.row,
+            number: error.number,
+            reasons: error
+                .reasons
+                .into_iter()
+                .map(|reason| match reason {
+                    domain::import_pokemons::Reason::Invalid(e) => {
+                        fields.push(problem::field(&e, &format!("[{}].", error.row - 1)));
+                        format!("invalid_{}", e.field())
+                    }
+                    domain::import_pokemons::Reason::Duplicate => String::from("duplicate"),
+                    domain::import_pokemons::Reason::Conflict => String::from("conflict"),
+                })
+                .collect(),
+        })
+        .collect();
+    Problem::rows(status, rows, fields)
+}
@@ -1,59 +1,1074 @@
+mod add_evolution;
+mod create_ability;
+mod create_move;
 mod create_pokemon;
+mod delete_ability;
+mod delete_evolution;
 mod delete_pokemon;
+mod fetch_ability;
+mod fetch_ability_holders;
+mod fetch_all_abilities;
+mod fetch_all_moves;
 mod fetch_all_pokemons;
+mod fetch_evolution_chain;
+mod fetch_learnset;
+mod fetch_move;
+mod fetch_move_learners;
 mod fetch_pokemon;
+mod fetch_pokemon_abilities;
 mod health;
+mod import_pokemons;
+mod problem;
+mod set_learnset;
+mod set_pokemon_abilities;
+mod stats;
+mod type_matchups;
+mod update_ability;
+mod update_pokemon;
 
-use std::sync::Arc;
-
-use rouille::router;
-
-use crate::repositories::pokemon::Repository;
-
-pub fn serve(url: &str, repo: Arc<dyn Repository>) {
-    rouille::start_server(url, move |req| {
-        router!(req,
-            (GET) (/health) => {
-                health::serve()
-            },
-            (POST) (/) => {
-                create_pokemon::serve(repo.clone(), req)
-            },
-            (GET) (/) => {
-                fetch_all_pokemons::serve(repo.clone())
-            },
-            (DELETE) (/{number: u16}) => {
-                delete_pokemon::serve(repo.clone(), number)
-            },
-            _ => {
-                rouille::Response::from(Status::NotFound)
-            }
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread,
+    time::Duration,
+};
+
+use rouille::{router, Server};
+use signal_hook::consts::{SIGINT, SIGTERM};
+
+use crate::{
+    log::{self, Level},
+    repositories::{
+        ability::AbilityRepository, evolution::EvolutionRepository, moves::MoveRepository,
+        pokemon::Repository,
+    },
+};
+
+use problem::Problem;
+
+/// How often the server checks whether it was asked to stop.
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Where and how the API listens.
+pub struct ServerConfig {
+    pub bind: String,
+    pub port: u16,
+    /// Threads handling requests, 8 per CPU when not set.
+    pub workers: Option<usize>,
+}
+
+impl ServerConfig {
+    fn pool_size(&self) -> Result<usize, String> {
+        match self.workers {
+            Some(0) => Err(String::from("the server needs at least one worker")),
+            Some(workers) => Ok(workers),
+            None => Ok(8 * thread::available_parallelism().map_or(1, |n| n.get())),
+        }
+    }
+}
+
+/// Serves the API until SIGINT or SIGTERM, then lets the in-flight requests
+/// finish. A second SIGINT exits right away.
+pub fn serve(
+    config: &ServerConfig,
+    repo: Arc<dyn Repository>,
+    abilities: Arc<dyn AbilityRepository>,
+    moves: Arc<dyn MoveRepository>,
+    evolutions: Arc<dyn EvolutionRepository>,
+) -> Result<(), String> {
+    let pool_size = config.pool_size()?;
+    let server = match Server::new((config.bind.as_str(), config.port), move |req| {
+        handle(
+            repo.clone(),
+            abilities.clone(),
+            moves.clone(),
+            evolutions.clone(),
+            req,
         )
-    });
+    }) {
+        Ok(server) => server.pool_size(pool_size),
+        Err(e) => {
+            return Err(format!(
+                "cannot listen on {}:{}: {}",
+                config.bind, config.port, e
+            ))
+        }
+    };
+    let stop = Arc::new(AtomicBool::new(false));
+    for signal in [SIGINT, SIGTERM] {
+        let registered = signal_hook::flag::register_conditional_shutdown(signal, 1, stop.clone())
+            .and_then(|_| signal_hook::flag::register(signal, stop.clone()));
+        if let Err(e) = registered {
+            return Err(format!("cannot listen for signal {}: {}", signal, e));
+        }
+    }
+    if log::enabled(Level::Info) {
+        eprintln!(
+            "Listening on http://{} with {} workers",
+            server.server_addr(),
+            pool_size
+        );
+    }
+    run(&server, &stop);
+    Ok(())
+}
+
+fn run<F>(server: &Server<F>, stop: &AtomicBool)
+where
+    F: Send + Sync + 'static + Fn(&rouille::Request) -> rouille::Response,
+{
+    while !stop.load(Ordering::Relaxed) {
+        server.poll_timeout(POLL_INTERVAL);
+    }
+    if log::enabled(Level::Info) {
+        eprintln!("Shutting down gracefully");
+    }
+    // Accepts the connections already queued, then waits for every request.
+    server.poll_timeout(POLL_INTERVAL);
+    server.join();
+}
+
+fn handle(
+    repo: Arc<dyn Repository>,
+    abilities: Arc<dyn AbilityRepository>,
+    moves: Arc<dyn MoveRepository>,
+    evolutions: Arc<dyn EvolutionRepository>,
+    req: &rouille::Request,
+) -> rouille::Response {
+    let request_id = problem::request_id(req);
+    let res = route(repo, abilities, moves, evolutions, req)
+        .unwrap_or_else(|problem| problem.into_response(req, &request_id));
+    if log::enabled(Level::Debug) {
+        eprintln!(
+            "[{}] {} {} {}",
+            request_id,
+            req.method(),
+            req.url(),
+            res.status_code
+        );
+    }
+    res.with_additional_header(problem::REQUEST_ID_HEADER, request_id)
 }
 
-enum Status {
-    Ok,
-    BadRequest,
-    NotFound,
-    Conflict,
-    InternalServerError,
+fn route(
+    repo: Arc<dyn Repository>,
+    abilities: Arc<dyn AbilityRepository>,
+    moves: Arc<dyn MoveRepository>,
+    evolutions: Arc<dyn EvolutionRepository>,
+    req: &rouille::Request,
+) -> Result<rouille::Response, Problem> {
+    router!(req,
+        (GET) (/health) => {
+            Ok(health::serve())
+        },
+        (POST) (/) => {
+            create_pokemon::serve(repo.clone(), req)
+        },
+        (POST) (/import) => {
+            import_pokemons::serve(repo.clone(), req)
+        },
+        (GET) (/) => {
+            fetch_all_pokemons::serve(repo.clone(), req)
+        },
+        (GET) (/{number: u16}) => {
+            fetch_pokemon::serve(repo.clone(), number, req)
+        },
+        (GET) (/{number: u16}/matchups) => {
+            type_matchups::serve(repo.clone(), number, req)
+        },
+        (PUT) (/{number: u16}) => {
+            update_pokemon::serve_replace(repo.clone(), number, req)
+        },
+        (PATCH) (/{number: u16}) => {
+            update_pokemon::serve_patch(repo.clone(), number, req)
+        },
+        (DELETE) (/{number: u16}) => {
+            delete_pokemon::serve(repo.clone(), number, req)
+        },
+        (GET) (/{number: u16}/abilities) => {
+            fetch_pokemon_abilities::serve(repo.clone(), abilities.clone(), number, req)
+        },
+        (PUT) (/{number: u16}/abilities) => {
+            set_pokemon_abilities::serve(repo.clone(), abilities.clone(), number, req)
+        },
+        (GET) (/abilities) => {
+            fetch_all_abilities::serve(abilities.clone())
+        },
+        (POST) (/abilities) => {
+            create_ability::serve(abilities.clone(), req)
+        },
+        (GET) (/abilities/{name: String}) => {
+            fetch_ability::serve(abilities.clone(), name)
+        },
+        (PUT) (/abilities/{name: String}) => {
+            update_ability::serve(abilities.clone(), name, req)
+        },
+        (DELETE) (/abilities/{name: String}) => {
+            delete_ability::serve(abilities.clone(), name)
+        },
+        (GET) (/abilities/{name: String}/pokemons) => {
+            fetch_ability_holders::serve(repo.clone(), abilities.clone(), name)
+        },
+        (GET) (/{number: u16}/learnset) => {
+            fetch_learnset::serve(repo.clone(), moves.clone(), number, req)
+        },
+        (PUT) (/{number: u16}/learnset) => {
+            set_learnset::serve(repo.clone(), moves.clone(), number, req)
+        },
+        (GET) (/moves) => {
+            fetch_all_moves::serve(moves.clone())
+        },
+        (POST) (/moves) => {
+            create_move::serve(moves.clone(), req)
+        },
+        (GET) (/moves/{name: String}) => {
+            fetch_move::serve(moves.clone(), name)
+        },
+        (GET) (/moves/{name: String}/pokemons) => {
+            fetch_move_learners::serve(repo.clone(), moves.clone(), name)
+        },
+        (GET) (/{number: u16}/evolutions) => {
+            fetch_evolution_chain::serve(repo.clone(), evolutions.clone(), number, req)
+        },
+        (POST) (/{number: u16}/evolutions) => {
+            add_evolution::serve(repo.clone(), evolutions.clone(), number, req)
+        },
+        (DELETE) (/{number: u16}/evolutions/{to: u16}) => {
+            delete_evolution::serve(evolutions.clone(), number, to)
+        },
+        _ => {
+            Err(Problem::not_found(format!("no route for {} {}", req.method(), req.url())))
+        }
+    )
 }
 
-impl From<Status> for rouille::Response {
-    fn from(status: Status) -> Self {
-        let status_code = match status {
-            Status::Ok => 200,
-            Status::BadRequest => 400,
-            Status::NotFound => 404,
-            Status::Conflict => 409,
-            Status::InternalServerError => 500,
+#[cfg(test)]
+mod tests {
+    use std::sync::mpsc::Sender;
+
+    use super::*;
+    use crate::domain::entities::{
+        AbilityName, BaseStats, Evolution, Learnset, Move, PokemonAbilities, PokemonForm,
+        PokemonName, PokemonNumber, PokemonTypes,
+    };
+    use crate::repositories::{
+        ability::InMemoryAbilityRepository, evolution::InMemoryEvolutionRepository,
+        moves::InMemoryMoveRepository, pokemon::InMemoryRepository,
+    };
+
+    struct TestServer {
+        url: String,
+        stop: Sender<()>,
+    }
+
+    impl TestServer {
+        fn start(repo: Arc<dyn Repository>) -> Self {
+            Self::with_abilities(repo, Arc::new(InMemoryAbilityRepository::new()))
+        }
+
+        fn with_abilities(
+            repo: Arc<dyn Repository>,
+            abilities: Arc<dyn AbilityRepository>,
+        ) -> Self {
+            Self::with_repos(
+                repo,
+                abilities,
+                Arc::new(InMemoryMoveRepository::new()),
+                Arc::new(InMemoryEvolutionRepository::new()),
+            )
+        }
+
+        fn with_moves(repo: Arc<dyn Repository>, moves: Arc<dyn MoveRepository>) -> Self {
+            Self::with_repos(
+                repo,
+                Arc::new(InMemoryAbilityRepository::new()),
+                moves,
+                Arc::new(InMemoryEvolutionRepository::new()),
+            )
+        }
+
+        fn with_evolutions(
+            repo: Arc<dyn Repository>,
+            evolutions: Arc<dyn EvolutionRepository>,
+        ) -> Self {
+            Self::with_repos(
+                repo,
+                Arc::new(InMemoryAbilityRepository::new()),
+                Arc::new(InMemoryMoveRepository::new()),
+                evolutions,
+            )
+        }
+
+        fn with_repos(
+            repo: Arc<dyn Repository>,
+            abilities: Arc<dyn AbilityRepository>,
+            moves: Arc<dyn MoveRepository>,
+            evolutions: Arc<dyn EvolutionRepository>,
+        ) -> Self {
+            let server = rouille::Server::new("localhost:0", move |req| {
+                handle(
+                    repo.clone(),
+                    abilities.clone(),
+                    moves.clone(),
+                    evolutions.clone(),
+                    req,
+                )
+            })
+            .expect("failed to start test server");
+            let url = format!("http://{}", server.server_addr());
+            let (_, stop) = server.stoppable();
+            Self { url, stop }
+        }
+
+        fn get(&self, path: &str) -> (u16, Option<ureq::serde_json::Value>) {
+            self.send("GET", path, None)
+        }
+
+        fn send(
+            &self,
+            method: &str,
+            path: &str,
+            body: Option<ureq::serde_json::Value>,
+        ) -> (u16, Option<ureq::serde_json::Value>) {
+            let req = ureq::request(method, &format!("{}{}", self.url, path));
+            let res = match body {
+                Some(body) => req.send_json(body),
+                None => req.call(),
+            };
+            match res {
+                Ok(res) => (res.status(), res.into_json().ok()),
+                Err(ureq::Error::Status(status, res)) => (status, res.into_json().ok()),
+                Err(_) => unreachable!(),
+            }
+        }
+
+        fn get_with_headers(&self, path: &str) -> (u16, ureq::Response) {
+            match ureq::get(&format!("{}{}", self.url, path)).call() {
+                Ok(res) => (res.status(), res),
+                Err(ureq::Error::Status(status, res)) => (status, res),
+                Err(_) => unreachable!(),
+            }
+        }
+    }
+
+    impl Drop for TestServer {
+        fn drop(&mut self) {
+            self.stop.send(()).ok();
+        }
+    }
+
+    #[test]
+    fn it_should_finish_in_flight_requests_when_stopping() {
+        let server = Server::new("localhost:0", |_| {
+            thread::sleep(Duration::from_millis(500));
+            rouille::Response::text("done")
+        })
+        .unwrap()
+        .pool_size(2);
+        let url = format!("http://{}", server.server_addr());
+        let stop = Arc::new(AtomicBool::new(false));
+        let running = {
+            let stop = stop.clone();
+            thread::spawn(move || run(&server, &stop))
+        };
+        let client = thread::spawn(move || match ureq::get(&url).call() {
+            Ok(res) => res.into_string().ok(),
+            Err(_) => None,
+        });
+
+        thread::sleep(Duration::from_millis(200));
+        stop.store(true, Ordering::Relaxed);
+        running.join().unwrap();
+
+        assert_eq!(client.join().unwrap().as_deref(), Some("done"));
+    }
+
+    #[test]
+    fn it_should_refuse_an_empty_thread_pool() {
+        let config = ServerConfig {
+            bind: String::from("localhost"),
+            port: 0,
+            workers: Some(0),
+        };
+
+        assert!(config.pool_size().is_err());
+    }
+
+    #[test]
+    fn it_should_return_the_pokemon_on_get_number() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonForm::default(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+            Some(BaseStats::pikachu()),
+        )
+        .ok();
+        let server = TestServer::start(repo);
+
+        let (status, body) = server.get("/25");
+
+        assert_eq!(status, 200);
+        assert_eq!(
+            body,
+            Some(ureq::json!({
+                "number": 25,
+                "name": "Pikachu",
+                "types": ["Electric"],
+                "stats": {
+                    "hp": 35,
+                    "attack": 55,
+                    "defense": 40,
+                    "special_attack": 50,
+                    "special_defense": 50,
+                    "speed": 90,
+                    "total": 320,
+                },
+            }))
+        );
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_on_get_invalid_number() {
+        let server = TestServer::start(Arc::new(InMemoryRepository::new()));
+
+        let (status, res) = server.get_with_headers("/0");
+
+        assert_eq!(status, 400);
+        assert_eq!(res.content_type(), "application/problem+json");
+        let request_id = res.header("X-Request-Id").map(String::from);
+        let body = res.into_json::<ureq::serde_json::Value>().unwrap();
+        assert_eq!(body["status"], 400);
+        assert_eq!(body["code"], "invalid_request");
+        assert_eq!(body["instance"], "/0");
+        assert_eq!(
+            body["fields"],
+            ureq::json!([{
+                "field": "number",
+                "code": "number_out_of_range",
+                "message": "number 0 is out of range 1..=898",
+            }])
+        );
+        assert_eq!(body["request_id"].as_str().map(String::from), request_id);
+    }
+
+    #[test]
+    fn it_should_describe_a_malformed_body_on_post() {
+        let server = TestServer::start(Arc::new(InMemoryRepository::new()));
+
+        let (status, body) = server.send("POST", "/", Some(ureq::json!({ "number": 25 })));
+
+        assert_eq!(status, 400);
+        let body = body.unwrap();
+        assert_eq!(body["code"], "malformed_body");
+        assert_eq!(body["fields"][0]["field"], "name");
+        assert_eq!(body["fields"][0]["code"], "missing_field");
+    }
+
+    #[test]
+    fn it_should_return_a_problem_on_an_unknown_route() {
+        let server = TestServer::start(Arc::new(InMemoryRepository::new()));
+
+        let (status, body) = server.get("/25/moves");
+
+        assert_eq!(status, 404);
+        assert_eq!(body.unwrap()["code"], "not_found");
+    }
+
+    #[test]
+    fn it_should_return_a_not_found_on_get_missing_pokemon() {
+        let server = TestServer::start(Arc::new(InMemoryRepository::new()));
+
+        let (status, _) = server.get("/25");
+
+        assert_eq!(status, 404);
+    }
+
+    #[test]
+    fn it_should_return_an_internal_server_error_on_get_when_repo_fails() {
+        let server = TestServer::start(Arc::new(InMemoryRepository::new().with_error()));
+
+        let res = ureq::get(&format!("{}/25", server.url))
+            .set("X-Request-Id", "req-42")
+            .call();
+
+        match res {
+            Err(ureq::Error::Status(500, res)) => {
+                assert_eq!(res.header("X-Request-Id"), Some("req-42"));
+                let body = res.into_json::<ureq::serde_json::Value>().unwrap();
+                assert_eq!(body["code"], "internal_error");
+                assert_eq!(body["request_id"], "req-42");
+            }
+            _ => unreachable!(),
+        };
+    }
+
+    fn repo_with_pikachu() -> Arc<InMemoryRepository> {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonForm::default(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+            None,
+        )
+        .ok();
+        repo
+    }
+
+    #[test]
+    fn it_should_replace_the_pokemon_on_put_number() {
+        let server = TestServer::start(repo_with_pikachu());
+
+        let (status, body) = server.send(
+            "PUT",
+            "/25",
+            Some(ureq::json!({ "name": "Raichu", "types": ["Fire"] })),
+        );
+
+        assert_eq!(status, 200);
+        assert_eq!(
+            body,
+            Some(ureq::json!({
+                "number": 25,
+                "name": "Raichu",
+                "types": ["Fire"],
+                "stats": null,
+            }))
+        );
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_on_put_with_missing_fields() {
+        let server = TestServer::start(repo_with_pikachu());
+
+        let (status, _) = server.send("PUT", "/25", Some(ureq::json!({ "name": "Raichu" })));
+
+        assert_eq!(status, 400);
+    }
+
+    #[test]
+    fn it_should_patch_the_given_fields_on_patch_number() {
+        let server = TestServer::start(repo_with_pikachu());
+
+        let (status, _) = server.send("PATCH", "/25", Some(ureq::json!({ "name": "Raichu" })));
+        let (_, body) = server.get("/25");
+
+        assert_eq!(status, 200);
+        assert_eq!(
+            body,
+            Some(ureq::json!({
+                "number": 25,
+                "name": "Raichu",
+                "types": ["Electric"],
+                "stats": null,
+            }))
+        );
+    }
+
+    #[test]
+    fn it_should_return_a_not_found_on_patch_missing_pokemon() {
+        let server = TestServer::start(Arc::new(InMemoryRepository::new()));
+
+        let (status, _) = server.send("PATCH", "/25", Some(ureq::json!({ "name": "Raichu" })));
+
+        assert_eq!(status, 404);
+    }
+
+    #[test]
+    fn it_should_return_the_type_matchups_on_get_number_matchups() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::charizard(),
+            PokemonForm::default(),
+            PokemonName::charizard(),
+            PokemonTypes::charizard(),
+            None,
+        )
+        .ok();
+        let server = TestServer::start(repo);
+
+        let (status, body) = server.get("/6/matchups");
+
+        assert_eq!(status, 200);
+        let body = body.unwrap();
+        assert_eq!(body["4x"], ureq::json!(["Rock"]));
+        assert_eq!(body["0x"], ureq::json!(["Ground"]));
+    }
+
+    #[test]
+    fn it_should_return_a_not_found_on_get_matchups_of_missing_pokemon() {
+        let server = TestServer::start(Arc::new(InMemoryRepository::new()));
+
+        let (status, _) = server.get("/6/matchups");
+
+        assert_eq!(status, 404);
+    }
+
+    #[test]
+    fn it_should_only_serve_another_form_when_requested() {
+        let server = TestServer::start(repo_with_pikachu());
+
+        let (status, body) = server.send(
+            "POST",
+            "/",
+            Some(ureq::json!({
+                "number": 25,
+                "form": "alola",
+                "name": "Pikachu",
+                "types": ["Electric", "Psychic"],
+            })),
+        );
+        assert_eq!(status, 200);
+        assert_eq!(body.unwrap()["form"], "alola");
+
+        let (_, body) = server.get("/25");
+        let body = body.unwrap();
+        assert_eq!(body.get("form"), None);
+        assert_eq!(body["types"], ureq::json!(["Electric"]));
+        let (_, body) = server.get("/25?form=alola");
+        assert_eq!(body.unwrap()["types"], ureq::json!(["Electric", "Psychic"]));
+        let (_, body) = server.get("/");
+        assert_eq!(body.unwrap().as_array().map(Vec::len), Some(1));
+        let (_, body) = server.get("/?forms=all");
+        assert_eq!(body.unwrap()[1]["form"], "alola");
+
+        let (status, body) = server.send("DELETE", "/25?form=alola", None);
+        assert_eq!(status, 200);
+        assert_eq!(body, None);
+        let (status, body) = server.get("/25?form=alola");
+        assert_eq!(status, 404);
+        assert_eq!(
+            body.unwrap()["detail"],
+            "pokemon 25 in form \"alola\" does not exist"
+        );
+    }
+
+    #[test]
+    fn it_should_return_a_page_with_a_next_link_on_get_with_limit() {
+        let repo = repo_with_pikachu();
+        repo.insert(
+            PokemonNumber::charmander(),
+            PokemonForm::default(),
+            PokemonName::charmander(),
+            PokemonTypes::charmander(),
+            None,
+        )
+        .ok();
+        let server = TestServer::start(repo);
+
+        let (status, res) = server.get_with_headers("/?limit=1&sort=name");
+
+        assert_eq!(status, 200);
+        assert_eq!(res.header("X-Next-Cursor"), Some("1"));
+        assert_eq!(
+            res.header("Link"),
+            Some("</?limit=1&sort=name&cursor=1>; rel=\"next\"")
+        );
+        assert_eq!(
+            res.into_json::<ureq::serde_json::Value>().ok(),
+            Some(ureq::json!([{
+                "number": 4,
+                "name": "Charmander",
+                "types": ["Fire"],
+                "stats": null,
+            }]))
+        );
+
+        let (_, body) = server.get("/?limit=1&sort=name&cursor=1");
+        assert_eq!(body.unwrap()[0]["number"], 25);
+    }
+
+    #[test]
+    fn it_should_import_a_batch_on_post_import() {
+        let server = TestServer::start(repo_with_pikachu());
+
+        let (status, body) = server.send(
+            "POST",
+            "/import?on_conflict=skip",
+            Some(ureq::json!([
+                { "number": 25, "name": "Pikachu", "types": ["Electric"] },
+                { "number": 4, "name": "Charmander", "types": ["Fire"] },
+            ])),
+        );
+
+        assert_eq!(status, 200);
+        assert_eq!(
+            body,
+            Some(ureq::json!({
+                "created": [{ "number": 4 }],
+                "overwritten": [],
+                "skipped": [{ "number": 25 }],
+            }))
+        );
+    }
+
+    #[test]
+    fn it_should_return_the_row_errors_on_post_import_with_invalid_rows() {
+        let server = TestServer::start(Arc::new(InMemoryRepository::new()));
+
+        let res = ureq::post(&format!("{}/import", server.url)).send_json(ureq::json!([
+            { "number": 25, "name": "Pikachu", "types": ["Electric"] },
+            { "number": 0, "name": "Missingno", "types": ["Bird"] },
+        ]));
+
+        match res {
+            Err(ureq::Error::Status(400, res)) => {
+                let body = res.into_json::<ureq::serde_json::Value>().unwrap();
+                assert_eq!(
+                    body["rows"],
+                    ureq::json!([{
+                        "row": 2,
+                        "number": 0,
+                        "reasons": ["invalid_number", "invalid_types"],
+                    }])
+                );
+                assert_eq!(body["fields"][0]["field"], "[1].number");
+                assert_eq!(body["fields"][1]["field"], "[1].types");
+            }
+            _ => unreachable!(),
         };
-        Self {
-            status_code,
-            headers: vec![],
-            data: rouille::ResponseBody::empty(),
-            upgrade: None,
+    }
+
+    #[test]
+    fn it_should_return_a_conflict_on_post_import_by_default() {
+        let server = TestServer::start(repo_with_pikachu());
+
+        let (status, _) = server.send(
+            "POST",
+            "/import",
+            Some(ureq::json!([{ "number": 25, "name": "Pikachu", "types": ["Electric"] }])),
+        );
+
+        assert_eq!(status, 409);
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_on_get_with_invalid_query() {
+        let server = TestServer::start(Arc::new(InMemoryRepository::new()));
+
+        let (status, _) = server.get("/?limit=many");
+
+        assert_eq!(status, 400);
+    }
+
+    fn abilities_of_pikachu() -> Arc<InMemoryAbilityRepository> {
+        let abilities = Arc::new(InMemoryAbilityRepository::new());
+        abilities
+            .insert(AbilityName::r#static(), String::from("May paralyze."))
+            .ok();
+        abilities
+            .insert(
+                AbilityName::lightning_rod(),
+                String::from("Draws in Electric moves."),
+            )
+            .ok();
+        abilities
+            .set_pokemon_abilities(PokemonNumber::pikachu(), PokemonAbilities::pikachu())
+            .ok();
+        abilities
+    }
+
+    #[test]
+    fn it_should_create_an_ability_on_post_abilities() {
+        let server = TestServer::start(Arc::new(InMemoryRepository::new()));
+
+        let (status, _) = server.send(
+            "POST",
+            "/abilities",
+            Some(
+                ureq::json!({ "name": "Lightning Rod", "description": "Draws in Electric moves." }),
+            ),
+        );
+        let (_, body) = server.get("/abilities/Lightning%20Rod");
+
+        assert_eq!(status, 200);
+        assert_eq!(
+            body,
+            Some(ureq::json!({
+                "name": "Lightning Rod",
+                "description": "Draws in Electric moves.",
+            }))
+        );
+    }
+
+    #[test]
+    fn it_should_return_the_abilities_on_get_number_abilities() {
+        let server = TestServer::with_abilities(repo_with_pikachu(), abilities_of_pikachu());
+
+        let (status, body) = server.get("/25/abilities");
+
+        assert_eq!(status, 200);
+        assert_eq!(
+            body,
+            Some(ureq::json!([
+                { "name": "Static", "description": "May paralyze.", "hidden": false },
+                {
+                    "name": "Lightning Rod",
+                    "description": "Draws in Electric moves.",
+                    "hidden": true,
+                },
+            ]))
+        );
+    }
+
+    #[test]
+    fn it_should_share_the_species_data_with_an_existing_form_only() {
+        let repo = repo_with_pikachu();
+        repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonForm::alola(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+            None,
+        )
+        .ok();
+        let server = TestServer::with_repos(
+            repo,
+            abilities_of_pikachu(),
+            moves_of_pikachu(),
+            Arc::new(InMemoryEvolutionRepository::new()),
+        );
+
+        for route in ["abilities", "learnset", "evolutions"] {
+            let (status, _) = server.get(&format!("/25/{}?form=alola", route));
+            assert_eq!(status, 200);
+            let (status, body) = server.get(&format!("/25/{}?form=galar", route));
+            assert_eq!(status, 404);
+            assert_eq!(
+                body.unwrap()["detail"],
+                "pokemon 25 in form \"galar\" does not exist"
+            );
         }
+        let (status, body) = server.send(
+            "PUT",
+            "/25/abilities?form=alola",
+            Some(ureq::json!({ "abilities": ["Lightning Rod"], "hidden": null })),
+        );
+        assert_eq!(status, 400);
+        assert_eq!(body.unwrap()["fields"][0]["code"], "shared_by_species");
+        let (_, body) = server.get("/25/abilities");
+        assert_eq!(body.unwrap()[0]["name"], "Static");
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_on_put_number_abilities_with_an_unknown_ability() {
+        let server = TestServer::with_abilities(repo_with_pikachu(), abilities_of_pikachu());
+
+        let (status, body) = server.send(
+            "PUT",
+            "/25/abilities",
+            Some(ureq::json!({ "abilities": ["Static", "Plus"], "hidden": null })),
+        );
+
+        assert_eq!(status, 400);
+        let body = body.unwrap();
+        assert_eq!(body["fields"][0]["field"], "abilities");
+        assert_eq!(body["fields"][0]["code"], "unknown_ability");
+    }
+
+    #[test]
+    fn it_should_return_the_pokemons_having_an_ability_on_get_abilities_name_pokemons() {
+        let server = TestServer::with_abilities(repo_with_pikachu(), abilities_of_pikachu());
+
+        let (status, body) = server.get("/abilities/Lightning%20Rod/pokemons");
+
+        assert_eq!(status, 200);
+        assert_eq!(
+            body,
+            Some(ureq::json!([{ "number": 25, "name": "Pikachu", "hidden": true }]))
+        );
+    }
+
+    fn moves_of_pikachu() -> Arc<InMemoryMoveRepository> {
+        let moves = Arc::new(InMemoryMoveRepository::new());
+        for r#move in [Move::growl(), Move::thunder_shock(), Move::thunderbolt()] {
+            moves.insert(r#move).ok();
+        }
+        moves
+            .set_learnset(PokemonNumber::pikachu(), Learnset::pikachu())
+            .ok();
+        moves
+    }
+
+    #[test]
+    fn it_should_create_a_move_on_post_moves() {
+        let server = TestServer::start(Arc::new(InMemoryRepository::new()));
+        let thunder_shock = ureq::json!({
+            "name": "Thunder Shock",
+            "type": "Electric",
+            "category": "Special",
+            "power": 40,
+            "accuracy": 100,
+            "pp": 30,
+        });
+
+        let (status, _) = server.send("POST", "/moves", Some(thunder_shock.clone()));
+        let (_, body) = server.get("/moves/Thunder%20Shock");
+
+        assert_eq!(status, 200);
+        assert_eq!(body, Some(thunder_shock));
+    }
+
+    #[test]
+    fn it_should_return_the_learnset_on_get_number_learnset() {
+        let server = TestServer::with_moves(repo_with_pikachu(), moves_of_pikachu());
+
+        let (status, body) = server.get("/25/learnset");
+
+        assert_eq!(status, 200);
+        let body = body.unwrap();
+        assert_eq!(body.as_array().map(Vec::len), Some(3));
+        assert_eq!(
+            body[2],
+            ureq::json!({
+                "name": "Thunderbolt",
+                "method": "tm",
+                "level": null,
+                "type": "Electric",
+                "category": "Special",
+                "power": 90,
+                "accuracy": 100,
+                "pp": 15,
+            })
+        );
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_on_put_number_learnset_without_a_level() {
+        let server = TestServer::with_moves(repo_with_pikachu(), moves_of_pikachu());
+
+        let (status, body) = server.send(
+            "PUT",
+            "/25/learnset",
+            Some(ureq::json!({ "moves": [{ "name": "Growl", "method": "level-up" }] })),
+        );
+
+        assert_eq!(status, 400);
+        let body = body.unwrap();
+        assert_eq!(body["fields"][0]["field"], "learnset");
+        assert_eq!(body["fields"][0]["code"], "missing_level");
+    }
+
+    #[test]
+    fn it_should_return_the_pokemons_learning_a_move_on_get_moves_name_pokemons() {
+        let server = TestServer::with_moves(repo_with_pikachu(), moves_of_pikachu());
+
+        let (status, body) = server.get("/moves/Thunderbolt/pokemons");
+
+        assert_eq!(status, 200);
+        assert_eq!(
+            body,
+            Some(ureq::json!([
+                { "number": 25, "name": "Pikachu", "method": "tm", "level": null }
+            ]))
+        );
+    }
+
+    fn repo_with_charmanders() -> Arc<InMemoryRepository> {
+        let repo = Arc::new(InMemoryRepository::new());
+        for (number, name) in [
+            (PokemonNumber::charmander(), PokemonName::charmander()),
+            (PokemonNumber::charmeleon(), PokemonName::charmeleon()),
+            (PokemonNumber::charizard(), PokemonName::charizard()),
+        ] {
+            repo.insert(
+                number,
+                PokemonForm::default(),
+                name,
+                PokemonTypes::charmander(),
+                None,
+            )
+            .ok();
+        }
+        repo
+    }
+
+    #[test]
+    fn it_should_return_the_whole_chain_on_get_number_evolutions() {
+        let evolutions = Arc::new(InMemoryEvolutionRepository::new());
+        evolutions.insert(Evolution::charmander()).ok();
+        evolutions.insert(Evolution::charmeleon()).ok();
+        let server = TestServer::with_evolutions(repo_with_charmanders(), evolutions);
+
+        let (status, body) = server.get("/6/evolutions");
+
+        assert_eq!(status, 200);
+        assert_eq!(
+            body,
+            Some(ureq::json!({
+                "number": 4,
+                "name": "Charmander",
+                "trigger": null,
+                "evolutions": [{
+                    "number": 5,
+                    "name": "Charmeleon",
+                    "trigger": { "name": "level", "level": 16, "item": null },
+                    "evolutions": [{
+                        "number": 6,
+                        "name": "Charizard",
+                        "trigger": { "name": "level", "level": 36, "item": null },
+                        "evolutions": [],
+                    }],
+                }],
+            }))
+        );
+    }
+
+    #[test]
+    fn it_should_add_and_delete_an_evolution_on_post_and_delete_number_evolutions() {
+        let server = TestServer::with_evolutions(
+            repo_with_charmanders(),
+            Arc::new(InMemoryEvolutionRepository::new()),
+        );
+
+        let (status, body) = server.send(
+            "POST",
+            "/4/evolutions",
+            Some(ureq::json!({ "to": 5, "trigger": "level", "level": 16 })),
+        );
+        assert_eq!(status, 200);
+        assert_eq!(
+            body,
+            Some(ureq::json!({
+                "from": 4,
+                "to": 5,
+                "trigger": "level",
+                "level": 16,
+                "item": null,
+            }))
+        );
+        let (status, _) = server.send(
+            "POST",
+            "/6/evolutions",
+            Some(ureq::json!({ "to": 5, "trigger": "trade" })),
+        );
+        assert_eq!(status, 409);
+
+        let (status, _) = server.send("DELETE", "/4/evolutions/5", None);
+        assert_eq!(status, 200);
+        let (status, _) = server.send("DELETE", "/4/evolutions/5", None);
+        assert_eq!(status, 404);
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_on_post_number_evolutions_making_a_cycle() {
+        let evolutions = Arc::new(InMemoryEvolutionRepository::new());
+        evolutions.insert(Evolution::charmander()).ok();
+        let server = TestServer::with_evolutions(repo_with_charmanders(), evolutions);
+
+        let (status, body) = server.send(
+            "POST",
+            "/5/evolutions",
+            Some(ureq::json!({ "to": 4, "trigger": "item", "item": "Fire Stone" })),
+        );
+
+        assert_eq!(status, 400);
+        let body = body.unwrap();
+        assert_eq!(body["fields"][0]["field"], "to");
+        assert_eq!(body["fields"][0]["code"], "evolution_cycle");
     }
 }
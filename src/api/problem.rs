@@ -0,0 +1,322 @@
+//! RFC 7807 `application/problem+json` bodies for every error of the API.
+
+use std::{
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        OnceLock,
+    },
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use rouille::input::json::JsonError;
+use serde::Serialize;
+
+use crate::{
+    domain::entities::{
+        AbilityError, EvolutionError, FormError, LearnsetError, MoveError, StatsError,
+        ValidationError,
+    },
+    log::{self, Level},
+    repositories::pokemon::RepositoryError,
+};
+
+pub const CONTENT_TYPE: &str = "application/problem+json";
+pub const REQUEST_ID_HEADER: &str = "X-Request-Id";
+
+/// An error answer, rendered once the request ID is known.
+#[derive(Debug)]
+pub struct Problem {
+    status: u16,
+    code: &'static str,
+    title: &'static str,
+    detail: String,
+    fields: Vec<Field>,
+    rows: Vec<Row>,
+    /// Kept for the server log only, never sent to the client.
+    cause: Option<Box<RepositoryError>>,
+}
+
+/// A request field that was refused, e.g. `number` or `[2].types` in a batch.
+#[derive(Debug, Serialize)]
+pub struct Field {
+    pub field: String,
+    pub code: &'static str,
+    pub message: String,
+}
+
+/// A row of a batch that could not be imported.
+#[derive(Debug, Serialize)]
+pub struct Row {
+    pub row: usize,
+    pub number: u16,
+    pub reasons: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct Body<'a> {
+    #[serde(rename = "type")]
+    r#type: String,
+    title: &'a str,
+    status: u16,
+    detail: &'a str,
+    instance: &'a str,
+    code: &'a str,
+    #[serde(skip_serializing_if = "<[_]>::is_empty")]
+    fields: &'a [Field],
+    #[serde(skip_serializing_if = "<[_]>::is_empty")]
+    rows: &'a [Row],
+    request_id: &'a str,
+}
+
+impl Problem {
+    fn new(status: u16, code: &'static str, title: &'static str, detail: String) -> Self {
+        Self {
+            status,
+            code,
+            title,
+            detail,
+            fields: vec![],
+            rows: vec![],
+            cause: None,
+        }
+    }
+
+    pub fn invalid(errors: Vec<ValidationError>) -> Self {
+        Self::invalid_fields(errors.iter().map(|e| field(e, "")).collect())
+    }
+
+    pub fn invalid_fields(fields: Vec<Field>) -> Self {
+        let detail = fields
+            .iter()
+            .map(|f| f.message.as_str())
+            .collect::<Vec<&str>>()
+            .join(", ");
+        Self {
+            fields,
+            ..Self::new(400, "invalid_request", "The request is invalid", detail)
+        }
+    }
+
+    pub fn invalid_parameter(name: &'static str, reason: String) -> Self {
+        Self::invalid(vec![ValidationError::InvalidParameter { name, reason }])
+    }
+
+    /// A body that could not be read as the expected JSON document.
+    pub fn malformed_body(e: JsonError) -> Self {
+        match e {
+            JsonError::WrongContentType => Self::new(
+                400,
+                "wrong_content_type",
+                "The body must be JSON",
+                String::from("the Content-Type header must be application/json"),
+            ),
+            JsonError::ParseError(e) => {
+                let detail = e.to_string();
+                let fields = match missing_field(&detail) {
+                    Some(name) => vec![Field {
+                        field: name,
+                        code: "missing_field",
+                        message: detail.clone(),
+                    }],
+                    None => vec![],
+                };
+                Self {
+                    fields,
+                    ..Self::new(
+                        400,
+                        "malformed_body",
+                        "The body is not valid JSON for this request",
+                        detail,
+                    )
+                }
+            }
+            e => Self::new(
+                400,
+                "malformed_body",
+                "The body could not be read",
+                e.to_string(),
+            ),
+        }
+    }
+
+    pub fn not_found(detail: String) -> Self {
+        Self::new(404, "not_found", "The resource does not exist", detail)
+    }
+
+    pub fn conflict(detail: String) -> Self {
+        Self::new(409, "conflict", "The resource already exists", detail)
+    }
+
+    /// Rows of a batch rejected as a whole, with the fields of the invalid ones.
+    pub fn rows(status: u16, rows: Vec<Row>, fields: Vec<Field>) -> Self {
+        let (code, title) = match status {
+            409 => ("conflict", "Some rows already exist"),
+            _ => ("invalid_request", "Some rows are invalid"),
+        };
+        let detail = format!("{} rows cannot be imported", rows.len());
+        Self {
+            rows,
+            fields,
+            ..Self::new(status, code, title, detail)
+        }
+    }
+
+    pub fn internal(cause: RepositoryError) -> Self {
+        Self {
+            cause: Some(Box::new(cause)),
+            ..Self::new(
+                500,
+                "internal_error",
+                "An unexpected error occurred",
+                String::from("the pokemons could not be reached, retry later"),
+            )
+        }
+    }
+
+    pub fn into_response(self, req: &rouille::Request, request_id: &str) -> rouille::Response {
+        if let (Some(cause), true) = (&self.cause, log::enabled(Level::Error)) {
+            eprintln!(
+                "[{}] {} {}: {}",
+                request_id,
+                req.method(),
+                req.url(),
+                cause.chain()
+            );
+        }
+        let body = Body {
+            r#type: format!("/problems/{}", self.code),
+            title: self.title,
+            status: self.status,
+            detail: &self.detail,
+            instance: &req.url(),
+            code: self.code,
+            fields: &self.fields,
+            rows: &self.rows,
+            request_id,
+        };
+        let data = serde_json::to_string(&body).unwrap_or_default();
+        rouille::Response::from_data(CONTENT_TYPE, data).with_status_code(self.status)
+    }
+}
+
+/// Names the pokemon in a detail, along with its form when not the default.
+pub fn pokemon(number: u16, form: Option<&str>) -> String {
+    match form {
+        Some(form) if !form.is_empty() => format!("pokemon {} in form {:?}", number, form),
+        _ => format!("pokemon {}", number),
+    }
+}
+
+/// Describes a refused value, `prefix` locating it in a batch.
+pub fn field(e: &ValidationError, prefix: &str) -> Field {
+    let code = match e {
+        ValidationError::NumberOutOfRange(_) => "number_out_of_range",
+        ValidationError::EmptyName => "empty_name",
+        ValidationError::NoTypes => "no_types",
+        ValidationError::TooManyTypes(_) => "too_many_types",
+        ValidationError::DuplicateType(_) => "duplicate_type",
+        ValidationError::UnknownType(_) => "unknown_type",
+        ValidationError::Stats(StatsError::OutOfRange(..)) => "stat_out_of_range",
+        ValidationError::Ability(AbilityError::EmptyName) => "empty_ability_name",
+        ValidationError::Ability(AbilityError::TooMany(_)) => "too_many_abilities",
+        ValidationError::Ability(AbilityError::Duplicate(_)) => "duplicate_ability",
+        ValidationError::Ability(AbilityError::Unknown(_)) => "unknown_ability",
+        ValidationError::Move(MoveError::EmptyName) => "empty_move_name",
+        ValidationError::Move(MoveError::UnknownCategory(_)) => "unknown_category",
+        ValidationError::Move(MoveError::PowerOutOfRange(_)) => "power_out_of_range",
+        ValidationError::Move(MoveError::StatusWithPower) => "status_move_with_power",
+        ValidationError::Move(MoveError::AccuracyOutOfRange(_)) => "accuracy_out_of_range",
+        ValidationError::Move(MoveError::PpOutOfRange(_)) => "pp_out_of_range",
+        ValidationError::Learnset(LearnsetError::UnknownMethod(_)) => "unknown_learn_method",
+        ValidationError::Learnset(LearnsetError::MissingLevel) => "missing_level",
+        ValidationError::Learnset(LearnsetError::UnexpectedLevel(_)) => "unexpected_level",
+        ValidationError::Learnset(LearnsetError::LevelOutOfRange(_)) => "level_out_of_range",
+        ValidationError::Learnset(LearnsetError::DuplicateEntry(_)) => "duplicate_learnset_entry",
+        ValidationError::Learnset(LearnsetError::UnknownMove(_)) => "unknown_move",
+        ValidationError::Evolution(EvolutionError::UnknownTrigger(_)) => "unknown_trigger",
+        ValidationError::Evolution(EvolutionError::MissingLevel) => "missing_level",
+        ValidationError::Evolution(EvolutionError::UnexpectedLevel(_)) => "unexpected_level",
+        ValidationError::Evolution(EvolutionError::LevelOutOfRange(_)) => "level_out_of_range",
+        ValidationError::Evolution(EvolutionError::MissingItem) => "missing_item",
+        ValidationError::Evolution(EvolutionError::UnexpectedItem(_)) => "unexpected_item",
+        ValidationError::Evolution(EvolutionError::EmptyItemName) => "empty_item_name",
+        ValidationError::Evolution(EvolutionError::UnknownPokemon(_)) => "unknown_pokemon",
+        ValidationError::Evolution(EvolutionError::Cycle(_)) => "evolution_cycle",
+        ValidationError::Form(FormError::Invalid(_)) => "invalid_form",
+        ValidationError::Form(FormError::SharedBySpecies(_)) => "shared_by_species",
+        ValidationError::InvalidParameter { .. } => "invalid_parameter",
+    };
+    Field {
+        field: format!("{}{}", prefix, e.field()),
+        code,
+        message: e.to_string(),
+    }
+}
+
+/// Reads the field name out of serde's "missing field `name` at ..." message.
+fn missing_field(detail: &str) -> Option<String> {
+    let rest = detail.strip_prefix("missing field `")?;
+    rest.split('`').next().map(String::from)
+}
+
+/// Reuses the caller's request ID when it looks like one, so logs can be
+/// correlated across services, and makes one up otherwise.
+pub fn request_id(req: &rouille::Request) -> String {
+    match req.header(REQUEST_ID_HEADER) {
+        Some(id)
+            if !id.is_empty()
+                && id.len() <= 64
+                && id
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') =>
+        {
+            String::from(id)
+        }
+        _ => generate_request_id(),
+    }
+}
+
+fn generate_request_id() -> String {
+    static PREFIX: OnceLock<u64> = OnceLock::new();
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let prefix = PREFIX.get_or_init(|| match SystemTime::now().duration_since(UNIX_EPOCH) {
+        Ok(elapsed) => elapsed.as_nanos() as u64,
+        Err(_) => 0,
+    });
+    format!(
+        "{:016x}-{:08x}",
+        prefix,
+        COUNTER.fetch_add(1, Ordering::Relaxed)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_should_describe_a_validation_error_as_a_field() {
+        let f = field(
+            &ValidationError::UnknownType(String::from("Shadow")),
+            "[1].",
+        );
+
+        assert_eq!(f.field, "[1].types");
+        assert_eq!(f.code, "unknown_type");
+        assert_eq!(f.message, "unknown type \"Shadow\"");
+    }
+
+    #[test]
+    fn it_should_find_the_missing_field_of_a_parse_error() {
+        assert_eq!(
+            missing_field("missing field `types` at line 1 column 17"),
+            Some(String::from("types"))
+        );
+        assert_eq!(missing_field("expected value at line 1 column 1"), None);
+    }
+
+    #[test]
+    fn it_should_generate_distinct_request_ids() {
+        assert_ne!(generate_request_id(), generate_request_id());
+    }
+}
@@ -0,0 +1,76 @@
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    domain::{self, set_learnset::Entry},
+    repositories::{moves::MoveRepository, pokemon::Repository},
+};
+
+use super::problem::{self, Problem};
+
+#[derive(Serialize)]
+struct Response {
+    number: u16,
+    moves: Vec<LearnedMove>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct LearnedMove {
+    name: String,
+    method: String,
+    #[serde(default)]
+    level: Option<u16>,
+}
+
+#[derive(Deserialize)]
+struct Request {
+    moves: Vec<LearnedMove>,
+}
+
+/// Every form shares the learnset of its species, so `?form=` is refused.
+pub fn serve(
+    repo: Arc<dyn Repository>,
+    moves: Arc<dyn MoveRepository>,
+    number: u16,
+    req: &rouille::Request,
+) -> Result<rouille::Response, Problem> {
+    let form = req.get_param("form");
+    let req = match rouille::input::json_input::<Request>(req) {
+        Ok(req) => domain::set_learnset::Request {
+            number,
+            form: form.clone(),
+            moves: req
+                .moves
+                .into_iter()
+                .map(|m| Entry {
+                    name: m.name,
+                    method: m.method,
+                    level: m.level,
+                })
+                .collect(),
+        },
+        Err(e) => return Err(Problem::malformed_body(e)),
+    };
+    match domain::set_learnset::execute(repo, moves, req) {
+        Ok(domain::set_learnset::Response { number, moves }) => {
+            Ok(rouille::Response::json(&Response {
+                number,
+                moves: moves
+                    .into_iter()
+                    .map(|e| LearnedMove {
+                        name: e.name,
+                        method: e.method,
+                        level: e.level,
+                    })
+                    .collect(),
+            }))
+        }
+        Err(domain::set_learnset::Error::BadRequest(errors)) => Err(Problem::invalid(errors)),
+        Err(domain::set_learnset::Error::NotFound) => Err(Problem::not_found(format!(
+            "{} does not exist",
+            problem::pokemon(number, form.as_deref())
+        ))),
+        Err(domain::set_learnset::Error::Unknown(e)) => Err(Problem::internal(e)),
+    }
+}
@@ -0,0 +1,61 @@
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    domain,
+    repositories::{ability::AbilityRepository, pokemon::Repository},
+};
+
+use super::problem::{self, Problem};
+
+#[derive(Serialize)]
+struct Response {
+    number: u16,
+    abilities: Vec<String>,
+    hidden: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Request {
+    abilities: Vec<String>,
+    hidden: Option<String>,
+}
+
+/// Every form shares the abilities of its species, so `?form=` is refused.
+pub fn serve(
+    repo: Arc<dyn Repository>,
+    abilities: Arc<dyn AbilityRepository>,
+    number: u16,
+    req: &rouille::Request,
+) -> Result<rouille::Response, Problem> {
+    let form = req.get_param("form");
+    let req = match rouille::input::json_input::<Request>(req) {
+        Ok(req) => domain::set_pokemon_abilities::Request {
+            number,
+            form: form.clone(),
+            abilities: req.abilities,
+            hidden: req.hidden,
+        },
+        Err(e) => return Err(Problem::malformed_body(e)),
+    };
+    match domain::set_pokemon_abilities::execute(repo, abilities, req) {
+        Ok(domain::set_pokemon_abilities::Response {
+            number,
+            abilities,
+            hidden,
+        }) => Ok(rouille::Response::json(&Response {
+            number,
+            abilities,
+            hidden,
+        })),
+        Err(domain::set_pokemon_abilities::Error::BadRequest(errors)) => {
+            Err(Problem::invalid(errors))
+        }
+        Err(domain::set_pokemon_abilities::Error::NotFound) => Err(Problem::not_found(format!(
+            "{} does not exist",
+            problem::pokemon(number, form.as_deref())
+        ))),
+        Err(domain::set_pokemon_abilities::Error::Unknown(e)) => Err(Problem::internal(e)),
+    }
+}
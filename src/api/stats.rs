@@ -0,0 +1,57 @@
+use serde::{Deserialize, Serialize};
+
+use crate::domain;
+
+#[derive(Serialize, Deserialize)]
+pub struct Stats {
+    hp: u16,
+    attack: u16,
+    defense: u16,
+    special_attack: u16,
+    special_defense: u16,
+    speed: u16,
+}
+
+/// The stats of a response, with their total.
+#[derive(Serialize)]
+pub struct StatsResponse {
+    #[serde(flatten)]
+    stats: Stats,
+    total: u16,
+}
+
+impl From<Stats> for domain::Stats {
+    fn from(s: Stats) -> Self {
+        Self {
+            hp: s.hp,
+            attack: s.attack,
+            defense: s.defense,
+            special_attack: s.special_attack,
+            special_defense: s.special_defense,
+            speed: s.speed,
+        }
+    }
+}
+
+impl From<domain::Stats> for Stats {
+    fn from(s: domain::Stats) -> Self {
+        Self {
+            hp: s.hp,
+            attack: s.attack,
+            defense: s.defense,
+            special_attack: s.special_attack,
+            special_defense: s.special_defense,
+            speed: s.speed,
+        }
+    }
+}
+
+pub fn response(stats: Option<domain::Stats>, total: Option<u16>) -> Option<StatsResponse> {
+    match (stats, total) {
+        (Some(stats), Some(total)) => Some(StatsResponse {
+            stats: Stats::from(stats),
+            total,
+        }),
+        _ => None,
+    }
+}
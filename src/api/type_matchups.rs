@@ -0,0 +1,65 @@
+use std::sync::Arc;
+
+use serde::Serialize;
+
+use crate::{domain, repositories::pokemon::Repository};
+
+use super::problem::{self, Problem};
+
+#[derive(Serialize)]
+struct Response {
+    number: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    form: Option<String>,
+    #[serde(rename = "4x")]
+    quadruple: Vec<String>,
+    #[serde(rename = "2x")]
+    double: Vec<String>,
+    #[serde(rename = "1x")]
+    neutral: Vec<String>,
+    #[serde(rename = "0.5x")]
+    half: Vec<String>,
+    #[serde(rename = "0.25x")]
+    quarter: Vec<String>,
+    #[serde(rename = "0x")]
+    immune: Vec<String>,
+}
+
+pub fn serve(
+    repo: Arc<dyn Repository>,
+    number: u16,
+    req: &rouille::Request,
+) -> Result<rouille::Response, Problem> {
+    let form = req.get_param("form");
+    let req = domain::type_matchups::Request {
+        number,
+        form: form.clone(),
+    };
+    match domain::type_matchups::execute(repo, req) {
+        Ok(domain::type_matchups::Response {
+            number,
+            form,
+            quadruple,
+            double,
+            neutral,
+            half,
+            quarter,
+            immune,
+        }) => Ok(rouille::Response::json(&Response {
+            number,
+            form,
+            quadruple,
+            double,
+            neutral,
+            half,
+            quarter,
+            immune,
+        })),
+        Err(domain::type_matchups::Error::BadRequest(errors)) => Err(Problem::invalid(errors)),
+        Err(domain::type_matchups::Error::NotFound) => Err(Problem::not_found(format!(
+            "{} does not exist",
+            problem::pokemon(number, form.as_deref())
+        ))),
+        Err(domain::type_matchups::Error::Unknown(e)) => Err(Problem::internal(e)),
+    }
+}
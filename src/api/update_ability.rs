@@ -0,0 +1,43 @@
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{domain, repositories::ability::AbilityRepository};
+
+use super::problem::Problem;
+
+#[derive(Serialize)]
+struct Response {
+    name: String,
+    description: String,
+}
+
+#[derive(Deserialize)]
+struct Request {
+    description: String,
+}
+
+pub fn serve(
+    repo: Arc<dyn AbilityRepository>,
+    name: String,
+    req: &rouille::Request,
+) -> Result<rouille::Response, Problem> {
+    let req = match rouille::input::json_input::<Request>(req) {
+        Ok(req) => domain::update_ability::Request {
+            name: name.clone(),
+            description: req.description,
+        },
+        Err(e) => return Err(Problem::malformed_body(e)),
+    };
+    match domain::update_ability::execute(repo, req) {
+        Ok(domain::update_ability::Response { name, description }) => {
+            Ok(rouille::Response::json(&Response { name, description }))
+        }
+        Err(domain::update_ability::Error::BadRequest(errors)) => Err(Problem::invalid(errors)),
+        Err(domain::update_ability::Error::NotFound) => Err(Problem::not_found(format!(
+            "ability {:?} does not exist",
+            name
+        ))),
+        Err(domain::update_ability::Error::Unknown(e)) => Err(Problem::internal(e)),
+    }
+}
@@ -0,0 +1,99 @@
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{domain, repositories::pokemon::Repository};
+
+use super::{
+    problem::{self, Problem},
+    stats,
+};
+
+#[derive(Serialize)]
+struct Response {
+    number: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    form: Option<String>,
+    name: String,
+    types: Vec<String>,
+    stats: Option<stats::StatsResponse>,
+}
+
+#[derive(Deserialize)]
+struct ReplaceRequest {
+    name: String,
+    types: Vec<String>,
+    stats: Option<stats::Stats>,
+}
+
+#[derive(Deserialize)]
+struct PatchRequest {
+    name: Option<String>,
+    types: Option<Vec<String>>,
+    stats: Option<stats::Stats>,
+}
+
+pub fn serve_replace(
+    repo: Arc<dyn Repository>,
+    number: u16,
+    req: &rouille::Request,
+) -> Result<rouille::Response, Problem> {
+    let req = match rouille::input::json_input::<ReplaceRequest>(req) {
+        Ok(body) => domain::update_pokemon::Request {
+            number,
+            form: req.get_param("form"),
+            name: Some(body.name),
+            types: Some(body.types),
+            stats: body.stats.map(domain::Stats::from),
+        },
+        Err(e) => return Err(Problem::malformed_body(e)),
+    };
+    serve(repo, req)
+}
+
+pub fn serve_patch(
+    repo: Arc<dyn Repository>,
+    number: u16,
+    req: &rouille::Request,
+) -> Result<rouille::Response, Problem> {
+    let req = match rouille::input::json_input::<PatchRequest>(req) {
+        Ok(body) => domain::update_pokemon::Request {
+            number,
+            form: req.get_param("form"),
+            name: body.name,
+            types: body.types,
+            stats: body.stats.map(domain::Stats::from),
+        },
+        Err(e) => return Err(Problem::malformed_body(e)),
+    };
+    serve(repo, req)
+}
+
+fn serve(
+    repo: Arc<dyn Repository>,
+    req: domain::update_pokemon::Request,
+) -> Result<rouille::Response, Problem> {
+    let (number, form) = (req.number, req.form.clone());
+    match domain::update_pokemon::execute(repo, req) {
+        Ok(domain::update_pokemon::Response {
+            number,
+            form,
+            name,
+            types,
+            stats,
+            total,
+        }) => Ok(rouille::Response::json(&Response {
+            number,
+            form,
+            name,
+            types,
+            stats: stats::response(stats, total),
+        })),
+        Err(domain::update_pokemon::Error::BadRequest(errors)) => Err(Problem::invalid(errors)),
+        Err(domain::update_pokemon::Error::NotFound) => Err(Problem::not_found(format!(
+            "{} does not exist",
+            problem::pokemon(number, form.as_deref())
+        ))),
+        Err(domain::update_pokemon::Error::Unknown(e)) => Err(Problem::internal(e)),
+    }
+}
@@ -0,0 +1,78 @@
+use std::sync::Arc;
+
+use serde::Serialize;
+
+use crate::{
+    domain,
+    repositories::{evolution::EvolutionRepository, pokemon::Repository},
+};
+
+use super::{
+    optional_cell,
+    output::{self, Record},
+    print_invalid, Format, Status,
+};
+
+#[derive(Serialize)]
+struct Evolution {
+    from: u16,
+    to: u16,
+    trigger: String,
+    level: Option<u16>,
+    item: Option<String>,
+}
+
+impl Record for Evolution {
+    fn headers() -> &'static [&'static str] {
+        &["from", "to", "trigger", "level", "item"]
+    }
+
+    fn cells(&self) -> Vec<String> {
+        vec![
+            self.from.to_string(),
+            self.to.to_string(),
+            self.trigger.clone(),
+            optional_cell(self.level),
+            self.item.clone().unwrap_or_default(),
+        ]
+    }
+}
+
+pub fn execute(
+    repo: Arc<dyn Repository>,
+    evolutions: Arc<dyn EvolutionRepository>,
+    format: Format,
+    req: domain::add_evolution::Request,
+) -> Status {
+    match domain::add_evolution::execute(repo, evolutions, req) {
+        Ok(res) => {
+            output::print_one(
+                format,
+                &Evolution {
+                    from: res.from,
+                    to: res.to,
+                    trigger: res.trigger,
+                    level: res.level,
+                    item: res.item,
+                },
+            );
+            Status::Ok
+        }
+        Err(domain::add_evolution::Error::BadRequest(errors)) => {
+            print_invalid(&errors);
+            Status::BadRequest
+        }
+        Err(domain::add_evolution::Error::NotFound) => {
+            eprintln!("The Pokemon does not exist");
+            Status::NotFound
+        }
+        Err(domain::add_evolution::Error::Conflict) => {
+            eprintln!("The evolved Pokemon already evolves from another one");
+            Status::Conflict
+        }
+        Err(domain::add_evolution::Error::Unknown(e)) => {
+            eprintln!("An unknown error occurred: {}", e.chain());
+            Status::Unknown
+        }
+    }
+}
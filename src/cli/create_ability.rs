@@ -0,0 +1,38 @@
+use std::sync::Arc;
+
+use crate::{domain, repositories::ability::AbilityRepository};
+
+use super::{output, print_invalid, Ability, Format, Status};
+
+pub fn execute(
+    repo: Arc<dyn AbilityRepository>,
+    format: Format,
+    name: String,
+    description: String,
+) -> Status {
+    let req = domain::create_ability::Request { name, description };
+    match domain::create_ability::execute(repo, req) {
+        Ok(res) => {
+            output::print_one(
+                format,
+                &Ability {
+                    name: res.name,
+                    description: res.description,
+                },
+            );
+            Status::Ok
+        }
+        Err(domain::create_ability::Error::BadRequest(errors)) => {
+            print_invalid(&errors);
+            Status::BadRequest
+        }
+        Err(domain::create_ability::Error::Conflict) => {
+            eprintln!("The ability already exists");
+            Status::Conflict
+        }
+        Err(domain::create_ability::Error::Unknown(e)) => {
+            eprintln!("An unknown error occurred: {}", e.chain());
+            Status::Unknown
+        }
+    }
+}
@@ -0,0 +1,40 @@
+use std::sync::Arc;
+
+use crate::{domain, repositories::moves::MoveRepository};
+
+use super::{output, print_invalid, Format, Move, Status};
+
+pub fn execute(
+    repo: Arc<dyn MoveRepository>,
+    format: Format,
+    req: domain::create_move::Request,
+) -> Status {
+    match domain::create_move::execute(repo, req) {
+        Ok(res) => {
+            output::print_one(
+                format,
+                &Move {
+                    name: res.name,
+                    r#type: res.r#type,
+                    category: res.category,
+                    power: res.power,
+                    accuracy: res.accuracy,
+                    pp: res.pp,
+                },
+            );
+            Status::Ok
+        }
+        Err(domain::create_move::Error::BadRequest(errors)) => {
+            print_invalid(&errors);
+            Status::BadRequest
+        }
+        Err(domain::create_move::Error::Conflict) => {
+            eprintln!("The move already exists");
+            Status::Conflict
+        }
+        Err(domain::create_move::Error::Unknown(e)) => {
+            eprintln!("An unknown error occurred: {}", e.chain());
+            Status::Unknown
+        }
+    }
+}
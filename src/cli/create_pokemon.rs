@@ -2,40 +2,66 @@ use std::sync::Arc;
 
 use crate::{domain, repositories::pokemon::Repository};
 
-use super::{prompt_name, prompt_number, prompt_types};
-#[derive(Debug)]
-struct Response {
-    number: u16,
-    name: String,
-    types: Vec<String>,
-}
+use super::{
+    output, print_invalid, prompt_form, prompt_name, prompt_number, prompt_stats, prompt_types,
+    Format, Pokemon, Stats, Status,
+};
 
-pub fn run(repo: Arc<dyn Repository>) {
+pub fn run(repo: Arc<dyn Repository>, format: Format) {
     let number = prompt_number();
+    let form = prompt_form();
     let name = prompt_name();
     let types = prompt_types();
-    let req = match (number, name, types) {
-        (Ok(number), Ok(name), Ok(types)) => domain::create_pokemon::Request {
-            number,
-            name,
-            types,
-        },
-        _ => {
-            println!("An error occurred during the prompt");
-            return;
+    let stats = prompt_stats();
+    match (number, form, name, types, stats) {
+        (Ok(number), Ok(form), Ok(name), Ok(types), Ok(stats)) => {
+            execute(repo, format, number, form, name, types, stats);
         }
+        _ => println!("An error occurred during the prompt"),
+    };
+}
+
+pub fn execute(
+    repo: Arc<dyn Repository>,
+    format: Format,
+    number: u16,
+    form: Option<String>,
+    name: String,
+    types: Vec<String>,
+    stats: Option<domain::Stats>,
+) -> Status {
+    let req = domain::create_pokemon::Request {
+        number,
+        form,
+        name,
+        types,
+        stats,
     };
     match domain::create_pokemon::execute(repo, req) {
-        Ok(res) => println!(
-            "{:?}",
-            Response {
-                number: res.number,
-                name: res.name,
-                types: res.types,
-            }
-        ),
-        Err(domain::create_pokemon::Error::BadRequest) => println!("The request is invalid"),
-        Err(domain::create_pokemon::Error::Conflict) => println!("The Pokemon already exists"),
-        Err(domain::create_pokemon::Error::Unknown) => println!("An unknown error occurred"),
+        Ok(res) => {
+            output::print_one(
+                format,
+                &Pokemon {
+                    number: res.number,
+                    form: res.form,
+                    name: res.name,
+                    types: res.types,
+                    stats: Stats::new(res.stats, res.total),
+                },
+            );
+            Status::Ok
+        }
+        Err(domain::create_pokemon::Error::BadRequest(errors)) => {
+            print_invalid(&errors);
+            Status::BadRequest
+        }
+        Err(domain::create_pokemon::Error::Conflict) => {
+            eprintln!("The Pokemon already exists");
+            Status::Conflict
+        }
+        Err(domain::create_pokemon::Error::Unknown(e)) => {
+            eprintln!("An unknown error occurred: {}", e.chain());
+            Status::Unknown
+        }
     }
 }
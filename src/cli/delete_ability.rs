@@ -0,0 +1,24 @@
+use std::sync::Arc;
+
+use crate::{domain, repositories::ability::AbilityRepository};
+
+use super::{print_invalid, Status};
+
+pub fn execute(repo: Arc<dyn AbilityRepository>, name: String) -> Status {
+    let req = domain::delete_ability::Request { name };
+    match domain::delete_ability::execute(repo, req) {
+        Ok(()) => Status::Ok,
+        Err(domain::delete_ability::Error::BadRequest(errors)) => {
+            print_invalid(&errors);
+            Status::BadRequest
+        }
+        Err(domain::delete_ability::Error::NotFound) => {
+            eprintln!("The ability does not exist");
+            Status::NotFound
+        }
+        Err(domain::delete_ability::Error::Unknown(e)) => {
+            eprintln!("An unknown error occurred: {}", e.chain());
+            Status::Unknown
+        }
+    }
+}
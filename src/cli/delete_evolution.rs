@@ -0,0 +1,24 @@
+use std::sync::Arc;
+
+use crate::{domain, repositories::evolution::EvolutionRepository};
+
+use super::{print_invalid, Status};
+
+pub fn execute(evolutions: Arc<dyn EvolutionRepository>, from: u16, to: u16) -> Status {
+    let req = domain::delete_evolution::Request { from, to };
+    match domain::delete_evolution::execute(evolutions, req) {
+        Ok(()) => Status::Ok,
+        Err(domain::delete_evolution::Error::BadRequest(errors)) => {
+            print_invalid(&errors);
+            Status::BadRequest
+        }
+        Err(domain::delete_evolution::Error::NotFound) => {
+            eprintln!("The Pokemon does not evolve this way");
+            Status::NotFound
+        }
+        Err(domain::delete_evolution::Error::Unknown(e)) => {
+            eprintln!("An unknown error occurred: {}", e.chain());
+            Status::Unknown
+        }
+    }
+}
@@ -2,27 +2,32 @@ use std::sync::Arc;
 
 use crate::{domain, repositories::pokemon::Repository};
 
-use super::prompt_number;
-#[derive(Debug)]
-struct Response {
-    number: u16,
-    name: String,
-    types: Vec<String>,
-}
+use super::{print_invalid, prompt_form, prompt_number, Status};
 
 pub fn run(repo: Arc<dyn Repository>) {
-    let number = prompt_number();
-    let req = match number {
-        Ok(number) => domain::delete_pokemon::Request { number },
-        _ => {
-            println!("An error occurred during the prompt");
-            return;
+    match (prompt_number(), prompt_form()) {
+        (Ok(number), Ok(form)) => {
+            execute(repo, number, form);
         }
+        _ => println!("An error occurred during the prompt"),
     };
+}
+
+pub fn execute(repo: Arc<dyn Repository>, number: u16, form: Option<String>) -> Status {
+    let req = domain::delete_pokemon::Request { number, form };
     match domain::delete_pokemon::execute(repo, req) {
-        Ok(_) => {}
-        Err(domain::delete_pokemon::Error::BadRequest) => println!("The request is invalid"),
-        Err(domain::delete_pokemon::Error::NotFound) => println!("The Pokemon doexn;t exists"),
-        Err(domain::delete_pokemon::Error::Unknown) => println!("An unknown error occurred"),
+        Ok(_) => Status::Ok,
+        Err(domain::delete_pokemon::Error::BadRequest(errors)) => {
+            print_invalid(&errors);
+            Status::BadRequest
+        }
+        Err(domain::delete_pokemon::Error::NotFound) => {
+            eprintln!("The Pokemon does not exist");
+            Status::NotFound
+        }
+        Err(domain::delete_pokemon::Error::Unknown(e)) => {
+            eprintln!("An unknown error occurred: {}", e.chain());
+            Status::Unknown
+        }
     }
 }
@@ -0,0 +1,225 @@
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    sync::Arc,
+    time::SystemTime,
+};
+
+use clap::ArgEnum;
+
+use crate::{domain, repositories::pokemon::Repository};
+
+use super::{
+    output::{self, Record},
+    Pokemon, Stats, Status,
+};
+
+/// Version of the backup layout, bumped whenever it changes incompatibly.
+pub const BACKUP_VERSION: u32 = 1;
+pub const BACKUP_FORMAT: &str = "pokedex-backup";
+
+#[derive(ArgEnum, Clone, Copy, Debug)]
+pub enum ExportFormat {
+    Jsonl,
+    Csv,
+    Backup,
+}
+
+pub fn execute(repo: Arc<dyn Repository>, format: ExportFormat, file: Option<&str>) -> Status {
+    let mut out: Box<dyn Write> = match file {
+        Some(file) => match File::create(file) {
+            Ok(file) => Box::new(BufWriter::new(file)),
+            Err(e) => {
+                eprintln!("Cannot create {}: {}", file, e);
+                return Status::BadRequest;
+            }
+        },
+        None => Box::new(BufWriter::new(io::stdout())),
+    };
+    let res = match format {
+        ExportFormat::Jsonl => export_jsonl(repo, &mut out),
+        ExportFormat::Csv => export_csv(repo, &mut out),
+        ExportFormat::Backup => export_backup(repo, &mut out, SystemTime::now()),
+    };
+    match (res, out.flush()) {
+        (Ok(count), Ok(())) => {
+            eprintln!("Exported {} pokemons", count);
+            Status::Ok
+        }
+        (Err(domain::export_pokemons::Error::Unknown(e)), _) => {
+            eprintln!("An unknown error occurred: {}", e.chain());
+            Status::Unknown
+        }
+        (Err(domain::export_pokemons::Error::Sink), _) | (_, Err(_)) => {
+            eprintln!("An error occurred while writing the export");
+            Status::Unknown
+        }
+    }
+}
+
+fn pokemon(res: domain::export_pokemons::Response) -> Pokemon {
+    Pokemon {
+        number: res.number,
+        form: res.form,
+        name: res.name,
+        types: res.types,
+        stats: Stats::new(res.stats, res.total),
+    }
+}
+
+fn export_jsonl(
+    repo: Arc<dyn Repository>,
+    out: &mut dyn Write,
+) -> Result<usize, domain::export_pokemons::Error> {
+    domain::export_pokemons::execute(repo, &mut |res| {
+        let line = serde_json::to_string(&pokemon(res)).map_err(|_| ())?;
+        writeln!(out, "{}", line).map_err(|_| ())
+    })
+}
+
+/// Writes the same columns as `--output csv`, so the file can be imported back.
+fn export_csv(
+    repo: Arc<dyn Repository>,
+    out: &mut dyn Write,
+) -> Result<usize, domain::export_pokemons::Error> {
+    let mut writer = match output::csv_writer::<Pokemon, _>(out) {
+        Ok(writer) => writer,
+        Err(_) => return Err(domain::export_pokemons::Error::Sink),
+    };
+    let count = domain::export_pokemons::execute(repo, &mut |res| {
+        writer.write_record(pokemon(res).cells()).map_err(|_| ())
+    })?;
+    match writer.flush() {
+        Ok(()) => Ok(count),
+        Err(_) => Err(domain::export_pokemons::Error::Sink),
+    }
+}
+
+/// Writes a single JSON document, the metadata first then the pokemons one
+/// by one so the whole repository never has to fit in memory.
+fn export_backup(
+    repo: Arc<dyn Repository>,
+    out: &mut dyn Write,
+    now: SystemTime,
+) -> Result<usize, domain::export_pokemons::Error> {
+    let header = write!(
+        out,
+        "{{\"format\":\"{}\",\"version\":{},\"exported_at\":\"{}\",\"pokemons\":[",
+        BACKUP_FORMAT,
+        BACKUP_VERSION,
+        humantime::format_rfc3339_seconds(now)
+    );
+    if header.is_err() {
+        return Err(domain::export_pokemons::Error::Sink);
+    }
+    let mut first = true;
+    let count = domain::export_pokemons::execute(repo, &mut |res| {
+        let separator = if first { "\n" } else { ",\n" };
+        first = false;
+        match (
+            out.write_all(separator.as_bytes()),
+            serde_json::to_string(&pokemon(res)),
+        ) {
+            (Ok(()), Ok(json)) => out.write_all(json.as_bytes()).map_err(|_| ()),
+            _ => Err(()),
+        }
+    })?;
+    match writeln!(out, "\n],\"count\":{}}}", count) {
+        Ok(()) => Ok(count),
+        Err(_) => Err(domain::export_pokemons::Error::Sink),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use super::*;
+    use crate::domain::entities::{
+        BaseStats, PokemonForm, PokemonName, PokemonNumber, PokemonTypes,
+    };
+    use crate::repositories::pokemon::InMemoryRepository;
+
+    fn repo() -> Arc<InMemoryRepository> {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonForm::default(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+            Some(BaseStats::pikachu()),
+        )
+        .ok();
+        repo.insert(
+            PokemonNumber::charizard(),
+            PokemonForm::default(),
+            PokemonName::charizard(),
+            PokemonTypes::charizard(),
+            None,
+        )
+        .ok();
+        repo
+    }
+
+    #[test]
+    fn it_should_export_one_json_object_per_line() {
+        let mut out = vec![];
+
+        let res = export_jsonl(repo(), &mut out);
+
+        assert!(matches!(res, Ok(2)));
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "{\"number\":6,\"name\":\"Charizard\",\"types\":[\"Fire\",\"Flying\"],\"stats\":null}\n\
+             {\"number\":25,\"name\":\"Pikachu\",\"types\":[\"Electric\"],\"stats\":{\"hp\":35,\"attack\":55,\"defense\":40,\
+             \"special_attack\":50,\"special_defense\":50,\"speed\":90,\"total\":320}}\n"
+        );
+    }
+
+    #[test]
+    fn it_should_export_a_csv_with_a_header() {
+        let mut out = vec![];
+
+        let res = export_csv(repo(), &mut out);
+
+        assert!(matches!(res, Ok(2)));
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "number,form,name,types,stats,total\n6,,Charizard,\"Fire, Flying\",,\n25,,Pikachu,Electric,35/55/40/50/50/90,320\n"
+        );
+    }
+
+    #[test]
+    fn it_should_export_a_backup_with_its_metadata() {
+        let mut out = vec![];
+
+        let res = export_backup(
+            repo(),
+            &mut out,
+            UNIX_EPOCH + Duration::from_secs(1_650_000_000),
+        );
+
+        assert!(matches!(res, Ok(2)));
+        let backup = serde_json::from_slice::<serde_json::Value>(&out).unwrap();
+        assert_eq!(backup["format"], "pokedex-backup");
+        assert_eq!(backup["version"], 1);
+        assert_eq!(backup["exported_at"], "2022-04-15T05:20:00Z");
+        assert_eq!(backup["count"], 2);
+        assert_eq!(backup["pokemons"][1]["name"], "Pikachu");
+    }
+
+    #[test]
+    fn it_should_export_an_empty_backup() {
+        let mut out = vec![];
+
+        export_backup(
+            Arc::new(InMemoryRepository::new()),
+            &mut out,
+            SystemTime::now(),
+        )
+        .ok();
+
+        let backup = serde_json::from_slice::<serde_json::Value>(&out).unwrap();
+        assert_eq!(backup["pokemons"], serde_json::json!([]));
+    }
+}
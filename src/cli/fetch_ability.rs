@@ -0,0 +1,33 @@
+use std::sync::Arc;
+
+use crate::{domain, repositories::ability::AbilityRepository};
+
+use super::{output, print_invalid, Ability, Format, Status};
+
+pub fn execute(repo: Arc<dyn AbilityRepository>, format: Format, name: String) -> Status {
+    let req = domain::fetch_ability::Request { name };
+    match domain::fetch_ability::execute(repo, req) {
+        Ok(res) => {
+            output::print_one(
+                format,
+                &Ability {
+                    name: res.name,
+                    description: res.description,
+                },
+            );
+            Status::Ok
+        }
+        Err(domain::fetch_ability::Error::BadRequest(errors)) => {
+            print_invalid(&errors);
+            Status::BadRequest
+        }
+        Err(domain::fetch_ability::Error::NotFound) => {
+            eprintln!("The ability does not exist");
+            Status::NotFound
+        }
+        Err(domain::fetch_ability::Error::Unknown(e)) => {
+            eprintln!("An unknown error occurred: {}", e.chain());
+            Status::Unknown
+        }
+    }
+}
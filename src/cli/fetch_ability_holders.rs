@@ -0,0 +1,84 @@
+use std::sync::Arc;
+
+use dialoguer::Input;
+use serde::Serialize;
+
+use crate::{
+    domain,
+    repositories::{ability::AbilityRepository, pokemon::Repository},
+};
+
+use super::{
+    output::{self, Record},
+    print_invalid, Format, Status,
+};
+
+#[derive(Serialize)]
+struct Holder {
+    number: u16,
+    name: String,
+    hidden: bool,
+}
+
+impl Record for Holder {
+    fn headers() -> &'static [&'static str] {
+        &["number", "name", "hidden"]
+    }
+
+    fn cells(&self) -> Vec<String> {
+        vec![
+            self.number.to_string(),
+            self.name.clone(),
+            String::from(if self.hidden { "yes" } else { "no" }),
+        ]
+    }
+}
+
+pub fn run(repo: Arc<dyn Repository>, abilities: Arc<dyn AbilityRepository>, format: Format) {
+    match Input::<String>::new()
+        .with_prompt("Ability name")
+        .interact_text()
+    {
+        Ok(name) => {
+            execute(repo, abilities, format, name);
+        }
+        _ => println!("An error occurred during the prompt"),
+    };
+}
+
+pub fn execute(
+    repo: Arc<dyn Repository>,
+    abilities: Arc<dyn AbilityRepository>,
+    format: Format,
+    name: String,
+) -> Status {
+    let req = domain::fetch_ability_holders::Request { name };
+    match domain::fetch_ability_holders::execute(repo, abilities, req) {
+        Ok(res) => {
+            output::print_all(
+                format,
+                &res.pokemons
+                    .into_iter()
+                    .map(|p| Holder {
+                        number: p.number,
+                        name: p.name,
+                        hidden: p.hidden,
+                    })
+                    .collect::<Vec<Holder>>(),
+            );
+            Status::Ok
+        }
+        Err(domain::fetch_ability_holders::Error::BadRequest(errors)) => {
+            print_invalid(&errors);
+            Status::BadRequest
+        }
+        Err(domain::fetch_ability_holders::Error::NotFound) => {
+            eprintln!("The ability does not exist");
+            Status::NotFound
+        }
+        Err(domain::fetch_ability_holders::Error::Unknown(e)) => {
+            eprintln!("An unknown error occurred: {}", e.chain());
+            Status::Unknown
+        }
+    }
+}
@@ -0,0 +1,26 @@
+use std::sync::Arc;
+
+use crate::{domain, repositories::ability::AbilityRepository};
+
+use super::{output, Ability, Format, Status};
+
+pub fn execute(repo: Arc<dyn AbilityRepository>, format: Format) -> Status {
+    match domain::fetch_all_abilities::execute(repo) {
+        Ok(res) => {
+            output::print_all(
+                format,
+                &res.into_iter()
+                    .map(|a| Ability {
+                        name: a.name,
+                        description: a.description,
+                    })
+                    .collect::<Vec<Ability>>(),
+            );
+            Status::Ok
+        }
+        Err(domain::fetch_all_abilities::Error::Unknown(e)) => {
+            eprintln!("An unknown error occurred: {}", e.chain());
+            Status::Unknown
+        }
+    }
+}
@@ -0,0 +1,30 @@
+use std::sync::Arc;
+
+use crate::{domain, repositories::moves::MoveRepository};
+
+use super::{output, Format, Move, Status};
+
+pub fn execute(repo: Arc<dyn MoveRepository>, format: Format) -> Status {
+    match domain::fetch_all_moves::execute(repo) {
+        Ok(res) => {
+            output::print_all(
+                format,
+                &res.into_iter()
+                    .map(|m| Move {
+                        name: m.name,
+                        r#type: m.r#type,
+                        category: m.category,
+                        power: m.power,
+                        accuracy: m.accuracy,
+                        pp: m.pp,
+                    })
+                    .collect::<Vec<Move>>(),
+            );
+            Status::Ok
+        }
+        Err(domain::fetch_all_moves::Error::Unknown(e)) => {
+            eprintln!("An unknown error occurred: {}", e.chain());
+            Status::Unknown
+        }
+    }
+}
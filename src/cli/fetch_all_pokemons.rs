@@ -2,27 +2,49 @@ use std::sync::Arc;
 
 use crate::{domain, repositories::pokemon::Repository};
 
-#[derive(Debug)]
-struct Response {
-    number: u16,
-    name: String,
-    types: Vec<String>,
+use super::{output, print_invalid, Format, Pokemon, Stats, Status};
+
+pub fn run(repo: Arc<dyn Repository>, format: Format) {
+    execute(repo, format, None, None, false);
 }
 
-pub fn run(repo: Arc<dyn Repository>) {
-    match domain::fetch_all_pokemons::execute(repo) {
+pub fn execute(
+    repo: Arc<dyn Repository>,
+    format: Format,
+    sort: Option<String>,
+    direction: Option<String>,
+    all_forms: bool,
+) -> Status {
+    let req = domain::fetch_all_pokemons::Request {
+        sort,
+        direction,
+        forms: all_forms.then(|| String::from("all")),
+        ..domain::fetch_all_pokemons::Request::default()
+    };
+    match domain::fetch_all_pokemons::execute(repo, req) {
         Ok(res) => {
-            for res in res {
-                println!(
-                    "{:?}",
-                    Response {
-                        number: res.number,
-                        name: res.name,
-                        types: res.types,
-                    }
-                )
-            }
+            output::print_all(
+                format,
+                &res.pokemons
+                    .into_iter()
+                    .map(|p| Pokemon {
+                        number: p.number,
+                        form: p.form,
+                        name: p.name,
+                        types: p.types,
+                        stats: Stats::new(p.stats, p.total),
+                    })
+                    .collect::<Vec<Pokemon>>(),
+            );
+            Status::Ok
+        }
+        Err(domain::fetch_all_pokemons::Error::BadRequest(errors)) => {
+            print_invalid(&errors);
+            Status::BadRequest
+        }
+        Err(domain::fetch_all_pokemons::Error::Unknown(e)) => {
+            eprintln!("An unknown error occurred: {}", e.chain());
+            Status::Unknown
         }
-        Err(domain::fetch_all_pokemons::Error::Unknown) => println!("An unknown error occurred"),
     }
 }
@@ -0,0 +1,106 @@
+use std::sync::Arc;
+
+use serde::Serialize;
+
+use crate::{
+    domain::{self, fetch_evolution_chain::Stage},
+    repositories::{evolution::EvolutionRepository, pokemon::Repository},
+};
+
+use super::{
+    optional_cell,
+    output::{self, Record},
+    print_invalid, prompt_form, prompt_number, Format, Status,
+};
+
+/// A stage of the chain, listed after the one it evolves from.
+#[derive(Serialize)]
+struct ChainStage {
+    stage: u16,
+    number: u16,
+    name: String,
+    from: Option<u16>,
+    trigger: Option<String>,
+    level: Option<u16>,
+    item: Option<String>,
+}
+
+impl Record for ChainStage {
+    fn headers() -> &'static [&'static str] {
+        &[
+            "stage", "number", "name", "from", "trigger", "level", "item",
+        ]
+    }
+
+    fn cells(&self) -> Vec<String> {
+        vec![
+            self.stage.to_string(),
+            self.number.to_string(),
+            self.name.clone(),
+            optional_cell(self.from),
+            self.trigger.clone().unwrap_or_default(),
+            optional_cell(self.level),
+            self.item.clone().unwrap_or_default(),
+        ]
+    }
+}
+
+/// Lists the stages depth first, the base form being the first stage.
+fn flatten(stage: Stage, depth: u16, from: Option<u16>, stages: &mut Vec<ChainStage>) {
+    let number = stage.number;
+    let (trigger, level, item) = match stage.trigger {
+        Some(t) => (Some(t.name), t.level, t.item),
+        None => (None, None, None),
+    };
+    stages.push(ChainStage {
+        stage: depth,
+        number,
+        name: stage.name,
+        from,
+        trigger,
+        level,
+        item,
+    });
+    for evolution in stage.evolutions {
+        flatten(evolution, depth + 1, Some(number), stages);
+    }
+}
+
+pub fn run(repo: Arc<dyn Repository>, evolutions: Arc<dyn EvolutionRepository>, format: Format) {
+    match (prompt_number(), prompt_form()) {
+        (Ok(number), Ok(form)) => {
+            execute(repo, evolutions, format, number, form);
+        }
+        _ => println!("An error occurred during the prompt"),
+    };
+}
+
+pub fn execute(
+    repo: Arc<dyn Repository>,
+    evolutions: Arc<dyn EvolutionRepository>,
+    format: Format,
+    number: u16,
+    form: Option<String>,
+) -> Status {
+    let req = domain::fetch_evolution_chain::Request { number, form };
+    match domain::fetch_evolution_chain::execute(repo, evolutions, req) {
+        Ok(res) => {
+            let mut stages = vec![];
+            flatten(res.base, 1, None, &mut stages);
+            output::print_all(format, &stages);
+            Status::Ok
+        }
+        Err(domain::fetch_evolution_chain::Error::BadRequest(errors)) => {
+            print_invalid(&errors);
+            Status::BadRequest
+        }
+        Err(domain::fetch_evolution_chain::Error::NotFound) => {
+            eprintln!("The Pokemon does not exist");
+            Status::NotFound
+        }
+        Err(domain::fetch_evolution_chain::Error::Unknown(e)) => {
+            eprintln!("An unknown error occurred: {}", e.chain());
+            Status::Unknown
+        }
+    }
+}
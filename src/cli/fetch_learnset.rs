@@ -0,0 +1,99 @@
+use std::sync::Arc;
+
+use serde::Serialize;
+
+use crate::{
+    domain,
+    repositories::{moves::MoveRepository, pokemon::Repository},
+};
+
+use super::{
+    optional_cell,
+    output::{self, Record},
+    print_invalid, prompt_form, prompt_number, Format, Status,
+};
+
+#[derive(Serialize)]
+struct LearnedMove {
+    name: String,
+    method: String,
+    level: Option<u16>,
+    r#type: String,
+    category: String,
+    power: Option<u16>,
+    accuracy: Option<u16>,
+    pp: u16,
+}
+
+impl Record for LearnedMove {
+    fn headers() -> &'static [&'static str] {
+        &[
+            "name", "method", "level", "type", "category", "power", "accuracy", "pp",
+        ]
+    }
+
+    fn cells(&self) -> Vec<String> {
+        vec![
+            self.name.clone(),
+            self.method.clone(),
+            optional_cell(self.level),
+            self.r#type.clone(),
+            self.category.clone(),
+            optional_cell(self.power),
+            optional_cell(self.accuracy),
+            self.pp.to_string(),
+        ]
+    }
+}
+
+pub fn run(repo: Arc<dyn Repository>, moves: Arc<dyn MoveRepository>, format: Format) {
+    match (prompt_number(), prompt_form()) {
+        (Ok(number), Ok(form)) => {
+            execute(repo, moves, format, number, form);
+        }
+        _ => println!("An error occurred during the prompt"),
+    };
+}
+
+pub fn execute(
+    repo: Arc<dyn Repository>,
+    moves: Arc<dyn MoveRepository>,
+    format: Format,
+    number: u16,
+    form: Option<String>,
+) -> Status {
+    let req = domain::fetch_learnset::Request { number, form };
+    match domain::fetch_learnset::execute(repo, moves, req) {
+        Ok(res) => {
+            output::print_all(
+                format,
+                &res.moves
+                    .into_iter()
+                    .map(|m| LearnedMove {
+                        name: m.name,
+                        method: m.method,
+                        level: m.level,
+                        r#type: m.r#type,
+                        category: m.category,
+                        power: m.power,
+                        accuracy: m.accuracy,
+                        pp: m.pp,
+                    })
+                    .collect::<Vec<LearnedMove>>(),
+            );
+            Status::Ok
+        }
+        Err(domain::fetch_learnset::Error::BadRequest(errors)) => {
+            print_invalid(&errors);
+            Status::BadRequest
+        }
+        Err(domain::fetch_learnset::Error::NotFound) => {
+            eprintln!("The Pokemon does not exist");
+            Status::NotFound
+        }
+        Err(domain::fetch_learnset::Error::Unknown(e)) => {
+            eprintln!("An unknown error occurred: {}", e.chain());
+            Status::Unknown
+        }
+    }
+}
@@ -0,0 +1,37 @@
+use std::sync::Arc;
+
+use crate::{domain, repositories::moves::MoveRepository};
+
+use super::{output, print_invalid, Format, Move, Status};
+
+pub fn execute(repo: Arc<dyn MoveRepository>, format: Format, name: String) -> Status {
+    let req = domain::fetch_move::Request { name };
+    match domain::fetch_move::execute(repo, req) {
+        Ok(res) => {
+            output::print_one(
+                format,
+                &Move {
+                    name: res.name,
+                    r#type: res.r#type,
+                    category: res.category,
+                    power: res.power,
+                    accuracy: res.accuracy,
+                    pp: res.pp,
+                },
+            );
+            Status::Ok
+        }
+        Err(domain::fetch_move::Error::BadRequest(errors)) => {
+            print_invalid(&errors);
+            Status::BadRequest
+        }
+        Err(domain::fetch_move::Error::NotFound) => {
+            eprintln!("The move does not exist");
+            Status::NotFound
+        }
+        Err(domain::fetch_move::Error::Unknown(e)) => {
+            eprintln!("An unknown error occurred: {}", e.chain());
+            Status::Unknown
+        }
+    }
+}
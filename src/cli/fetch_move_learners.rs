@@ -0,0 +1,88 @@
+use std::sync::Arc;
+
+use dialoguer::Input;
+use serde::Serialize;
+
+use crate::{
+    domain,
+    repositories::{moves::MoveRepository, pokemon::Repository},
+};
+
+use super::{
+    optional_cell,
+    output::{self, Record},
+    print_invalid, Format, Status,
+};
+
+#[derive(Serialize)]
+struct Learner {
+    number: u16,
+    name: String,
+    method: String,
+    level: Option<u16>,
+}
+
+impl Record for Learner {
+    fn headers() -> &'static [&'static str] {
+        &["number", "name", "method", "level"]
+    }
+
+    fn cells(&self) -> Vec<String> {
+        vec![
+            self.number.to_string(),
+            self.name.clone(),
+            self.method.clone(),
+            optional_cell(self.level),
+        ]
+    }
+}
+
+pub fn run(repo: Arc<dyn Repository>, moves: Arc<dyn MoveRepository>, format: Format) {
+    match Input::<String>::new()
+        .with_prompt("Move name")
+        .interact_text()
+    {
+        Ok(name) => {
+            execute(repo, moves, format, name);
+        }
+        _ => println!("An error occurred during the prompt"),
+    };
+}
+
+pub fn execute(
+    repo: Arc<dyn Repository>,
+    moves: Arc<dyn MoveRepository>,
+    format: Format,
+    name: String,
+) -> Status {
+    let req = domain::fetch_move_learners::Request { name };
+    match domain::fetch_move_learners::execute(repo, moves, req) {
+        Ok(res) => {
+            output::print_all(
+                format,
+                &res.pokemons
+                    .into_iter()
+                    .map(|p| Learner {
+                        number: p.number,
+                        name: p.name,
+                        method: p.method,
+                        level: p.level,
+                    })
+                    .collect::<Vec<Learner>>(),
+            );
+            Status::Ok
+        }
+        Err(domain::fetch_move_learners::Error::BadRequest(errors)) => {
+            print_invalid(&errors);
+            Status::BadRequest
+        }
+        Err(domain::fetch_move_learners::Error::NotFound) => {
+            eprintln!("The move does not exist");
+            Status::NotFound
+        }
+        Err(domain::fetch_move_learners::Error::Unknown(e)) => {
+            eprintln!("An unknown error occurred: {}", e.chain());
+            Status::Unknown
+        }
+    }
+}
@@ -2,34 +2,49 @@ use std::sync::Arc;
 
 use crate::{domain, repositories::pokemon::Repository};
 
-use super::prompt_number;
-#[derive(Debug)]
-struct Response {
-    number: u16,
-    name: String,
-    types: Vec<String>,
-}
+use super::{output, print_invalid, prompt_form, prompt_number, Format, Pokemon, Stats, Status};
 
-pub fn run(repo: Arc<dyn Repository>) {
-    let number = prompt_number();
-    let req = match number {
-        Ok(number) => domain::fetch_pokemon::Request { number },
-        _ => {
-            println!("An error occurred during the prompt");
-            return;
+pub fn run(repo: Arc<dyn Repository>, format: Format) {
+    match (prompt_number(), prompt_form()) {
+        (Ok(number), Ok(form)) => {
+            execute(repo, format, number, form);
         }
+        _ => println!("An error occurred during the prompt"),
     };
+}
+
+pub fn execute(
+    repo: Arc<dyn Repository>,
+    format: Format,
+    number: u16,
+    form: Option<String>,
+) -> Status {
+    let req = domain::fetch_pokemon::Request { number, form };
     match domain::fetch_pokemon::execute(repo, req) {
-        Ok(res) => println!(
-            "{:?}",
-            Response {
-                number: res.number,
-                name: res.name,
-                types: res.types,
-            }
-        ),
-        Err(domain::fetch_pokemon::Error::BadRequest) => println!("The request is invalid"),
-        Err(domain::fetch_pokemon::Error::NotFound) => println!("The Pokemon doesn't not exists"),
-        Err(domain::fetch_pokemon::Error::Unknown) => println!("An unknown error occurred"),
+        Ok(res) => {
+            output::print_one(
+                format,
+                &Pokemon {
+                    number: res.number,
+                    form: res.form,
+                    name: res.name,
+                    types: res.types,
+                    stats: Stats::new(res.stats, res.total),
+                },
+            );
+            Status::Ok
+        }
+        Err(domain::fetch_pokemon::Error::BadRequest(errors)) => {
+            print_invalid(&errors);
+            Status::BadRequest
+        }
+        Err(domain::fetch_pokemon::Error::NotFound) => {
+            eprintln!("The Pokemon does not exist");
+            Status::NotFound
+        }
+        Err(domain::fetch_pokemon::Error::Unknown(e)) => {
+            eprintln!("An unknown error occurred: {}", e.chain());
+            Status::Unknown
+        }
     }
 }
@@ -0,0 +1,81 @@
+use std::sync::Arc;
+
+use serde::Serialize;
+
+use crate::{
+    domain,
+    repositories::{ability::AbilityRepository, pokemon::Repository},
+};
+
+use super::{
+    output::{self, Record},
+    print_invalid, prompt_form, prompt_number, Format, Status,
+};
+
+#[derive(Serialize)]
+struct PokemonAbility {
+    name: String,
+    hidden: bool,
+    description: String,
+}
+
+impl Record for PokemonAbility {
+    fn headers() -> &'static [&'static str] {
+        &["name", "hidden", "description"]
+    }
+
+    fn cells(&self) -> Vec<String> {
+        vec![
+            self.name.clone(),
+            String::from(if self.hidden { "yes" } else { "no" }),
+            self.description.clone(),
+        ]
+    }
+}
+
+pub fn run(repo: Arc<dyn Repository>, abilities: Arc<dyn AbilityRepository>, format: Format) {
+    match (prompt_number(), prompt_form()) {
+        (Ok(number), Ok(form)) => {
+            execute(repo, abilities, format, number, form);
+        }
+        _ => println!("An error occurred during the prompt"),
+    };
+}
+
+pub fn execute(
+    repo: Arc<dyn Repository>,
+    abilities: Arc<dyn AbilityRepository>,
+    format: Format,
+    number: u16,
+    form: Option<String>,
+) -> Status {
+    let req = domain::fetch_pokemon_abilities::Request { number, form };
+    match domain::fetch_pokemon_abilities::execute(repo, abilities, req) {
+        Ok(res) => {
+            output::print_all(
+                format,
+                &res.abilities
+                    .into_iter()
+                    .map(|a| PokemonAbility {
+                        name: a.name,
+                        hidden: a.hidden,
+                        description: a.description,
+                    })
+                    .collect::<Vec<PokemonAbility>>(),
+            );
+            Status::Ok
+        }
+        Err(domain::fetch_pokemon_abilities::Error::BadRequest(errors)) => {
+            print_invalid(&errors);
+            Status::BadRequest
+        }
+        Err(domain::fetch_pokemon_abilities::Error::NotFound) => {
+            eprintln!("The Pokemon does not exist");
+            Status::NotFound
+        }
+        Err(domain::fetch_pokemon_abilities::Error::Unknown(e)) => {
+            eprintln!("An unknown error occurred: {}", e.chain());
+            Status::Unknown
+        }
+    }
+}
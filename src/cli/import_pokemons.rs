@@ -0,0 +1,316 @@
+use std::{fs, path::Path, sync::Arc};
+
+use clap::ArgEnum;
+use serde::Deserialize;
+
+use crate::{domain, repositories::pokemon::Repository};
+
+use super::{
+    export_pokemons::{BACKUP_FORMAT, BACKUP_VERSION},
+    output, parse_stats, Format, Outcome, Status,
+};
+
+#[derive(ArgEnum, Clone, Copy, Debug)]
+pub enum OnConflict {
+    Skip,
+    Overwrite,
+    Fail,
+}
+
+impl From<OnConflict> for domain::import_pokemons::Policy {
+    fn from(on_conflict: OnConflict) -> Self {
+        match on_conflict {
+            OnConflict::Skip => Self::Skip,
+            OnConflict::Overwrite => Self::Overwrite,
+            OnConflict::Fail => Self::Fail,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct JsonRow {
+    number: u16,
+    form: Option<String>,
+    name: String,
+    types: Vec<String>,
+    stats: Option<JsonStats>,
+}
+
+/// The stats as exported, their `total` being ignored since it is computed.
+#[derive(Deserialize)]
+struct JsonStats {
+    hp: u16,
+    attack: u16,
+    defense: u16,
+    special_attack: u16,
+    special_defense: u16,
+    speed: u16,
+}
+
+/// Either a plain array of pokemons or a file written by `export --format backup`.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum JsonFile {
+    Rows(Vec<JsonRow>),
+    Backup {
+        format: String,
+        version: u32,
+        pokemons: Vec<JsonRow>,
+    },
+}
+
+/// A CSV row as printed by `--output csv`, types being comma separated and
+/// stats slash separated.
+#[derive(Deserialize)]
+struct CsvRow {
+    number: u16,
+    #[serde(default)]
+    form: String,
+    name: String,
+    types: String,
+    #[serde(default)]
+    stats: String,
+}
+
+pub fn execute(
+    repo: Arc<dyn Repository>,
+    format: Format,
+    file: &str,
+    on_conflict: OnConflict,
+) -> Status {
+    let rows = match read_rows(Path::new(file)) {
+        Ok(rows) => rows,
+        Err(message) => {
+            eprintln!("{}", message);
+            return Status::BadRequest;
+        }
+    };
+    let req = domain::import_pokemons::Request {
+        rows,
+        policy: domain::import_pokemons::Policy::from(on_conflict),
+    };
+    match domain::import_pokemons::execute(repo, req) {
+        Ok(res) => {
+            let outcomes = Outcome::list(vec![
+                (res.created, "created"),
+                (res.overwritten, "overwritten"),
+                (res.skipped, "skipped"),
+            ]);
+            output::print_all(format, &outcomes);
+            Status::Ok
+        }
+        Err(domain::import_pokemons::Error::BadRequest(errors)) => {
+            print_row_errors(&errors);
+            Status::BadRequest
+        }
+        Err(domain::import_pokemons::Error::Conflict(errors)) => {
+            print_row_errors(&errors);
+            Status::Conflict
+        }
+        Err(domain::import_pokemons::Error::Unknown(e)) => {
+            eprintln!("An unknown error occurred: {}", e.chain());
+            Status::Unknown
+        }
+    }
+}
+
+fn print_row_errors(errors: &[domain::import_pokemons::RowError]) {
+    for error in errors {
+        let reasons = error
+            .reasons
+            .iter()
+            .map(|reason| match reason {
+                domain::import_pokemons::Reason::Invalid(e) => e.to_string(),
+                domain::import_pokemons::Reason::Duplicate => {
+                    String::from("duplicated in the file")
+                }
+                domain::import_pokemons::Reason::Conflict => String::from("already exists"),
+            })
+            .collect::<Vec<String>>();
+        eprintln!(
+            "Row {} (Pokemon {}): {}",
+            error.row,
+            error.number,
+            reasons.join(", ")
+        );
+    }
+}
+
+fn read_rows(path: &Path) -> Result<Vec<domain::import_pokemons::Row>, String> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) => return Err(format!("Cannot read {}: {}", path.display(), e)),
+    };
+    match path.extension().and_then(|extension| extension.to_str()) {
+        Some("json") => parse_json(&content),
+        Some("jsonl") => parse_jsonl(&content),
+        Some("csv") => parse_csv(&content),
+        _ => Err(String::from(
+            "The file must be a .json, a .jsonl or a .csv file",
+        )),
+    }
+}
+
+fn parse_json(content: &str) -> Result<Vec<domain::import_pokemons::Row>, String> {
+    let rows = match serde_json::from_str::<JsonFile>(content) {
+        Ok(JsonFile::Rows(rows)) => rows,
+        Ok(JsonFile::Backup {
+            format,
+            version,
+            pokemons,
+        }) if format == BACKUP_FORMAT && version <= BACKUP_VERSION => pokemons,
+        Ok(JsonFile::Backup {
+            format, version, ..
+        }) => {
+            return Err(format!(
+                "Unsupported backup {} version {}, expected {} version {} at most",
+                format, version, BACKUP_FORMAT, BACKUP_VERSION
+            ))
+        }
+        Err(e) => return Err(format!("Invalid JSON: {}", e)),
+    };
+    Ok(rows.into_iter().map(row).collect())
+}
+
+fn parse_jsonl(content: &str) -> Result<Vec<domain::import_pokemons::Row>, String> {
+    content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(
+            |(index, line)| match serde_json::from_str::<JsonRow>(line) {
+                Ok(json) => Ok(row(json)),
+                Err(e) => Err(format!("Invalid JSON on line {}: {}", index + 1, e)),
+            },
+        )
+        .collect()
+}
+
+fn row(json: JsonRow) -> domain::import_pokemons::Row {
+    domain::import_pokemons::Row {
+        number: json.number,
+        form: json.form,
+        name: json.name,
+        types: json.types,
+        stats: json.stats.map(|stats| domain::Stats {
+            hp: stats.hp,
+            attack: stats.attack,
+            defense: stats.defense,
+            special_attack: stats.special_attack,
+            special_defense: stats.special_defense,
+            speed: stats.speed,
+        }),
+    }
+}
+
+fn parse_csv(content: &str) -> Result<Vec<domain::import_pokemons::Row>, String> {
+    csv::Reader::from_reader(content.as_bytes())
+        .deserialize::<CsvRow>()
+        .map(|row| match row {
+            Ok(row) => Ok(domain::import_pokemons::Row {
+                number: row.number,
+                form: Some(row.form).filter(|form| !form.is_empty()),
+                name: row.name,
+                types: row
+                    .types
+                    .split(',')
+                    .map(|t| String::from(t.trim()))
+                    .filter(|t| !t.is_empty())
+                    .collect(),
+                stats: parse_stats(&row.stats.replace('/', " "))
+                    .map_err(|e| format!("Invalid CSV: {}", e))?,
+            }),
+            Err(e) => Err(format!("Invalid CSV: {}", e)),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_should_parse_the_csv_printed_by_the_cli() {
+        let rows =
+            parse_csv("number,name,types\n6,Charizard,\"Fire, Flying\"\n25,Pikachu,Electric\n")
+                .unwrap();
+
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].number, 6);
+        assert_eq!(rows[0].name, "Charizard");
+        assert_eq!(rows[0].types, vec!["Fire", "Flying"]);
+        assert_eq!(rows[1].types, vec!["Electric"]);
+    }
+
+    #[test]
+    fn it_should_parse_the_stats_of_a_csv_row() {
+        let rows = parse_csv(
+            "number,name,types,stats,total\n25,Pikachu,Electric,35/55/40/50/50/90,320\n4,Charmander,Fire,,\n",
+        )
+        .unwrap();
+
+        assert_eq!(rows[0].stats.map(|s| s.speed), Some(90));
+        assert_eq!(rows[1].stats, None);
+    }
+
+    #[test]
+    fn it_should_read_the_form_of_a_csv_row() {
+        let rows = parse_csv(
+            "number,form,name,types\n26,,Raichu,Electric\n26,alola,Raichu,\"Electric, Psychic\"\n",
+        )
+        .unwrap();
+
+        assert_eq!(rows[0].form, None);
+        assert_eq!(rows[1].form.as_deref(), Some("alola"));
+    }
+
+    #[test]
+    fn it_should_reject_a_csv_row_with_a_non_numeric_number() {
+        match parse_csv("number,name,types\npika,Pikachu,Electric\n") {
+            Err(_) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_parse_a_json_array_of_pokemons() {
+        let rows =
+            parse_json(r#"[{"number": 25, "name": "Pikachu", "types": ["Electric"]}]"#).unwrap();
+
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].number, 25);
+    }
+
+    #[test]
+    fn it_should_parse_a_backup() {
+        let rows = parse_json(
+            r#"{"format":"pokedex-backup","version":1,"exported_at":"2022-04-15T05:20:00Z","pokemons":[
+{"number":25,"name":"Pikachu","types":["Electric"]}
+],"count":1}"#,
+        )
+        .unwrap();
+
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].name, "Pikachu");
+    }
+
+    #[test]
+    fn it_should_reject_a_backup_from_a_newer_version() {
+        match parse_json(r#"{"format":"pokedex-backup","version":99,"pokemons":[]}"#) {
+            Err(_) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_parse_json_lines() {
+        let rows = parse_jsonl(
+            "{\"number\":6,\"name\":\"Charizard\",\"types\":[\"Fire\",\"Flying\"]}\n\n\
+             {\"number\":25,\"name\":\"Pikachu\",\"types\":[\"Electric\"]}\n",
+        )
+        .unwrap();
+
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[1].number, 25);
+    }
+}
@@ -0,0 +1,74 @@
+use std::sync::Arc;
+
+use crate::{domain, repositories::pokemon::Repository};
+
+use super::{output, Format, OnConflict, Outcome, Status};
+
+/// Copies every pokemon of `source` into `target`, printing what changed.
+/// Their abilities, learnsets and evolutions are left behind, which the
+/// summary says.
+pub fn execute(
+    source: Arc<dyn Repository>,
+    target: Arc<dyn Repository>,
+    format: Format,
+    on_conflict: OnConflict,
+    dry_run: bool,
+) -> Status {
+    let req = domain::migrate_data::Request {
+        policy: domain::import_pokemons::Policy::from(on_conflict),
+        dry_run,
+    };
+    match domain::migrate_data::execute(source, target, req) {
+        Ok(res) => {
+            let summary = format!(
+                "{} created, {} overwritten, {} skipped, {} unchanged, {} only in target",
+                res.created.len(),
+                res.overwritten.len(),
+                res.skipped.len(),
+                res.unchanged.len(),
+                res.only_in_target.len()
+            );
+            let outcomes = Outcome::list(vec![
+                (res.created, "created"),
+                (res.overwritten, "overwritten"),
+                (res.skipped, "skipped"),
+                (res.unchanged, "unchanged"),
+                (res.only_in_target, "only in target"),
+            ]);
+            output::print_all(format, &outcomes);
+            if dry_run {
+                eprintln!("Dry run, nothing was written: {}", summary);
+            } else {
+                eprintln!("{}", summary);
+            }
+            eprintln!("Abilities, moves, learnsets and evolutions are not copied");
+            Status::Ok
+        }
+        Err(domain::migrate_data::Error::Conflict(keys)) => {
+            for key in keys {
+                match key.form {
+                    Some(form) => eprintln!(
+                        "Pokemon {} in form {:?} differs in the target",
+                        key.number, form
+                    ),
+                    None => eprintln!("Pokemon {} differs in the target", key.number),
+                }
+            }
+            Status::Conflict
+        }
+        Err(domain::migrate_data::Error::Source(e)) => {
+            eprintln!(
+                "An error occurred while reading the source repository: {}",
+                e.chain()
+            );
+            Status::Unknown
+        }
+        Err(domain::migrate_data::Error::Target(e)) => {
+            eprintln!(
+                "An error occurred while writing the target repository: {}",
+                e.chain()
+            );
+            Status::Unknown
+        }
+    }
+}
@@ -1,17 +1,577 @@
-use crate::repositories::pokemon::Repository;
+use crate::{
+    domain::{
+        self,
+        entities::{PokemonType, ValidationError},
+    },
+    repositories::{
+        ability::AbilityRepository, evolution::EvolutionRepository, moves::MoveRepository,
+        pokemon::Repository,
+    },
+};
+use clap::Subcommand;
 use dialoguer::{theme::ColorfulTheme, Input, MultiSelect, Select};
+use serde::Serialize;
 use std::sync::Arc;
+mod add_evolution;
+mod create_ability;
+mod create_move;
 mod create_pokemon;
+mod delete_ability;
+mod delete_evolution;
 mod delete_pokemon;
+mod export_pokemons;
+mod fetch_ability;
+mod fetch_ability_holders;
+mod fetch_all_abilities;
+mod fetch_all_moves;
 mod fetch_all_pokemons;
+mod fetch_evolution_chain;
+mod fetch_learnset;
+mod fetch_move;
+mod fetch_move_learners;
 mod fetch_pokemon;
+mod fetch_pokemon_abilities;
+mod import_pokemons;
+mod migrate_data;
+mod output;
+mod set_learnset;
+mod set_pokemon_abilities;
+mod type_matchups;
+mod update_ability;
+mod update_pokemon;
 
-pub fn run(repo: Arc<dyn Repository>) {
+pub use import_pokemons::OnConflict;
+pub use migrate_data::execute as migrate_data;
+pub use output::Format;
+use output::Record;
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    #[clap(about = "Lists all the pokemons")]
+    List {
+        #[clap(long, help = "number, name, a stat such as speed, or total")]
+        sort: Option<String>,
+        #[clap(long, help = "asc or desc")]
+        direction: Option<String>,
+        #[clap(long, help = "Lists the alternate forms along with the default ones")]
+        all_forms: bool,
+    },
+    #[clap(about = "Shows a pokemon")]
+    Get {
+        number: u16,
+        #[clap(long, help = "The default form unless given")]
+        form: Option<String>,
+    },
+    #[clap(about = "Creates a pokemon")]
+    Create {
+        #[clap(long)]
+        number: u16,
+        #[clap(
+            long,
+            help = "An alternate form such as alola, the default form unless given"
+        )]
+        form: Option<String>,
+        #[clap(long)]
+        name: String,
+        #[clap(long = "type", required = true)]
+        types: Vec<String>,
+        #[clap(
+            long,
+            value_names = &["HP", "ATK", "DEF", "SPA", "SPD", "SPE"],
+            help = "Base stats, each within 1..=255"
+        )]
+        stats: Vec<u16>,
+    },
+    #[clap(about = "Deletes a pokemon, along with its other forms for the default one")]
+    Delete {
+        number: u16,
+        #[clap(long, help = "The default form unless given")]
+        form: Option<String>,
+    },
+    #[clap(about = "Exports every pokemon, to stdout unless --file is given")]
+    Export {
+        #[clap(long, arg_enum, default_value = "backup")]
+        format: export_pokemons::ExportFormat,
+        #[clap(long)]
+        file: Option<String>,
+    },
+    #[clap(about = "Imports pokemons from a .json, .jsonl or .csv file")]
+    Import {
+        file: String,
+        #[clap(long, arg_enum, default_value = "fail")]
+        on_conflict: import_pokemons::OnConflict,
+    },
+    #[clap(about = "Shows the abilities of a pokemon")]
+    Abilities {
+        number: u16,
+        #[clap(
+            long,
+            help = "The default form unless given, every form sharing the abilities of its species"
+        )]
+        form: Option<String>,
+    },
+    #[clap(about = "Replaces the abilities of a pokemon")]
+    SetAbilities {
+        number: u16,
+        #[clap(
+            long,
+            help = "Refused unless empty, every form sharing the abilities of its species"
+        )]
+        form: Option<String>,
+        #[clap(long = "ability", help = "A regular ability, at most twice")]
+        abilities: Vec<String>,
+        #[clap(long)]
+        hidden: Option<String>,
+    },
+    #[clap(about = "Manages the abilities")]
+    Ability {
+        #[clap(subcommand)]
+        command: AbilityCommand,
+    },
+    #[clap(about = "Shows the moves a pokemon learns")]
+    Learnset {
+        number: u16,
+        #[clap(
+            long,
+            help = "The default form unless given, every form sharing the learnset of its species"
+        )]
+        form: Option<String>,
+    },
+    #[clap(about = "Replaces the learnset of a pokemon")]
+    SetLearnset {
+        number: u16,
+        #[clap(
+            long,
+            help = "Refused unless empty, every form sharing the learnset of its species"
+        )]
+        form: Option<String>,
+        #[clap(long = "level-up", value_name = "LEVEL:MOVE")]
+        level_up: Vec<String>,
+        #[clap(long, value_name = "MOVE")]
+        tm: Vec<String>,
+        #[clap(long, value_name = "MOVE")]
+        egg: Vec<String>,
+        #[clap(long, value_name = "MOVE")]
+        tutor: Vec<String>,
+    },
+    #[clap(about = "Manages the moves")]
+    Move {
+        #[clap(subcommand)]
+        command: MoveCommand,
+    },
+    #[clap(about = "Shows the evolution chain of a pokemon, from its base form")]
+    Evolutions {
+        number: u16,
+        #[clap(
+            long,
+            help = "The default form unless given, every form sharing the evolution chain of its species"
+        )]
+        form: Option<String>,
+    },
+    #[clap(about = "Manages the evolutions")]
+    Evolution {
+        #[clap(subcommand)]
+        command: EvolutionCommand,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum AbilityCommand {
+    #[clap(about = "Lists all the abilities")]
+    List,
+    #[clap(about = "Shows an ability")]
+    Get { name: String },
+    #[clap(about = "Creates an ability")]
+    Create {
+        name: String,
+        #[clap(long, default_value = "")]
+        description: String,
+    },
+    #[clap(about = "Changes the description of an ability")]
+    Update {
+        name: String,
+        #[clap(long)]
+        description: String,
+    },
+    #[clap(about = "Deletes an ability")]
+    Delete { name: String },
+    #[clap(about = "Lists the pokemons having an ability")]
+    Pokemons { name: String },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum MoveCommand {
+    #[clap(about = "Lists all the moves")]
+    List,
+    #[clap(about = "Shows a move")]
+    Get { name: String },
+    #[clap(about = "Creates a move")]
+    Create {
+        name: String,
+        #[clap(long = "type")]
+        r#type: String,
+        #[clap(long, help = "Physical, Special or Status")]
+        category: String,
+        #[clap(long, help = "None for status moves")]
+        power: Option<u16>,
+        #[clap(long, help = "None for the moves that never miss")]
+        accuracy: Option<u16>,
+        #[clap(long)]
+        pp: u16,
+    },
+    #[clap(about = "Lists the pokemons learning a move")]
+    Pokemons { name: String },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum EvolutionCommand {
+    #[clap(about = "Records that a pokemon evolves into another one")]
+    Add {
+        from: u16,
+        to: u16,
+        #[clap(long, help = "level, item, trade or friendship")]
+        trigger: String,
+        #[clap(long, help = "For level evolutions")]
+        level: Option<u16>,
+        #[clap(long, help = "For item evolutions, or the item held when traded")]
+        item: Option<String>,
+    },
+    #[clap(about = "Forgets that a pokemon evolves into another one")]
+    Delete { from: u16, to: u16 },
+}
+
+/// Outcome of a command, reported as the process exit code.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Status {
+    Ok,
+    Unknown,
+    BadRequest,
+    NotFound,
+    Conflict,
+}
+
+impl From<Status> for i32 {
+    fn from(status: Status) -> Self {
+        match status {
+            Status::Ok => 0,
+            Status::Unknown => 1,
+            Status::BadRequest => 3,
+            Status::NotFound => 4,
+            Status::Conflict => 5,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct Pokemon {
+    number: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    form: Option<String>,
+    name: String,
+    types: Vec<String>,
+    stats: Option<Stats>,
+}
+
+#[derive(Serialize)]
+struct Stats {
+    hp: u16,
+    attack: u16,
+    defense: u16,
+    special_attack: u16,
+    special_defense: u16,
+    speed: u16,
+    total: u16,
+}
+
+impl Stats {
+    fn new(stats: Option<domain::Stats>, total: Option<u16>) -> Option<Self> {
+        match (stats, total) {
+            (Some(stats), Some(total)) => Some(Self {
+                hp: stats.hp,
+                attack: stats.attack,
+                defense: stats.defense,
+                special_attack: stats.special_attack,
+                special_defense: stats.special_defense,
+                speed: stats.speed,
+                total,
+            }),
+            _ => None,
+        }
+    }
+}
+
+impl Record for Pokemon {
+    fn headers() -> &'static [&'static str] {
+        &["number", "form", "name", "types", "stats", "total"]
+    }
+
+    fn cells(&self) -> Vec<String> {
+        let (stats, total) = match &self.stats {
+            Some(s) => (
+                [
+                    s.hp,
+                    s.attack,
+                    s.defense,
+                    s.special_attack,
+                    s.special_defense,
+                    s.speed,
+                ]
+                .map(|n| n.to_string())
+                .join("/"),
+                s.total.to_string(),
+            ),
+            None => (String::new(), String::new()),
+        };
+        vec![
+            self.number.to_string(),
+            self.form.clone().unwrap_or_default(),
+            self.name.clone(),
+            self.types.join(", "),
+            stats,
+            total,
+        ]
+    }
+}
+
+#[derive(Serialize)]
+struct Ability {
+    name: String,
+    description: String,
+}
+
+impl Record for Ability {
+    fn headers() -> &'static [&'static str] {
+        &["name", "description"]
+    }
+
+    fn cells(&self) -> Vec<String> {
+        vec![self.name.clone(), self.description.clone()]
+    }
+}
+
+#[derive(Serialize)]
+struct Move {
+    name: String,
+    r#type: String,
+    category: String,
+    power: Option<u16>,
+    accuracy: Option<u16>,
+    pp: u16,
+}
+
+impl Record for Move {
+    fn headers() -> &'static [&'static str] {
+        &["name", "type", "category", "power", "accuracy", "pp"]
+    }
+
+    fn cells(&self) -> Vec<String> {
+        vec![
+            self.name.clone(),
+            self.r#type.clone(),
+            self.category.clone(),
+            optional_cell(self.power),
+            optional_cell(self.accuracy),
+            self.pp.to_string(),
+        ]
+    }
+}
+
+/// An empty cell for a missing value.
+fn optional_cell(value: Option<u16>) -> String {
+    value.map(|v| v.to_string()).unwrap_or_default()
+}
+
+/// What a bulk command did to a pokemon number and form.
+#[derive(Serialize)]
+struct Outcome {
+    number: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    form: Option<String>,
+    outcome: &'static str,
+}
+
+impl Outcome {
+    fn list(groups: Vec<(Vec<domain::import_pokemons::PokemonKey>, &'static str)>) -> Vec<Self> {
+        groups
+            .into_iter()
+            .flat_map(|(keys, outcome)| {
+                keys.into_iter().map(move |key| Outcome {
+                    number: key.number,
+                    form: key.form,
+                    outcome,
+                })
+            })
+            .collect()
+    }
+}
+
+impl Record for Outcome {
+    fn headers() -> &'static [&'static str] {
+        &["number", "form", "outcome"]
+    }
+
+    fn cells(&self) -> Vec<String> {
+        vec![
+            self.number.to_string(),
+            self.form.clone().unwrap_or_default(),
+            String::from(self.outcome),
+        ]
+    }
+}
+
+/// Runs a single command without prompting, for scripts.
+pub fn execute(
+    repo: Arc<dyn Repository>,
+    abilities: Arc<dyn AbilityRepository>,
+    moves: Arc<dyn MoveRepository>,
+    evolutions: Arc<dyn EvolutionRepository>,
+    command: Command,
+    format: Format,
+) -> Status {
+    match command {
+        Command::List {
+            sort,
+            direction,
+            all_forms,
+        } => fetch_all_pokemons::execute(repo, format, sort, direction, all_forms),
+        Command::Get { number, form } => fetch_pokemon::execute(repo, format, number, form),
+        Command::Create {
+            number,
+            form,
+            name,
+            types,
+            stats,
+        } => match stats_from(&stats) {
+            Ok(stats) => create_pokemon::execute(repo, format, number, form, name, types, stats),
+            Err(message) => {
+                eprintln!("{}", message);
+                Status::BadRequest
+            }
+        },
+        Command::Delete { number, form } => delete_pokemon::execute(repo, number, form),
+        Command::Export { format, file } => export_pokemons::execute(repo, format, file.as_deref()),
+        Command::Import { file, on_conflict } => {
+            import_pokemons::execute(repo, format, &file, on_conflict)
+        }
+        Command::Abilities { number, form } => {
+            fetch_pokemon_abilities::execute(repo, abilities, format, number, form)
+        }
+        Command::SetAbilities {
+            number,
+            form,
+            abilities: regular,
+            hidden,
+        } => set_pokemon_abilities::execute(repo, abilities, number, form, regular, hidden),
+        Command::Ability { command } => match command {
+            AbilityCommand::List => fetch_all_abilities::execute(abilities, format),
+            AbilityCommand::Get { name } => fetch_ability::execute(abilities, format, name),
+            AbilityCommand::Create { name, description } => {
+                create_ability::execute(abilities, format, name, description)
+            }
+            AbilityCommand::Update { name, description } => {
+                update_ability::execute(abilities, format, name, description)
+            }
+            AbilityCommand::Delete { name } => delete_ability::execute(abilities, name),
+            AbilityCommand::Pokemons { name } => {
+                fetch_ability_holders::execute(repo, abilities, format, name)
+            }
+        },
+        Command::Learnset { number, form } => {
+            fetch_learnset::execute(repo, moves, format, number, form)
+        }
+        Command::SetLearnset {
+            number,
+            form,
+            level_up,
+            tm,
+            egg,
+            tutor,
+        } => set_learnset::execute(
+            repo,
+            moves,
+            number,
+            form,
+            set_learnset::Moves {
+                level_up,
+                tm,
+                egg,
+                tutor,
+            },
+        ),
+        Command::Move { command } => match command {
+            MoveCommand::List => fetch_all_moves::execute(moves, format),
+            MoveCommand::Get { name } => fetch_move::execute(moves, format, name),
+            MoveCommand::Create {
+                name,
+                r#type,
+                category,
+                power,
+                accuracy,
+                pp,
+            } => create_move::execute(
+                moves,
+                format,
+                domain::create_move::Request {
+                    name,
+                    r#type,
+                    category,
+                    power,
+                    accuracy,
+                    pp,
+                },
+            ),
+            MoveCommand::Pokemons { name } => {
+                fetch_move_learners::execute(repo, moves, format, name)
+            }
+        },
+        Command::Evolutions { number, form } => {
+            fetch_evolution_chain::execute(repo, evolutions, format, number, form)
+        }
+        Command::Evolution { command } => match command {
+            EvolutionCommand::Add {
+                from,
+                to,
+                trigger,
+                level,
+                item,
+            } => add_evolution::execute(
+                repo,
+                evolutions,
+                format,
+                domain::add_evolution::Request {
+                    from,
+                    to,
+                    trigger,
+                    level,
+                    item,
+                },
+            ),
+            EvolutionCommand::Delete { from, to } => {
+                delete_evolution::execute(evolutions, from, to)
+            }
+        },
+    }
+}
+
+pub fn run(
+    repo: Arc<dyn Repository>,
+    abilities: Arc<dyn AbilityRepository>,
+    moves: Arc<dyn MoveRepository>,
+    evolutions: Arc<dyn EvolutionRepository>,
+    format: Format,
+) {
     loop {
         let choices = [
             "Fetch all Pokemons",
             "Fetch a Pokemon",
+            "Show type matchups of a Pokemon",
+            "Show abilities of a Pokemon",
+            "Find Pokemons with an ability",
+            "Show learnset of a Pokemon",
+            "Find Pokemons learning a move",
+            "Show evolution chain of a Pokemon",
             "Create a Pokemon",
+            "Update a Pokemon",
             "Delete a Pokemon",
             "Exit",
         ];
@@ -26,16 +586,32 @@ pub fn run(repo: Arc<dyn Repository>) {
         };
 
         match index {
-            0 => fetch_all_pokemons::run(repo.clone()),
-            1 => fetch_pokemon::run(repo.clone()),
-            2 => create_pokemon::run(repo.clone()),
-            3 => delete_pokemon::run(repo.clone()),
-            4 => return,
+            0 => fetch_all_pokemons::run(repo.clone(), format),
+            1 => fetch_pokemon::run(repo.clone(), format),
+            2 => type_matchups::run(repo.clone(), format),
+            3 => fetch_pokemon_abilities::run(repo.clone(), abilities.clone(), format),
+            4 => fetch_ability_holders::run(repo.clone(), abilities.clone(), format),
+            5 => fetch_learnset::run(repo.clone(), moves.clone(), format),
+            6 => fetch_move_learners::run(repo.clone(), moves.clone(), format),
+            7 => fetch_evolution_chain::run(repo.clone(), evolutions.clone(), format),
+            8 => create_pokemon::run(repo.clone(), format),
+            9 => update_pokemon::run(repo.clone(), format),
+            10 => delete_pokemon::run(repo.clone()),
+            11 => return,
             _ => continue,
         };
     }
 }
 
+/// Tells which fields were refused and why.
+fn print_invalid(errors: &[ValidationError]) {
+    let errors = errors
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<String>>();
+    eprintln!("The request is invalid: {}", errors.join(", "));
+}
+
 pub fn prompt_number() -> Result<u16, ()> {
     match Input::new().with_prompt("Pokemon number").interact_text() {
         Ok(number) => Ok(number),
@@ -43,6 +619,18 @@ pub fn prompt_number() -> Result<u16, ()> {
     }
 }
 
+pub fn prompt_form() -> Result<Option<String>, ()> {
+    match Input::<String>::new()
+        .with_prompt("Pokemon form (leave empty for the default one)")
+        .allow_empty(true)
+        .interact_text()
+    {
+        Ok(form) if form.is_empty() => Ok(None),
+        Ok(form) => Ok(Some(form)),
+        _ => Err(()),
+    }
+}
+
 pub fn prompt_name() -> Result<String, ()> {
     match Input::new().with_prompt("Pokemon name").interact_text() {
         Ok(name) => Ok(name),
@@ -50,17 +638,429 @@ pub fn prompt_name() -> Result<String, ()> {
     }
 }
 
+pub fn prompt_optional_name() -> Result<Option<String>, ()> {
+    match Input::<String>::new()
+        .with_prompt("Pokemon name (leave empty to keep)")
+        .allow_empty(true)
+        .interact_text()
+    {
+        Ok(name) if name.is_empty() => Ok(None),
+        Ok(name) => Ok(Some(name)),
+        _ => Err(()),
+    }
+}
+
+pub fn prompt_stats() -> Result<Option<domain::Stats>, ()> {
+    match Input::<String>::new()
+        .with_prompt("Base stats: HP Atk Def SpA SpD Spe (leave empty to skip)")
+        .allow_empty(true)
+        .validate_with(|input: &String| parse_stats(input).map(|_| ()))
+        .interact_text()
+    {
+        Ok(input) => parse_stats(&input).map_err(|_| ()),
+        _ => Err(()),
+    }
+}
+
+/// Reads the six stats separated by spaces, none when the input is empty.
+fn parse_stats(input: &str) -> Result<Option<domain::Stats>, String> {
+    let mut values = vec![];
+    for value in input.split_whitespace() {
+        match value.parse::<u16>() {
+            Ok(value) => values.push(value),
+            Err(_) => return Err(format!("{:?} is not a stat", value)),
+        }
+    }
+    stats_from(&values)
+}
+
+fn stats_from(values: &[u16]) -> Result<Option<domain::Stats>, String> {
+    match *values {
+        [] => Ok(None),
+        [hp, attack, defense, special_attack, special_defense, speed] => Ok(Some(domain::Stats {
+            hp,
+            attack,
+            defense,
+            special_attack,
+            special_defense,
+            speed,
+        })),
+        _ => Err(format!("expected 6 stats, got {}", values.len())),
+    }
+}
+
 pub fn prompt_types() -> Result<Vec<String>, ()> {
-    let types = ["Electric", "Fire"];
+    let types = PokemonType::ALL.map(String::from);
     match MultiSelect::new()
-        .with_prompt("Pokemon types")
+        .with_prompt("Pokemon types (at most two)")
         .items(&types)
         .interact()
     {
         Ok(indexes) => Ok(indexes
             .into_iter()
-            .map(|index| String::from(types[index]))
+            .map(|index| types[index].clone())
             .collect::<Vec<String>>()),
         _ => Err(()),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repositories::{
+        ability::InMemoryAbilityRepository, evolution::InMemoryEvolutionRepository,
+        moves::InMemoryMoveRepository, pokemon::InMemoryRepository,
+    };
+
+    fn create(number: u16) -> Command {
+        Command::Create {
+            number,
+            form: None,
+            name: String::from("Pikachu"),
+            types: vec![String::from("Electric")],
+            stats: vec![35, 55, 40, 50, 50, 90],
+        }
+    }
+
+    fn list() -> Command {
+        Command::List {
+            sort: None,
+            direction: None,
+            all_forms: false,
+        }
+    }
+
+    fn execute(repo: Arc<dyn Repository>, command: Command, format: Format) -> Status {
+        super::execute(
+            repo,
+            Arc::new(InMemoryAbilityRepository::new()),
+            Arc::new(InMemoryMoveRepository::new()),
+            Arc::new(InMemoryEvolutionRepository::new()),
+            command,
+            format,
+        )
+    }
+
+    #[test]
+    fn it_should_report_each_outcome_with_its_own_status() {
+        let repo = Arc::new(InMemoryRepository::new());
+
+        assert_eq!(
+            execute(repo.clone(), create(0), Format::Json),
+            Status::BadRequest
+        );
+        assert_eq!(execute(repo.clone(), create(25), Format::Json), Status::Ok);
+        assert_eq!(
+            execute(repo.clone(), create(25), Format::Json),
+            Status::Conflict
+        );
+        assert_eq!(
+            execute(
+                repo.clone(),
+                Command::Get {
+                    number: 25,
+                    form: None
+                },
+                Format::Json
+            ),
+            Status::Ok
+        );
+        assert_eq!(execute(repo.clone(), list(), Format::Json), Status::Ok);
+        assert_eq!(
+            execute(
+                repo.clone(),
+                Command::Delete {
+                    number: 25,
+                    form: None
+                },
+                Format::Json
+            ),
+            Status::Ok
+        );
+        assert_eq!(
+            execute(
+                repo,
+                Command::Get {
+                    number: 25,
+                    form: None
+                },
+                Format::Json
+            ),
+            Status::NotFound
+        );
+        assert_eq!(
+            execute(
+                Arc::new(InMemoryRepository::new().with_error()),
+                list(),
+                Format::Json
+            ),
+            Status::Unknown
+        );
+    }
+
+    #[test]
+    fn it_should_keep_the_forms_of_a_number_apart() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let alola = || Some(String::from("alola"));
+        let get = |form| Command::Get { number: 26, form };
+        execute(repo.clone(), create(26), Format::Json);
+
+        assert_eq!(
+            execute(repo.clone(), get(alola()), Format::Json),
+            Status::NotFound
+        );
+        assert_eq!(
+            execute(
+                repo.clone(),
+                Command::Create {
+                    number: 26,
+                    form: alola(),
+                    name: String::from("Raichu"),
+                    types: vec![String::from("Electric"), String::from("Psychic")],
+                    stats: vec![],
+                },
+                Format::Json
+            ),
+            Status::Ok
+        );
+        assert_eq!(
+            execute(repo.clone(), get(alola()), Format::Json),
+            Status::Ok
+        );
+        assert_eq!(
+            execute(
+                repo.clone(),
+                Command::Delete {
+                    number: 26,
+                    form: alola()
+                },
+                Format::Json
+            ),
+            Status::Ok
+        );
+        assert_eq!(execute(repo.clone(), get(None), Format::Json), Status::Ok);
+        assert_eq!(execute(repo, get(alola()), Format::Json), Status::NotFound);
+    }
+
+    #[test]
+    fn it_should_link_abilities_and_find_their_holders() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let abilities = Arc::new(InMemoryAbilityRepository::new());
+        let moves = Arc::new(InMemoryMoveRepository::new());
+        let evolutions = Arc::new(InMemoryEvolutionRepository::new());
+        let run = |command| {
+            super::execute(
+                repo.clone(),
+                abilities.clone(),
+                moves.clone(),
+                evolutions.clone(),
+                command,
+                Format::Json,
+            )
+        };
+        let set_static = || Command::SetAbilities {
+            number: 25,
+            form: None,
+            abilities: vec![String::from("Static")],
+            hidden: None,
+        };
+        run(create(25));
+
+        assert_eq!(run(set_static()), Status::BadRequest);
+        assert_eq!(
+            run(Command::Ability {
+                command: AbilityCommand::Create {
+                    name: String::from("Static"),
+                    description: String::new(),
+                },
+            }),
+            Status::Ok
+        );
+        assert_eq!(run(set_static()), Status::Ok);
+        assert_eq!(
+            run(Command::SetAbilities {
+                number: 25,
+                form: Some(String::from("alola")),
+                abilities: vec![String::from("Static")],
+                hidden: None,
+            }),
+            Status::BadRequest
+        );
+        let abilities_of = |number, form: Option<&str>| Command::Abilities {
+            number,
+            form: form.map(String::from),
+        };
+        assert_eq!(run(abilities_of(25, None)), Status::Ok);
+        assert_eq!(run(abilities_of(25, Some("alola"))), Status::NotFound);
+        assert_eq!(run(abilities_of(26, None)), Status::NotFound);
+        assert_eq!(
+            run(Command::Ability {
+                command: AbilityCommand::Pokemons {
+                    name: String::from("Static"),
+                },
+            }),
+            Status::Ok
+        );
+        assert_eq!(
+            run(Command::Ability {
+                command: AbilityCommand::Get {
+                    name: String::from("Plus"),
+                },
+            }),
+            Status::NotFound
+        );
+    }
+
+    #[test]
+    fn it_should_set_a_learnset_and_find_the_learners() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let abilities = Arc::new(InMemoryAbilityRepository::new());
+        let moves = Arc::new(InMemoryMoveRepository::new());
+        let evolutions = Arc::new(InMemoryEvolutionRepository::new());
+        let run = |command| {
+            super::execute(
+                repo.clone(),
+                abilities.clone(),
+                moves.clone(),
+                evolutions.clone(),
+                command,
+                Format::Json,
+            )
+        };
+        let set_growl = || Command::SetLearnset {
+            number: 25,
+            form: None,
+            level_up: vec![String::from("1:Growl")],
+            tm: vec![],
+            egg: vec![],
+            tutor: vec![],
+        };
+        run(create(25));
+
+        assert_eq!(run(set_growl()), Status::BadRequest);
+        assert_eq!(
+            run(Command::Move {
+                command: MoveCommand::Create {
+                    name: String::from("Growl"),
+                    r#type: String::from("Normal"),
+                    category: String::from("Status"),
+                    power: None,
+                    accuracy: Some(100),
+                    pp: 40,
+                },
+            }),
+            Status::Ok
+        );
+        assert_eq!(run(set_growl()), Status::Ok);
+        assert_eq!(
+            run(Command::Learnset {
+                number: 25,
+                form: None,
+            }),
+            Status::Ok
+        );
+        assert_eq!(
+            run(Command::Move {
+                command: MoveCommand::Pokemons {
+                    name: String::from("Growl"),
+                },
+            }),
+            Status::Ok
+        );
+        assert_eq!(
+            run(Command::Move {
+                command: MoveCommand::Get {
+                    name: String::from("Surf"),
+                },
+            }),
+            Status::NotFound
+        );
+    }
+
+    #[test]
+    fn it_should_add_evolutions_and_show_the_chain() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let evolutions = Arc::new(InMemoryEvolutionRepository::new());
+        let run = |command| {
+            super::execute(
+                repo.clone(),
+                Arc::new(InMemoryAbilityRepository::new()),
+                Arc::new(InMemoryMoveRepository::new()),
+                evolutions.clone(),
+                command,
+                Format::Json,
+            )
+        };
+        let evolve = |from, to| Command::Evolution {
+            command: EvolutionCommand::Add {
+                from,
+                to,
+                trigger: String::from("level"),
+                level: Some(16),
+                item: None,
+            },
+        };
+        run(create(4));
+        run(create(5));
+
+        assert_eq!(run(evolve(4, 6)), Status::BadRequest);
+        assert_eq!(run(evolve(4, 5)), Status::Ok);
+        assert_eq!(run(evolve(5, 4)), Status::BadRequest);
+        assert_eq!(run(evolve(4, 5)), Status::Conflict);
+        assert_eq!(
+            run(Command::Evolutions {
+                number: 5,
+                form: None,
+            }),
+            Status::Ok
+        );
+        assert_eq!(
+            run(Command::Evolutions {
+                number: 6,
+                form: None,
+            }),
+            Status::NotFound
+        );
+        assert_eq!(
+            run(Command::Evolution {
+                command: EvolutionCommand::Delete { from: 4, to: 5 },
+            }),
+            Status::Ok
+        );
+        assert_eq!(
+            run(Command::Evolution {
+                command: EvolutionCommand::Delete { from: 4, to: 5 },
+            }),
+            Status::NotFound
+        );
+    }
+
+    #[test]
+    fn it_should_read_six_stats_or_none() {
+        assert_eq!(parse_stats(""), Ok(None));
+        assert_eq!(
+            parse_stats(" 35 55 40 50 50 90 ")
+                .ok()
+                .flatten()
+                .map(|s| s.speed),
+            Some(90)
+        );
+        assert!(parse_stats("35 55 40").is_err());
+        assert!(parse_stats("35 55 40 50 50 fast").is_err());
+    }
+
+    #[test]
+    fn it_should_use_distinct_exit_codes() {
+        let mut codes = [
+            Status::Ok,
+            Status::Unknown,
+            Status::BadRequest,
+            Status::NotFound,
+            Status::Conflict,
+        ]
+        .map(i32::from);
+        codes.sort_unstable();
+        codes.windows(2).for_each(|w| assert_ne!(w[0], w[1]));
+        assert_eq!(i32::from(Status::Ok), 0);
+    }
+}
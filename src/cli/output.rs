@@ -0,0 +1,175 @@
+use std::io::{self, Write};
+
+use clap::ArgEnum;
+use serde::Serialize;
+
+#[derive(ArgEnum, Clone, Copy, Debug, PartialEq)]
+pub enum Format {
+    Table,
+    Json,
+    Csv,
+    Yaml,
+}
+
+/// A value the CLI can print in every output format.
+pub trait Record: Serialize {
+    fn headers() -> &'static [&'static str];
+    fn cells(&self) -> Vec<String>;
+}
+
+pub fn print_one<R: Record>(format: Format, record: &R) {
+    match format {
+        Format::Json => print_json(record),
+        Format::Yaml => print_yaml(record),
+        Format::Table | Format::Csv => print_all(format, std::slice::from_ref(record)),
+    }
+}
+
+pub fn print_all<R: Record>(format: Format, records: &[R]) {
+    match format {
+        Format::Table => print!("{}", table(records)),
+        Format::Csv => print_csv(records),
+        Format::Json => print_json(&records),
+        Format::Yaml => print_yaml(&records),
+    }
+}
+
+fn print_json<T: Serialize + ?Sized>(value: &T) {
+    match serde_json::to_string_pretty(value) {
+        Ok(json) => println!("{}", json),
+        Err(_) => eprintln!("An error occurred while formatting the output"),
+    }
+}
+
+fn print_yaml<T: Serialize + ?Sized>(value: &T) {
+    match serde_yaml::to_string(value) {
+        Ok(yaml) => print!("{}", yaml),
+        Err(_) => eprintln!("An error occurred while formatting the output"),
+    }
+}
+
+fn table<R: Record>(records: &[R]) -> String {
+    let rows = records.iter().map(R::cells).collect::<Vec<Vec<String>>>();
+    let mut widths = R::headers()
+        .iter()
+        .map(|h| h.chars().count())
+        .collect::<Vec<usize>>();
+    for row in rows.iter() {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let line = |cells: Vec<String>| {
+        cells
+            .iter()
+            .zip(widths.iter())
+            .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+            .collect::<Vec<String>>()
+            .join("  ")
+            .trim_end()
+            .to_string()
+            + "\n"
+    };
+    let mut out = line(R::headers().iter().map(|h| h.to_uppercase()).collect());
+    out += &line(widths.iter().map(|w| "-".repeat(*w)).collect());
+    for row in rows {
+        out += &line(row);
+    }
+    out
+}
+
+/// Starts a CSV document with the headers of `R`, the records then being
+/// written from their cells.
+pub fn csv_writer<R: Record, W: Write>(out: W) -> csv::Result<csv::Writer<W>> {
+    let mut writer = csv::Writer::from_writer(out);
+    writer.write_record(R::headers())?;
+    Ok(writer)
+}
+
+fn write_csv<R: Record, W: Write>(out: W, records: &[R]) -> csv::Result<()> {
+    let mut writer = csv_writer::<R, W>(out)?;
+    for record in records {
+        writer.write_record(record.cells())?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+fn print_csv<R: Record>(records: &[R]) {
+    if write_csv(io::stdout(), records).is_err() {
+        eprintln!("An error occurred while formatting the output");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Serialize)]
+    struct Pokemon {
+        number: u16,
+        name: &'static str,
+        types: Vec<&'static str>,
+    }
+
+    impl Record for Pokemon {
+        fn headers() -> &'static [&'static str] {
+            &["number", "name", "types"]
+        }
+
+        fn cells(&self) -> Vec<String> {
+            vec![
+                self.number.to_string(),
+                self.name.to_string(),
+                self.types.join(", "),
+            ]
+        }
+    }
+
+    fn pokemons() -> Vec<Pokemon> {
+        vec![
+            Pokemon {
+                number: 6,
+                name: "Charizard",
+                types: vec!["Fire", "Flying"],
+            },
+            Pokemon {
+                number: 25,
+                name: "Pikachu",
+                types: vec!["Electric"],
+            },
+        ]
+    }
+
+    #[test]
+    fn it_should_align_table_columns() {
+        assert_eq!(
+            table(&pokemons()),
+            "NUMBER  NAME       TYPES\n\
+             ------  ---------  ------------\n\
+             6       Charizard  Fire, Flying\n\
+             25      Pikachu    Electric\n"
+        );
+    }
+
+    #[test]
+    fn it_should_quote_csv_fields_when_needed() {
+        let mut out = vec![];
+        let mut records = pokemons();
+        records.push(Pokemon {
+            number: 122,
+            name: "Mr. \"Mime\"",
+            types: vec!["Psychic"],
+        });
+
+        assert!(write_csv(&mut out, &records).is_ok());
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "number,name,types\n\
+             6,Charizard,\"Fire, Flying\"\n\
+             25,Pikachu,Electric\n\
+             122,\"Mr. \"\"Mime\"\"\",Psychic\n"
+        );
+    }
+}
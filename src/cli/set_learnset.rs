@@ -0,0 +1,122 @@
+use std::sync::Arc;
+
+use crate::{
+    domain::{self, set_learnset::Entry},
+    repositories::{moves::MoveRepository, pokemon::Repository},
+};
+
+use super::{print_invalid, Status};
+
+/// The moves given for each method, level-up ones written `LEVEL:MOVE`.
+pub struct Moves {
+    pub level_up: Vec<String>,
+    pub tm: Vec<String>,
+    pub egg: Vec<String>,
+    pub tutor: Vec<String>,
+}
+
+pub fn execute(
+    repo: Arc<dyn Repository>,
+    moves: Arc<dyn MoveRepository>,
+    number: u16,
+    form: Option<String>,
+    learnset: Moves,
+) -> Status {
+    let entries = match entries(learnset) {
+        Ok(entries) => entries,
+        Err(message) => {
+            eprintln!("{}", message);
+            return Status::BadRequest;
+        }
+    };
+    let req = domain::set_learnset::Request {
+        number,
+        form,
+        moves: entries,
+    };
+    match domain::set_learnset::execute(repo, moves, req) {
+        Ok(_) => Status::Ok,
+        Err(domain::set_learnset::Error::BadRequest(errors)) => {
+            print_invalid(&errors);
+            Status::BadRequest
+        }
+        Err(domain::set_learnset::Error::NotFound) => {
+            eprintln!("The Pokemon does not exist");
+            Status::NotFound
+        }
+        Err(domain::set_learnset::Error::Unknown(e)) => {
+            eprintln!("An unknown error occurred: {}", e.chain());
+            Status::Unknown
+        }
+    }
+}
+
+fn entries(learnset: Moves) -> Result<Vec<Entry>, String> {
+    let mut entries = vec![];
+    for value in learnset.level_up {
+        match value.split_once(':') {
+            Some((level, name)) => match level.trim().parse::<u16>() {
+                Ok(level) => entries.push(Entry {
+                    name: String::from(name.trim()),
+                    method: String::from("level-up"),
+                    level: Some(level),
+                }),
+                Err(_) => return Err(format!("{:?} is not a level", level)),
+            },
+            None => return Err(format!("expected LEVEL:MOVE, got {:?}", value)),
+        }
+    }
+    for (method, names) in [
+        ("tm", learnset.tm),
+        ("egg", learnset.egg),
+        ("tutor", learnset.tutor),
+    ] {
+        entries.extend(names.into_iter().map(|name| Entry {
+            name,
+            method: String::from(method),
+            level: None,
+        }));
+    }
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn moves(level_up: &[&str], tm: &[&str]) -> Moves {
+        Moves {
+            level_up: level_up.iter().map(|m| String::from(*m)).collect(),
+            tm: tm.iter().map(|m| String::from(*m)).collect(),
+            egg: vec![],
+            tutor: vec![],
+        }
+    }
+
+    #[test]
+    fn it_should_read_the_level_of_level_up_moves() {
+        let res = entries(moves(&["1:Thunder Shock", " 5 : Growl"], &["Thunderbolt"]));
+
+        assert_eq!(
+            res.map(|e| e
+                .into_iter()
+                .map(|e| (e.name, e.method, e.level))
+                .collect::<Vec<(String, String, Option<u16>)>>()),
+            Ok(vec![
+                (
+                    String::from("Thunder Shock"),
+                    String::from("level-up"),
+                    Some(1)
+                ),
+                (String::from("Growl"), String::from("level-up"), Some(5)),
+                (String::from("Thunderbolt"), String::from("tm"), None),
+            ])
+        );
+    }
+
+    #[test]
+    fn it_should_refuse_a_level_up_move_without_a_level() {
+        assert!(entries(moves(&["Growl"], &[])).is_err());
+        assert!(entries(moves(&["one:Growl"], &[])).is_err());
+    }
+}
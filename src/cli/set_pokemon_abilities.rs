@@ -0,0 +1,39 @@
+use std::sync::Arc;
+
+use crate::{
+    domain,
+    repositories::{ability::AbilityRepository, pokemon::Repository},
+};
+
+use super::{print_invalid, Status};
+
+pub fn execute(
+    repo: Arc<dyn Repository>,
+    abilities: Arc<dyn AbilityRepository>,
+    number: u16,
+    form: Option<String>,
+    regular: Vec<String>,
+    hidden: Option<String>,
+) -> Status {
+    let req = domain::set_pokemon_abilities::Request {
+        number,
+        form,
+        abilities: regular,
+        hidden,
+    };
+    match domain::set_pokemon_abilities::execute(repo, abilities, req) {
+        Ok(_) => Status::Ok,
+        Err(domain::set_pokemon_abilities::Error::BadRequest(errors)) => {
+            print_invalid(&errors);
+            Status::BadRequest
+        }
+        Err(domain::set_pokemon_abilities::Error::NotFound) => {
+            eprintln!("The Pokemon does not exist");
+            Status::NotFound
+        }
+        Err(domain::set_pokemon_abilities::Error::Unknown(e)) => {
+            eprintln!("An unknown error occurred: {}", e.chain());
+            Status::Unknown
+        }
+    }
+}
@@ -0,0 +1,58 @@
+use std::sync::Arc;
+
+use serde::Serialize;
+
+use crate::{domain, repositories::pokemon::Repository};
+
+use super::{
+    output::{self, Record},
+    print_invalid, prompt_form, prompt_number, Format,
+};
+
+#[derive(Serialize)]
+struct Matchup {
+    multiplier: &'static str,
+    types: Vec<String>,
+}
+
+impl Record for Matchup {
+    fn headers() -> &'static [&'static str] {
+        &["multiplier", "types"]
+    }
+
+    fn cells(&self) -> Vec<String> {
+        vec![String::from(self.multiplier), self.types.join(", ")]
+    }
+}
+
+pub fn run(repo: Arc<dyn Repository>, format: Format) {
+    let req = match (prompt_number(), prompt_form()) {
+        (Ok(number), Ok(form)) => domain::type_matchups::Request { number, form },
+        _ => {
+            println!("An error occurred during the prompt");
+            return;
+        }
+    };
+    match domain::type_matchups::execute(repo, req) {
+        Ok(res) => output::print_all(
+            format,
+            &[
+                ("4x", res.quadruple),
+                ("2x", res.double),
+                ("1x", res.neutral),
+                ("0.5x", res.half),
+                ("0.25x", res.quarter),
+                ("0x", res.immune),
+            ]
+            .into_iter()
+            .filter(|(_, types)| !types.is_empty())
+            .map(|(multiplier, types)| Matchup { multiplier, types })
+            .collect::<Vec<Matchup>>(),
+        ),
+        Err(domain::type_matchups::Error::BadRequest(errors)) => print_invalid(&errors),
+        Err(domain::type_matchups::Error::NotFound) => eprintln!("The Pokemon does not exist"),
+        Err(domain::type_matchups::Error::Unknown(e)) => {
+            eprintln!("An unknown error occurred: {}", e.chain())
+        }
+    }
+}
@@ -0,0 +1,46 @@
+use std::sync::Arc;
+
+use crate::{domain, repositories::pokemon::Repository};
+
+use super::{
+    output, print_invalid, prompt_form, prompt_number, prompt_optional_name, prompt_stats,
+    prompt_types, Format, Pokemon, Stats,
+};
+
+pub fn run(repo: Arc<dyn Repository>, format: Format) {
+    let number = prompt_number();
+    let form = prompt_form();
+    let name = prompt_optional_name();
+    let types = prompt_types();
+    let stats = prompt_stats();
+    let req = match (number, form, name, types, stats) {
+        (Ok(number), Ok(form), Ok(name), Ok(types), Ok(stats)) => domain::update_pokemon::Request {
+            number,
+            form,
+            name,
+            types: if types.is_empty() { None } else { Some(types) },
+            stats,
+        },
+        _ => {
+            println!("An error occurred during the prompt");
+            return;
+        }
+    };
+    match domain::update_pokemon::execute(repo, req) {
+        Ok(res) => output::print_one(
+            format,
+            &Pokemon {
+                number: res.number,
+                form: res.form,
+                name: res.name,
+                types: res.types,
+                stats: Stats::new(res.stats, res.total),
+            },
+        ),
+        Err(domain::update_pokemon::Error::BadRequest(errors)) => print_invalid(&errors),
+        Err(domain::update_pokemon::Error::NotFound) => eprintln!("The Pokemon does not exist"),
+        Err(domain::update_pokemon::Error::Unknown(e)) => {
+            eprintln!("An unknown error occurred: {}", e.chain())
+        }
+    }
+}
@@ -0,0 +1,597 @@
+use std::{fmt, fs, str::FromStr};
+
+use serde::Deserialize;
+
+use crate::{
+    api::ServerConfig,
+    log::Level,
+    repositories::pokemon::{AirtableConfig, AirtableEvolutionFieldNames, AirtableFieldNames},
+};
+
+/// Settings read from the TOML file given with `--config`, then overridden by
+/// the `POKEDEX_*` environment variables and the command line, in that order.
+#[derive(Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    pub mode: Option<Mode>,
+    pub repository: Option<RepositoryKind>,
+    pub sqlite: Option<SqliteSection>,
+    pub airtable: Option<AirtableSection>,
+    #[serde(default)]
+    pub server: ServerSection,
+    #[serde(default)]
+    pub log: LogSection,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Mode {
+    Api,
+    Cli,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum RepositoryKind {
+    Memory,
+    Sqlite,
+    Airtable,
+    FakeAirtable,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+pub struct SqliteSection {
+    pub path: Option<String>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+pub struct ServerSection {
+    pub bind: Option<String>,
+    pub port: Option<u16>,
+    pub workers: Option<usize>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+pub struct LogSection {
+    pub level: Option<Level>,
+}
+
+impl Config {
+    pub fn load(path: &str) -> Result<Self, String> {
+        match fs::read_to_string(path) {
+            Ok(content) => Self::parse(&content),
+            Err(e) => Err(format!("cannot read {}: {}", path, e)),
+        }
+    }
+
+    fn parse(content: &str) -> Result<Self, String> {
+        toml::from_str(content).map_err(|e| e.to_string())
+    }
+
+    /// Overrides the settings with the `POKEDEX_*` variables among `vars`.
+    pub fn apply_env<I>(&mut self, vars: I) -> Result<(), String>
+    where
+        I: IntoIterator<Item = (String, String)>,
+    {
+        for (name, value) in vars {
+            let setting = match name.strip_prefix("POKEDEX_") {
+                Some(setting) => setting,
+                None => continue,
+            };
+            let invalid = |e: String| format!("invalid {}: {}", name, e);
+            match setting {
+                "MODE" => self.mode = Some(value.parse().map_err(invalid)?),
+                "REPOSITORY" => self.repository = Some(value.parse().map_err(invalid)?),
+                "SQLITE_PATH" => self.sqlite_mut().path = Some(value),
+                "AIRTABLE_API_URL" => self.airtable_mut().api_url = Some(value),
+                "AIRTABLE_API_KEY" => self.airtable_mut().api_key = Some(value),
+                "AIRTABLE_BASE_ID" => self.airtable_mut().base_id = Some(value),
+                "AIRTABLE_TABLE" => self.airtable_mut().table = Some(value),
+                "AIRTABLE_EVOLUTIONS_TABLE" => self.airtable_mut().evolutions_table = Some(value),
+                "BIND" => self.server.bind = Some(value),
+                "PORT" => {
+                    self.server.port =
+                        Some(value.parse::<u16>().map_err(|e| invalid(e.to_string()))?)
+                }
+                "WORKERS" => {
+                    self.server.workers =
+                        Some(value.parse::<usize>().map_err(|e| invalid(e.to_string()))?)
+                }
+                "LOG_LEVEL" => self.log.level = Some(value.parse().map_err(invalid)?),
+                _ => return Err(format!("unknown setting {}", name)),
+            };
+        }
+        Ok(())
+    }
+
+    pub fn sqlite_mut(&mut self) -> &mut SqliteSection {
+        self.sqlite.get_or_insert_with(SqliteSection::default)
+    }
+
+    pub fn airtable_mut(&mut self) -> &mut AirtableSection {
+        self.airtable.get_or_insert_with(AirtableSection::default)
+    }
+
+    pub fn sqlite_path(&self) -> Option<&str> {
+        self.sqlite.as_ref().and_then(|s| s.path.as_deref())
+    }
+
+    /// The repository to use: the chosen one if it has the settings it needs,
+    /// otherwise the only one configured, or memory when none is.
+    pub fn repository(&self) -> Result<RepositoryKind, String> {
+        let kind = match (self.repository, self.sqlite_path(), &self.airtable) {
+            (Some(kind), _, _) => kind,
+            (None, Some(_), Some(_)) => {
+                return Err(String::from(
+                    "both sqlite and airtable are configured, set the repository to use",
+                ))
+            }
+            (None, Some(_), None) => RepositoryKind::Sqlite,
+            (None, None, Some(_)) => RepositoryKind::Airtable,
+            (None, None, None) => RepositoryKind::Memory,
+        };
+        match kind {
+            RepositoryKind::Sqlite if self.sqlite_path().is_none() => {
+                Err(String::from("the sqlite repository needs a sqlite path"))
+            }
+            RepositoryKind::Airtable => {
+                match self.airtable.clone().unwrap_or_default().into_config() {
+                    Ok(_) => Ok(kind),
+                    Err(e) => Err(e),
+                }
+            }
+            _ => Ok(kind),
+        }
+    }
+
+    pub fn mode(&self) -> Mode {
+        self.mode.unwrap_or(Mode::Api)
+    }
+
+    pub fn log_level(&self) -> Level {
+        self.log.level.unwrap_or(Level::Info)
+    }
+
+    pub fn server(&self) -> ServerConfig {
+        ServerConfig {
+            bind: self
+                .server
+                .bind
+                .clone()
+                .unwrap_or_else(|| String::from("localhost")),
+            port: self.server.port.unwrap_or(8000),
+            workers: self.server.workers,
+        }
+    }
+}
+
+impl FromStr for Mode {
+    type Err = String;
+
+    fn from_str(mode: &str) -> Result<Self, Self::Err> {
+        match mode {
+            "api" => Ok(Self::Api),
+            "cli" => Ok(Self::Cli),
+            _ => Err(format!("invalid mode {:?}, expected api or cli", mode)),
+        }
+    }
+}
+
+impl FromStr for RepositoryKind {
+    type Err = String;
+
+    fn from_str(kind: &str) -> Result<Self, Self::Err> {
+        match kind {
+            "memory" => Ok(Self::Memory),
+            "sqlite" => Ok(Self::Sqlite),
+            "airtable" => Ok(Self::Airtable),
+            "fake-airtable" => Ok(Self::FakeAirtable),
+            _ => Err(format!(
+                "invalid repository {:?}, expected memory, sqlite, airtable or fake-airtable",
+                kind
+            )),
+        }
+    }
+}
+
+impl fmt::Display for RepositoryKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Memory => write!(f, "memory"),
+            Self::Sqlite => write!(f, "sqlite"),
+            Self::Airtable => write!(f, "airtable"),
+            Self::FakeAirtable => write!(f, "fake-airtable"),
+        }
+    }
+}
+
+#[derive(Clone, Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+pub struct AirtableSection {
+    pub api_url: Option<String>,
+    pub api_key: Option<String>,
+    pub base_id: Option<String>,
+    pub table: Option<String>,
+    pub evolutions_table: Option<String>,
+    #[serde(default)]
+    pub fields: AirtableFieldsSection,
+    #[serde(default)]
+    pub evolution_fields: AirtableEvolutionFieldsSection,
+}
+
+#[derive(Clone, Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+pub struct AirtableFieldsSection {
+    pub number: Option<String>,
+    pub form: Option<String>,
+    pub name: Option<String>,
+    pub types: Option<String>,
+    pub hp: Option<String>,
+    pub attack: Option<String>,
+    pub defense: Option<String>,
+    pub special_attack: Option<String>,
+    pub special_defense: Option<String>,
+    pub speed: Option<String>,
+    pub total: Option<String>,
+}
+
+#[derive(Clone, Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+pub struct AirtableEvolutionFieldsSection {
+    pub from: Option<String>,
+    pub to: Option<String>,
+    pub trigger: Option<String>,
+    pub level: Option<String>,
+    pub item: Option<String>,
+}
+
+impl AirtableSection {
+    /// Maps a Pokemon field to a table column from a `field=column` pair.
+    pub fn set_field(&mut self, mapping: &str) -> Result<(), String> {
+        let (field, column) = match mapping.split_once('=') {
+            Some((field, column)) if !column.is_empty() => (field, String::from(column)),
+            _ => return Err(format!("invalid field mapping {:?}", mapping)),
+        };
+        match field {
+            "number" => self.fields.number = Some(column),
+            "form" => self.fields.form = Some(column),
+            "name" => self.fields.name = Some(column),
+            "types" => self.fields.types = Some(column),
+            "hp" => self.fields.hp = Some(column),
+            "attack" => self.fields.attack = Some(column),
+            "defense" => self.fields.defense = Some(column),
+            "special_attack" => self.fields.special_attack = Some(column),
+            "special_defense" => self.fields.special_defense = Some(column),
+            "speed" => self.fields.speed = Some(column),
+            "total" => self.fields.total = Some(column),
+            _ => return Err(format!("unknown pokemon field {:?}", field)),
+        };
+        Ok(())
+    }
+
+    pub fn into_config(self) -> Result<AirtableConfig, String> {
+        let (api_key, base_id) = match (self.api_key, self.base_id) {
+            (Some(api_key), Some(base_id)) => (api_key, base_id),
+            _ => return Err(String::from("airtable needs both an api_key and a base_id")),
+        };
+        let mut config = AirtableConfig::new(&api_key, &base_id);
+        if let Some(api_url) = self.api_url {
+            config.api_url = api_url;
+        }
+        if let Some(table) = self.table {
+            config.table = table;
+        }
+        if let Some(table) = self.evolutions_table {
+            config.evolutions_table = table;
+        }
+        let defaults = AirtableFieldNames::default();
+        config.fields = AirtableFieldNames {
+            number: self.fields.number.unwrap_or(defaults.number),
+            form: self.fields.form.unwrap_or(defaults.form),
+            name: self.fields.name.unwrap_or(defaults.name),
+            types: self.fields.types.unwrap_or(defaults.types),
+            hp: self.fields.hp.unwrap_or(defaults.hp),
+            attack: self.fields.attack.unwrap_or(defaults.attack),
+            defense: self.fields.defense.unwrap_or(defaults.defense),
+            special_attack: self
+                .fields
+                .special_attack
+                .unwrap_or(defaults.special_attack),
+            special_defense: self
+                .fields
+                .special_defense
+                .unwrap_or(defaults.special_defense),
+            speed: self.fields.speed.unwrap_or(defaults.speed),
+            total: self.fields.total.unwrap_or(defaults.total),
+        };
+        // Formulas reference the columns as `{column}`, with no way to escape
+        // a brace inside the name.
+        let defaults = AirtableEvolutionFieldNames::default();
+        config.evolution_fields = AirtableEvolutionFieldNames {
+            from: self.evolution_fields.from.unwrap_or(defaults.from),
+            to: self.evolution_fields.to.unwrap_or(defaults.to),
+            trigger: self.evolution_fields.trigger.unwrap_or(defaults.trigger),
+            level: self.evolution_fields.level.unwrap_or(defaults.level),
+            item: self.evolution_fields.item.unwrap_or(defaults.item),
+        };
+        let invalid = config
+            .fields
+            .columns()
+            .into_iter()
+            .chain(config.evolution_fields.columns())
+            .find(|column| column.contains(['{', '}']))
+            .map(String::from);
+        match invalid {
+            Some(column) => Err(format!(
+                "airtable column {:?} cannot be used in a formula",
+                column
+            )),
+            None => Ok(config),
+        }
+    }
+}
+
+/// A repository named on the command line: `memory`, `sqlite:PATH`, or
+/// `airtable[:API_KEY:BASE_ID]` completed by the airtable options.
+#[derive(Debug, PartialEq)]
+pub enum RepoSpec {
+    Memory,
+    Sqlite(String),
+    Airtable {
+        api_key: Option<String>,
+        base_id: Option<String>,
+    },
+}
+
+impl FromStr for RepoSpec {
+    type Err = String;
+
+    fn from_str(spec: &str) -> Result<Self, Self::Err> {
+        let (kind, rest) = match spec.split_once(':') {
+            Some((kind, rest)) => (kind, Some(rest)),
+            None => (spec, None),
+        };
+        match (kind, rest) {
+            ("memory", None) => Ok(Self::Memory),
+            ("sqlite", Some(path)) if !path.is_empty() => Ok(Self::Sqlite(String::from(path))),
+            ("airtable", None) => Ok(Self::Airtable {
+                api_key: None,
+                base_id: None,
+            }),
+            ("airtable", Some(rest)) => match rest.split_once(':') {
+                Some((api_key, base_id)) if !api_key.is_empty() && !base_id.is_empty() => {
+                    Ok(Self::Airtable {
+                        api_key: Some(String::from(api_key)),
+                        base_id: Some(String::from(base_id)),
+                    })
+                }
+                _ => Err(format!("invalid airtable spec {:?}", spec)),
+            },
+            _ => Err(format!(
+                "invalid repository {:?}, expected memory, sqlite:PATH or airtable[:API_KEY:BASE_ID]",
+                spec
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_should_read_the_airtable_section() {
+        let config = Config::parse(
+            r#"
+            [airtable]
+            api_url = "http://localhost:9000"
+            api_key = "key"
+            base_id = "appStaging"
+            table = "Pokedex"
+            evolutions_table = "Evolutions"
+
+            [airtable.fields]
+            name = "Name"
+
+            [airtable.evolution_fields]
+            to = "Into"
+            "#,
+        );
+
+        let config = match config.ok().and_then(|c| c.airtable) {
+            Some(section) => section.into_config(),
+            None => unreachable!(),
+        };
+        match config {
+            Ok(config) => {
+                assert_eq!(config.api_url, "http://localhost:9000");
+                assert_eq!(config.base_id, "appStaging");
+                assert_eq!(config.table, "Pokedex");
+                assert_eq!(config.evolutions_table, "Evolutions");
+                assert_eq!(config.fields.number, "number");
+                assert_eq!(config.fields.name, "Name");
+                assert_eq!(config.evolution_fields.from, "from");
+                assert_eq!(config.evolution_fields.to, "Into");
+            }
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_read_every_section() {
+        let config = Config::parse(
+            r#"
+            mode = "cli"
+            repository = "sqlite"
+
+            [sqlite]
+            path = "pokedex.db"
+
+            [server]
+            bind = "0.0.0.0"
+            port = 9000
+            workers = 4
+
+            [log]
+            level = "debug"
+            "#,
+        );
+
+        match config {
+            Ok(config) => {
+                assert_eq!(config.mode(), Mode::Cli);
+                assert_eq!(config.repository(), Ok(RepositoryKind::Sqlite));
+                assert_eq!(config.sqlite_path(), Some("pokedex.db"));
+                assert_eq!(config.server().bind, "0.0.0.0");
+                assert_eq!(config.server().port, 9000);
+                assert_eq!(config.server().workers, Some(4));
+                assert_eq!(config.log_level(), Level::Debug);
+            }
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_default_to_the_api_on_memory() {
+        let config = Config::default();
+
+        assert_eq!(config.mode(), Mode::Api);
+        assert_eq!(config.repository(), Ok(RepositoryKind::Memory));
+        assert_eq!(config.server().bind, "localhost");
+        assert_eq!(config.server().port, 8000);
+        assert_eq!(config.log_level(), Level::Info);
+    }
+
+    #[test]
+    fn it_should_override_the_file_with_the_environment() {
+        let mut config = Config::parse("[server]\nport = 9000\nbind = \"0.0.0.0\"").unwrap();
+
+        let res = config.apply_env(vec![
+            (String::from("POKEDEX_PORT"), String::from("9100")),
+            (String::from("POKEDEX_SQLITE_PATH"), String::from("env.db")),
+            (String::from("HOME"), String::from("/root")),
+        ]);
+
+        assert!(res.is_ok());
+        assert_eq!(config.server().port, 9100);
+        assert_eq!(config.server().bind, "0.0.0.0");
+        assert_eq!(config.repository(), Ok(RepositoryKind::Sqlite));
+    }
+
+    #[test]
+    fn it_should_reject_invalid_environment_settings() {
+        let mut config = Config::default();
+
+        let unknown = config.apply_env(vec![(
+            String::from("POKEDEX_DATABASE"),
+            String::from("pokedex.db"),
+        )]);
+        let invalid = config.apply_env(vec![(String::from("POKEDEX_PORT"), String::from("http"))]);
+
+        assert_eq!(
+            unknown,
+            Err(String::from("unknown setting POKEDEX_DATABASE"))
+        );
+        assert!(invalid.is_err());
+    }
+
+    #[test]
+    fn it_should_reject_sqlite_and_airtable_configured_together() {
+        let config = Config::parse(
+            "[sqlite]\npath = \"pokedex.db\"\n[airtable]\napi_key = \"key\"\nbase_id = \"base\"",
+        )
+        .unwrap();
+
+        assert!(config.repository().is_err());
+    }
+
+    #[test]
+    fn it_should_use_the_chosen_repository_among_the_configured_ones() {
+        let config = Config::parse(
+            "repository = \"airtable\"\n[sqlite]\npath = \"pokedex.db\"\n[airtable]\napi_key = \"key\"\nbase_id = \"base\"",
+        )
+        .unwrap();
+
+        assert_eq!(config.repository(), Ok(RepositoryKind::Airtable));
+    }
+
+    #[test]
+    fn it_should_require_the_settings_of_the_chosen_repository() {
+        let sqlite = Config::parse("repository = \"sqlite\"").unwrap();
+        let airtable = Config::parse("repository = \"airtable\"").unwrap();
+
+        assert!(sqlite.repository().is_err());
+        assert!(airtable.repository().is_err());
+    }
+
+    #[test]
+    fn it_should_reject_unknown_keys() {
+        assert!(Config::parse("[airtable]\nworkspace = \"wsp\"").is_err());
+    }
+
+    #[test]
+    fn it_should_require_an_api_key_and_a_base_id() {
+        let section = AirtableSection {
+            api_key: Some(String::from("key")),
+            ..AirtableSection::default()
+        };
+
+        assert!(section.into_config().is_err());
+    }
+
+    #[test]
+    fn it_should_parse_field_mappings() {
+        let mut section = AirtableSection::default();
+
+        assert!(section.set_field("types=Types").is_ok());
+        assert!(section.set_field("special_attack=Sp. Atk").is_ok());
+        assert!(section.set_field("weight=Weight").is_err());
+        assert!(section.set_field("name").is_err());
+        assert_eq!(section.fields.types, Some(String::from("Types")));
+        assert_eq!(section.fields.special_attack, Some(String::from("Sp. Atk")));
+    }
+
+    #[test]
+    fn it_should_refuse_columns_with_braces() {
+        let mut section = AirtableSection {
+            api_key: Some(String::from("key")),
+            base_id: Some(String::from("base")),
+            ..AirtableSection::default()
+        };
+        assert!(section.set_field("name=Name} & {Secret").is_ok());
+
+        assert!(section.clone().into_config().is_err());
+        section.fields.name = None;
+        section.evolution_fields.item = Some(String::from("{Item}"));
+        assert!(section.into_config().is_err());
+    }
+
+    #[test]
+    fn it_should_parse_repository_specs() {
+        assert_eq!("memory".parse(), Ok(RepoSpec::Memory));
+        assert_eq!(
+            "sqlite:/tmp/pokedex.db".parse(),
+            Ok(RepoSpec::Sqlite(String::from("/tmp/pokedex.db")))
+        );
+        assert_eq!(
+            "airtable".parse(),
+            Ok(RepoSpec::Airtable {
+                api_key: None,
+                base_id: None
+            })
+        );
+        assert_eq!(
+            "airtable:key:base".parse(),
+            Ok(RepoSpec::Airtable {
+                api_key: Some(String::from("key")),
+                base_id: Some(String::from("base"))
+            })
+        );
+        assert!("sqlite".parse::<RepoSpec>().is_err());
+        assert!("airtable:key".parse::<RepoSpec>().is_err());
+        assert!("postgres:db".parse::<RepoSpec>().is_err());
+    }
+}
@@ -0,0 +1,280 @@
+use std::sync::Arc;
+
+use super::entities::{
+    validation_errors, Evolution, EvolutionError, EvolutionTrigger, PokemonForm, PokemonNumber,
+    ValidationError,
+};
+use crate::repositories::{
+    evolution::{EvolutionRepository, InsertError},
+    pokemon::{FetchOneError, Repository, RepositoryError},
+};
+
+pub struct Request {
+    pub from: u16,
+    pub to: u16,
+    pub trigger: String,
+    pub level: Option<u16>,
+    pub item: Option<String>,
+}
+
+pub struct Response {
+    pub from: u16,
+    pub to: u16,
+    pub trigger: String,
+    pub level: Option<u16>,
+    pub item: Option<String>,
+}
+
+pub enum Error {
+    BadRequest(Vec<ValidationError>),
+    NotFound,
+    Conflict,
+    Unknown(RepositoryError),
+}
+
+/// Links the Pokemon to one it evolves into. Both have to exist, the evolved
+/// one must not already evolve from another Pokemon nor come first in the
+/// chain.
+pub fn execute(
+    pokemons: Arc<dyn Repository>,
+    evolutions: Arc<dyn EvolutionRepository>,
+    req: Request,
+) -> Result<Response, Error> {
+    let (from, to, trigger) = match (
+        PokemonNumber::try_from(req.from),
+        PokemonNumber::try_from(req.to),
+        EvolutionTrigger::try_new(req.trigger, req.level, req.item).map_err(ValidationError::from),
+    ) {
+        (Ok(from), Ok(to), Ok(trigger)) => (from, to, trigger),
+        (from, to, trigger) => {
+            return Err(Error::BadRequest(validation_errors([
+                from.err(),
+                to.err(),
+                trigger.err(),
+            ])))
+        }
+    };
+    match pokemons.fetch_one(from.clone(), PokemonForm::default()) {
+        Ok(_) => {}
+        Err(FetchOneError::NotFound) => return Err(Error::NotFound),
+        Err(FetchOneError::Unknown(e)) => return Err(Error::Unknown(e)),
+    }
+    match pokemons.fetch_one(to.clone(), PokemonForm::default()) {
+        Ok(_) => {}
+        Err(FetchOneError::NotFound) => {
+            return Err(Error::BadRequest(vec![EvolutionError::UnknownPokemon(
+                req.to,
+            )
+            .into()]))
+        }
+        Err(FetchOneError::Unknown(e)) => return Err(Error::Unknown(e)),
+    }
+    match comes_before(evolutions.clone(), &to, from.clone()) {
+        Ok(false) => {}
+        Ok(true) => {
+            return Err(Error::BadRequest(
+                vec![EvolutionError::Cycle(req.to).into()],
+            ))
+        }
+        Err(e) => return Err(Error::Unknown(e)),
+    }
+    match evolutions.insert(Evolution { from, to, trigger }) {
+        Ok(evolution) => Ok(Response {
+            from: u16::from(evolution.from),
+            to: u16::from(evolution.to),
+            trigger: String::from(evolution.trigger.name()),
+            level: evolution.trigger.level(),
+            item: evolution.trigger.item(),
+        }),
+        Err(InsertError::Conflict) => Err(Error::Conflict),
+        Err(InsertError::Unknown(e)) => Err(Error::Unknown(e)),
+    }
+}
+
+/// Whether `number` is `stage` or one of its pre-evolutions.
+fn comes_before(
+    evolutions: Arc<dyn EvolutionRepository>,
+    number: &PokemonNumber,
+    stage: PokemonNumber,
+) -> Result<bool, RepositoryError> {
+    let mut visited = vec![];
+    let mut stage = stage;
+    loop {
+        if stage == *number {
+            return Ok(true);
+        }
+        if visited.contains(&stage) {
+            return Err(RepositoryError::new(format!(
+                "the evolutions of pokemon {} make a cycle",
+                u16::from(stage)
+            )));
+        }
+        visited.push(stage.clone());
+        match evolutions.fetch_pre_evolution(stage) {
+            Ok(Some(evolution)) => stage = evolution.from,
+            Ok(None) => return Ok(false),
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        domain::entities::{PokemonName, PokemonTypes},
+        repositories::{evolution::InMemoryEvolutionRepository, pokemon::InMemoryRepository},
+    };
+
+    fn repos() -> (Arc<InMemoryRepository>, Arc<InMemoryEvolutionRepository>) {
+        let pokemons = Arc::new(InMemoryRepository::new());
+        for (number, name) in [
+            (PokemonNumber::charmander(), PokemonName::charmander()),
+            (PokemonNumber::charmeleon(), PokemonName::charmeleon()),
+            (PokemonNumber::charizard(), PokemonName::charizard()),
+        ] {
+            pokemons
+                .insert(
+                    number,
+                    PokemonForm::default(),
+                    name,
+                    PokemonTypes::charmander(),
+                    None,
+                )
+                .ok();
+        }
+        (pokemons, Arc::new(InMemoryEvolutionRepository::new()))
+    }
+
+    fn request(from: u16, to: u16) -> Request {
+        Request {
+            from,
+            to,
+            trigger: String::from("level"),
+            level: Some(16),
+            item: None,
+        }
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_request_is_invalid() {
+        let (pokemons, evolutions) = repos();
+        let req = Request {
+            level: None,
+            ..request(0, 5)
+        };
+
+        let res = execute(pokemons, evolutions, req);
+
+        match res {
+            Err(Error::BadRequest(errors)) => assert_eq!(
+                errors,
+                vec![
+                    ValidationError::NumberOutOfRange(0),
+                    ValidationError::Evolution(EvolutionError::MissingLevel)
+                ]
+            ),
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_not_found_error_when_the_pokemon_does_not_exist() {
+        let (pokemons, evolutions) = repos();
+
+        let res = execute(pokemons, evolutions, request(25, 5));
+
+        match res {
+            Err(Error::NotFound) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_the_evolution_does_not_exist() {
+        let (pokemons, evolutions) = repos();
+
+        let res = execute(pokemons, evolutions, request(4, 26));
+
+        match res {
+            Err(Error::BadRequest(errors)) => {
+                assert_eq!(
+                    errors,
+                    vec![ValidationError::Evolution(EvolutionError::UnknownPokemon(
+                        26
+                    ))]
+                )
+            }
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_the_evolution_makes_a_cycle() {
+        let (pokemons, evolutions) = repos();
+        evolutions.insert(Evolution::charmander()).ok();
+        evolutions.insert(Evolution::charmeleon()).ok();
+
+        for (from, to) in [(6, 4), (5, 5)] {
+            let res = execute(pokemons.clone(), evolutions.clone(), request(from, to));
+
+            match res {
+                Err(Error::BadRequest(errors)) => {
+                    assert_eq!(
+                        errors,
+                        vec![ValidationError::Evolution(EvolutionError::Cycle(to))]
+                    )
+                }
+                _ => unreachable!(),
+            };
+        }
+    }
+
+    #[test]
+    fn it_should_return_a_conflict_error_when_the_evolution_already_evolves_from_another() {
+        let (pokemons, evolutions) = repos();
+        evolutions.insert(Evolution::charmeleon()).ok();
+
+        let res = execute(pokemons, evolutions, request(4, 6));
+
+        match res {
+            Err(Error::Conflict) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let (pokemons, _) = repos();
+        let evolutions = Arc::new(InMemoryEvolutionRepository::new().with_error());
+
+        let res = execute(pokemons, evolutions, request(4, 5));
+
+        match res {
+            Err(Error::Unknown(_)) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_the_evolution_otherwise() {
+        let (pokemons, evolutions) = repos();
+        evolutions.insert(Evolution::charmander()).ok();
+
+        let res = execute(pokemons, evolutions.clone(), request(5, 6));
+
+        match res {
+            Ok(res) => {
+                assert_eq!((res.from, res.to), (5, 6));
+                assert_eq!(res.trigger, "level");
+                assert_eq!(res.level, Some(16));
+                assert_eq!(res.item, None);
+            }
+            _ => unreachable!(),
+        };
+        match evolutions.fetch_pre_evolution(PokemonNumber::charizard()) {
+            Ok(Some(evolution)) => assert!(evolution.from == PokemonNumber::charmeleon()),
+            _ => unreachable!(),
+        };
+    }
+}
@@ -0,0 +1,107 @@
+use std::sync::Arc;
+
+use super::entities::{Ability, AbilityName, ValidationError};
+use crate::repositories::ability::{AbilityRepository, InsertError, RepositoryError};
+
+pub struct Request {
+    pub name: String,
+    pub description: String,
+}
+
+pub struct Response {
+    pub name: String,
+    pub description: String,
+}
+
+pub enum Error {
+    BadRequest(Vec<ValidationError>),
+    Conflict,
+    Unknown(RepositoryError),
+}
+
+pub fn execute(repo: Arc<dyn AbilityRepository>, req: Request) -> Result<Response, Error> {
+    match AbilityName::try_from(req.name) {
+        Ok(name) => match repo.insert(name, req.description) {
+            Ok(Ability { name, description }) => Ok(Response {
+                name: String::from(name),
+                description,
+            }),
+            Err(InsertError::Conflict) => Err(Error::Conflict),
+            Err(InsertError::Unknown(e)) => Err(Error::Unknown(e)),
+        },
+        Err(e) => Err(Error::BadRequest(vec![e.into()])),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::AbilityError;
+    use crate::repositories::ability::InMemoryAbilityRepository;
+
+    impl Request {
+        fn new(name: &str) -> Self {
+            Self {
+                name: String::from(name),
+                description: String::from("May paralyze on contact."),
+            }
+        }
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_the_name_is_empty() {
+        let repo = Arc::new(InMemoryAbilityRepository::new());
+
+        let res = execute(repo, Request::new(""));
+
+        match res {
+            Err(Error::BadRequest(errors)) => {
+                assert_eq!(
+                    errors,
+                    vec![ValidationError::Ability(AbilityError::EmptyName)]
+                )
+            }
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_conflict_error_when_the_ability_already_exists() {
+        let repo = Arc::new(InMemoryAbilityRepository::new());
+        repo.insert(AbilityName::r#static(), String::new()).ok();
+
+        let res = execute(repo, Request::new("Static"));
+
+        match res {
+            Err(Error::Conflict) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let repo = Arc::new(InMemoryAbilityRepository::new().with_error());
+
+        let res = execute(repo, Request::new("Static"));
+
+        match res {
+            Err(Error::Unknown(_)) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_the_ability_otherwise() {
+        let repo = Arc::new(InMemoryAbilityRepository::new());
+
+        let res = execute(repo, Request::new("Static"));
+
+        match res {
+            Ok(res) => {
+                assert_eq!(res.name, "Static");
+                assert_eq!(res.description, "May paralyze on contact.");
+            }
+            _ => unreachable!(),
+        };
+    }
+}
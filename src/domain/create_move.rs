@@ -0,0 +1,194 @@
+use std::sync::Arc;
+
+use super::entities::{
+    validation_errors, Accuracy, Move, MoveCategory, MoveName, MovePower, PokemonType, PowerPoints,
+    ValidationError,
+};
+use crate::repositories::moves::{InsertError, MoveRepository, RepositoryError};
+
+pub struct Request {
+    pub name: String,
+    pub r#type: String,
+    pub category: String,
+    pub power: Option<u16>,
+    pub accuracy: Option<u16>,
+    pub pp: u16,
+}
+
+pub struct Response {
+    pub name: String,
+    pub r#type: String,
+    pub category: String,
+    pub power: Option<u16>,
+    pub accuracy: Option<u16>,
+    pub pp: u16,
+}
+
+pub enum Error {
+    BadRequest(Vec<ValidationError>),
+    Conflict,
+    Unknown(RepositoryError),
+}
+
+pub fn execute(repo: Arc<dyn MoveRepository>, req: Request) -> Result<Response, Error> {
+    let r#move = match (
+        MoveName::try_from(req.name).map_err(ValidationError::from),
+        PokemonType::try_from(req.r#type),
+        MoveCategory::try_from(req.category).map_err(ValidationError::from),
+        req.power
+            .map(MovePower::try_from)
+            .transpose()
+            .map_err(ValidationError::from),
+        req.accuracy
+            .map(Accuracy::try_from)
+            .transpose()
+            .map_err(ValidationError::from),
+        PowerPoints::try_from(req.pp).map_err(ValidationError::from),
+    ) {
+        (Ok(name), Ok(r#type), Ok(category), Ok(power), Ok(accuracy), Ok(pp)) => {
+            match Move::try_new(name, r#type, category, power, accuracy, pp) {
+                Ok(r#move) => r#move,
+                Err(e) => return Err(Error::BadRequest(vec![e.into()])),
+            }
+        }
+        (name, r#type, category, power, accuracy, pp) => {
+            return Err(Error::BadRequest(validation_errors([
+                name.err(),
+                r#type.err(),
+                category.err(),
+                power.err(),
+                accuracy.err(),
+                pp.err(),
+            ])))
+        }
+    };
+    match repo.insert(r#move) {
+        Ok(Move {
+            name,
+            r#type,
+            category,
+            power,
+            accuracy,
+            pp,
+        }) => Ok(Response {
+            name: String::from(name),
+            r#type: String::from(r#type),
+            category: String::from(category),
+            power: power.map(u16::from),
+            accuracy: accuracy.map(u16::from),
+            pp: u16::from(pp),
+        }),
+        Err(InsertError::Conflict) => Err(Error::Conflict),
+        Err(InsertError::Unknown(e)) => Err(Error::Unknown(e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::MoveError;
+    use crate::repositories::moves::InMemoryMoveRepository;
+
+    impl Request {
+        fn thunderbolt() -> Self {
+            Self {
+                name: String::from("Thunderbolt"),
+                r#type: String::from("Electric"),
+                category: String::from("Special"),
+                power: Some(90),
+                accuracy: Some(100),
+                pp: 15,
+            }
+        }
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_request_is_invalid() {
+        let repo = Arc::new(InMemoryMoveRepository::new());
+        let req = Request {
+            name: String::new(),
+            category: String::from("Magic"),
+            accuracy: Some(101),
+            ..Request::thunderbolt()
+        };
+
+        let res = execute(repo, req);
+
+        match res {
+            Err(Error::BadRequest(errors)) => assert_eq!(
+                errors,
+                vec![
+                    ValidationError::Move(MoveError::EmptyName),
+                    ValidationError::Move(MoveError::UnknownCategory(String::from("Magic"))),
+                    ValidationError::Move(MoveError::AccuracyOutOfRange(101))
+                ]
+            ),
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_a_status_move_has_a_power() {
+        let repo = Arc::new(InMemoryMoveRepository::new());
+        let req = Request {
+            category: String::from("Status"),
+            ..Request::thunderbolt()
+        };
+
+        let res = execute(repo, req);
+
+        match res {
+            Err(Error::BadRequest(errors)) => {
+                assert_eq!(
+                    errors,
+                    vec![ValidationError::Move(MoveError::StatusWithPower)]
+                )
+            }
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_conflict_error_when_the_move_already_exists() {
+        let repo = Arc::new(InMemoryMoveRepository::new());
+        repo.insert(Move::thunderbolt()).ok();
+
+        let res = execute(repo, Request::thunderbolt());
+
+        match res {
+            Err(Error::Conflict) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let repo = Arc::new(InMemoryMoveRepository::new().with_error());
+
+        let res = execute(repo, Request::thunderbolt());
+
+        match res {
+            Err(Error::Unknown(_)) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_the_move_otherwise() {
+        let repo = Arc::new(InMemoryMoveRepository::new());
+
+        let res = execute(repo, Request::thunderbolt());
+
+        match res {
+            Ok(res) => {
+                assert_eq!(res.name, "Thunderbolt");
+                assert_eq!(res.r#type, "Electric");
+                assert_eq!(res.category, "Special");
+                assert_eq!(res.power, Some(90));
+                assert_eq!(res.accuracy, Some(100));
+                assert_eq!(res.pp, 15);
+            }
+            _ => unreachable!(),
+        };
+    }
+}
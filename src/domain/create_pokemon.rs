@@ -1,46 +1,71 @@
 use std::{result::Result, sync::Arc};
 
-use super::entities::*;
-use crate::repositories::pokemon::{InsertError, Repository};
+use super::{entities::*, Stats};
+use crate::repositories::pokemon::{InsertError, Repository, RepositoryError};
 
 pub struct Request {
     pub number: u16,
+    /// The default form when none is given.
+    pub form: Option<String>,
     pub name: String,
     pub types: Vec<String>,
+    pub stats: Option<Stats>,
 }
 
 pub struct Response {
     pub number: u16,
+    /// None for the default form.
+    pub form: Option<String>,
     pub name: String,
     pub types: Vec<String>,
+    pub stats: Option<Stats>,
+    pub total: Option<u16>,
 }
 
 pub enum Error {
-    BadRequest,
+    BadRequest(Vec<ValidationError>),
     Conflict,
-    Unknown,
+    Unknown(RepositoryError),
 }
 
 pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response, Error> {
     match (
         PokemonNumber::try_from(req.number),
+        PokemonForm::try_from(req.form.unwrap_or_default()).map_err(ValidationError::from),
         PokemonName::try_from(req.name),
         PokemonTypes::try_from(req.types),
+        req.stats
+            .map(BaseStats::try_from)
+            .transpose()
+            .map_err(ValidationError::from),
     ) {
-        (Ok(number), Ok(name), Ok(types)) => match repo.insert(number, name, types) {
-            Ok(Pokemon {
-                number,
-                name,
-                types,
-            }) => Ok(Response {
-                number: u16::from(number),
-                name: String::from(name),
-                types: Vec::<String>::from(types),
-            }),
-            Err(InsertError::Conflict) => Err(Error::Conflict),
-            Err(InsertError::Unknown) => Err(Error::Unknown),
-        },
-        _ => Err(Error::BadRequest),
+        (Ok(number), Ok(form), Ok(name), Ok(types), Ok(stats)) => {
+            match repo.insert(number, form, name, types, stats) {
+                Ok(Pokemon {
+                    number,
+                    form,
+                    name,
+                    types,
+                    stats,
+                }) => Ok(Response {
+                    number: u16::from(number),
+                    form: form.into(),
+                    name: String::from(name),
+                    types: Vec::<String>::from(types),
+                    stats: stats.map(Stats::from),
+                    total: stats.map(|stats| stats.total()),
+                }),
+                Err(InsertError::Conflict) => Err(Error::Conflict),
+                Err(InsertError::Unknown(e)) => Err(Error::Unknown(e)),
+            }
+        }
+        (number, form, name, types, stats) => Err(Error::BadRequest(validation_errors([
+            number.err(),
+            form.err(),
+            name.err(),
+            types.err(),
+            stats.err(),
+        ]))),
     }
 }
 
@@ -53,15 +78,17 @@ mod tests {
         fn new(number: PokemonNumber, name: PokemonName, types: PokemonTypes) -> Self {
             Self {
                 number: u16::from(number),
+                form: None,
                 name: String::from(name),
                 types: Vec::<String>::from(types),
+                stats: None,
             }
         }
     }
 
     #[test]
     fn it_should_return_a_bad_request_error_when_request_is_invalid() {
-        let mut repo = Arc::new(InMemoryRepository::new());
+        let repo = Arc::new(InMemoryRepository::new());
 
         let req = Request::new(
             PokemonNumber::pikachu(),
@@ -72,17 +99,19 @@ mod tests {
         let res = execute(repo.clone(), req);
 
         match res {
-            Err(Error::BadRequest) => {}
+            Err(Error::BadRequest(errors)) => assert_eq!(errors, vec![ValidationError::EmptyName]),
             _ => unreachable!(),
         };
     }
     #[test]
     fn it_should_return_a_conflict_error_when_pokemon_number_already_exists() {
-        let mut repo = Arc::new(InMemoryRepository::new());
+        let repo = Arc::new(InMemoryRepository::new());
         repo.insert(
             PokemonNumber::pikachu(),
+            PokemonForm::default(),
             PokemonName::pikachu(),
             PokemonTypes::pikachu(),
+            None,
         )
         .ok();
         let req = Request::new(
@@ -99,10 +128,62 @@ mod tests {
         }
     }
 
+    #[test]
+    fn it_should_create_another_form_of_an_existing_number() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::raichu(),
+            PokemonForm::default(),
+            PokemonName::raichu(),
+            PokemonTypes::pikachu(),
+            None,
+        )
+        .ok();
+        let req = Request {
+            form: Some(String::from("alola")),
+            ..Request::new(
+                PokemonNumber::raichu(),
+                PokemonName::raichu(),
+                PokemonTypes::alolan_raichu(),
+            )
+        };
+
+        let res = execute(repo, req);
+
+        match res {
+            Ok(res) => assert_eq!(res.form, Some(String::from("alola"))),
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_the_form_is_invalid() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = Request {
+            form: Some(String::from("Alola")),
+            ..Request::new(
+                PokemonNumber::raichu(),
+                PokemonName::raichu(),
+                PokemonTypes::alolan_raichu(),
+            )
+        };
+
+        let res = execute(repo, req);
+
+        match res {
+            Err(Error::BadRequest(errors)) => assert_eq!(
+                errors,
+                vec![ValidationError::Form(FormError::Invalid(String::from(
+                    "Alola"
+                )))]
+            ),
+            _ => unreachable!(),
+        };
+    }
+
     #[test]
     fn it_should_return_an_error_when_an_unexpected_error_happens() {
-        let mut repo = Arc::new(InMemoryRepository::new().with_error());
-        let number = 25;
+        let repo = Arc::new(InMemoryRepository::new().with_error());
         let req = Request::new(
             PokemonNumber::pikachu(),
             PokemonName::pikachu(),
@@ -112,7 +193,55 @@ mod tests {
         let res = execute(repo.clone(), req);
 
         match res {
-            Err(Error::Unknown) => {}
+            Err(Error::Unknown(_)) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_a_stat_is_out_of_range() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let mut req = Request::new(
+            PokemonNumber::pikachu(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+        );
+        req.stats = Some(Stats {
+            speed: 256,
+            ..Stats::from(BaseStats::pikachu())
+        });
+
+        let res = execute(repo, req);
+
+        match res {
+            Err(Error::BadRequest(errors)) => assert_eq!(
+                errors,
+                vec![ValidationError::Stats(StatsError::OutOfRange(
+                    Stat::Speed,
+                    256
+                ))]
+            ),
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_the_stats_and_their_total() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let mut req = Request::new(
+            PokemonNumber::pikachu(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+        );
+        req.stats = Some(Stats::from(BaseStats::pikachu()));
+
+        let res = execute(repo, req);
+
+        match res {
+            Ok(res) => {
+                assert_eq!(res.stats, Some(Stats::from(BaseStats::pikachu())));
+                assert_eq!(res.total, Some(320));
+            }
             _ => unreachable!(),
         };
     }
@@ -133,6 +262,7 @@ mod tests {
                 number,
                 name,
                 types,
+                ..
             }) => {
                 assert_eq!(number, u16::from(PokemonNumber::pikachu()));
                 assert_eq!(name, String::from(PokemonName::pikachu()));
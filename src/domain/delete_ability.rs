@@ -0,0 +1,62 @@
+use std::sync::Arc;
+
+use super::entities::{AbilityName, ValidationError};
+use crate::repositories::ability::{AbilityRepository, DeleteError, RepositoryError};
+
+pub struct Request {
+    pub name: String,
+}
+
+pub enum Error {
+    BadRequest(Vec<ValidationError>),
+    NotFound,
+    Unknown(RepositoryError),
+}
+
+pub fn execute(repo: Arc<dyn AbilityRepository>, req: Request) -> Result<(), Error> {
+    match AbilityName::try_from(req.name) {
+        Ok(name) => match repo.delete(name) {
+            Ok(()) => Ok(()),
+            Err(DeleteError::NotFound) => Err(Error::NotFound),
+            Err(DeleteError::Unknown(e)) => Err(Error::Unknown(e)),
+        },
+        Err(e) => Err(Error::BadRequest(vec![e.into()])),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repositories::ability::InMemoryAbilityRepository;
+
+    impl Request {
+        fn new(name: AbilityName) -> Self {
+            Self {
+                name: String::from(name),
+            }
+        }
+    }
+
+    #[test]
+    fn it_should_return_a_not_found_error_when_the_repo_does_not_contain_the_ability() {
+        let repo = Arc::new(InMemoryAbilityRepository::new());
+
+        let res = execute(repo, Request::new(AbilityName::blaze()));
+
+        match res {
+            Err(Error::NotFound) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_delete_the_ability_otherwise() {
+        let repo = Arc::new(InMemoryAbilityRepository::new());
+        repo.insert(AbilityName::blaze(), String::new()).ok();
+
+        let res = execute(repo.clone(), Request::new(AbilityName::blaze()));
+
+        assert!(res.is_ok());
+        assert!(repo.fetch_one(AbilityName::blaze()).is_err());
+    }
+}
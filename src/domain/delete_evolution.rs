@@ -0,0 +1,99 @@
+use std::sync::Arc;
+
+use super::entities::{validation_errors, PokemonNumber, ValidationError};
+use crate::repositories::{
+    evolution::{DeleteError, EvolutionRepository},
+    pokemon::RepositoryError,
+};
+
+pub struct Request {
+    pub from: u16,
+    pub to: u16,
+}
+
+pub enum Error {
+    BadRequest(Vec<ValidationError>),
+    NotFound,
+    Unknown(RepositoryError),
+}
+
+/// Unlinks the Pokemon from one it evolves into, leaving both Pokemon in
+/// place.
+pub fn execute(evolutions: Arc<dyn EvolutionRepository>, req: Request) -> Result<(), Error> {
+    match (
+        PokemonNumber::try_from(req.from),
+        PokemonNumber::try_from(req.to),
+    ) {
+        (Ok(from), Ok(to)) => match evolutions.delete(from, to) {
+            Ok(()) => Ok(()),
+            Err(DeleteError::NotFound) => Err(Error::NotFound),
+            Err(DeleteError::Unknown(e)) => Err(Error::Unknown(e)),
+        },
+        (from, to) => Err(Error::BadRequest(validation_errors([from.err(), to.err()]))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        domain::entities::Evolution, repositories::evolution::InMemoryEvolutionRepository,
+    };
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_request_is_invalid() {
+        let evolutions = Arc::new(InMemoryEvolutionRepository::new());
+        let req = Request { from: 0, to: 5 };
+
+        let res = execute(evolutions, req);
+
+        match res {
+            Err(Error::BadRequest(errors)) => {
+                assert_eq!(errors, vec![ValidationError::NumberOutOfRange(0)])
+            }
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_not_found_error_when_the_evolution_does_not_exist() {
+        let evolutions = Arc::new(InMemoryEvolutionRepository::new());
+        evolutions.insert(Evolution::charmander()).ok();
+        let req = Request { from: 4, to: 6 };
+
+        let res = execute(evolutions, req);
+
+        match res {
+            Err(Error::NotFound) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let evolutions = Arc::new(InMemoryEvolutionRepository::new().with_error());
+        let req = Request { from: 4, to: 5 };
+
+        let res = execute(evolutions, req);
+
+        match res {
+            Err(Error::Unknown(_)) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_delete_the_evolution_otherwise() {
+        let evolutions = Arc::new(InMemoryEvolutionRepository::new());
+        evolutions.insert(Evolution::charmander()).ok();
+        let req = Request { from: 4, to: 5 };
+
+        let res = execute(evolutions.clone(), req);
+
+        assert!(res.is_ok());
+        match evolutions.fetch_pre_evolution(PokemonNumber::charmeleon()) {
+            Ok(None) => {}
+            _ => unreachable!(),
+        };
+    }
+}
@@ -1,26 +1,34 @@
-use crate::domain::entities::PokemonNumber;
-use crate::repositories::pokemon::{DeleteError, Repository};
+use crate::domain::entities::{validation_errors, PokemonForm, PokemonNumber, ValidationError};
+use crate::repositories::pokemon::{DeleteError, Repository, RepositoryError};
 use std::convert::TryFrom;
 use std::sync::Arc;
 
 pub struct Request {
     pub number: u16,
+    /// The default form when none is given, deleting the other forms too.
+    pub form: Option<String>,
 }
 
 pub enum Error {
-    BadRequest,
+    BadRequest(Vec<ValidationError>),
     NotFound,
-    Unknown,
+    Unknown(RepositoryError),
 }
 
 pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<(), Error> {
-    match PokemonNumber::try_from(req.number) {
-        Ok(number) => match repo.delete(number) {
+    match (
+        PokemonNumber::try_from(req.number),
+        PokemonForm::try_from(req.form.unwrap_or_default()).map_err(ValidationError::from),
+    ) {
+        (Ok(number), Ok(form)) => match repo.delete(number, form) {
             Ok(()) => Ok(()),
             Err(DeleteError::NotFound) => Err(Error::NotFound),
-            Err(DeleteError::Unknown) => Err(Error::Unknown),
+            Err(DeleteError::Unknown(e)) => Err(Error::Unknown(e)),
         },
-        _ => Err(Error::BadRequest),
+        (number, form) => Err(Error::BadRequest(validation_errors([
+            number.err(),
+            form.err(),
+        ]))),
     }
 }
 
@@ -30,7 +38,7 @@ mod tests {
 
     use super::*;
     use crate::domain::entities::{PokemonName, PokemonNumber, PokemonTypes};
-    use crate::repositories::pokemon::InMemoryRepository;
+    use crate::repositories::pokemon::{FetchOneError, InMemoryRepository};
 
     #[test]
     fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
@@ -40,7 +48,7 @@ mod tests {
         let res = execute(repo, req);
 
         match res {
-            Err(Error::Unknown) => {}
+            Err(Error::Unknown(_)) => {}
             _ => unreachable!(),
         };
     }
@@ -53,7 +61,7 @@ mod tests {
         let res = execute(repo, req);
 
         match res {
-            Err(Error::BadRequest) => {}
+            Err(Error::BadRequest(_)) => {}
             _ => unreachable!(),
         };
     }
@@ -76,8 +84,10 @@ mod tests {
         let repo = Arc::new(InMemoryRepository::new());
         repo.insert(
             PokemonNumber::pikachu(),
+            PokemonForm::default(),
             PokemonName::pikachu(),
             PokemonTypes::pikachu(),
+            None,
         )
         .ok();
         let req = Request::new(PokemonNumber::pikachu());
@@ -90,10 +100,41 @@ mod tests {
         };
     }
 
+    #[test]
+    fn it_should_keep_the_default_form_when_deleting_another() {
+        let repo = Arc::new(InMemoryRepository::new());
+        for form in [PokemonForm::default(), PokemonForm::alola()] {
+            repo.insert(
+                PokemonNumber::raichu(),
+                form,
+                PokemonName::raichu(),
+                PokemonTypes::pikachu(),
+                None,
+            )
+            .ok();
+        }
+        let req = Request {
+            form: Some(String::from("alola")),
+            ..Request::new(PokemonNumber::raichu())
+        };
+
+        let res = execute(repo.clone(), req);
+
+        assert!(res.is_ok());
+        assert!(repo
+            .fetch_one(PokemonNumber::raichu(), PokemonForm::default())
+            .is_ok());
+        match repo.fetch_one(PokemonNumber::raichu(), PokemonForm::alola()) {
+            Err(FetchOneError::NotFound) => {}
+            _ => unreachable!(),
+        };
+    }
+
     impl Request {
         fn new(number: PokemonNumber) -> Self {
             Self {
                 number: u16::from(number),
+                form: None,
             }
         }
     }
@@ -1,155 +0,0 @@
-use std::cmp::PartialEq;
-
-#[derive(Clone, PartialEq, Eq, PartialOrd, Ord)]
-pub struct PokemonNumber(u16);
-impl TryFrom<u16> for PokemonNumber {
-    type Error = ();
-
-    fn try_from(n: u16) -> Result<Self, Self::Error> {
-        if n > 0 && n < 899 {
-            Ok(Self(n))
-        } else {
-            Err(())
-        }
-    }
-}
-
-impl From<PokemonNumber> for u16 {
-    fn from(n: PokemonNumber) -> u16 {
-        n.0
-    }
-}
-
-#[cfg(test)]
-impl PokemonNumber {
-    pub fn pikachu() -> Self {
-        Self(25)
-    }
-    pub fn charmander() -> Self {
-        Self(4)
-    }
-    pub fn bad() -> Self {
-        Self(0)
-    }
-}
-
-#[derive(Clone)]
-pub struct PokemonName(String);
-
-impl TryFrom<String> for PokemonName {
-    type Error = ();
-
-    fn try_from(n: String) -> Result<Self, Self::Error> {
-        if n.is_empty() {
-            Err(())
-        } else {
-            Ok(Self(n))
-        }
-    }
-}
-
-impl From<PokemonName> for String {
-    fn from(n: PokemonName) -> Self {
-        n.0
-    }
-}
-
-#[cfg(test)]
-impl PokemonName {
-    pub fn pikachu() -> Self {
-        Self(String::from("Pikachu"))
-    }
-
-    pub fn charmander() -> Self {
-        Self(String::from("Charmander"))
-    }
-
-    pub fn bad() -> Self {
-        Self(String::from(""))
-    }
-}
-
-#[derive(Clone)]
-pub struct PokemonTypes(Vec<PokemonType>);
-
-impl TryFrom<Vec<String>> for PokemonTypes {
-    type Error = ();
-
-    fn try_from(ts: Vec<String>) -> Result<Self, Self::Error> {
-        if ts.is_empty() {
-            Err(())
-        } else {
-            let mut pts = vec![];
-            for t in ts.iter() {
-                match PokemonType::try_from(String::from(t)) {
-                    Ok(pt) => pts.push(pt),
-                    _ => return Err(()),
-                }
-            }
-            Ok(Self(pts))
-        }
-    }
-}
-
-impl From<PokemonTypes> for Vec<String> {
-    fn from(pts: PokemonTypes) -> Self {
-        pts.0
-            .iter()
-            .map(|x| String::from(x.clone()))
-            .collect::<Self>()
-    }
-}
-
-#[cfg(test)]
-impl PokemonTypes {
-    pub fn pikachu() -> Self {
-        Self(vec![PokemonType::Electric])
-    }
-
-    pub fn charmander() -> Self {
-        Self(vec![PokemonType::Fire])
-    }
-}
-
-#[derive(Clone)]
-enum PokemonType {
-    Electric,
-    Fire,
-}
-
-impl TryFrom<String> for PokemonType {
-    type Error = ();
-
-    fn try_from(t: String) -> Result<Self, Self::Error> {
-        match t.as_str() {
-            "Electric" => Ok(Self::Electric),
-            "Fire" => Ok(Self::Fire),
-            _ => Err(()),
-        }
-    }
-}
-impl From<PokemonType> for String {
-    fn from(t: PokemonType) -> Self {
-        String::from(match t {
-            PokemonType::Electric => "Electric",
-            PokemonType::Fire => "Fire",
-        })
-    }
-}
-
-#[derive(Clone)]
-pub struct Pokemon {
-    pub number: PokemonNumber,
-    pub name: PokemonName,
-    pub types: PokemonTypes,
-}
-
-impl Pokemon {
-    pub fn new(number: PokemonNumber, name: PokemonName, types: PokemonTypes) -> Self {
-        Self {
-            number,
-            name,
-            types,
-        }
-    }
-}
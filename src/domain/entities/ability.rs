@@ -0,0 +1,207 @@
+use std::{error::Error, fmt};
+
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct AbilityName(String);
+
+impl TryFrom<String> for AbilityName {
+    type Error = AbilityError;
+
+    fn try_from(n: String) -> Result<Self, Self::Error> {
+        if n.trim().is_empty() {
+            Err(AbilityError::EmptyName)
+        } else {
+            Ok(Self(n))
+        }
+    }
+}
+
+impl From<AbilityName> for String {
+    fn from(n: AbilityName) -> Self {
+        n.0
+    }
+}
+
+#[cfg(test)]
+impl AbilityName {
+    pub fn r#static() -> Self {
+        Self(String::from("Static"))
+    }
+
+    pub fn lightning_rod() -> Self {
+        Self(String::from("Lightning Rod"))
+    }
+
+    pub fn blaze() -> Self {
+        Self(String::from("Blaze"))
+    }
+
+    pub fn solar_power() -> Self {
+        Self(String::from("Solar Power"))
+    }
+}
+
+#[derive(Clone)]
+pub struct Ability {
+    pub name: AbilityName,
+    pub description: String,
+}
+
+impl Ability {
+    pub fn new(name: AbilityName, description: String) -> Self {
+        Self { name, description }
+    }
+}
+
+/// The abilities a Pokemon can have: at most two regular ones, plus a hidden one.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PokemonAbilities {
+    regular: Vec<AbilityName>,
+    hidden: Option<AbilityName>,
+}
+
+impl PokemonAbilities {
+    pub const MAX_REGULAR: usize = 2;
+
+    pub fn try_new(regular: Vec<String>, hidden: Option<String>) -> Result<Self, AbilityError> {
+        if regular.len() > Self::MAX_REGULAR {
+            return Err(AbilityError::TooMany(regular.len()));
+        }
+        let mut names: Vec<AbilityName> = vec![];
+        for name in regular.into_iter().chain(hidden.clone()) {
+            match AbilityName::try_from(name) {
+                Ok(name) if names.contains(&name) => {
+                    return Err(AbilityError::Duplicate(String::from(name)))
+                }
+                Ok(name) => names.push(name),
+                Err(e) => return Err(e),
+            }
+        }
+        let hidden = match hidden {
+            Some(_) => names.pop(),
+            None => None,
+        };
+        Ok(Self {
+            regular: names,
+            hidden,
+        })
+    }
+
+    pub fn regular(&self) -> &[AbilityName] {
+        &self.regular
+    }
+
+    pub fn hidden(&self) -> Option<&AbilityName> {
+        self.hidden.as_ref()
+    }
+
+    #[cfg(test)]
+    pub fn is_empty(&self) -> bool {
+        self.regular.is_empty() && self.hidden.is_none()
+    }
+
+    pub fn contains(&self, name: &AbilityName) -> bool {
+        self.regular.contains(name) || self.hidden.as_ref() == Some(name)
+    }
+
+    /// The same abilities, less `name`.
+    pub fn without(mut self, name: &AbilityName) -> Self {
+        self.regular.retain(|n| n != name);
+        if self.hidden.as_ref() == Some(name) {
+            self.hidden = None;
+        }
+        self
+    }
+}
+
+#[cfg(test)]
+impl PokemonAbilities {
+    pub fn pikachu() -> Self {
+        Self {
+            regular: vec![AbilityName::r#static()],
+            hidden: Some(AbilityName::lightning_rod()),
+        }
+    }
+
+    pub fn charmander() -> Self {
+        Self {
+            regular: vec![AbilityName::blaze()],
+            hidden: Some(AbilityName::solar_power()),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum AbilityError {
+    EmptyName,
+    TooMany(usize),
+    Duplicate(String),
+    Unknown(String),
+}
+
+impl AbilityError {
+    pub fn field(&self) -> &'static str {
+        match self {
+            Self::EmptyName => "name",
+            Self::TooMany(_) | Self::Duplicate(_) | Self::Unknown(_) => "abilities",
+        }
+    }
+}
+
+impl fmt::Display for AbilityError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::EmptyName => write!(f, "ability name must not be empty"),
+            Self::TooMany(n) => write!(
+                f,
+                "abilities must hold at most {} regular abilities, got {}",
+                PokemonAbilities::MAX_REGULAR,
+                n
+            ),
+            Self::Duplicate(name) => write!(f, "ability {:?} is given twice", name),
+            Self::Unknown(name) => write!(f, "unknown ability {:?}", name),
+        }
+    }
+}
+
+impl Error for AbilityError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_should_accept_two_regular_abilities_and_a_hidden_one() {
+        let abilities = PokemonAbilities::try_new(
+            vec![String::from("Static"), String::from("Plus")],
+            Some(String::from("Lightning Rod")),
+        )
+        .unwrap();
+
+        assert_eq!(abilities.regular().len(), 2);
+        assert_eq!(abilities.hidden(), Some(&AbilityName::lightning_rod()));
+        assert!(PokemonAbilities::try_new(vec![], None).unwrap().is_empty());
+    }
+
+    #[test]
+    fn it_should_reject_more_than_two_regular_abilities() {
+        let names = ["Static", "Plus", "Minus"].map(String::from).to_vec();
+
+        let err = PokemonAbilities::try_new(names, None).err().unwrap();
+
+        assert_eq!(err, AbilityError::TooMany(3));
+        assert_eq!(err.field(), "abilities");
+    }
+
+    #[test]
+    fn it_should_reject_an_ability_given_twice() {
+        assert_eq!(
+            PokemonAbilities::try_new(vec![String::from("Static")], Some(String::from("Static")))
+                .err(),
+            Some(AbilityError::Duplicate(String::from("Static")))
+        );
+        assert_eq!(
+            PokemonAbilities::try_new(vec![String::from(" ")], None).err(),
+            Some(AbilityError::EmptyName)
+        );
+    }
+}
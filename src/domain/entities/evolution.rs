@@ -0,0 +1,235 @@
+use std::{error::Error, fmt};
+
+use super::{Level, PokemonNumber};
+
+/// The name of an item, such as an evolution stone or a held item.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ItemName(String);
+
+impl TryFrom<String> for ItemName {
+    type Error = EvolutionError;
+
+    fn try_from(n: String) -> Result<Self, Self::Error> {
+        if n.is_empty() {
+            Err(EvolutionError::EmptyItemName)
+        } else {
+            Ok(Self(n))
+        }
+    }
+}
+
+impl From<ItemName> for String {
+    fn from(n: ItemName) -> Self {
+        n.0
+    }
+}
+
+/// What makes a Pokemon evolve. A traded Pokemon may have to hold an item.
+#[derive(Clone, Debug, PartialEq)]
+pub enum EvolutionTrigger {
+    Level(Level),
+    Item(ItemName),
+    Trade(Option<ItemName>),
+    Friendship,
+}
+
+impl EvolutionTrigger {
+    /// Reads a trigger as exchanged with the adapters, the level and the
+    /// item being given only to the triggers using them.
+    pub fn try_new(
+        trigger: String,
+        level: Option<u16>,
+        item: Option<String>,
+    ) -> Result<Self, EvolutionError> {
+        match trigger.as_str() {
+            "level" | "item" | "trade" | "friendship" => {}
+            _ => return Err(EvolutionError::UnknownTrigger(trigger)),
+        }
+        if trigger != "level" && level.is_some() {
+            return Err(EvolutionError::UnexpectedLevel(trigger));
+        }
+        if (trigger == "level" || trigger == "friendship") && item.is_some() {
+            return Err(EvolutionError::UnexpectedItem(trigger));
+        }
+        let item = item.map(ItemName::try_from).transpose()?;
+        match (trigger.as_str(), level, item) {
+            ("level", Some(level), _) => match Level::try_from(level) {
+                Ok(level) => Ok(Self::Level(level)),
+                Err(_) => Err(EvolutionError::LevelOutOfRange(level)),
+            },
+            ("level", None, _) => Err(EvolutionError::MissingLevel),
+            ("item", _, Some(item)) => Ok(Self::Item(item)),
+            ("item", _, None) => Err(EvolutionError::MissingItem),
+            ("trade", _, item) => Ok(Self::Trade(item)),
+            _ => Ok(Self::Friendship),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Level(_) => "level",
+            Self::Item(_) => "item",
+            Self::Trade(_) => "trade",
+            Self::Friendship => "friendship",
+        }
+    }
+
+    pub fn level(&self) -> Option<u16> {
+        match self {
+            Self::Level(level) => Some(u16::from(*level)),
+            _ => None,
+        }
+    }
+
+    pub fn item(&self) -> Option<String> {
+        match self {
+            Self::Item(item) | Self::Trade(Some(item)) => Some(String::from(item.clone())),
+            _ => None,
+        }
+    }
+}
+
+/// A Pokemon evolving into another one. A Pokemon evolves from one other at
+/// most, but may evolve into several ones.
+#[derive(Clone, PartialEq)]
+pub struct Evolution {
+    pub from: PokemonNumber,
+    pub to: PokemonNumber,
+    pub trigger: EvolutionTrigger,
+}
+
+#[cfg(test)]
+impl Evolution {
+    pub fn charmander() -> Self {
+        Self {
+            from: PokemonNumber::charmander(),
+            to: PokemonNumber::charmeleon(),
+            trigger: EvolutionTrigger::Level(Level(16)),
+        }
+    }
+
+    pub fn charmeleon() -> Self {
+        Self {
+            from: PokemonNumber::charmeleon(),
+            to: PokemonNumber::charizard(),
+            trigger: EvolutionTrigger::Level(Level(36)),
+        }
+    }
+
+    pub fn pikachu() -> Self {
+        Self {
+            from: PokemonNumber::pikachu(),
+            to: PokemonNumber::raichu(),
+            trigger: EvolutionTrigger::Item(ItemName(String::from("Thunder Stone"))),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum EvolutionError {
+    UnknownTrigger(String),
+    MissingLevel,
+    UnexpectedLevel(String),
+    LevelOutOfRange(u16),
+    MissingItem,
+    UnexpectedItem(String),
+    EmptyItemName,
+    UnknownPokemon(u16),
+    Cycle(u16),
+}
+
+impl EvolutionError {
+    pub fn field(&self) -> &'static str {
+        match self {
+            Self::UnknownTrigger(_) => "trigger",
+            Self::MissingLevel | Self::UnexpectedLevel(_) | Self::LevelOutOfRange(_) => "level",
+            Self::MissingItem | Self::UnexpectedItem(_) | Self::EmptyItemName => "item",
+            Self::UnknownPokemon(_) | Self::Cycle(_) => "to",
+        }
+    }
+}
+
+impl fmt::Display for EvolutionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::UnknownTrigger(t) => write!(
+                f,
+                "unknown trigger {:?}, expected level, item, trade or friendship",
+                t
+            ),
+            Self::MissingLevel => write!(f, "level evolutions need a level"),
+            Self::UnexpectedLevel(t) => write!(f, "{} evolutions do not happen at a level", t),
+            Self::LevelOutOfRange(n) => write!(
+                f,
+                "level {} is out of range {}..={}",
+                n,
+                Level::MIN,
+                Level::MAX
+            ),
+            Self::MissingItem => write!(f, "item evolutions need an item"),
+            Self::UnexpectedItem(t) => write!(f, "{} evolutions do not use an item", t),
+            Self::EmptyItemName => write!(f, "item name must not be empty"),
+            Self::UnknownPokemon(n) => write!(f, "pokemon {} does not exist", n),
+            Self::Cycle(n) => write!(
+                f,
+                "pokemon {} already comes first in the chain, evolving into it makes a cycle",
+                n
+            ),
+        }
+    }
+}
+
+impl Error for EvolutionError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_should_give_a_level_or_an_item_to_the_triggers_using_them() {
+        let trigger = |name: &str, level, item: Option<&str>| {
+            EvolutionTrigger::try_new(String::from(name), level, item.map(String::from))
+        };
+
+        assert_eq!(
+            trigger("level", Some(16), None),
+            Ok(EvolutionTrigger::Level(Level(16)))
+        );
+        assert_eq!(
+            trigger("trade", None, Some("Metal Coat")).map(|t| t.item()),
+            Ok(Some(String::from("Metal Coat")))
+        );
+        assert_eq!(
+            trigger("friendship", None, None),
+            Ok(EvolutionTrigger::Friendship)
+        );
+        assert_eq!(
+            trigger("level", None, None),
+            Err(EvolutionError::MissingLevel)
+        );
+        assert_eq!(
+            trigger("level", Some(101), None),
+            Err(EvolutionError::LevelOutOfRange(101))
+        );
+        assert_eq!(
+            trigger("item", None, None),
+            Err(EvolutionError::MissingItem)
+        );
+        assert_eq!(
+            trigger("item", Some(1), Some("Moon Stone")),
+            Err(EvolutionError::UnexpectedLevel(String::from("item")))
+        );
+        assert_eq!(
+            trigger("friendship", None, Some("Soothe Bell")),
+            Err(EvolutionError::UnexpectedItem(String::from("friendship")))
+        );
+        assert_eq!(
+            trigger("trade", None, Some("")),
+            Err(EvolutionError::EmptyItemName)
+        );
+        assert_eq!(
+            trigger("moon", None, None),
+            Err(EvolutionError::UnknownTrigger(String::from("moon")))
+        );
+    }
+}
@@ -0,0 +1,121 @@
+use std::{error::Error, fmt};
+
+/// Tells apart the Pokemon sharing a national number, e.g. `alola` or
+/// `mega-x`. The default form has no name.
+#[derive(Clone, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct PokemonForm(String);
+
+impl PokemonForm {
+    pub fn is_default(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Reads the form of a change to the data every form shares with its
+    /// species, which only the default form may name.
+    pub fn try_species(f: String) -> Result<Self, FormError> {
+        match Self::try_from(f)? {
+            form if form.is_default() => Ok(form),
+            form => Err(FormError::SharedBySpecies(form.0)),
+        }
+    }
+}
+
+impl TryFrom<String> for PokemonForm {
+    type Error = FormError;
+
+    fn try_from(f: String) -> Result<Self, Self::Error> {
+        if f.chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+        {
+            Ok(Self(f))
+        } else {
+            Err(FormError::Invalid(f))
+        }
+    }
+}
+
+/// An empty string for the default form, as stored.
+impl From<PokemonForm> for String {
+    fn from(f: PokemonForm) -> Self {
+        f.0
+    }
+}
+
+/// None for the default form, as returned.
+impl From<PokemonForm> for Option<String> {
+    fn from(f: PokemonForm) -> Self {
+        if f.is_default() {
+            None
+        } else {
+            Some(f.0)
+        }
+    }
+}
+
+#[cfg(test)]
+impl PokemonForm {
+    pub fn alola() -> Self {
+        Self(String::from("alola"))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum FormError {
+    Invalid(String),
+    SharedBySpecies(String),
+}
+
+impl FormError {
+    pub fn field(&self) -> &'static str {
+        "form"
+    }
+}
+
+impl fmt::Display for FormError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Invalid(form) => write!(
+                f,
+                "form {:?} must only hold lowercase letters, digits and dashes",
+                form
+            ),
+            Self::SharedBySpecies(form) => write!(
+                f,
+                "form {:?} shares this data with its species, change it without a form",
+                form
+            ),
+        }
+    }
+}
+
+impl Error for FormError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_should_only_accept_lowercase_slugs_as_forms() {
+        for form in ["", "alola", "mega-x", "10-percent"] {
+            assert!(PokemonForm::try_from(String::from(form)).is_ok());
+        }
+        assert_eq!(
+            PokemonForm::try_from(String::from("Alola")).err(),
+            Some(FormError::Invalid(String::from("Alola")))
+        );
+        assert!(PokemonForm::try_from(String::from("mega x")).is_err());
+    }
+
+    #[test]
+    fn it_should_only_accept_the_default_form_for_the_species_data() {
+        assert!(PokemonForm::try_species(String::new()).is_ok());
+        assert_eq!(
+            PokemonForm::try_species(String::from("alola")).err(),
+            Some(FormError::SharedBySpecies(String::from("alola")))
+        );
+        assert_eq!(
+            PokemonForm::try_species(String::from("Alola")).err(),
+            Some(FormError::Invalid(String::from("Alola")))
+        );
+    }
+}
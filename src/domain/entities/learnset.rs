@@ -0,0 +1,267 @@
+use std::{error::Error, fmt};
+
+use super::{MoveName, ValidationError};
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub struct Level(pub(super) u8);
+
+impl Level {
+    pub const MIN: u16 = 1;
+    pub const MAX: u16 = 100;
+}
+
+impl TryFrom<u16> for Level {
+    type Error = LearnsetError;
+
+    fn try_from(n: u16) -> Result<Self, Self::Error> {
+        if (Self::MIN..=Self::MAX).contains(&n) {
+            Ok(Self(n as u8))
+        } else {
+            Err(LearnsetError::LevelOutOfRange(n))
+        }
+    }
+}
+
+impl From<Level> for u16 {
+    fn from(n: Level) -> u16 {
+        u16::from(n.0)
+    }
+}
+
+/// How a Pokemon learns a move. The variants are ordered the way a learnset
+/// lists them, level-up moves coming first by ascending level.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum LearnMethod {
+    LevelUp(Level),
+    Tm,
+    Egg,
+    Tutor,
+}
+
+impl LearnMethod {
+    /// Reads a method as exchanged with the adapters, only level-up moves
+    /// being learnt at a level.
+    pub fn try_new(method: String, level: Option<u16>) -> Result<Self, LearnsetError> {
+        let method = match method.as_str() {
+            "level-up" => match level {
+                Some(level) => return Level::try_from(level).map(Self::LevelUp),
+                None => return Err(LearnsetError::MissingLevel),
+            },
+            "tm" => Self::Tm,
+            "egg" => Self::Egg,
+            "tutor" => Self::Tutor,
+            _ => return Err(LearnsetError::UnknownMethod(method)),
+        };
+        match level {
+            Some(_) => Err(LearnsetError::UnexpectedLevel(method.name().to_string())),
+            None => Ok(method),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::LevelUp(_) => "level-up",
+            Self::Tm => "tm",
+            Self::Egg => "egg",
+            Self::Tutor => "tutor",
+        }
+    }
+
+    pub fn level(&self) -> Option<u16> {
+        match self {
+            Self::LevelUp(level) => Some(u16::from(*level)),
+            _ => None,
+        }
+    }
+}
+
+/// A move of a learnset, the method coming first so that entries sort the
+/// way a learnset lists them.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct LearnsetEntry {
+    pub method: LearnMethod,
+    pub move_name: MoveName,
+}
+
+impl LearnsetEntry {
+    pub fn try_new(
+        move_name: String,
+        method: String,
+        level: Option<u16>,
+    ) -> Result<Self, ValidationError> {
+        match (
+            MoveName::try_from(move_name),
+            LearnMethod::try_new(method, level),
+        ) {
+            (Ok(move_name), Ok(method)) => Ok(Self { method, move_name }),
+            (Err(e), _) => Err(e.into()),
+            (_, Err(e)) => Err(e.into()),
+        }
+    }
+}
+
+/// The moves a Pokemon can learn. A move may be learnt several ways, or at
+/// several levels, but never twice the same way.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Learnset(Vec<LearnsetEntry>);
+
+impl TryFrom<Vec<LearnsetEntry>> for Learnset {
+    type Error = LearnsetError;
+
+    fn try_from(mut entries: Vec<LearnsetEntry>) -> Result<Self, Self::Error> {
+        entries.sort();
+        for pair in entries.windows(2) {
+            if pair[0] == pair[1] {
+                return Err(LearnsetError::DuplicateEntry(String::from(
+                    pair[0].move_name.clone(),
+                )));
+            }
+        }
+        Ok(Self(entries))
+    }
+}
+
+impl Learnset {
+    pub fn entries(&self) -> &[LearnsetEntry] {
+        &self.0
+    }
+
+    /// The ways the move is learnt, none when it is not part of the learnset.
+    pub fn methods_of<'a>(&'a self, name: &'a MoveName) -> impl Iterator<Item = LearnMethod> + 'a {
+        self.0
+            .iter()
+            .filter(move |e| e.move_name == *name)
+            .map(|e| e.method)
+    }
+}
+
+#[cfg(test)]
+impl Learnset {
+    pub fn pikachu() -> Self {
+        Self(vec![
+            LearnsetEntry {
+                method: LearnMethod::LevelUp(Level(1)),
+                move_name: MoveName::growl(),
+            },
+            LearnsetEntry {
+                method: LearnMethod::LevelUp(Level(1)),
+                move_name: MoveName::thunder_shock(),
+            },
+            LearnsetEntry {
+                method: LearnMethod::Tm,
+                move_name: MoveName::thunderbolt(),
+            },
+        ])
+    }
+
+    pub fn charmander() -> Self {
+        Self(vec![
+            LearnsetEntry {
+                method: LearnMethod::LevelUp(Level(1)),
+                move_name: MoveName::growl(),
+            },
+            LearnsetEntry {
+                method: LearnMethod::LevelUp(Level(4)),
+                move_name: MoveName::ember(),
+            },
+        ])
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum LearnsetError {
+    UnknownMethod(String),
+    MissingLevel,
+    UnexpectedLevel(String),
+    LevelOutOfRange(u16),
+    DuplicateEntry(String),
+    UnknownMove(String),
+}
+
+impl LearnsetError {
+    pub fn field(&self) -> &'static str {
+        "learnset"
+    }
+}
+
+impl fmt::Display for LearnsetError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::UnknownMethod(m) => write!(
+                f,
+                "unknown learn method {:?}, expected level-up, tm, egg or tutor",
+                m
+            ),
+            Self::MissingLevel => write!(f, "level-up moves need a level"),
+            Self::UnexpectedLevel(m) => write!(f, "{} moves are not learnt at a level", m),
+            Self::LevelOutOfRange(n) => write!(
+                f,
+                "level {} is out of range {}..={}",
+                n,
+                Level::MIN,
+                Level::MAX
+            ),
+            Self::DuplicateEntry(name) => write!(f, "move {:?} is given twice the same way", name),
+            Self::UnknownMove(name) => write!(f, "unknown move {:?}", name),
+        }
+    }
+}
+
+impl Error for LearnsetError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_should_require_a_level_for_level_up_moves_only() {
+        let method = |m: &str, level| LearnMethod::try_new(String::from(m), level);
+
+        assert_eq!(method("level-up", Some(16)).unwrap().level(), Some(16));
+        assert_eq!(method("level-up", None), Err(LearnsetError::MissingLevel));
+        assert_eq!(
+            method("level-up", Some(101)),
+            Err(LearnsetError::LevelOutOfRange(101))
+        );
+        assert_eq!(method("tm", None), Ok(LearnMethod::Tm));
+        assert_eq!(
+            method("egg", Some(1)),
+            Err(LearnsetError::UnexpectedLevel(String::from("egg")))
+        );
+        assert_eq!(
+            method("hm", None),
+            Err(LearnsetError::UnknownMethod(String::from("hm")))
+        );
+    }
+
+    #[test]
+    fn it_should_sort_a_learnset_and_reject_an_entry_given_twice() {
+        let entry = |name: &str, method: &str, level| {
+            LearnsetEntry::try_new(String::from(name), String::from(method), level).unwrap()
+        };
+
+        let learnset = Learnset::try_from(vec![
+            entry("Thunderbolt", "tm", None),
+            entry("Thunder Shock", "level-up", Some(1)),
+            entry("Growl", "level-up", Some(1)),
+        ]);
+
+        assert_eq!(learnset, Ok(Learnset::pikachu()));
+        assert_eq!(
+            Learnset::try_from(vec![
+                entry("Growl", "level-up", Some(1)),
+                entry("Growl", "level-up", Some(5)),
+                entry("Growl", "egg", None),
+            ])
+            .map(|l| l.entries().len()),
+            Ok(3)
+        );
+        assert_eq!(
+            Learnset::try_from(vec![
+                entry("Growl", "egg", None),
+                entry("Growl", "egg", None)
+            ]),
+            Err(LearnsetError::DuplicateEntry(String::from("Growl")))
+        );
+    }
+}
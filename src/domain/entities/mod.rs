@@ -0,0 +1,548 @@
+mod ability;
+mod evolution;
+mod form;
+mod learnset;
+mod moves;
+mod stats;
+
+use std::{cmp::PartialEq, error::Error, fmt};
+
+pub use ability::{Ability, AbilityError, AbilityName, PokemonAbilities};
+pub use evolution::{Evolution, EvolutionError, EvolutionTrigger};
+pub use form::{FormError, PokemonForm};
+pub use learnset::{LearnMethod, Learnset, LearnsetEntry, LearnsetError, Level};
+pub use moves::{Accuracy, Move, MoveCategory, MoveError, MoveName, MovePower, PowerPoints};
+pub use stats::{BaseStats, Stat, StatsError};
+
+/// Why a value was refused, naming the field and the rule it breaks. The
+/// values having their own module report their errors through it.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ValidationError {
+    NumberOutOfRange(u16),
+    EmptyName,
+    NoTypes,
+    TooManyTypes(usize),
+    DuplicateType(PokemonType),
+    UnknownType(String),
+    Form(FormError),
+    Stats(StatsError),
+    Ability(AbilityError),
+    Move(MoveError),
+    Learnset(LearnsetError),
+    Evolution(EvolutionError),
+    /// A request parameter that is not part of a Pokemon, e.g. a page limit.
+    InvalidParameter {
+        name: &'static str,
+        reason: String,
+    },
+}
+
+impl ValidationError {
+    pub fn field(&self) -> &'static str {
+        match self {
+            Self::NumberOutOfRange(_) => "number",
+            Self::EmptyName => "name",
+            Self::NoTypes
+            | Self::TooManyTypes(_)
+            | Self::DuplicateType(_)
+            | Self::UnknownType(_) => "types",
+            Self::Form(e) => e.field(),
+            Self::Stats(e) => e.field(),
+            Self::Ability(e) => e.field(),
+            Self::Move(e) => e.field(),
+            Self::Learnset(e) => e.field(),
+            Self::Evolution(e) => e.field(),
+            Self::InvalidParameter { name, .. } => name,
+        }
+    }
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::NumberOutOfRange(n) => write!(
+                f,
+                "number {} is out of range {}..={}",
+                n,
+                PokemonNumber::MIN,
+                PokemonNumber::MAX
+            ),
+            Self::EmptyName => write!(f, "name must not be empty"),
+            Self::NoTypes => write!(f, "types must hold at least one type"),
+            Self::TooManyTypes(n) => write!(f, "types must hold at most 2 types, got {}", n),
+            Self::DuplicateType(t) => write!(f, "type {:?} is given twice", String::from(*t)),
+            Self::UnknownType(t) => write!(f, "unknown type {:?}", t),
+            Self::Form(e) => write!(f, "{}", e),
+            Self::Stats(e) => write!(f, "{}", e),
+            Self::Ability(e) => write!(f, "{}", e),
+            Self::Move(e) => write!(f, "{}", e),
+            Self::Learnset(e) => write!(f, "{}", e),
+            Self::Evolution(e) => write!(f, "{}", e),
+            Self::InvalidParameter { name, reason } => write!(f, "{} {}", name, reason),
+        }
+    }
+}
+
+impl Error for ValidationError {}
+
+impl From<FormError> for ValidationError {
+    fn from(e: FormError) -> Self {
+        Self::Form(e)
+    }
+}
+
+impl From<StatsError> for ValidationError {
+    fn from(e: StatsError) -> Self {
+        Self::Stats(e)
+    }
+}
+
+impl From<AbilityError> for ValidationError {
+    fn from(e: AbilityError) -> Self {
+        Self::Ability(e)
+    }
+}
+
+impl From<MoveError> for ValidationError {
+    fn from(e: MoveError) -> Self {
+        Self::Move(e)
+    }
+}
+
+impl From<LearnsetError> for ValidationError {
+    fn from(e: LearnsetError) -> Self {
+        Self::Learnset(e)
+    }
+}
+
+impl From<EvolutionError> for ValidationError {
+    fn from(e: EvolutionError) -> Self {
+        Self::Evolution(e)
+    }
+}
+
+/// Gathers the errors of fields validated independently of each other.
+pub fn validation_errors<const N: usize>(
+    errors: [Option<ValidationError>; N],
+) -> Vec<ValidationError> {
+    errors.into_iter().flatten().collect()
+}
+
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct PokemonNumber(u16);
+
+impl PokemonNumber {
+    pub const MIN: u16 = 1;
+    pub const MAX: u16 = 898;
+}
+
+impl TryFrom<u16> for PokemonNumber {
+    type Error = ValidationError;
+
+    fn try_from(n: u16) -> Result<Self, Self::Error> {
+        if (Self::MIN..=Self::MAX).contains(&n) {
+            Ok(Self(n))
+        } else {
+            Err(ValidationError::NumberOutOfRange(n))
+        }
+    }
+}
+
+impl From<PokemonNumber> for u16 {
+    fn from(n: PokemonNumber) -> u16 {
+        n.0
+    }
+}
+
+#[cfg(test)]
+impl PokemonNumber {
+    pub fn pikachu() -> Self {
+        Self(25)
+    }
+    pub fn charmander() -> Self {
+        Self(4)
+    }
+    pub fn charmeleon() -> Self {
+        Self(5)
+    }
+    pub fn charizard() -> Self {
+        Self(6)
+    }
+    pub fn raichu() -> Self {
+        Self(26)
+    }
+    pub fn bad() -> Self {
+        Self(0)
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct PokemonName(String);
+
+impl PokemonName {
+    pub fn starts_with(&self, prefix: &str) -> bool {
+        self.0.starts_with(prefix)
+    }
+}
+
+impl TryFrom<String> for PokemonName {
+    type Error = ValidationError;
+
+    fn try_from(n: String) -> Result<Self, Self::Error> {
+        if n.is_empty() {
+            Err(ValidationError::EmptyName)
+        } else {
+            Ok(Self(n))
+        }
+    }
+}
+
+impl From<PokemonName> for String {
+    fn from(n: PokemonName) -> Self {
+        n.0
+    }
+}
+
+#[cfg(test)]
+impl PokemonName {
+    pub fn pikachu() -> Self {
+        Self(String::from("Pikachu"))
+    }
+
+    pub fn charmander() -> Self {
+        Self(String::from("Charmander"))
+    }
+
+    pub fn charmeleon() -> Self {
+        Self(String::from("Charmeleon"))
+    }
+
+    pub fn charizard() -> Self {
+        Self(String::from("Charizard"))
+    }
+
+    pub fn raichu() -> Self {
+        Self(String::from("Raichu"))
+    }
+
+    pub fn bad() -> Self {
+        Self(String::from(""))
+    }
+}
+
+#[derive(Clone)]
+pub struct PokemonTypes(Vec<PokemonType>);
+
+impl PokemonTypes {
+    pub fn contains(&self, t: &PokemonType) -> bool {
+        self.0.contains(t)
+    }
+}
+
+impl TryFrom<Vec<String>> for PokemonTypes {
+    type Error = ValidationError;
+
+    fn try_from(ts: Vec<String>) -> Result<Self, Self::Error> {
+        if ts.is_empty() {
+            Err(ValidationError::NoTypes)
+        } else if ts.len() > 2 {
+            Err(ValidationError::TooManyTypes(ts.len()))
+        } else {
+            let mut pts = vec![];
+            for t in ts.into_iter() {
+                match PokemonType::try_from(t) {
+                    Ok(pt) if pts.contains(&pt) => return Err(ValidationError::DuplicateType(pt)),
+                    Ok(pt) => pts.push(pt),
+                    Err(e) => return Err(e),
+                }
+            }
+            Ok(Self(pts))
+        }
+    }
+}
+
+impl From<PokemonTypes> for Vec<String> {
+    fn from(pts: PokemonTypes) -> Self {
+        pts.0.iter().map(|x| String::from(*x)).collect::<Self>()
+    }
+}
+
+#[cfg(test)]
+impl PokemonTypes {
+    pub fn pikachu() -> Self {
+        Self(vec![PokemonType::Electric])
+    }
+
+    pub fn charmander() -> Self {
+        Self(vec![PokemonType::Fire])
+    }
+
+    pub fn charizard() -> Self {
+        Self(vec![PokemonType::Fire, PokemonType::Flying])
+    }
+
+    pub fn alolan_raichu() -> Self {
+        Self(vec![PokemonType::Electric, PokemonType::Psychic])
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PokemonType {
+    Normal,
+    Fire,
+    Water,
+    Electric,
+    Grass,
+    Ice,
+    Fighting,
+    Poison,
+    Ground,
+    Flying,
+    Psychic,
+    Bug,
+    Rock,
+    Ghost,
+    Dragon,
+    Dark,
+    Steel,
+    Fairy,
+}
+
+impl PokemonType {
+    pub const ALL: [PokemonType; 18] = [
+        Self::Normal,
+        Self::Fire,
+        Self::Water,
+        Self::Electric,
+        Self::Grass,
+        Self::Ice,
+        Self::Fighting,
+        Self::Poison,
+        Self::Ground,
+        Self::Flying,
+        Self::Psychic,
+        Self::Bug,
+        Self::Rock,
+        Self::Ghost,
+        Self::Dragon,
+        Self::Dark,
+        Self::Steel,
+        Self::Fairy,
+    ];
+
+    /// Damage multiplier of an attack of this type against a defender,
+    /// combining the multipliers of each of its types.
+    pub fn effectiveness_against(&self, defender: &PokemonTypes) -> f32 {
+        defender
+            .0
+            .iter()
+            .map(|t| self.effectiveness_against_type(*t))
+            .product()
+    }
+
+    fn effectiveness_against_type(&self, defender: PokemonType) -> f32 {
+        use PokemonType::*;
+        match (self, defender) {
+            (Normal, Rock | Steel) => 0.5,
+            (Normal, Ghost) => 0.0,
+            (Fire, Grass | Ice | Bug | Steel) => 2.0,
+            (Fire, Fire | Water | Rock | Dragon) => 0.5,
+            (Water, Fire | Ground | Rock) => 2.0,
+            (Water, Water | Grass | Dragon) => 0.5,
+            (Electric, Water | Flying) => 2.0,
+            (Electric, Electric | Grass | Dragon) => 0.5,
+            (Electric, Ground) => 0.0,
+            (Grass, Water | Ground | Rock) => 2.0,
+            (Grass, Fire | Grass | Poison | Flying | Bug | Dragon | Steel) => 0.5,
+            (Ice, Grass | Ground | Flying | Dragon) => 2.0,
+            (Ice, Fire | Water | Ice | Steel) => 0.5,
+            (Fighting, Normal | Ice | Rock | Dark | Steel) => 2.0,
+            (Fighting, Poison | Flying | Psychic | Bug | Fairy) => 0.5,
+            (Fighting, Ghost) => 0.0,
+            (Poison, Grass | Fairy) => 2.0,
+            (Poison, Poison | Ground | Rock | Ghost) => 0.5,
+            (Poison, Steel) => 0.0,
+            (Ground, Fire | Electric | Poison | Rock | Steel) => 2.0,
+            (Ground, Grass | Bug) => 0.5,
+            (Ground, Flying) => 0.0,
+            (Flying, Grass | Fighting | Bug) => 2.0,
+            (Flying, Electric | Rock | Steel) => 0.5,
+            (Psychic, Fighting | Poison) => 2.0,
+            (Psychic, Psychic | Steel) => 0.5,
+            (Psychic, Dark) => 0.0,
+            (Bug, Grass | Psychic | Dark) => 2.0,
+            (Bug, Fire | Fighting | Poison | Flying | Ghost | Steel | Fairy) => 0.5,
+            (Rock, Fire | Ice | Flying | Bug) => 2.0,
+            (Rock, Fighting | Ground | Steel) => 0.5,
+            (Ghost, Psychic | Ghost) => 2.0,
+            (Ghost, Dark) => 0.5,
+            (Ghost, Normal) => 0.0,
+            (Dragon, Dragon) => 2.0,
+            (Dragon, Steel) => 0.5,
+            (Dragon, Fairy) => 0.0,
+            (Dark, Psychic | Ghost) => 2.0,
+            (Dark, Fighting | Dark | Fairy) => 0.5,
+            (Steel, Ice | Rock | Fairy) => 2.0,
+            (Steel, Fire | Water | Electric | Steel) => 0.5,
+            (Fairy, Fighting | Dragon | Dark) => 2.0,
+            (Fairy, Fire | Poison | Steel) => 0.5,
+            _ => 1.0,
+        }
+    }
+}
+
+impl TryFrom<String> for PokemonType {
+    type Error = ValidationError;
+
+    fn try_from(t: String) -> Result<Self, Self::Error> {
+        match PokemonType::ALL.iter().find(|pt| String::from(**pt) == t) {
+            Some(pt) => Ok(*pt),
+            None => Err(ValidationError::UnknownType(t)),
+        }
+    }
+}
+
+impl From<PokemonType> for String {
+    fn from(t: PokemonType) -> Self {
+        String::from(match t {
+            PokemonType::Normal => "Normal",
+            PokemonType::Fire => "Fire",
+            PokemonType::Water => "Water",
+            PokemonType::Electric => "Electric",
+            PokemonType::Grass => "Grass",
+            PokemonType::Ice => "Ice",
+            PokemonType::Fighting => "Fighting",
+            PokemonType::Poison => "Poison",
+            PokemonType::Ground => "Ground",
+            PokemonType::Flying => "Flying",
+            PokemonType::Psychic => "Psychic",
+            PokemonType::Bug => "Bug",
+            PokemonType::Rock => "Rock",
+            PokemonType::Ghost => "Ghost",
+            PokemonType::Dragon => "Dragon",
+            PokemonType::Dark => "Dark",
+            PokemonType::Steel => "Steel",
+            PokemonType::Fairy => "Fairy",
+        })
+    }
+}
+
+#[derive(Clone)]
+pub struct Pokemon {
+    pub number: PokemonNumber,
+    pub form: PokemonForm,
+    pub name: PokemonName,
+    pub types: PokemonTypes,
+    /// Unknown for the Pokemon stored before stats were recorded.
+    pub stats: Option<BaseStats>,
+}
+
+impl Pokemon {
+    pub fn new(
+        number: PokemonNumber,
+        form: PokemonForm,
+        name: PokemonName,
+        types: PokemonTypes,
+        stats: Option<BaseStats>,
+    ) -> Self {
+        Self {
+            number,
+            form,
+            name,
+            types,
+            stats,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn types(ts: &[&str]) -> Result<PokemonTypes, ValidationError> {
+        PokemonTypes::try_from(ts.iter().map(|t| String::from(*t)).collect::<Vec<String>>())
+    }
+
+    #[test]
+    fn it_should_accept_every_official_type() {
+        for t in PokemonType::ALL {
+            assert_eq!(PokemonType::try_from(String::from(t)), Ok(t));
+        }
+    }
+
+    #[test]
+    fn it_should_reject_an_unknown_type() {
+        assert_eq!(
+            types(&["Shadow"]).err(),
+            Some(ValidationError::UnknownType(String::from("Shadow")))
+        );
+    }
+
+    #[test]
+    fn it_should_reject_empty_types() {
+        assert_eq!(types(&[]).err(), Some(ValidationError::NoTypes));
+    }
+
+    #[test]
+    fn it_should_reject_more_than_two_types() {
+        assert_eq!(
+            types(&["Fire", "Flying", "Dragon"]).err(),
+            Some(ValidationError::TooManyTypes(3))
+        );
+    }
+
+    #[test]
+    fn it_should_reject_duplicated_types() {
+        assert_eq!(
+            types(&["Fire", "Fire"]).err(),
+            Some(ValidationError::DuplicateType(PokemonType::Fire))
+        );
+    }
+
+    #[test]
+    fn it_should_name_the_field_and_rule_of_a_validation_error() {
+        let err = PokemonNumber::try_from(899).err().unwrap();
+        assert_eq!(err.field(), "number");
+        assert_eq!(err.to_string(), "number 899 is out of range 1..=898");
+        let err = types(&["Shadow"]).err().unwrap();
+        assert_eq!(err.field(), "types");
+        assert_eq!(err.to_string(), "unknown type \"Shadow\"");
+    }
+
+    #[test]
+    fn it_should_report_the_errors_of_the_other_values_as_is() {
+        let stats = BaseStats::try_from([35, 55, 40, 50, 50, 256])
+            .err()
+            .unwrap();
+        let err = ValidationError::from(stats.clone());
+
+        assert_eq!(err.field(), stats.field());
+        assert_eq!(err.to_string(), stats.to_string());
+        assert_eq!(
+            ValidationError::from(FormError::Invalid(String::from("Alola"))).field(),
+            "form"
+        );
+    }
+
+    #[test]
+    fn it_should_accept_dual_types() {
+        assert!(types(&["Fire", "Flying"]).is_ok());
+    }
+
+    #[test]
+    fn it_should_compute_single_type_effectiveness() {
+        let grass = types(&["Grass"]).unwrap();
+        assert_eq!(PokemonType::Fire.effectiveness_against(&grass), 2.0);
+        assert_eq!(PokemonType::Water.effectiveness_against(&grass), 0.5);
+        assert_eq!(PokemonType::Normal.effectiveness_against(&grass), 1.0);
+    }
+
+    #[test]
+    fn it_should_combine_dual_type_effectiveness() {
+        let charizard = types(&["Fire", "Flying"]).unwrap();
+        assert_eq!(PokemonType::Rock.effectiveness_against(&charizard), 4.0);
+        assert_eq!(PokemonType::Ground.effectiveness_against(&charizard), 0.0);
+        assert_eq!(PokemonType::Grass.effectiveness_against(&charizard), 0.25);
+        assert_eq!(PokemonType::Water.effectiveness_against(&charizard), 2.0);
+    }
+}
@@ -0,0 +1,318 @@
+use std::{error::Error, fmt};
+
+use super::PokemonType;
+
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct MoveName(String);
+
+impl TryFrom<String> for MoveName {
+    type Error = MoveError;
+
+    fn try_from(n: String) -> Result<Self, Self::Error> {
+        if n.trim().is_empty() {
+            Err(MoveError::EmptyName)
+        } else {
+            Ok(Self(n))
+        }
+    }
+}
+
+impl From<MoveName> for String {
+    fn from(n: MoveName) -> Self {
+        n.0
+    }
+}
+
+#[cfg(test)]
+impl MoveName {
+    pub fn thunder_shock() -> Self {
+        Self(String::from("Thunder Shock"))
+    }
+
+    pub fn thunderbolt() -> Self {
+        Self(String::from("Thunderbolt"))
+    }
+
+    pub fn growl() -> Self {
+        Self(String::from("Growl"))
+    }
+
+    pub fn ember() -> Self {
+        Self(String::from("Ember"))
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MoveCategory {
+    Physical,
+    Special,
+    Status,
+}
+
+impl TryFrom<String> for MoveCategory {
+    type Error = MoveError;
+
+    fn try_from(c: String) -> Result<Self, Self::Error> {
+        match c.as_str() {
+            "Physical" => Ok(Self::Physical),
+            "Special" => Ok(Self::Special),
+            "Status" => Ok(Self::Status),
+            _ => Err(MoveError::UnknownCategory(c)),
+        }
+    }
+}
+
+impl From<MoveCategory> for String {
+    fn from(c: MoveCategory) -> Self {
+        String::from(match c {
+            MoveCategory::Physical => "Physical",
+            MoveCategory::Special => "Special",
+            MoveCategory::Status => "Status",
+        })
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct MovePower(u8);
+
+impl MovePower {
+    pub const MIN: u16 = 1;
+    pub const MAX: u16 = 250;
+}
+
+impl TryFrom<u16> for MovePower {
+    type Error = MoveError;
+
+    fn try_from(n: u16) -> Result<Self, Self::Error> {
+        if (Self::MIN..=Self::MAX).contains(&n) {
+            Ok(Self(n as u8))
+        } else {
+            Err(MoveError::PowerOutOfRange(n))
+        }
+    }
+}
+
+impl From<MovePower> for u16 {
+    fn from(n: MovePower) -> u16 {
+        u16::from(n.0)
+    }
+}
+
+/// The chance in percent that a move hits.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Accuracy(u8);
+
+impl Accuracy {
+    pub const MIN: u16 = 1;
+    pub const MAX: u16 = 100;
+}
+
+impl TryFrom<u16> for Accuracy {
+    type Error = MoveError;
+
+    fn try_from(n: u16) -> Result<Self, Self::Error> {
+        if (Self::MIN..=Self::MAX).contains(&n) {
+            Ok(Self(n as u8))
+        } else {
+            Err(MoveError::AccuracyOutOfRange(n))
+        }
+    }
+}
+
+impl From<Accuracy> for u16 {
+    fn from(n: Accuracy) -> u16 {
+        u16::from(n.0)
+    }
+}
+
+/// How many times a move can be used before resting.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct PowerPoints(u8);
+
+impl PowerPoints {
+    pub const MIN: u16 = 1;
+    pub const MAX: u16 = 40;
+}
+
+impl TryFrom<u16> for PowerPoints {
+    type Error = MoveError;
+
+    fn try_from(n: u16) -> Result<Self, Self::Error> {
+        if (Self::MIN..=Self::MAX).contains(&n) {
+            Ok(Self(n as u8))
+        } else {
+            Err(MoveError::PpOutOfRange(n))
+        }
+    }
+}
+
+impl From<PowerPoints> for u16 {
+    fn from(n: PowerPoints) -> u16 {
+        u16::from(n.0)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Move {
+    pub name: MoveName,
+    pub r#type: PokemonType,
+    pub category: MoveCategory,
+    /// None for status moves and for the moves whose power varies.
+    pub power: Option<MovePower>,
+    /// None for the moves that never miss.
+    pub accuracy: Option<Accuracy>,
+    pub pp: PowerPoints,
+}
+
+impl Move {
+    pub fn try_new(
+        name: MoveName,
+        r#type: PokemonType,
+        category: MoveCategory,
+        power: Option<MovePower>,
+        accuracy: Option<Accuracy>,
+        pp: PowerPoints,
+    ) -> Result<Self, MoveError> {
+        if category == MoveCategory::Status && power.is_some() {
+            return Err(MoveError::StatusWithPower);
+        }
+        Ok(Self {
+            name,
+            r#type,
+            category,
+            power,
+            accuracy,
+            pp,
+        })
+    }
+}
+
+#[cfg(test)]
+impl Move {
+    pub fn thunder_shock() -> Self {
+        Self {
+            name: MoveName::thunder_shock(),
+            r#type: PokemonType::Electric,
+            category: MoveCategory::Special,
+            power: Some(MovePower(40)),
+            accuracy: Some(Accuracy(100)),
+            pp: PowerPoints(30),
+        }
+    }
+
+    pub fn thunderbolt() -> Self {
+        Self {
+            name: MoveName::thunderbolt(),
+            r#type: PokemonType::Electric,
+            category: MoveCategory::Special,
+            power: Some(MovePower(90)),
+            accuracy: Some(Accuracy(100)),
+            pp: PowerPoints(15),
+        }
+    }
+
+    pub fn growl() -> Self {
+        Self {
+            name: MoveName::growl(),
+            r#type: PokemonType::Normal,
+            category: MoveCategory::Status,
+            power: None,
+            accuracy: Some(Accuracy(100)),
+            pp: PowerPoints(40),
+        }
+    }
+
+    pub fn ember() -> Self {
+        Self {
+            name: MoveName::ember(),
+            r#type: PokemonType::Fire,
+            category: MoveCategory::Special,
+            power: Some(MovePower(40)),
+            accuracy: Some(Accuracy(100)),
+            pp: PowerPoints(25),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum MoveError {
+    EmptyName,
+    UnknownCategory(String),
+    PowerOutOfRange(u16),
+    StatusWithPower,
+    AccuracyOutOfRange(u16),
+    PpOutOfRange(u16),
+}
+
+impl MoveError {
+    pub fn field(&self) -> &'static str {
+        match self {
+            Self::EmptyName => "name",
+            Self::UnknownCategory(_) => "category",
+            Self::PowerOutOfRange(_) | Self::StatusWithPower => "power",
+            Self::AccuracyOutOfRange(_) => "accuracy",
+            Self::PpOutOfRange(_) => "pp",
+        }
+    }
+}
+
+impl fmt::Display for MoveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::EmptyName => write!(f, "move name must not be empty"),
+            Self::UnknownCategory(c) => write!(
+                f,
+                "unknown category {:?}, expected Physical, Special or Status",
+                c
+            ),
+            Self::PowerOutOfRange(n) => write!(
+                f,
+                "power {} is out of range {}..={}",
+                n,
+                MovePower::MIN,
+                MovePower::MAX
+            ),
+            Self::StatusWithPower => write!(f, "status moves must not have a power"),
+            Self::AccuracyOutOfRange(n) => write!(
+                f,
+                "accuracy {} is out of range {}..={}",
+                n,
+                Accuracy::MIN,
+                Accuracy::MAX
+            ),
+            Self::PpOutOfRange(n) => write!(
+                f,
+                "pp {} is out of range {}..={}",
+                n,
+                PowerPoints::MIN,
+                PowerPoints::MAX
+            ),
+        }
+    }
+}
+
+impl Error for MoveError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_should_reject_a_status_move_with_a_power() {
+        let res = Move::try_new(
+            MoveName::growl(),
+            PokemonType::Normal,
+            MoveCategory::Status,
+            Some(MovePower::try_from(40).unwrap()),
+            None,
+            PowerPoints::try_from(40).unwrap(),
+        );
+
+        assert_eq!(res.err(), Some(MoveError::StatusWithPower));
+        assert_eq!(MovePower::try_from(251).err().unwrap().field(), "power");
+        assert_eq!(
+            PowerPoints::try_from(0).err().unwrap().to_string(),
+            "pp 0 is out of range 1..=40"
+        );
+    }
+}
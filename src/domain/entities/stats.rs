@@ -0,0 +1,162 @@
+use std::{error::Error, fmt};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Stat {
+    Hp,
+    Attack,
+    Defense,
+    SpecialAttack,
+    SpecialDefense,
+    Speed,
+}
+
+impl Stat {
+    pub const ALL: [Stat; 6] = [
+        Self::Hp,
+        Self::Attack,
+        Self::Defense,
+        Self::SpecialAttack,
+        Self::SpecialDefense,
+        Self::Speed,
+    ];
+}
+
+impl From<Stat> for String {
+    fn from(stat: Stat) -> Self {
+        String::from(match stat {
+            Stat::Hp => "hp",
+            Stat::Attack => "attack",
+            Stat::Defense => "defense",
+            Stat::SpecialAttack => "special_attack",
+            Stat::SpecialDefense => "special_defense",
+            Stat::Speed => "speed",
+        })
+    }
+}
+
+/// The six base stats of a Pokemon, in the order of `Stat::ALL`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct BaseStats([u8; 6]);
+
+impl BaseStats {
+    pub const MIN: u16 = 1;
+    pub const MAX: u16 = 255;
+
+    pub fn get(&self, stat: Stat) -> u16 {
+        match Stat::ALL.iter().position(|s| *s == stat) {
+            Some(i) => u16::from(self.0[i]),
+            None => 0,
+        }
+    }
+
+    pub fn total(&self) -> u16 {
+        self.0.iter().map(|n| u16::from(*n)).sum()
+    }
+}
+
+impl TryFrom<[u16; 6]> for BaseStats {
+    type Error = StatsError;
+
+    fn try_from(ns: [u16; 6]) -> Result<Self, Self::Error> {
+        let mut stats = [0; 6];
+        for (i, (stat, n)) in Stat::ALL.into_iter().zip(ns).enumerate() {
+            match u8::try_from(n) {
+                Ok(value) if u16::from(value) >= Self::MIN => stats[i] = value,
+                _ => return Err(StatsError::OutOfRange(stat, n)),
+            }
+        }
+        Ok(Self(stats))
+    }
+}
+
+impl From<BaseStats> for [u16; 6] {
+    fn from(stats: BaseStats) -> Self {
+        stats.0.map(u16::from)
+    }
+}
+
+#[cfg(test)]
+impl BaseStats {
+    pub fn pikachu() -> Self {
+        Self([35, 55, 40, 50, 50, 90])
+    }
+
+    pub fn charmander() -> Self {
+        Self([39, 52, 43, 60, 50, 65])
+    }
+
+    pub fn charizard() -> Self {
+        Self([78, 84, 78, 109, 85, 100])
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum StatsError {
+    OutOfRange(Stat, u16),
+}
+
+impl StatsError {
+    pub fn field(&self) -> &'static str {
+        match self {
+            Self::OutOfRange(stat, _) => match stat {
+                Stat::Hp => "stats.hp",
+                Stat::Attack => "stats.attack",
+                Stat::Defense => "stats.defense",
+                Stat::SpecialAttack => "stats.special_attack",
+                Stat::SpecialDefense => "stats.special_defense",
+                Stat::Speed => "stats.speed",
+            },
+        }
+    }
+}
+
+impl fmt::Display for StatsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::OutOfRange(stat, n) => write!(
+                f,
+                "{} {} is out of range {}..={}",
+                String::from(*stat),
+                n,
+                BaseStats::MIN,
+                BaseStats::MAX
+            ),
+        }
+    }
+}
+
+impl Error for StatsError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_should_reject_stats_out_of_range() {
+        assert_eq!(
+            BaseStats::try_from([35, 55, 0, 50, 50, 90]).err(),
+            Some(StatsError::OutOfRange(Stat::Defense, 0))
+        );
+        assert_eq!(
+            BaseStats::try_from([35, 55, 40, 50, 50, 256]).err(),
+            Some(StatsError::OutOfRange(Stat::Speed, 256))
+        );
+        let err = BaseStats::try_from([35, 55, 40, 300, 50, 90])
+            .err()
+            .unwrap();
+        assert_eq!(err.field(), "stats.special_attack");
+        assert_eq!(
+            err.to_string(),
+            "special_attack 300 is out of range 1..=255"
+        );
+    }
+
+    #[test]
+    fn it_should_compute_the_total_of_the_stats() {
+        let stats = BaseStats::try_from([1, 255, 40, 50, 50, 90]).unwrap();
+
+        assert_eq!(stats.get(Stat::Attack), 255);
+        assert_eq!(stats.total(), 486);
+        assert_eq!(<[u16; 6]>::from(stats), [1, 255, 40, 50, 50, 90]);
+    }
+}
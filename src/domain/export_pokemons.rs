@@ -0,0 +1,148 @@
+use std::sync::Arc;
+
+use super::{entities::Pokemon, Stats};
+use crate::repositories::pokemon::{
+    FetchAllError, FetchAllPage, FetchAllQuery, Repository, RepositoryError,
+};
+
+/// How many pokemons are read from the repository at a time.
+const PAGE_SIZE: usize = 100;
+
+pub struct Response {
+    pub number: u16,
+    /// None for the default form.
+    pub form: Option<String>,
+    pub name: String,
+    pub types: Vec<String>,
+    pub stats: Option<Stats>,
+    pub total: Option<u16>,
+}
+
+pub enum Error {
+    /// The sink refused a pokemon, e.g. because the output file is not writable.
+    Sink,
+    Unknown(RepositoryError),
+}
+
+/// Walks the whole repository page by page in number order, every form
+/// included, handing each pokemon to `sink` as soon as it is read. Returns how many were exported.
+pub fn execute(
+    repo: Arc<dyn Repository>,
+    sink: &mut dyn FnMut(Response) -> Result<(), ()>,
+) -> Result<usize, Error> {
+    for_each(repo, &mut |p| {
+        sink(Response {
+            number: u16::from(p.number),
+            form: p.form.into(),
+            name: String::from(p.name),
+            types: Vec::<String>::from(p.types),
+            stats: p.stats.map(Stats::from),
+            total: p.stats.map(|stats| stats.total()),
+        })
+    })
+}
+
+/// The paging behind `execute`, for use cases needing the entities themselves.
+pub(super) fn for_each(
+    repo: Arc<dyn Repository>,
+    f: &mut dyn FnMut(Pokemon) -> Result<(), ()>,
+) -> Result<usize, Error> {
+    let mut count = 0;
+    let mut cursor = None;
+    loop {
+        let query = FetchAllQuery {
+            cursor,
+            limit: Some(PAGE_SIZE),
+            all_forms: true,
+            ..FetchAllQuery::default()
+        };
+        let (pokemons, next_cursor) = match repo.fetch_all(query) {
+            Ok(FetchAllPage {
+                pokemons,
+                next_cursor,
+            }) => (pokemons, next_cursor),
+            Err(FetchAllError::InvalidCursor) => {
+                return Err(Error::Unknown(RepositoryError::new(
+                    "the repository refused its own cursor",
+                )))
+            }
+            Err(FetchAllError::Unknown(e)) => return Err(Error::Unknown(e)),
+        };
+        for p in pokemons {
+            match f(p) {
+                Ok(()) => count += 1,
+                Err(()) => return Err(Error::Sink),
+            }
+        }
+        match next_cursor {
+            Some(next_cursor) => cursor = Some(next_cursor),
+            None => return Ok(count),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{PokemonForm, PokemonName, PokemonNumber, PokemonTypes};
+    use crate::repositories::pokemon::InMemoryRepository;
+
+    #[test]
+    fn it_should_export_every_pokemon_across_pages_in_number_order() {
+        let repo = Arc::new(InMemoryRepository::new());
+        for number in (1..=250).rev() {
+            repo.insert(
+                PokemonNumber::try_from(number).ok().unwrap(),
+                PokemonForm::default(),
+                PokemonName::pikachu(),
+                PokemonTypes::pikachu(),
+                None,
+            )
+            .ok();
+        }
+        let mut numbers = vec![];
+
+        let res = execute(repo, &mut |p| {
+            numbers.push(p.number);
+            Ok(())
+        });
+
+        match res {
+            Ok(250) => {}
+            _ => unreachable!(),
+        };
+        assert_eq!(numbers, (1..=250).collect::<Vec<u16>>());
+    }
+
+    #[test]
+    fn it_should_stop_when_the_sink_fails() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonForm::default(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+            None,
+        )
+        .ok();
+
+        let res = execute(repo, &mut |_| Err(()));
+
+        match res {
+            Err(Error::Sink) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let repo = Arc::new(InMemoryRepository::new().with_error());
+
+        let res = execute(repo, &mut |_| Ok(()));
+
+        match res {
+            Err(Error::Unknown(_)) => {}
+            _ => unreachable!(),
+        };
+    }
+}
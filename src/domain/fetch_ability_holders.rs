@@ -0,0 +1,144 @@
+use std::sync::Arc;
+
+use super::entities::{AbilityName, PokemonForm, ValidationError};
+use crate::repositories::{
+    ability::{AbilityRepository, FetchOneError},
+    pokemon::{self, Repository, RepositoryError},
+};
+
+pub struct Request {
+    pub name: String,
+}
+
+pub struct Response {
+    pub pokemons: Vec<Holder>,
+}
+
+pub struct Holder {
+    pub number: u16,
+    pub name: String,
+    pub hidden: bool,
+}
+
+pub enum Error {
+    BadRequest(Vec<ValidationError>),
+    NotFound,
+    Unknown(RepositoryError),
+}
+
+/// Finds which Pokemon have the ability, by ascending number.
+pub fn execute(
+    pokemons: Arc<dyn Repository>,
+    abilities: Arc<dyn AbilityRepository>,
+    req: Request,
+) -> Result<Response, Error> {
+    let name = match AbilityName::try_from(req.name) {
+        Ok(name) => name,
+        Err(e) => return Err(Error::BadRequest(vec![e.into()])),
+    };
+    let holders = match abilities.fetch_holders(name) {
+        Ok(holders) => holders,
+        Err(FetchOneError::NotFound) => return Err(Error::NotFound),
+        Err(FetchOneError::Unknown(e)) => return Err(Error::Unknown(e)),
+    };
+    let mut res = Response { pokemons: vec![] };
+    for holder in holders {
+        match pokemons.fetch_one(holder.number, PokemonForm::default()) {
+            Ok(pokemon) => res.pokemons.push(Holder {
+                number: u16::from(pokemon.number),
+                name: String::from(pokemon.name),
+                hidden: holder.hidden,
+            }),
+            // The repository may still link abilities to a deleted Pokemon.
+            Err(pokemon::FetchOneError::NotFound) => {}
+            Err(pokemon::FetchOneError::Unknown(e)) => return Err(Error::Unknown(e)),
+        }
+    }
+    Ok(res)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        domain::entities::{PokemonAbilities, PokemonName, PokemonNumber, PokemonTypes},
+        repositories::{ability::InMemoryAbilityRepository, pokemon::InMemoryRepository},
+    };
+
+    impl Request {
+        fn new(name: AbilityName) -> Self {
+            Self {
+                name: String::from(name),
+            }
+        }
+    }
+
+    #[test]
+    fn it_should_return_a_not_found_error_when_the_ability_does_not_exist() {
+        let res = execute(
+            Arc::new(InMemoryRepository::new()),
+            Arc::new(InMemoryAbilityRepository::new()),
+            Request::new(AbilityName::blaze()),
+        );
+
+        match res {
+            Err(Error::NotFound) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_the_pokemons_having_the_ability_otherwise() {
+        let pokemons = Arc::new(InMemoryRepository::new());
+        for (number, name, types) in [
+            (
+                PokemonNumber::charizard(),
+                PokemonName::charizard(),
+                PokemonTypes::charizard(),
+            ),
+            (
+                PokemonNumber::charmander(),
+                PokemonName::charmander(),
+                PokemonTypes::charmander(),
+            ),
+        ] {
+            pokemons
+                .insert(number, PokemonForm::default(), name, types, None)
+                .ok();
+        }
+        let abilities = Arc::new(InMemoryAbilityRepository::new());
+        abilities.insert(AbilityName::blaze(), String::new()).ok();
+        abilities
+            .insert(AbilityName::solar_power(), String::new())
+            .ok();
+        for number in [
+            PokemonNumber::charizard(),
+            PokemonNumber::charmander(),
+            PokemonNumber::pikachu(),
+        ] {
+            abilities
+                .set_pokemon_abilities(number, PokemonAbilities::charmander())
+                .ok();
+        }
+
+        let res = execute(
+            pokemons,
+            abilities,
+            Request::new(AbilityName::solar_power()),
+        );
+
+        match res {
+            Ok(res) => assert_eq!(
+                res.pokemons
+                    .into_iter()
+                    .map(|p| (p.number, p.name, p.hidden))
+                    .collect::<Vec<(u16, String, bool)>>(),
+                vec![
+                    (4, String::from("Charmander"), true),
+                    (6, String::from("Charizard"), true)
+                ]
+            ),
+            _ => unreachable!(),
+        };
+    }
+}
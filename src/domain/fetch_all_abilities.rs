@@ -0,0 +1,60 @@
+use std::sync::Arc;
+
+use crate::repositories::ability::{AbilityRepository, RepositoryError};
+
+pub struct Response {
+    pub name: String,
+    pub description: String,
+}
+
+pub enum Error {
+    Unknown(RepositoryError),
+}
+
+pub fn execute(repo: Arc<dyn AbilityRepository>) -> Result<Vec<Response>, Error> {
+    match repo.fetch_all() {
+        Ok(abilities) => Ok(abilities
+            .into_iter()
+            .map(|a| Response {
+                name: String::from(a.name),
+                description: a.description,
+            })
+            .collect()),
+        Err(e) => Err(Error::Unknown(e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{domain::entities::AbilityName, repositories::ability::InMemoryAbilityRepository};
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let repo = Arc::new(InMemoryAbilityRepository::new().with_error());
+
+        let res = execute(repo);
+
+        match res {
+            Err(Error::Unknown(_)) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_the_abilities_sorted_by_name_otherwise() {
+        let repo = Arc::new(InMemoryAbilityRepository::new());
+        repo.insert(AbilityName::r#static(), String::new()).ok();
+        repo.insert(AbilityName::blaze(), String::new()).ok();
+
+        let res = execute(repo);
+
+        match res {
+            Ok(res) => assert_eq!(
+                res.into_iter().map(|a| a.name).collect::<Vec<String>>(),
+                vec!["Blaze", "Static"]
+            ),
+            _ => unreachable!(),
+        };
+    }
+}
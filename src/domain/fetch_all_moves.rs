@@ -0,0 +1,72 @@
+use std::sync::Arc;
+
+use crate::repositories::moves::{MoveRepository, RepositoryError};
+
+pub struct Response {
+    pub name: String,
+    pub r#type: String,
+    pub category: String,
+    pub power: Option<u16>,
+    pub accuracy: Option<u16>,
+    pub pp: u16,
+}
+
+pub enum Error {
+    Unknown(RepositoryError),
+}
+
+pub fn execute(repo: Arc<dyn MoveRepository>) -> Result<Vec<Response>, Error> {
+    match repo.fetch_all() {
+        Ok(moves) => Ok(moves
+            .into_iter()
+            .map(|m| Response {
+                name: String::from(m.name),
+                r#type: String::from(m.r#type),
+                category: String::from(m.category),
+                power: m.power.map(u16::from),
+                accuracy: m.accuracy.map(u16::from),
+                pp: u16::from(m.pp),
+            })
+            .collect()),
+        Err(e) => Err(Error::Unknown(e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{domain::entities::Move, repositories::moves::InMemoryMoveRepository};
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let repo = Arc::new(InMemoryMoveRepository::new().with_error());
+
+        let res = execute(repo);
+
+        match res {
+            Err(Error::Unknown(_)) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_the_moves_sorted_by_name_otherwise() {
+        let repo = Arc::new(InMemoryMoveRepository::new());
+        repo.insert(Move::thunderbolt()).ok();
+        repo.insert(Move::growl()).ok();
+
+        let res = execute(repo);
+
+        match res {
+            Ok(res) => {
+                assert_eq!(
+                    res.iter().map(|m| m.name.clone()).collect::<Vec<String>>(),
+                    vec!["Growl", "Thunderbolt"]
+                );
+                assert_eq!(res[0].category, "Status");
+                assert_eq!(res[0].power, None);
+            }
+            _ => unreachable!(),
+        };
+    }
+}
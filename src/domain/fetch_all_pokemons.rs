@@ -1,28 +1,152 @@
 use std::sync::Arc;
 
-use crate::repositories::pokemon::{FetchAllError, Repository};
+use super::{
+    entities::{PokemonType, Stat, ValidationError},
+    Stats,
+};
+use crate::repositories::pokemon::{
+    FetchAllError, FetchAllPage, FetchAllQuery, Repository, RepositoryError, SortDirection, SortKey,
+};
+
+pub const MAX_LIMIT: u16 = 100;
+
+#[derive(Default)]
+pub struct Request {
+    pub cursor: Option<String>,
+    pub limit: Option<u16>,
+    pub sort: Option<String>,
+    pub direction: Option<String>,
+    pub r#type: Option<String>,
+    pub name_prefix: Option<String>,
+    /// Either "default", the default, or "all" to list the other forms too.
+    pub forms: Option<String>,
+}
 
 pub struct Response {
     pub number: u16,
+    /// None for the default form.
+    pub form: Option<String>,
     pub name: String,
     pub types: Vec<String>,
+    pub stats: Option<Stats>,
+    pub total: Option<u16>,
+}
+
+pub struct Page {
+    pub pokemons: Vec<Response>,
+    pub next_cursor: Option<String>,
 }
 
 pub enum Error {
-    Unknown,
+    BadRequest(Vec<ValidationError>),
+    Unknown(RepositoryError),
+}
+
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Page, Error> {
+    let query = match FetchAllQuery::try_from(req) {
+        Ok(query) => query,
+        Err(e) => return Err(Error::BadRequest(vec![e])),
+    };
+    match repo.fetch_all(query) {
+        Ok(FetchAllPage {
+            pokemons,
+            next_cursor,
+        }) => Ok(Page {
+            pokemons: pokemons
+                .into_iter()
+                .map(|p| Response {
+                    number: u16::from(p.number),
+                    form: p.form.into(),
+                    name: String::from(p.name),
+                    types: Vec::<String>::from(p.types),
+                    stats: p.stats.map(Stats::from),
+                    total: p.stats.map(|stats| stats.total()),
+                })
+                .collect::<Vec<Response>>(),
+            next_cursor,
+        }),
+        Err(FetchAllError::InvalidCursor) => Err(Error::BadRequest(vec![invalid(
+            "cursor",
+            "does not point into this listing",
+        )])),
+        Err(FetchAllError::Unknown(e)) => Err(Error::Unknown(e)),
+    }
 }
 
-pub fn execute(repo: Arc<dyn Repository>) -> Result<Vec<Response>, Error> {
-    match repo.fetch_all() {
-        Ok(pokemons) => Ok(pokemons
-            .into_iter()
-            .map(|p| Response {
-                number: u16::from(p.number),
-                name: String::from(p.name),
-                types: Vec::<String>::from(p.types),
-            })
-            .collect::<Vec<Response>>()),
-        Err(FetchAllError::Unknown) => Err(Error::Unknown),
+fn invalid(name: &'static str, reason: impl Into<String>) -> ValidationError {
+    ValidationError::InvalidParameter {
+        name,
+        reason: reason.into(),
+    }
+}
+
+impl TryFrom<Request> for FetchAllQuery {
+    type Error = ValidationError;
+
+    fn try_from(req: Request) -> Result<Self, Self::Error> {
+        let limit = match req.limit {
+            Some(limit) if limit == 0 || limit > MAX_LIMIT => {
+                return Err(invalid(
+                    "limit",
+                    format!("must be within 1..={}, got {}", MAX_LIMIT, limit),
+                ))
+            }
+            limit => limit.map(usize::from),
+        };
+        let sort = match req.sort.as_deref() {
+            None | Some("number") => SortKey::Number,
+            Some("name") => SortKey::Name,
+            Some("total") => SortKey::Total,
+            Some(sort) => match Stat::ALL
+                .into_iter()
+                .find(|stat| String::from(*stat) == sort)
+            {
+                Some(stat) => SortKey::Stat(stat),
+                None => {
+                    return Err(invalid(
+                        "sort",
+                        format!(
+                            "must be \"number\", \"name\", a stat or \"total\", got {:?}",
+                            sort
+                        ),
+                    ))
+                }
+            },
+        };
+        let direction = match req.direction.as_deref() {
+            None | Some("asc") => SortDirection::Ascending,
+            Some("desc") => SortDirection::Descending,
+            Some(direction) => {
+                return Err(invalid(
+                    "direction",
+                    format!("must be \"asc\" or \"desc\", got {:?}", direction),
+                ))
+            }
+        };
+        let r#type = match req.r#type.map(PokemonType::try_from) {
+            Some(Ok(t)) => Some(t),
+            Some(Err(e)) => return Err(invalid("type", e.to_string())),
+            None => None,
+        };
+        let all_forms = match req.forms.as_deref() {
+            None | Some("default") => false,
+            Some("all") => true,
+            Some(forms) => {
+                return Err(invalid(
+                    "forms",
+                    format!("must be \"default\" or \"all\", got {:?}", forms),
+                ))
+            }
+        };
+        Ok(Self {
+            cursor: req.cursor,
+            limit,
+            sort,
+            direction,
+            r#type,
+            name_prefix: req.name_prefix.filter(|prefix| !prefix.is_empty()),
+            all_forms,
+        })
     }
 }
 
@@ -30,38 +154,131 @@ pub fn execute(repo: Arc<dyn Repository>) -> Result<Vec<Response>, Error> {
 mod test {
     use super::*;
     use crate::{
-        domain::entities::{PokemonName, PokemonNumber, PokemonTypes},
+        domain::entities::{BaseStats, PokemonForm, PokemonName, PokemonNumber, PokemonTypes},
         repositories::pokemon::InMemoryRepository,
     };
+
+    fn repo_with_pokemons() -> Arc<InMemoryRepository> {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonForm::default(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+            None,
+        )
+        .ok();
+        repo.insert(
+            PokemonNumber::charmander(),
+            PokemonForm::default(),
+            PokemonName::charmander(),
+            PokemonTypes::charmander(),
+            None,
+        )
+        .ok();
+        repo.insert(
+            PokemonNumber::charizard(),
+            PokemonForm::default(),
+            PokemonName::charizard(),
+            PokemonTypes::charizard(),
+            None,
+        )
+        .ok();
+        repo
+    }
+
+    fn numbers(page: &Page) -> Vec<u16> {
+        page.pokemons.iter().map(|p| p.number).collect()
+    }
+
     #[test]
     fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
         let repo = Arc::new(InMemoryRepository::new().with_error());
 
-        let res = execute(repo);
+        let res = execute(repo, Request::default());
 
         match res {
-            Err(Error::Unknown) => {}
+            Err(Error::Unknown(_)) => {}
             _ => unreachable!(),
         };
     }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_query_is_invalid() {
+        let invalid = [
+            Request {
+                limit: Some(0),
+                ..Request::default()
+            },
+            Request {
+                limit: Some(MAX_LIMIT + 1),
+                ..Request::default()
+            },
+            Request {
+                sort: Some(String::from("weight")),
+                ..Request::default()
+            },
+            Request {
+                direction: Some(String::from("up")),
+                ..Request::default()
+            },
+            Request {
+                r#type: Some(String::from("Shadow")),
+                ..Request::default()
+            },
+            Request {
+                cursor: Some(String::from("not-a-cursor")),
+                ..Request::default()
+            },
+            Request {
+                forms: Some(String::from("alola")),
+                ..Request::default()
+            },
+        ];
+
+        let fields = [
+            "limit",
+            "limit",
+            "sort",
+            "direction",
+            "type",
+            "cursor",
+            "forms",
+        ];
+
+        for (req, field) in invalid.into_iter().zip(fields) {
+            match execute(repo_with_pokemons(), req) {
+                Err(Error::BadRequest(errors)) => assert_eq!(errors[0].field(), field),
+                _ => unreachable!(),
+            };
+        }
+    }
+
     #[test]
     fn it_should_return_all_pokemons_ordered_by_number_otherwise() {
         let repo = Arc::new(InMemoryRepository::new());
         repo.insert(
             PokemonNumber::pikachu(),
+            PokemonForm::default(),
             PokemonName::pikachu(),
             PokemonTypes::pikachu(),
+            None,
         )
         .ok();
         repo.insert(
             PokemonNumber::charmander(),
+            PokemonForm::default(),
             PokemonName::charmander(),
             PokemonTypes::charmander(),
+            None,
         )
         .ok();
-        let res = execute(repo);
+        let res = execute(repo, Request::default());
         match res {
-            Ok(res) => {
+            Ok(Page {
+                pokemons: res,
+                next_cursor: None,
+            }) => {
                 assert_eq!(res[0].number, u16::from(PokemonNumber::charmander()));
                 assert_eq!(res[0].name, String::from(PokemonName::charmander()));
                 assert_eq!(
@@ -75,4 +292,140 @@ mod test {
             _ => unreachable!(),
         };
     }
+
+    #[test]
+    fn it_should_sort_by_the_given_key_and_direction() {
+        let req = Request {
+            sort: Some(String::from("name")),
+            direction: Some(String::from("desc")),
+            ..Request::default()
+        };
+
+        let res = execute(repo_with_pokemons(), req);
+
+        match res {
+            Ok(page) => assert_eq!(numbers(&page), vec![25, 4, 6]),
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_sort_by_a_stat_or_the_total() {
+        let repo = repo_with_pokemons();
+        repo.update(
+            PokemonNumber::pikachu(),
+            PokemonForm::default(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+            Some(BaseStats::pikachu()),
+        )
+        .ok();
+        repo.update(
+            PokemonNumber::charizard(),
+            PokemonForm::default(),
+            PokemonName::charizard(),
+            PokemonTypes::charizard(),
+            Some(BaseStats::charizard()),
+        )
+        .ok();
+        let by = |sort: &str| Request {
+            sort: Some(String::from(sort)),
+            direction: Some(String::from("desc")),
+            ..Request::default()
+        };
+
+        let speed = execute(repo.clone(), by("speed"));
+        let total = execute(repo, by("total"));
+
+        match (speed, total) {
+            (Ok(speed), Ok(total)) => {
+                assert_eq!(numbers(&speed), vec![6, 25, 4]);
+                assert_eq!(numbers(&total), vec![6, 25, 4]);
+                assert_eq!(total.pokemons[0].total, Some(534));
+                assert_eq!(total.pokemons[2].total, None);
+            }
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_filter_by_type_and_name_prefix() {
+        let req = Request {
+            r#type: Some(String::from("Fire")),
+            name_prefix: Some(String::from("Charm")),
+            ..Request::default()
+        };
+
+        let res = execute(repo_with_pokemons(), req);
+
+        match res {
+            Ok(page) => assert_eq!(numbers(&page), vec![4]),
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_list_the_other_forms_when_asked() {
+        let repo = repo_with_pokemons();
+        repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonForm::alola(),
+            PokemonName::pikachu(),
+            PokemonTypes::alolan_raichu(),
+            None,
+        )
+        .ok();
+        let req = Request {
+            forms: Some(String::from("all")),
+            ..Request::default()
+        };
+
+        let default = execute(repo.clone(), Request::default());
+        let all = execute(repo, req);
+
+        match (default, all) {
+            (Ok(default), Ok(all)) => {
+                assert_eq!(numbers(&default), vec![4, 6, 25]);
+                assert_eq!(numbers(&all), vec![4, 6, 25, 25]);
+                assert_eq!(all.pokemons[3].form, Some(String::from("alola")));
+            }
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_paginate_with_the_next_cursor() {
+        let repo = repo_with_pokemons();
+        let first = execute(
+            repo.clone(),
+            Request {
+                limit: Some(2),
+                ..Request::default()
+            },
+        );
+        let cursor = match first {
+            Ok(page) => {
+                assert_eq!(numbers(&page), vec![4, 6]);
+                page.next_cursor
+            }
+            _ => unreachable!(),
+        };
+
+        let second = execute(
+            repo,
+            Request {
+                limit: Some(2),
+                cursor,
+                ..Request::default()
+            },
+        );
+
+        match second {
+            Ok(page) => {
+                assert_eq!(numbers(&page), vec![25]);
+                assert!(page.next_cursor.is_none());
+            }
+            _ => unreachable!(),
+        };
+    }
 }
@@ -0,0 +1,317 @@
+use std::sync::Arc;
+
+use super::entities::{
+    validation_errors, EvolutionTrigger, PokemonForm, PokemonNumber, ValidationError,
+};
+use crate::repositories::{
+    evolution::EvolutionRepository,
+    pokemon::{FetchOneError, Repository, RepositoryError},
+};
+
+pub struct Request {
+    pub number: u16,
+    /// Only checked to exist, every form sharing the evolutions of its species.
+    pub form: Option<String>,
+}
+
+pub struct Response {
+    pub base: Stage,
+}
+
+/// A Pokemon of the chain along with the ones it evolves into, the final
+/// stages evolving into none.
+pub struct Stage {
+    pub number: u16,
+    pub name: String,
+    /// How the previous stage evolves into this one, none for the base form.
+    pub trigger: Option<Trigger>,
+    pub evolutions: Vec<Stage>,
+}
+
+pub struct Trigger {
+    pub name: String,
+    pub level: Option<u16>,
+    pub item: Option<String>,
+}
+
+pub enum Error {
+    BadRequest(Vec<ValidationError>),
+    NotFound,
+    Unknown(RepositoryError),
+}
+
+/// Returns the whole chain the Pokemon belongs to, from its base form down
+/// to every final stage, each stage listing its evolutions by ascending
+/// number.
+pub fn execute(
+    pokemons: Arc<dyn Repository>,
+    evolutions: Arc<dyn EvolutionRepository>,
+    req: Request,
+) -> Result<Response, Error> {
+    let (number, form) = match (
+        PokemonNumber::try_from(req.number),
+        PokemonForm::try_from(req.form.unwrap_or_default()).map_err(ValidationError::from),
+    ) {
+        (Ok(number), Ok(form)) => (number, form),
+        (number, form) => {
+            return Err(Error::BadRequest(validation_errors([
+                number.err(),
+                form.err(),
+            ])))
+        }
+    };
+    match pokemons.fetch_one(number.clone(), form) {
+        Ok(_) => {}
+        Err(FetchOneError::NotFound) => return Err(Error::NotFound),
+        Err(FetchOneError::Unknown(e)) => return Err(Error::Unknown(e)),
+    }
+    let base = match base_form(pokemons.clone(), evolutions.clone(), number) {
+        Ok(base) => base,
+        Err(e) => return Err(Error::Unknown(e)),
+    };
+    match stage(pokemons, evolutions, base, None) {
+        Ok(Some(base)) => Ok(Response { base }),
+        Ok(None) => Err(Error::NotFound),
+        Err(e) => Err(Error::Unknown(e)),
+    }
+}
+
+/// Walks back the pre-evolutions, stopping at a Pokemon that was deleted.
+fn base_form(
+    pokemons: Arc<dyn Repository>,
+    evolutions: Arc<dyn EvolutionRepository>,
+    number: PokemonNumber,
+) -> Result<PokemonNumber, RepositoryError> {
+    let mut visited = vec![];
+    let mut number = number;
+    loop {
+        if visited.contains(&number) {
+            return Err(RepositoryError::new(format!(
+                "the evolutions of pokemon {} make a cycle",
+                u16::from(number)
+            )));
+        }
+        visited.push(number.clone());
+        let evolution = match evolutions.fetch_pre_evolution(number.clone())? {
+            Some(evolution) => evolution,
+            None => return Ok(number),
+        };
+        match pokemons.fetch_one(evolution.from.clone(), PokemonForm::default()) {
+            Ok(_) => number = evolution.from,
+            Err(FetchOneError::NotFound) => return Ok(number),
+            Err(FetchOneError::Unknown(e)) => return Err(e),
+        }
+    }
+}
+
+/// The Pokemon and its evolutions, none when it was deleted.
+fn stage(
+    pokemons: Arc<dyn Repository>,
+    evolutions: Arc<dyn EvolutionRepository>,
+    number: PokemonNumber,
+    trigger: Option<EvolutionTrigger>,
+) -> Result<Option<Stage>, RepositoryError> {
+    let pokemon = match pokemons.fetch_one(number.clone(), PokemonForm::default()) {
+        Ok(pokemon) => pokemon,
+        Err(FetchOneError::NotFound) => return Ok(None),
+        Err(FetchOneError::Unknown(e)) => return Err(e),
+    };
+    let mut stages = vec![];
+    for evolution in evolutions.fetch_evolutions(number)? {
+        if let Some(stage) = stage(
+            pokemons.clone(),
+            evolutions.clone(),
+            evolution.to,
+            Some(evolution.trigger),
+        )? {
+            stages.push(stage);
+        }
+    }
+    Ok(Some(Stage {
+        number: u16::from(pokemon.number),
+        name: String::from(pokemon.name),
+        trigger: trigger.map(|trigger| Trigger {
+            name: String::from(trigger.name()),
+            level: trigger.level(),
+            item: trigger.item(),
+        }),
+        evolutions: stages,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        domain::entities::{Evolution, PokemonName, PokemonTypes},
+        repositories::{evolution::InMemoryEvolutionRepository, pokemon::InMemoryRepository},
+    };
+
+    fn repos() -> (Arc<InMemoryRepository>, Arc<InMemoryEvolutionRepository>) {
+        let pokemons = Arc::new(InMemoryRepository::new());
+        for (number, name) in [
+            (PokemonNumber::charmander(), PokemonName::charmander()),
+            (PokemonNumber::charmeleon(), PokemonName::charmeleon()),
+            (PokemonNumber::charizard(), PokemonName::charizard()),
+            (PokemonNumber::pikachu(), PokemonName::pikachu()),
+            (PokemonNumber::raichu(), PokemonName::raichu()),
+        ] {
+            pokemons
+                .insert(
+                    number,
+                    PokemonForm::default(),
+                    name,
+                    PokemonTypes::charmander(),
+                    None,
+                )
+                .ok();
+        }
+        let evolutions = Arc::new(InMemoryEvolutionRepository::new());
+        for evolution in [Evolution::charmander(), Evolution::charmeleon()] {
+            evolutions.insert(evolution).ok();
+        }
+        (pokemons, evolutions)
+    }
+
+    /// The chain as (depth, number) in depth-first order.
+    fn flatten(stage: &Stage, depth: u16, stages: &mut Vec<(u16, u16)>) {
+        stages.push((depth, stage.number));
+        for evolution in &stage.evolutions {
+            flatten(evolution, depth + 1, stages);
+        }
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_request_is_invalid() {
+        let (pokemons, evolutions) = repos();
+        let req = Request {
+            number: 0,
+            form: None,
+        };
+
+        let res = execute(pokemons, evolutions, req);
+
+        match res {
+            Err(Error::BadRequest(errors)) => {
+                assert_eq!(errors, vec![ValidationError::NumberOutOfRange(0)])
+            }
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_not_found_error_when_the_pokemon_does_not_exist() {
+        let (pokemons, evolutions) = repos();
+        let req = Request {
+            number: 133,
+            form: None,
+        };
+
+        let res = execute(pokemons, evolutions, req);
+
+        match res {
+            Err(Error::NotFound) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let (pokemons, _) = repos();
+        let evolutions = Arc::new(InMemoryEvolutionRepository::new().with_error());
+        let req = Request {
+            number: 4,
+            form: None,
+        };
+
+        let res = execute(pokemons, evolutions, req);
+
+        match res {
+            Err(Error::Unknown(_)) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_the_whole_chain_from_any_of_its_stages() {
+        let (pokemons, evolutions) = repos();
+        evolutions
+            .insert(Evolution {
+                from: PokemonNumber::charmeleon(),
+                to: PokemonNumber::raichu(),
+                trigger: EvolutionTrigger::Friendship,
+            })
+            .ok();
+
+        for number in [4, 5, 6, 26] {
+            let res = execute(
+                pokemons.clone(),
+                evolutions.clone(),
+                Request { number, form: None },
+            );
+
+            match res {
+                Ok(res) => {
+                    let mut stages = vec![];
+                    flatten(&res.base, 0, &mut stages);
+                    assert_eq!(stages, vec![(0, 4), (1, 5), (2, 6), (2, 26)]);
+                    assert!(res.base.trigger.is_none());
+                    let charizard = &res.base.evolutions[0].evolutions[0];
+                    assert_eq!(charizard.name, "Charizard");
+                    match &charizard.trigger {
+                        Some(trigger) => {
+                            assert_eq!(trigger.name, "level");
+                            assert_eq!(trigger.level, Some(36));
+                        }
+                        None => unreachable!(),
+                    };
+                }
+                _ => unreachable!(),
+            };
+        }
+    }
+
+    #[test]
+    fn it_should_return_a_single_stage_for_a_pokemon_that_does_not_evolve() {
+        let (pokemons, evolutions) = repos();
+        let req = Request {
+            number: 25,
+            form: None,
+        };
+
+        let res = execute(pokemons, evolutions, req);
+
+        match res {
+            Ok(res) => {
+                assert_eq!(res.base.number, 25);
+                assert!(res.base.evolutions.is_empty());
+            }
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_skip_the_deleted_pokemons() {
+        let (pokemons, evolutions) = repos();
+        pokemons
+            .delete(PokemonNumber::charmander(), PokemonForm::default())
+            .ok();
+        pokemons
+            .delete(PokemonNumber::charizard(), PokemonForm::default())
+            .ok();
+        let req = Request {
+            number: 5,
+            form: None,
+        };
+
+        let res = execute(pokemons, evolutions, req);
+
+        match res {
+            Ok(res) => {
+                assert_eq!(res.base.number, 5);
+                assert!(res.base.evolutions.is_empty());
+            }
+            _ => unreachable!(),
+        };
+    }
+}
@@ -0,0 +1,197 @@
+use std::sync::Arc;
+
+use super::entities::{
+    validation_errors, Move, MoveName, PokemonForm, PokemonNumber, ValidationError,
+};
+use crate::repositories::{
+    moves::{self, MoveRepository},
+    pokemon::{FetchOneError, Repository, RepositoryError},
+};
+
+pub struct Request {
+    pub number: u16,
+    /// Only checked to exist, every form sharing the learnset of its species.
+    pub form: Option<String>,
+}
+
+pub struct Response {
+    pub moves: Vec<LearnedMove>,
+}
+
+/// A move of the learnset, along with what the move does.
+pub struct LearnedMove {
+    pub name: String,
+    pub method: String,
+    pub level: Option<u16>,
+    pub r#type: String,
+    pub category: String,
+    pub power: Option<u16>,
+    pub accuracy: Option<u16>,
+    pub pp: u16,
+}
+
+pub enum Error {
+    BadRequest(Vec<ValidationError>),
+    NotFound,
+    Unknown(RepositoryError),
+}
+
+/// Lists the level-up moves of the Pokemon first by ascending level, then
+/// the ones learnt by TM, egg and tutor.
+pub fn execute(
+    pokemons: Arc<dyn Repository>,
+    moves: Arc<dyn MoveRepository>,
+    req: Request,
+) -> Result<Response, Error> {
+    let (number, form) = match (
+        PokemonNumber::try_from(req.number),
+        PokemonForm::try_from(req.form.unwrap_or_default()).map_err(ValidationError::from),
+    ) {
+        (Ok(number), Ok(form)) => (number, form),
+        (number, form) => {
+            return Err(Error::BadRequest(validation_errors([
+                number.err(),
+                form.err(),
+            ])))
+        }
+    };
+    match pokemons.fetch_one(number.clone(), form) {
+        Ok(_) => {}
+        Err(FetchOneError::NotFound) => return Err(Error::NotFound),
+        Err(FetchOneError::Unknown(e)) => return Err(Error::Unknown(e)),
+    }
+    let learnset = match moves.fetch_learnset(number) {
+        Ok(learnset) => learnset,
+        Err(e) => return Err(Error::Unknown(e)),
+    };
+    let mut res = Response { moves: vec![] };
+    for entry in learnset.entries() {
+        let r#move = match fetch_move(moves.clone(), entry.move_name.clone()) {
+            Ok(r#move) => r#move,
+            Err(e) => return Err(Error::Unknown(e)),
+        };
+        res.moves.push(LearnedMove {
+            name: String::from(r#move.name),
+            method: String::from(entry.method.name()),
+            level: entry.method.level(),
+            r#type: String::from(r#move.r#type),
+            category: String::from(r#move.category),
+            power: r#move.power.map(u16::from),
+            accuracy: r#move.accuracy.map(u16::from),
+            pp: u16::from(r#move.pp),
+        });
+    }
+    Ok(res)
+}
+
+fn fetch_move(moves: Arc<dyn MoveRepository>, name: MoveName) -> Result<Move, RepositoryError> {
+    match moves.fetch_one(name.clone()) {
+        Ok(r#move) => Ok(r#move),
+        Err(moves::FetchOneError::NotFound) => Err(RepositoryError::new(format!(
+            "move {:?} vanished while being read",
+            String::from(name)
+        ))),
+        Err(moves::FetchOneError::Unknown(e)) => Err(e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        domain::entities::{Learnset, PokemonName, PokemonTypes},
+        repositories::{moves::InMemoryMoveRepository, pokemon::InMemoryRepository},
+    };
+
+    fn repos() -> (Arc<InMemoryRepository>, Arc<InMemoryMoveRepository>) {
+        let pokemons = Arc::new(InMemoryRepository::new());
+        pokemons
+            .insert(
+                PokemonNumber::pikachu(),
+                PokemonForm::default(),
+                PokemonName::pikachu(),
+                PokemonTypes::pikachu(),
+                None,
+            )
+            .ok();
+        let moves = Arc::new(InMemoryMoveRepository::new());
+        for r#move in [Move::growl(), Move::thunder_shock(), Move::thunderbolt()] {
+            moves.insert(r#move).ok();
+        }
+        (pokemons, moves)
+    }
+
+    #[test]
+    fn it_should_return_a_not_found_error_when_the_pokemon_does_not_exist() {
+        let (_, moves) = repos();
+
+        let res = execute(
+            Arc::new(InMemoryRepository::new()),
+            moves,
+            Request {
+                number: 25,
+                form: None,
+            },
+        );
+
+        match res {
+            Err(Error::NotFound) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let (pokemons, _) = repos();
+
+        let res = execute(
+            pokemons,
+            Arc::new(InMemoryMoveRepository::new().with_error()),
+            Request {
+                number: 25,
+                form: None,
+            },
+        );
+
+        match res {
+            Err(Error::Unknown(_)) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_the_described_learnset_otherwise() {
+        let (pokemons, moves) = repos();
+        moves
+            .set_learnset(PokemonNumber::pikachu(), Learnset::pikachu())
+            .ok();
+
+        let res = execute(
+            pokemons,
+            moves,
+            Request {
+                number: 25,
+                form: None,
+            },
+        );
+
+        match res {
+            Ok(res) => {
+                assert_eq!(
+                    res.moves
+                        .iter()
+                        .map(|m| (m.name.as_str(), m.method.as_str(), m.level))
+                        .collect::<Vec<(&str, &str, Option<u16>)>>(),
+                    vec![
+                        ("Growl", "level-up", Some(1)),
+                        ("Thunder Shock", "level-up", Some(1)),
+                        ("Thunderbolt", "tm", None)
+                    ]
+                );
+                assert_eq!(res.moves[2].power, Some(90));
+                assert_eq!(res.moves[2].r#type, "Electric");
+            }
+            _ => unreachable!(),
+        };
+    }
+}
@@ -0,0 +1,111 @@
+use std::sync::Arc;
+
+use super::entities::{Move, MoveName, ValidationError};
+use crate::repositories::moves::{FetchOneError, MoveRepository, RepositoryError};
+
+pub struct Request {
+    pub name: String,
+}
+
+pub struct Response {
+    pub name: String,
+    pub r#type: String,
+    pub category: String,
+    pub power: Option<u16>,
+    pub accuracy: Option<u16>,
+    pub pp: u16,
+}
+
+pub enum Error {
+    BadRequest(Vec<ValidationError>),
+    NotFound,
+    Unknown(RepositoryError),
+}
+
+pub fn execute(repo: Arc<dyn MoveRepository>, req: Request) -> Result<Response, Error> {
+    match MoveName::try_from(req.name) {
+        Ok(name) => match repo.fetch_one(name) {
+            Ok(Move {
+                name,
+                r#type,
+                category,
+                power,
+                accuracy,
+                pp,
+            }) => Ok(Response {
+                name: String::from(name),
+                r#type: String::from(r#type),
+                category: String::from(category),
+                power: power.map(u16::from),
+                accuracy: accuracy.map(u16::from),
+                pp: u16::from(pp),
+            }),
+            Err(FetchOneError::NotFound) => Err(Error::NotFound),
+            Err(FetchOneError::Unknown(e)) => Err(Error::Unknown(e)),
+        },
+        Err(e) => Err(Error::BadRequest(vec![e.into()])),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::MoveError;
+    use crate::repositories::moves::InMemoryMoveRepository;
+
+    impl Request {
+        fn new(name: MoveName) -> Self {
+            Self {
+                name: String::from(name),
+            }
+        }
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_the_name_is_empty() {
+        let repo = Arc::new(InMemoryMoveRepository::new());
+
+        let res = execute(
+            repo,
+            Request {
+                name: String::from(" "),
+            },
+        );
+
+        match res {
+            Err(Error::BadRequest(errors)) => {
+                assert_eq!(errors, vec![ValidationError::Move(MoveError::EmptyName)])
+            }
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_not_found_error_when_the_repo_does_not_contain_the_move() {
+        let repo = Arc::new(InMemoryMoveRepository::new());
+
+        let res = execute(repo, Request::new(MoveName::ember()));
+
+        match res {
+            Err(Error::NotFound) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_the_move_otherwise() {
+        let repo = Arc::new(InMemoryMoveRepository::new());
+        repo.insert(Move::ember()).ok();
+
+        let res = execute(repo, Request::new(MoveName::ember()));
+
+        match res {
+            Ok(res) => {
+                assert_eq!(res.name, "Ember");
+                assert_eq!(res.r#type, "Fire");
+                assert_eq!(res.pp, 25);
+            }
+            _ => unreachable!(),
+        };
+    }
+}
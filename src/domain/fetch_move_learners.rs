@@ -0,0 +1,155 @@
+use std::sync::Arc;
+
+use super::entities::{MoveName, PokemonForm, ValidationError};
+use crate::repositories::{
+    moves::{FetchOneError, MoveRepository},
+    pokemon::{self, Repository, RepositoryError},
+};
+
+pub struct Request {
+    pub name: String,
+}
+
+pub struct Response {
+    pub pokemons: Vec<Learner>,
+}
+
+pub struct Learner {
+    pub number: u16,
+    pub name: String,
+    pub method: String,
+    pub level: Option<u16>,
+}
+
+pub enum Error {
+    BadRequest(Vec<ValidationError>),
+    NotFound,
+    Unknown(RepositoryError),
+}
+
+/// Finds every Pokemon learning the move, by ascending number, once for each
+/// way it learns it.
+pub fn execute(
+    pokemons: Arc<dyn Repository>,
+    moves: Arc<dyn MoveRepository>,
+    req: Request,
+) -> Result<Response, Error> {
+    let name = match MoveName::try_from(req.name) {
+        Ok(name) => name,
+        Err(e) => return Err(Error::BadRequest(vec![e.into()])),
+    };
+    let learners = match moves.fetch_learners(name) {
+        Ok(learners) => learners,
+        Err(FetchOneError::NotFound) => return Err(Error::NotFound),
+        Err(FetchOneError::Unknown(e)) => return Err(Error::Unknown(e)),
+    };
+    let mut res = Response { pokemons: vec![] };
+    for learner in learners {
+        match pokemons.fetch_one(learner.number, PokemonForm::default()) {
+            Ok(pokemon) => res.pokemons.push(Learner {
+                number: u16::from(pokemon.number),
+                name: String::from(pokemon.name),
+                method: String::from(learner.method.name()),
+                level: learner.method.level(),
+            }),
+            // The repository may still hold the learnset of a deleted Pokemon.
+            Err(pokemon::FetchOneError::NotFound) => {}
+            Err(pokemon::FetchOneError::Unknown(e)) => return Err(Error::Unknown(e)),
+        }
+    }
+    Ok(res)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        domain::entities::{Learnset, Move, PokemonName, PokemonNumber, PokemonTypes},
+        repositories::{moves::InMemoryMoveRepository, pokemon::InMemoryRepository},
+    };
+
+    impl Request {
+        fn new(name: MoveName) -> Self {
+            Self {
+                name: String::from(name),
+            }
+        }
+    }
+
+    #[test]
+    fn it_should_return_a_not_found_error_when_the_move_does_not_exist() {
+        let res = execute(
+            Arc::new(InMemoryRepository::new()),
+            Arc::new(InMemoryMoveRepository::new()),
+            Request::new(MoveName::growl()),
+        );
+
+        match res {
+            Err(Error::NotFound) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_the_pokemons_learning_the_move_otherwise() {
+        let pokemons = Arc::new(InMemoryRepository::new());
+        for (number, name, types) in [
+            (
+                PokemonNumber::pikachu(),
+                PokemonName::pikachu(),
+                PokemonTypes::pikachu(),
+            ),
+            (
+                PokemonNumber::charmander(),
+                PokemonName::charmander(),
+                PokemonTypes::charmander(),
+            ),
+        ] {
+            pokemons
+                .insert(number, PokemonForm::default(), name, types, None)
+                .ok();
+        }
+        let moves = Arc::new(InMemoryMoveRepository::new());
+        for r#move in [
+            Move::growl(),
+            Move::thunder_shock(),
+            Move::thunderbolt(),
+            Move::ember(),
+        ] {
+            moves.insert(r#move).ok();
+        }
+        for (number, learnset) in [
+            (PokemonNumber::pikachu(), Learnset::pikachu()),
+            (PokemonNumber::charmander(), Learnset::charmander()),
+            (PokemonNumber::charizard(), Learnset::charmander()),
+        ] {
+            moves.set_learnset(number, learnset).ok();
+        }
+
+        let res = execute(pokemons, moves, Request::new(MoveName::growl()));
+
+        match res {
+            Ok(res) => assert_eq!(
+                res.pokemons
+                    .into_iter()
+                    .map(|p| (p.number, p.name, p.method, p.level))
+                    .collect::<Vec<(u16, String, String, Option<u16>)>>(),
+                vec![
+                    (
+                        4,
+                        String::from("Charmander"),
+                        String::from("level-up"),
+                        Some(1)
+                    ),
+                    (
+                        25,
+                        String::from("Pikachu"),
+                        String::from("level-up"),
+                        Some(1)
+                    )
+                ]
+            ),
+            _ => unreachable!(),
+        };
+    }
+}
@@ -1,13 +1,15 @@
 use crate::{
-    domain::entities::PokemonNumber,
-    repositories::pokemon::{FetchOneError, Repository},
+    domain::entities::{validation_errors, PokemonForm, PokemonNumber, ValidationError},
+    repositories::pokemon::{FetchOneError, Repository, RepositoryError},
 };
 use std::sync::Arc;
 
-use super::entities::Pokemon;
+use super::{entities::Pokemon, Stats};
 
 pub struct Request {
     pub(crate) number: u16,
+    /// The default form when none is given.
+    pub(crate) form: Option<String>,
 }
 
 #[cfg(test)]
@@ -15,38 +17,54 @@ impl Request {
     pub fn new(number: PokemonNumber) -> Self {
         Self {
             number: u16::from(number),
+            form: None,
         }
     }
 }
 
 pub struct Response {
     pub number: u16,
+    /// None for the default form.
+    pub form: Option<String>,
     pub name: String,
     pub types: Vec<String>,
+    pub stats: Option<Stats>,
+    pub total: Option<u16>,
 }
 
 pub enum Error {
-    Unknown,
-    BadRequest,
+    Unknown(RepositoryError),
+    BadRequest(Vec<ValidationError>),
     NotFound,
 }
 
 pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response, Error> {
-    match PokemonNumber::try_from(req.number) {
-        Ok(number) => match repo.fetch_one(number) {
+    match (
+        PokemonNumber::try_from(req.number),
+        PokemonForm::try_from(req.form.unwrap_or_default()).map_err(ValidationError::from),
+    ) {
+        (Ok(number), Ok(form)) => match repo.fetch_one(number, form) {
             Ok(Pokemon {
                 number,
+                form,
                 name,
                 types,
+                stats,
             }) => Ok(Response {
                 number: u16::from(number),
+                form: form.into(),
                 name: String::from(name),
                 types: Vec::from(types),
+                stats: stats.map(Stats::from),
+                total: stats.map(|stats| stats.total()),
             }),
             Err(FetchOneError::NotFound) => Err(Error::NotFound),
-            Err(FetchOneError::Unknown) => Err(Error::Unknown),
+            Err(FetchOneError::Unknown(e)) => Err(Error::Unknown(e)),
         },
-        _ => Err(Error::BadRequest),
+        (number, form) => Err(Error::BadRequest(validation_errors([
+            number.err(),
+            form.err(),
+        ]))),
     }
 }
 
@@ -54,7 +72,7 @@ pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response, Erro
 mod tests {
     use super::*;
     use crate::{
-        domain::entities::{PokemonName, PokemonTypes},
+        domain::entities::{BaseStats, PokemonName, PokemonTypes},
         repositories::pokemon::InMemoryRepository,
     };
 
@@ -66,7 +84,7 @@ mod tests {
         let res = execute(repo, req);
 
         match res {
-            Err(Error::Unknown) => {}
+            Err(Error::Unknown(_)) => {}
             _ => unreachable!(),
         };
     }
@@ -79,7 +97,7 @@ mod tests {
         let res = execute(repo, req);
 
         match res {
-            Err(Error::BadRequest) => {}
+            Err(Error::BadRequest(_)) => {}
             _ => unreachable!(),
         };
     }
@@ -102,8 +120,10 @@ mod tests {
         let repo = Arc::new(InMemoryRepository::new());
         repo.insert(
             PokemonNumber::pikachu(),
+            PokemonForm::default(),
             PokemonName::pikachu(),
             PokemonTypes::pikachu(),
+            Some(BaseStats::pikachu()),
         )
         .ok();
         let req = Request::new(PokemonNumber::pikachu());
@@ -113,8 +133,43 @@ mod tests {
         match res {
             Ok(res) => {
                 assert_eq!(res.number, u16::from(PokemonNumber::pikachu()));
+                assert_eq!(res.form, None);
                 assert_eq!(res.name, String::from(PokemonName::pikachu()));
                 assert_eq!(res.types, Vec::<String>::from(PokemonTypes::pikachu()));
+                assert_eq!(res.stats, Some(Stats::from(BaseStats::pikachu())));
+                assert_eq!(res.total, Some(320));
+            }
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_the_requested_form() {
+        let repo = Arc::new(InMemoryRepository::new());
+        for (form, types) in [
+            (PokemonForm::default(), PokemonTypes::pikachu()),
+            (PokemonForm::alola(), PokemonTypes::alolan_raichu()),
+        ] {
+            repo.insert(
+                PokemonNumber::raichu(),
+                form,
+                PokemonName::raichu(),
+                types,
+                None,
+            )
+            .ok();
+        }
+        let req = Request {
+            form: Some(String::from("alola")),
+            ..Request::new(PokemonNumber::raichu())
+        };
+
+        let res = execute(repo, req);
+
+        match res {
+            Ok(res) => {
+                assert_eq!(res.form, Some(String::from("alola")));
+                assert_eq!(res.types, vec!["Electric", "Psychic"]);
             }
             _ => unreachable!(),
         };
@@ -0,0 +1,210 @@
+use std::sync::Arc;
+
+use super::entities::{
+    validation_errors, AbilityName, PokemonForm, PokemonNumber, ValidationError,
+};
+use crate::repositories::{
+    ability::{self, AbilityRepository},
+    pokemon::{FetchOneError, Repository, RepositoryError},
+};
+
+pub struct Request {
+    pub number: u16,
+    /// Only checked to exist, every form sharing the abilities of its species.
+    pub form: Option<String>,
+}
+
+pub struct Response {
+    pub abilities: Vec<PokemonAbility>,
+}
+
+pub struct PokemonAbility {
+    pub name: String,
+    pub description: String,
+    pub hidden: bool,
+}
+
+pub enum Error {
+    BadRequest(Vec<ValidationError>),
+    NotFound,
+    Unknown(RepositoryError),
+}
+
+/// Lists the regular abilities of the Pokemon first, then its hidden one.
+pub fn execute(
+    pokemons: Arc<dyn Repository>,
+    abilities: Arc<dyn AbilityRepository>,
+    req: Request,
+) -> Result<Response, Error> {
+    let (number, form) = match (
+        PokemonNumber::try_from(req.number),
+        PokemonForm::try_from(req.form.unwrap_or_default()).map_err(ValidationError::from),
+    ) {
+        (Ok(number), Ok(form)) => (number, form),
+        (number, form) => {
+            return Err(Error::BadRequest(validation_errors([
+                number.err(),
+                form.err(),
+            ])))
+        }
+    };
+    match pokemons.fetch_one(number.clone(), form) {
+        Ok(_) => {}
+        Err(FetchOneError::NotFound) => return Err(Error::NotFound),
+        Err(FetchOneError::Unknown(e)) => return Err(Error::Unknown(e)),
+    }
+    let names = match abilities.fetch_pokemon_abilities(number) {
+        Ok(names) => names,
+        Err(e) => return Err(Error::Unknown(e)),
+    };
+    let slots = names
+        .regular()
+        .iter()
+        .map(|name| (name, false))
+        .chain(names.hidden().map(|name| (name, true)));
+    let mut res = Response { abilities: vec![] };
+    for (name, hidden) in slots {
+        match describe(abilities.clone(), name.clone()) {
+            Ok(description) => res.abilities.push(PokemonAbility {
+                name: String::from(name.clone()),
+                description,
+                hidden,
+            }),
+            Err(e) => return Err(Error::Unknown(e)),
+        }
+    }
+    Ok(res)
+}
+
+fn describe(
+    abilities: Arc<dyn AbilityRepository>,
+    name: AbilityName,
+) -> Result<String, RepositoryError> {
+    match abilities.fetch_one(name.clone()) {
+        Ok(ability) => Ok(ability.description),
+        Err(ability::FetchOneError::NotFound) => Err(RepositoryError::new(format!(
+            "ability {:?} vanished while being read",
+            String::from(name)
+        ))),
+        Err(ability::FetchOneError::Unknown(e)) => Err(e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        domain::entities::{PokemonAbilities, PokemonName, PokemonTypes},
+        repositories::{ability::InMemoryAbilityRepository, pokemon::InMemoryRepository},
+    };
+
+    fn repos() -> (Arc<InMemoryRepository>, Arc<InMemoryAbilityRepository>) {
+        let pokemons = Arc::new(InMemoryRepository::new());
+        pokemons
+            .insert(
+                PokemonNumber::pikachu(),
+                PokemonForm::default(),
+                PokemonName::pikachu(),
+                PokemonTypes::pikachu(),
+                None,
+            )
+            .ok();
+        let abilities = Arc::new(InMemoryAbilityRepository::new());
+        abilities
+            .insert(AbilityName::r#static(), String::from("May paralyze."))
+            .ok();
+        abilities
+            .insert(
+                AbilityName::lightning_rod(),
+                String::from("Draws in Electric moves."),
+            )
+            .ok();
+        (pokemons, abilities)
+    }
+
+    #[test]
+    fn it_should_return_a_not_found_error_when_the_pokemon_does_not_exist() {
+        let (_, abilities) = repos();
+
+        let res = execute(
+            Arc::new(InMemoryRepository::new()),
+            abilities,
+            Request {
+                number: 25,
+                form: None,
+            },
+        );
+
+        match res {
+            Err(Error::NotFound) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_not_found_error_when_the_form_does_not_exist() {
+        let (pokemons, abilities) = repos();
+
+        let res = execute(
+            pokemons,
+            abilities,
+            Request {
+                number: 25,
+                form: Some(String::from("alola")),
+            },
+        );
+
+        match res {
+            Err(Error::NotFound) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let (pokemons, _) = repos();
+
+        let res = execute(
+            pokemons,
+            Arc::new(InMemoryAbilityRepository::new().with_error()),
+            Request {
+                number: 25,
+                form: None,
+            },
+        );
+
+        match res {
+            Err(Error::Unknown(_)) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_the_described_abilities_otherwise() {
+        let (pokemons, abilities) = repos();
+        abilities
+            .set_pokemon_abilities(PokemonNumber::pikachu(), PokemonAbilities::pikachu())
+            .ok();
+
+        let res = execute(
+            pokemons,
+            abilities,
+            Request {
+                number: 25,
+                form: None,
+            },
+        );
+
+        match res {
+            Ok(res) => {
+                assert_eq!(res.abilities.len(), 2);
+                assert_eq!(res.abilities[0].name, "Static");
+                assert_eq!(res.abilities[0].description, "May paralyze.");
+                assert!(!res.abilities[0].hidden);
+                assert_eq!(res.abilities[1].name, "Lightning Rod");
+                assert!(res.abilities[1].hidden);
+            }
+            _ => unreachable!(),
+        };
+    }
+}
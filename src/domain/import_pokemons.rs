@@ -0,0 +1,459 @@
+use std::{collections::HashSet, result::Result, sync::Arc};
+
+use super::{entities::*, Stats};
+use crate::repositories::pokemon::{
+    FetchOneError, InsertError, Repository, RepositoryError, UpdateError,
+};
+
+pub struct Row {
+    pub number: u16,
+    /// The default form when none is given.
+    pub form: Option<String>,
+    pub name: String,
+    pub types: Vec<String>,
+    pub stats: Option<Stats>,
+}
+
+/// What to do with a row whose number and form already exist in the repository.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Policy {
+    Skip,
+    Overwrite,
+    Fail,
+}
+
+pub struct Request {
+    pub rows: Vec<Row>,
+    pub policy: Policy,
+}
+
+pub struct Response {
+    pub created: Vec<PokemonKey>,
+    pub overwritten: Vec<PokemonKey>,
+    pub skipped: Vec<PokemonKey>,
+}
+
+/// A pokemon by its number and form, the default one being `None`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PokemonKey {
+    pub number: u16,
+    pub form: Option<String>,
+}
+
+impl From<&Pokemon> for PokemonKey {
+    fn from(pokemon: &Pokemon) -> Self {
+        Self {
+            number: u16::from(pokemon.number.clone()),
+            form: Option::<String>::from(pokemon.form.clone()),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Reason {
+    Invalid(ValidationError),
+    Duplicate,
+    Conflict,
+}
+
+/// A row that could not be imported, `row` being its 1-based position in the batch.
+#[derive(Debug, PartialEq)]
+pub struct RowError {
+    pub row: usize,
+    pub number: u16,
+    pub reasons: Vec<Reason>,
+}
+
+pub enum Error {
+    BadRequest(Vec<RowError>),
+    Conflict(Vec<RowError>),
+    Unknown(RepositoryError),
+}
+
+/// Validates the whole batch before writing anything, so an invalid row
+/// never leaves the repository half imported. With `Policy::Fail`, existing
+/// numbers are also checked upfront.
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response, Error> {
+    let pokemons = match validate(req.rows) {
+        Ok(pokemons) => pokemons,
+        Err(errors) => return Err(Error::BadRequest(errors)),
+    };
+
+    if req.policy == Policy::Fail {
+        match find_conflicts(repo.clone(), &pokemons) {
+            Ok(conflicts) if conflicts.is_empty() => {}
+            Ok(conflicts) => return Err(Error::Conflict(conflicts)),
+            Err(e) => return Err(Error::Unknown(e)),
+        }
+    }
+
+    let mut res = Response {
+        created: vec![],
+        overwritten: vec![],
+        skipped: vec![],
+    };
+    for (row, pokemon) in pokemons.into_iter().enumerate() {
+        let key = PokemonKey::from(&pokemon);
+        match repo.insert(
+            pokemon.number.clone(),
+            pokemon.form.clone(),
+            pokemon.name.clone(),
+            pokemon.types.clone(),
+            pokemon.stats,
+        ) {
+            Ok(_) => res.created.push(key),
+            Err(InsertError::Conflict) => match req.policy {
+                Policy::Skip => res.skipped.push(key),
+                Policy::Overwrite => {
+                    match repo.update(
+                        pokemon.number,
+                        pokemon.form,
+                        pokemon.name,
+                        pokemon.types,
+                        pokemon.stats,
+                    ) {
+                        Ok(_) => res.overwritten.push(key),
+                        Err(UpdateError::NotFound) => {
+                            return Err(Error::Unknown(RepositoryError::new(format!(
+                                "pokemon {} vanished while being overwritten",
+                                key.number
+                            ))))
+                        }
+                        Err(UpdateError::Unknown(e)) => return Err(Error::Unknown(e)),
+                    }
+                }
+                Policy::Fail => {
+                    return Err(Error::Conflict(vec![RowError {
+                        row: row + 1,
+                        number: key.number,
+                        reasons: vec![Reason::Conflict],
+                    }]))
+                }
+            },
+            Err(InsertError::Unknown(e)) => return Err(Error::Unknown(e)),
+        }
+    }
+    Ok(res)
+}
+
+fn validate(rows: Vec<Row>) -> Result<Vec<Pokemon>, Vec<RowError>> {
+    let mut pokemons = vec![];
+    let mut errors = vec![];
+    let mut seen = HashSet::new();
+    for (
+        row,
+        Row {
+            number,
+            form,
+            name,
+            types,
+            stats,
+        },
+    ) in rows.into_iter().enumerate()
+    {
+        let mut reasons = vec![];
+        let form = form.unwrap_or_default();
+        if !seen.insert((number, form.clone())) {
+            reasons.push(Reason::Duplicate);
+        }
+        match (
+            PokemonNumber::try_from(number),
+            PokemonForm::try_from(form).map_err(ValidationError::from),
+            PokemonName::try_from(name),
+            PokemonTypes::try_from(types),
+            stats
+                .map(BaseStats::try_from)
+                .transpose()
+                .map_err(ValidationError::from),
+        ) {
+            (Ok(n), Ok(form), Ok(name), Ok(types), Ok(stats)) if reasons.is_empty() => {
+                pokemons.push(Pokemon::new(n, form, name, types, stats));
+                continue;
+            }
+            (n, form, name, types, stats) => reasons.extend(
+                validation_errors([n.err(), form.err(), name.err(), types.err(), stats.err()])
+                    .into_iter()
+                    .map(Reason::Invalid),
+            ),
+        }
+        errors.push(RowError {
+            row: row + 1,
+            number,
+            reasons,
+        });
+    }
+    if errors.is_empty() {
+        Ok(pokemons)
+    } else {
+        Err(errors)
+    }
+}
+
+fn find_conflicts(
+    repo: Arc<dyn Repository>,
+    pokemons: &[Pokemon],
+) -> Result<Vec<RowError>, RepositoryError> {
+    let mut conflicts = vec![];
+    for (row, pokemon) in pokemons.iter().enumerate() {
+        match repo.fetch_one(pokemon.number.clone(), pokemon.form.clone()) {
+            Ok(_) => conflicts.push(RowError {
+                row: row + 1,
+                number: u16::from(pokemon.number.clone()),
+                reasons: vec![Reason::Conflict],
+            }),
+            Err(FetchOneError::NotFound) => {}
+            Err(FetchOneError::Unknown(e)) => return Err(e),
+        }
+    }
+    Ok(conflicts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repositories::pokemon::InMemoryRepository;
+
+    impl Row {
+        fn new(number: PokemonNumber, name: PokemonName, types: PokemonTypes) -> Self {
+            Self {
+                number: u16::from(number),
+                form: None,
+                name: String::from(name),
+                types: Vec::<String>::from(types),
+                stats: None,
+            }
+        }
+    }
+
+    fn rows() -> Vec<Row> {
+        vec![
+            Row::new(
+                PokemonNumber::pikachu(),
+                PokemonName::pikachu(),
+                PokemonTypes::pikachu(),
+            ),
+            Row::new(
+                PokemonNumber::charmander(),
+                PokemonName::charmander(),
+                PokemonTypes::charmander(),
+            ),
+        ]
+    }
+
+    fn repo_with_pikachu() -> Arc<InMemoryRepository> {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonForm::default(),
+            PokemonName::charizard(),
+            PokemonTypes::charizard(),
+            None,
+        )
+        .ok();
+        repo
+    }
+
+    #[test]
+    fn it_should_report_every_invalid_row_and_import_nothing() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let mut rows = rows();
+        rows.push(Row {
+            number: 0,
+            form: None,
+            name: String::from(""),
+            types: vec![String::from("Shadow")],
+            stats: Some(Stats {
+                hp: 0,
+                ..Stats::from(BaseStats::pikachu())
+            }),
+        });
+        rows.push(Row::new(
+            PokemonNumber::pikachu(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+        ));
+        let req = Request {
+            rows,
+            policy: Policy::Skip,
+        };
+
+        let res = execute(repo.clone(), req);
+
+        match res {
+            Err(Error::BadRequest(errors)) => assert_eq!(
+                errors,
+                vec![
+                    RowError {
+                        row: 3,
+                        number: 0,
+                        reasons: vec![
+                            Reason::Invalid(ValidationError::NumberOutOfRange(0)),
+                            Reason::Invalid(ValidationError::EmptyName),
+                            Reason::Invalid(ValidationError::UnknownType(String::from("Shadow"))),
+                            Reason::Invalid(ValidationError::Stats(StatsError::OutOfRange(
+                                Stat::Hp,
+                                0
+                            ))),
+                        ],
+                    },
+                    RowError {
+                        row: 4,
+                        number: 25,
+                        reasons: vec![Reason::Duplicate],
+                    },
+                ]
+            ),
+            _ => unreachable!(),
+        };
+        match repo.fetch_one(PokemonNumber::charmander(), PokemonForm::default()) {
+            Err(FetchOneError::NotFound) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_skip_existing_pokemons_with_the_skip_policy() {
+        let repo = repo_with_pikachu();
+        let req = Request {
+            rows: rows(),
+            policy: Policy::Skip,
+        };
+
+        let res = execute(repo.clone(), req);
+
+        match res {
+            Ok(res) => {
+                assert_eq!(
+                    res.created,
+                    vec![PokemonKey {
+                        number: 4,
+                        form: None
+                    }]
+                );
+                assert_eq!(
+                    res.skipped,
+                    vec![PokemonKey {
+                        number: 25,
+                        form: None
+                    }]
+                );
+                assert!(res.overwritten.is_empty());
+            }
+            _ => unreachable!(),
+        };
+        match repo.fetch_one(PokemonNumber::pikachu(), PokemonForm::default()) {
+            Ok(pokemon) => assert!(pokemon.name == PokemonName::charizard()),
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_replace_existing_pokemons_with_the_overwrite_policy() {
+        let repo = repo_with_pikachu();
+        let req = Request {
+            rows: rows(),
+            policy: Policy::Overwrite,
+        };
+
+        let res = execute(repo.clone(), req);
+
+        match res {
+            Ok(res) => {
+                assert_eq!(
+                    res.created,
+                    vec![PokemonKey {
+                        number: 4,
+                        form: None
+                    }]
+                );
+                assert_eq!(
+                    res.overwritten,
+                    vec![PokemonKey {
+                        number: 25,
+                        form: None
+                    }]
+                );
+            }
+            _ => unreachable!(),
+        };
+        match repo.fetch_one(PokemonNumber::pikachu(), PokemonForm::default()) {
+            Ok(pokemon) => assert!(pokemon.name == PokemonName::pikachu()),
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_import_nothing_on_conflict_with_the_fail_policy() {
+        let repo = repo_with_pikachu();
+        let req = Request {
+            rows: rows(),
+            policy: Policy::Fail,
+        };
+
+        let res = execute(repo.clone(), req);
+
+        match res {
+            Err(Error::Conflict(errors)) => assert_eq!(
+                errors,
+                vec![RowError {
+                    row: 1,
+                    number: 25,
+                    reasons: vec![Reason::Conflict],
+                }]
+            ),
+            _ => unreachable!(),
+        };
+        match repo.fetch_one(PokemonNumber::charmander(), PokemonForm::default()) {
+            Err(FetchOneError::NotFound) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let repo = Arc::new(InMemoryRepository::new().with_error());
+        let req = Request {
+            rows: rows(),
+            policy: Policy::Skip,
+        };
+
+        let res = execute(repo, req);
+
+        match res {
+            Err(Error::Unknown(_)) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_import_another_form_of_an_existing_number() {
+        let repo = repo_with_pikachu();
+        let req = Request {
+            rows: vec![Row {
+                form: Some(String::from("alola")),
+                ..Row::new(
+                    PokemonNumber::pikachu(),
+                    PokemonName::pikachu(),
+                    PokemonTypes::alolan_raichu(),
+                )
+            }],
+            policy: Policy::Fail,
+        };
+
+        let res = execute(repo.clone(), req);
+
+        match res {
+            Ok(res) => assert_eq!(
+                res.created,
+                vec![PokemonKey {
+                    number: 25,
+                    form: Some(String::from("alola")),
+                }]
+            ),
+            _ => unreachable!(),
+        };
+        assert!(repo
+            .fetch_one(PokemonNumber::pikachu(), PokemonForm::alola())
+            .is_ok());
+    }
+}
@@ -0,0 +1,415 @@
+use std::{collections::HashSet, sync::Arc};
+
+use super::{
+    entities::*,
+    export_pokemons,
+    import_pokemons::{PokemonKey, Policy},
+};
+use crate::repositories::pokemon::{
+    FetchOneError, InsertError, Repository, RepositoryError, UpdateError,
+};
+
+pub struct Request {
+    pub policy: Policy,
+    /// Computes the report without writing anything to the target.
+    pub dry_run: bool,
+}
+
+/// What happened, or would happen on a dry run, to each pokemon number and form.
+#[derive(Default)]
+pub struct Response {
+    pub created: Vec<PokemonKey>,
+    pub overwritten: Vec<PokemonKey>,
+    pub skipped: Vec<PokemonKey>,
+    pub unchanged: Vec<PokemonKey>,
+    /// Pokemons of the target that the source does not have, left untouched.
+    pub only_in_target: Vec<PokemonKey>,
+}
+
+pub enum Error {
+    /// Pokemons differing between both repositories, with `Policy::Fail`.
+    Conflict(Vec<PokemonKey>),
+    Source(RepositoryError),
+    Target(RepositoryError),
+}
+
+enum Outcome {
+    Created,
+    Overwritten,
+    Skipped,
+    Unchanged,
+}
+
+/// Copies every pokemon of `source` into `target`. With `Policy::Fail`, the
+/// conflicts are all looked for before the first write. Only the pokemons
+/// are copied, not the abilities, moves, learnsets or evolutions linked to
+/// them.
+pub fn execute(
+    source: Arc<dyn Repository>,
+    target: Arc<dyn Repository>,
+    req: Request,
+) -> Result<Response, Error> {
+    if req.policy == Policy::Fail {
+        let mut conflicts = vec![];
+        copy(source.clone(), target.clone(), |pokemon, existing| {
+            match existing {
+                Some(existing) if !same(pokemon, existing) => {
+                    conflicts.push(PokemonKey::from(pokemon))
+                }
+                _ => {}
+            };
+            Ok(Outcome::Unchanged)
+        })?;
+        if !conflicts.is_empty() {
+            return Err(Error::Conflict(conflicts));
+        }
+    }
+
+    let mut res = Response::default();
+    let copied = copy(source, target.clone(), |pokemon, existing| {
+        let outcome = match existing {
+            None => Outcome::Created,
+            Some(existing) if same(pokemon, existing) => Outcome::Unchanged,
+            Some(_) if req.policy == Policy::Skip => Outcome::Skipped,
+            Some(_) => Outcome::Overwritten,
+        };
+        if req.dry_run {
+            return Ok(outcome);
+        }
+        let written = match outcome {
+            Outcome::Created => match target.insert(
+                pokemon.number.clone(),
+                pokemon.form.clone(),
+                pokemon.name.clone(),
+                pokemon.types.clone(),
+                pokemon.stats,
+            ) {
+                Ok(_) => Ok(()),
+                Err(InsertError::Conflict) => Err(RepositoryError::new(format!(
+                    "pokemon {} appeared in the target while being copied",
+                    u16::from(pokemon.number.clone())
+                ))),
+                Err(InsertError::Unknown(e)) => Err(e),
+            },
+            Outcome::Overwritten => match target.update(
+                pokemon.number.clone(),
+                pokemon.form.clone(),
+                pokemon.name.clone(),
+                pokemon.types.clone(),
+                pokemon.stats,
+            ) {
+                Ok(_) => Ok(()),
+                Err(UpdateError::NotFound) => Err(RepositoryError::new(format!(
+                    "pokemon {} vanished from the target while being copied",
+                    u16::from(pokemon.number.clone())
+                ))),
+                Err(UpdateError::Unknown(e)) => Err(e),
+            },
+            Outcome::Skipped | Outcome::Unchanged => Ok(()),
+        };
+        written.map(|_| outcome)
+    })?;
+    let mut in_source = HashSet::new();
+    for (key, outcome) in copied {
+        in_source.insert((key.number, key.form.clone()));
+        match outcome {
+            Outcome::Created => res.created.push(key),
+            Outcome::Overwritten => res.overwritten.push(key),
+            Outcome::Skipped => res.skipped.push(key),
+            Outcome::Unchanged => res.unchanged.push(key),
+        }
+    }
+
+    match export_pokemons::execute(target, &mut |pokemon| {
+        if !in_source.contains(&(pokemon.number, pokemon.form.clone())) {
+            res.only_in_target.push(PokemonKey {
+                number: pokemon.number,
+                form: pokemon.form,
+            });
+        }
+        Ok(())
+    }) {
+        Ok(_) => Ok(res),
+        Err(export_pokemons::Error::Unknown(e)) => Err(Error::Target(e)),
+        Err(export_pokemons::Error::Sink) => unreachable!("the sink never fails"),
+    }
+}
+
+/// Walks the source, handing each pokemon and its current version in the
+/// target, if any, to `step`. Returns the outcome of each number and form.
+fn copy<F>(
+    source: Arc<dyn Repository>,
+    target: Arc<dyn Repository>,
+    mut step: F,
+) -> Result<Vec<(PokemonKey, Outcome)>, Error>
+where
+    F: FnMut(&Pokemon, Option<&Pokemon>) -> Result<Outcome, RepositoryError>,
+{
+    let mut outcomes = vec![];
+    // The sink can only say it failed, so the target error waits here.
+    let mut target_error = None;
+    let res = export_pokemons::for_each(source, &mut |pokemon| {
+        let existing = match target.fetch_one(pokemon.number.clone(), pokemon.form.clone()) {
+            Ok(existing) => Some(existing),
+            Err(FetchOneError::NotFound) => None,
+            Err(FetchOneError::Unknown(e)) => {
+                target_error = Some(e);
+                return Err(());
+            }
+        };
+        match step(&pokemon, existing.as_ref()) {
+            Ok(outcome) => outcomes.push((PokemonKey::from(&pokemon), outcome)),
+            Err(e) => {
+                target_error = Some(e);
+                return Err(());
+            }
+        }
+        Ok(())
+    });
+    match (res, target_error) {
+        (Ok(_), _) => Ok(outcomes),
+        (Err(export_pokemons::Error::Sink), Some(e)) => Err(Error::Target(e)),
+        (Err(export_pokemons::Error::Sink), None) => unreachable!("the sink fails with an error"),
+        (Err(export_pokemons::Error::Unknown(e)), _) => Err(Error::Source(e)),
+    }
+}
+
+fn same(a: &Pokemon, b: &Pokemon) -> bool {
+    a.name == b.name
+        && Vec::<String>::from(a.types.clone()) == Vec::<String>::from(b.types.clone())
+        && a.stats == b.stats
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repositories::pokemon::InMemoryRepository;
+
+    fn repos() -> (Arc<InMemoryRepository>, Arc<InMemoryRepository>) {
+        let source = Arc::new(InMemoryRepository::new());
+        let target = Arc::new(InMemoryRepository::new());
+        source
+            .insert(
+                PokemonNumber::charmander(),
+                PokemonForm::default(),
+                PokemonName::charmander(),
+                PokemonTypes::charmander(),
+                None,
+            )
+            .ok();
+        source
+            .insert(
+                PokemonNumber::charizard(),
+                PokemonForm::default(),
+                PokemonName::charizard(),
+                PokemonTypes::charizard(),
+                None,
+            )
+            .ok();
+        source
+            .insert(
+                PokemonNumber::pikachu(),
+                PokemonForm::default(),
+                PokemonName::pikachu(),
+                PokemonTypes::pikachu(),
+                None,
+            )
+            .ok();
+        target
+            .insert(
+                PokemonNumber::charmander(),
+                PokemonForm::default(),
+                PokemonName::charmander(),
+                PokemonTypes::charmander(),
+                None,
+            )
+            .ok();
+        target
+            .insert(
+                PokemonNumber::charizard(),
+                PokemonForm::default(),
+                PokemonName::pikachu(),
+                PokemonTypes::pikachu(),
+                None,
+            )
+            .ok();
+        target
+            .insert(
+                PokemonNumber::try_from(150).ok().unwrap(),
+                PokemonForm::default(),
+                PokemonName::charizard(),
+                PokemonTypes::charizard(),
+                None,
+            )
+            .ok();
+        (source, target)
+    }
+
+    #[test]
+    fn it_should_copy_and_report_the_differences() {
+        let (source, target) = repos();
+        let req = Request {
+            policy: Policy::Overwrite,
+            dry_run: false,
+        };
+
+        let res = execute(source, target.clone(), req);
+
+        match res {
+            Ok(res) => {
+                assert_eq!(
+                    res.created,
+                    vec![PokemonKey {
+                        number: 25,
+                        form: None
+                    }]
+                );
+                assert_eq!(
+                    res.overwritten,
+                    vec![PokemonKey {
+                        number: 6,
+                        form: None
+                    }]
+                );
+                assert_eq!(
+                    res.unchanged,
+                    vec![PokemonKey {
+                        number: 4,
+                        form: None
+                    }]
+                );
+                assert!(res.skipped.is_empty());
+                assert_eq!(
+                    res.only_in_target,
+                    vec![PokemonKey {
+                        number: 150,
+                        form: None
+                    }]
+                );
+            }
+            _ => unreachable!(),
+        };
+        match target.fetch_one(PokemonNumber::charizard(), PokemonForm::default()) {
+            Ok(pokemon) => assert!(pokemon.name == PokemonName::charizard()),
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_not_write_anything_on_a_dry_run() {
+        let (source, target) = repos();
+        let req = Request {
+            policy: Policy::Skip,
+            dry_run: true,
+        };
+
+        let res = execute(source, target.clone(), req);
+
+        match res {
+            Ok(res) => {
+                assert_eq!(
+                    res.created,
+                    vec![PokemonKey {
+                        number: 25,
+                        form: None
+                    }]
+                );
+                assert_eq!(
+                    res.skipped,
+                    vec![PokemonKey {
+                        number: 6,
+                        form: None
+                    }]
+                );
+            }
+            _ => unreachable!(),
+        };
+        match target.fetch_one(PokemonNumber::pikachu(), PokemonForm::default()) {
+            Err(FetchOneError::NotFound) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_copy_nothing_when_a_pokemon_differs_with_the_fail_policy() {
+        let (source, target) = repos();
+        let req = Request {
+            policy: Policy::Fail,
+            dry_run: false,
+        };
+
+        let res = execute(source, target.clone(), req);
+
+        match res {
+            Err(Error::Conflict(numbers)) => assert_eq!(
+                numbers,
+                vec![PokemonKey {
+                    number: 6,
+                    form: None
+                }]
+            ),
+            _ => unreachable!(),
+        };
+        match target.fetch_one(PokemonNumber::pikachu(), PokemonForm::default()) {
+            Err(FetchOneError::NotFound) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_source_error_when_the_source_fails() {
+        let (_, target) = repos();
+        let req = Request {
+            policy: Policy::Skip,
+            dry_run: false,
+        };
+
+        let res = execute(
+            Arc::new(InMemoryRepository::new().with_error()),
+            target,
+            req,
+        );
+
+        match res {
+            Err(Error::Source(_)) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_report_the_form_of_each_pokemon() {
+        let (source, target) = repos();
+        source
+            .insert(
+                PokemonNumber::pikachu(),
+                PokemonForm::alola(),
+                PokemonName::pikachu(),
+                PokemonTypes::alolan_raichu(),
+                None,
+            )
+            .ok();
+        let req = Request {
+            policy: Policy::Skip,
+            dry_run: true,
+        };
+
+        let res = execute(source, target, req);
+
+        match res {
+            Ok(res) => assert_eq!(
+                res.created,
+                vec![
+                    PokemonKey {
+                        number: 25,
+                        form: None,
+                    },
+                    PokemonKey {
+                        number: 25,
+                        form: Some(String::from("alola")),
+                    },
+                ]
+            ),
+            _ => unreachable!(),
+        };
+    }
+}
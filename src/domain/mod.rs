@@ -1,5 +1,69 @@
+pub mod add_evolution;
+pub mod create_ability;
+pub mod create_move;
 pub mod create_pokemon;
+pub mod delete_ability;
+pub mod delete_evolution;
 pub mod delete_pokemon;
 pub mod entities;
+pub mod export_pokemons;
+pub mod fetch_ability;
+pub mod fetch_ability_holders;
+pub mod fetch_all_abilities;
+pub mod fetch_all_moves;
 pub mod fetch_all_pokemons;
+pub mod fetch_evolution_chain;
+pub mod fetch_learnset;
+pub mod fetch_move;
+pub mod fetch_move_learners;
 pub mod fetch_pokemon;
+pub mod fetch_pokemon_abilities;
+pub mod import_pokemons;
+pub mod migrate_data;
+pub mod set_learnset;
+pub mod set_pokemon_abilities;
+pub mod type_matchups;
+pub mod update_ability;
+pub mod update_pokemon;
+
+use entities::{BaseStats, StatsError};
+
+/// Base stats as exchanged with the adapters.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Stats {
+    pub hp: u16,
+    pub attack: u16,
+    pub defense: u16,
+    pub special_attack: u16,
+    pub special_defense: u16,
+    pub speed: u16,
+}
+
+impl TryFrom<Stats> for BaseStats {
+    type Error = StatsError;
+
+    fn try_from(s: Stats) -> Result<Self, Self::Error> {
+        BaseStats::try_from([
+            s.hp,
+            s.attack,
+            s.defense,
+            s.special_attack,
+            s.special_defense,
+            s.speed,
+        ])
+    }
+}
+
+impl From<BaseStats> for Stats {
+    fn from(stats: BaseStats) -> Self {
+        let [hp, attack, defense, special_attack, special_defense, speed] = <[u16; 6]>::from(stats);
+        Self {
+            hp,
+            attack,
+            defense,
+            special_attack,
+            special_defense,
+            speed,
+        }
+    }
+}
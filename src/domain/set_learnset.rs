@@ -0,0 +1,238 @@
+use std::sync::Arc;
+
+use super::entities::{
+    Learnset, LearnsetEntry, LearnsetError, PokemonForm, PokemonNumber, ValidationError,
+};
+use crate::repositories::{
+    moves::{MoveRepository, SetLearnsetError},
+    pokemon::{FetchOneError, Repository, RepositoryError},
+};
+
+pub struct Request {
+    pub number: u16,
+    /// Refused unless empty, every form sharing the learnset of its species.
+    pub form: Option<String>,
+    pub moves: Vec<Entry>,
+}
+
+/// A move of the learnset, the level being given for level-up moves only.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Entry {
+    pub name: String,
+    pub method: String,
+    pub level: Option<u16>,
+}
+
+pub struct Response {
+    pub number: u16,
+    /// The learnset as stored, level-up moves first by ascending level.
+    pub moves: Vec<Entry>,
+}
+
+pub enum Error {
+    BadRequest(Vec<ValidationError>),
+    NotFound,
+    Unknown(RepositoryError),
+}
+
+/// Replaces the whole learnset of the Pokemon, an empty request clearing it.
+pub fn execute(
+    pokemons: Arc<dyn Repository>,
+    moves: Arc<dyn MoveRepository>,
+    req: Request,
+) -> Result<Response, Error> {
+    let mut errors = vec![];
+    let number = match PokemonNumber::try_from(req.number) {
+        Ok(number) => Some(number),
+        Err(e) => {
+            errors.push(e);
+            None
+        }
+    };
+    let form = match PokemonForm::try_species(req.form.unwrap_or_default()) {
+        Ok(form) => Some(form),
+        Err(e) => {
+            errors.push(e.into());
+            None
+        }
+    };
+    let mut entries = vec![];
+    for entry in req.moves {
+        match LearnsetEntry::try_new(entry.name, entry.method, entry.level) {
+            Ok(entry) => entries.push(entry),
+            Err(e) => errors.push(e),
+        }
+    }
+    let learnset = match Learnset::try_from(entries) {
+        Ok(learnset) => Some(learnset),
+        Err(e) => {
+            errors.push(e.into());
+            None
+        }
+    };
+    let (number, form, learnset) = match (number, form, learnset) {
+        (Some(number), Some(form), Some(learnset)) if errors.is_empty() => (number, form, learnset),
+        _ => return Err(Error::BadRequest(errors)),
+    };
+    match pokemons.fetch_one(number.clone(), form) {
+        Ok(_) => {}
+        Err(FetchOneError::NotFound) => return Err(Error::NotFound),
+        Err(FetchOneError::Unknown(e)) => return Err(Error::Unknown(e)),
+    }
+    match moves.set_learnset(number, learnset.clone()) {
+        Ok(()) => Ok(Response {
+            number: req.number,
+            moves: learnset
+                .entries()
+                .iter()
+                .map(|e| Entry {
+                    name: String::from(e.move_name.clone()),
+                    method: String::from(e.method.name()),
+                    level: e.method.level(),
+                })
+                .collect(),
+        }),
+        Err(SetLearnsetError::UnknownMove(name)) => {
+            Err(Error::BadRequest(vec![LearnsetError::UnknownMove(
+                String::from(name),
+            )
+            .into()]))
+        }
+        Err(SetLearnsetError::Unknown(e)) => Err(Error::Unknown(e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        domain::entities::{Move, PokemonName, PokemonTypes},
+        repositories::{moves::InMemoryMoveRepository, pokemon::InMemoryRepository},
+    };
+
+    impl Entry {
+        fn new(name: &str, method: &str, level: Option<u16>) -> Self {
+            Self {
+                name: String::from(name),
+                method: String::from(method),
+                level,
+            }
+        }
+    }
+
+    fn repos() -> (Arc<InMemoryRepository>, Arc<InMemoryMoveRepository>) {
+        let pokemons = Arc::new(InMemoryRepository::new());
+        pokemons
+            .insert(
+                PokemonNumber::pikachu(),
+                PokemonForm::default(),
+                PokemonName::pikachu(),
+                PokemonTypes::pikachu(),
+                None,
+            )
+            .ok();
+        let moves = Arc::new(InMemoryMoveRepository::new());
+        for r#move in [Move::growl(), Move::thunder_shock(), Move::thunderbolt()] {
+            moves.insert(r#move).ok();
+        }
+        (pokemons, moves)
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_request_is_invalid() {
+        let (pokemons, moves) = repos();
+        let req = Request {
+            number: 0,
+            form: None,
+            moves: vec![
+                Entry::new("Growl", "level-up", None),
+                Entry::new("Thunderbolt", "hm", None),
+            ],
+        };
+
+        let res = execute(pokemons, moves, req);
+
+        match res {
+            Err(Error::BadRequest(errors)) => assert_eq!(
+                errors,
+                vec![
+                    ValidationError::NumberOutOfRange(0),
+                    ValidationError::Learnset(LearnsetError::MissingLevel),
+                    ValidationError::Learnset(LearnsetError::UnknownMethod(String::from("hm")))
+                ]
+            ),
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_a_move_does_not_exist() {
+        let (pokemons, moves) = repos();
+        let req = Request {
+            number: 25,
+            form: None,
+            moves: vec![Entry::new("Surf", "tm", None)],
+        };
+
+        let res = execute(pokemons, moves, req);
+
+        match res {
+            Err(Error::BadRequest(errors)) => assert_eq!(
+                errors,
+                vec![ValidationError::Learnset(LearnsetError::UnknownMove(
+                    String::from("Surf")
+                ))]
+            ),
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_not_found_error_when_the_pokemon_does_not_exist() {
+        let (_, moves) = repos();
+        let req = Request {
+            number: 25,
+            form: None,
+            moves: vec![],
+        };
+
+        let res = execute(Arc::new(InMemoryRepository::new()), moves, req);
+
+        match res {
+            Err(Error::NotFound) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_store_the_sorted_learnset_otherwise() {
+        let (pokemons, moves) = repos();
+        let req = Request {
+            number: 25,
+            form: None,
+            moves: vec![
+                Entry::new("Thunderbolt", "tm", None),
+                Entry::new("Thunder Shock", "level-up", Some(1)),
+                Entry::new("Growl", "level-up", Some(1)),
+            ],
+        };
+
+        let res = execute(pokemons, moves.clone(), req);
+
+        match res {
+            Ok(res) => assert_eq!(
+                res.moves,
+                vec![
+                    Entry::new("Growl", "level-up", Some(1)),
+                    Entry::new("Thunder Shock", "level-up", Some(1)),
+                    Entry::new("Thunderbolt", "tm", None),
+                ]
+            ),
+            _ => unreachable!(),
+        };
+        match moves.fetch_learnset(PokemonNumber::pikachu()) {
+            Ok(learnset) => assert_eq!(learnset, Learnset::pikachu()),
+            _ => unreachable!(),
+        };
+    }
+}
@@ -0,0 +1,203 @@
+use std::sync::Arc;
+
+use super::entities::{
+    validation_errors, AbilityError, PokemonAbilities, PokemonForm, PokemonNumber, ValidationError,
+};
+use crate::repositories::{
+    ability::{AbilityRepository, SetAbilitiesError},
+    pokemon::{FetchOneError, Repository, RepositoryError},
+};
+
+pub struct Request {
+    pub number: u16,
+    /// Refused unless empty, every form sharing the abilities of its species.
+    pub form: Option<String>,
+    /// The regular abilities, at most two.
+    pub abilities: Vec<String>,
+    pub hidden: Option<String>,
+}
+
+pub struct Response {
+    pub number: u16,
+    pub abilities: Vec<String>,
+    pub hidden: Option<String>,
+}
+
+pub enum Error {
+    BadRequest(Vec<ValidationError>),
+    NotFound,
+    Unknown(RepositoryError),
+}
+
+/// Replaces every ability of the Pokemon, an empty request removing them all.
+pub fn execute(
+    pokemons: Arc<dyn Repository>,
+    abilities: Arc<dyn AbilityRepository>,
+    req: Request,
+) -> Result<Response, Error> {
+    let (number, form, names) = match (
+        PokemonNumber::try_from(req.number),
+        PokemonForm::try_species(req.form.unwrap_or_default()).map_err(ValidationError::from),
+        PokemonAbilities::try_new(req.abilities, req.hidden).map_err(ValidationError::from),
+    ) {
+        (Ok(number), Ok(form), Ok(names)) => (number, form, names),
+        (number, form, names) => {
+            return Err(Error::BadRequest(validation_errors([
+                number.err(),
+                form.err(),
+                names.err(),
+            ])))
+        }
+    };
+    match pokemons.fetch_one(number.clone(), form) {
+        Ok(_) => {}
+        Err(FetchOneError::NotFound) => return Err(Error::NotFound),
+        Err(FetchOneError::Unknown(e)) => return Err(Error::Unknown(e)),
+    }
+    match abilities.set_pokemon_abilities(number, names.clone()) {
+        Ok(()) => Ok(Response {
+            number: req.number,
+            abilities: names.regular().iter().cloned().map(String::from).collect(),
+            hidden: names.hidden().cloned().map(String::from),
+        }),
+        Err(SetAbilitiesError::UnknownAbility(name)) => {
+            Err(Error::BadRequest(vec![AbilityError::Unknown(
+                String::from(name),
+            )
+            .into()]))
+        }
+        Err(SetAbilitiesError::Unknown(e)) => Err(Error::Unknown(e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        domain::entities::{AbilityName, FormError, PokemonName, PokemonTypes},
+        repositories::{ability::InMemoryAbilityRepository, pokemon::InMemoryRepository},
+    };
+
+    impl Request {
+        fn new(number: u16, abilities: &[&str], hidden: Option<&str>) -> Self {
+            Self {
+                number,
+                form: None,
+                abilities: abilities.iter().map(|a| String::from(*a)).collect(),
+                hidden: hidden.map(String::from),
+            }
+        }
+    }
+
+    fn repos() -> (Arc<InMemoryRepository>, Arc<InMemoryAbilityRepository>) {
+        let pokemons = Arc::new(InMemoryRepository::new());
+        pokemons
+            .insert(
+                PokemonNumber::pikachu(),
+                PokemonForm::default(),
+                PokemonName::pikachu(),
+                PokemonTypes::pikachu(),
+                None,
+            )
+            .ok();
+        let abilities = Arc::new(InMemoryAbilityRepository::new());
+        abilities
+            .insert(AbilityName::r#static(), String::new())
+            .ok();
+        abilities
+            .insert(AbilityName::lightning_rod(), String::new())
+            .ok();
+        (pokemons, abilities)
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_request_is_invalid() {
+        let (pokemons, abilities) = repos();
+        let req = Request::new(0, &["Static", "Plus", "Minus"], None);
+
+        let res = execute(pokemons, abilities, req);
+
+        match res {
+            Err(Error::BadRequest(errors)) => assert_eq!(
+                errors,
+                vec![
+                    ValidationError::NumberOutOfRange(0),
+                    ValidationError::Ability(AbilityError::TooMany(3))
+                ]
+            ),
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_an_ability_does_not_exist() {
+        let (pokemons, abilities) = repos();
+        let req = Request::new(25, &["Static", "Plus"], None);
+
+        let res = execute(pokemons, abilities, req);
+
+        match res {
+            Err(Error::BadRequest(errors)) => assert_eq!(
+                errors,
+                vec![ValidationError::Ability(AbilityError::Unknown(
+                    String::from("Plus")
+                ))]
+            ),
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_a_form_is_given() {
+        let (pokemons, abilities) = repos();
+        let req = Request {
+            form: Some(String::from("alola")),
+            ..Request::new(25, &["Static"], None)
+        };
+
+        let res = execute(pokemons, abilities, req);
+
+        match res {
+            Err(Error::BadRequest(errors)) => assert_eq!(
+                errors,
+                vec![ValidationError::Form(FormError::SharedBySpecies(
+                    String::from("alola")
+                ))]
+            ),
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_not_found_error_when_the_pokemon_does_not_exist() {
+        let (_, abilities) = repos();
+        let req = Request::new(25, &["Static"], None);
+
+        let res = execute(Arc::new(InMemoryRepository::new()), abilities, req);
+
+        match res {
+            Err(Error::NotFound) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_store_the_abilities_otherwise() {
+        let (pokemons, abilities) = repos();
+        let req = Request::new(25, &["Static"], Some("Lightning Rod"));
+
+        let res = execute(pokemons, abilities.clone(), req);
+
+        match res {
+            Ok(res) => {
+                assert_eq!(res.abilities, vec!["Static"]);
+                assert_eq!(res.hidden.as_deref(), Some("Lightning Rod"));
+            }
+            _ => unreachable!(),
+        };
+        match abilities.fetch_pokemon_abilities(PokemonNumber::pikachu()) {
+            Ok(stored) => assert_eq!(stored, PokemonAbilities::pikachu()),
+            _ => unreachable!(),
+        };
+    }
+}
@@ -0,0 +1,196 @@
+use std::sync::Arc;
+
+use super::entities::{
+    validation_errors, Pokemon, PokemonForm, PokemonNumber, PokemonType, PokemonTypes,
+    ValidationError,
+};
+use crate::repositories::pokemon::{FetchOneError, Repository, RepositoryError};
+
+pub struct Request {
+    pub number: u16,
+    /// The default form when none is given.
+    pub form: Option<String>,
+}
+
+pub struct Response {
+    pub number: u16,
+    /// None for the default form.
+    pub form: Option<String>,
+    pub quadruple: Vec<String>,
+    pub double: Vec<String>,
+    pub neutral: Vec<String>,
+    pub half: Vec<String>,
+    pub quarter: Vec<String>,
+    pub immune: Vec<String>,
+}
+
+pub enum Error {
+    BadRequest(Vec<ValidationError>),
+    NotFound,
+    Unknown(RepositoryError),
+}
+
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response, Error> {
+    match (
+        PokemonNumber::try_from(req.number),
+        PokemonForm::try_from(req.form.unwrap_or_default()).map_err(ValidationError::from),
+    ) {
+        (Ok(number), Ok(form)) => match repo.fetch_one(number, form) {
+            Ok(Pokemon {
+                number,
+                form,
+                types,
+                ..
+            }) => Ok(matchups(u16::from(number), form.into(), &types)),
+            Err(FetchOneError::NotFound) => Err(Error::NotFound),
+            Err(FetchOneError::Unknown(e)) => Err(Error::Unknown(e)),
+        },
+        (number, form) => Err(Error::BadRequest(validation_errors([
+            number.err(),
+            form.err(),
+        ]))),
+    }
+}
+
+fn matchups(number: u16, form: Option<String>, types: &PokemonTypes) -> Response {
+    let mut res = Response {
+        number,
+        form,
+        quadruple: vec![],
+        double: vec![],
+        neutral: vec![],
+        half: vec![],
+        quarter: vec![],
+        immune: vec![],
+    };
+    for attacker in PokemonType::ALL {
+        let group = match attacker.effectiveness_against(types) {
+            m if m >= 4.0 => &mut res.quadruple,
+            m if m >= 2.0 => &mut res.double,
+            m if m >= 1.0 => &mut res.neutral,
+            m if m >= 0.5 => &mut res.half,
+            m if m > 0.0 => &mut res.quarter,
+            _ => &mut res.immune,
+        };
+        group.push(String::from(attacker));
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{domain::entities::PokemonName, repositories::pokemon::InMemoryRepository};
+
+    impl Request {
+        fn new(number: PokemonNumber) -> Self {
+            Self {
+                number: u16::from(number),
+                form: None,
+            }
+        }
+    }
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let repo = Arc::new(InMemoryRepository::new().with_error());
+        let req = Request::new(PokemonNumber::pikachu());
+
+        let res = execute(repo, req);
+
+        match res {
+            Err(Error::Unknown(_)) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_request_is_invalid() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = Request::new(PokemonNumber::bad());
+
+        let res = execute(repo, req);
+
+        match res {
+            Err(Error::BadRequest(_)) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_not_found_error_when_the_repo_does_not_contain_the_pokemon() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = Request::new(PokemonNumber::pikachu());
+
+        let res = execute(repo, req);
+
+        match res {
+            Err(Error::NotFound) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_the_matchups_grouped_by_multiplier_otherwise() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::charizard(),
+            PokemonForm::default(),
+            PokemonName::charizard(),
+            PokemonTypes::charizard(),
+            None,
+        )
+        .ok();
+        let req = Request::new(PokemonNumber::charizard());
+
+        let res = execute(repo, req);
+
+        match res {
+            Ok(res) => {
+                assert_eq!(res.number, u16::from(PokemonNumber::charizard()));
+                assert_eq!(res.quadruple, vec!["Rock"]);
+                assert_eq!(res.double, vec!["Water", "Electric"]);
+                assert_eq!(
+                    res.neutral,
+                    vec!["Normal", "Ice", "Poison", "Flying", "Psychic", "Ghost", "Dragon", "Dark"]
+                );
+                assert_eq!(res.half, vec!["Fire", "Fighting", "Steel", "Fairy"]);
+                assert_eq!(res.quarter, vec!["Grass", "Bug"]);
+                assert_eq!(res.immune, vec!["Ground"]);
+            }
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_use_the_types_of_the_requested_form() {
+        let repo = Arc::new(InMemoryRepository::new());
+        for (form, types) in [
+            (PokemonForm::default(), PokemonTypes::pikachu()),
+            (PokemonForm::alola(), PokemonTypes::alolan_raichu()),
+        ] {
+            repo.insert(
+                PokemonNumber::raichu(),
+                form,
+                PokemonName::raichu(),
+                types,
+                None,
+            )
+            .ok();
+        }
+        let req = Request {
+            form: Some(String::from("alola")),
+            ..Request::new(PokemonNumber::raichu())
+        };
+
+        let res = execute(repo, req);
+
+        match res {
+            Ok(res) => {
+                assert_eq!(res.form, Some(String::from("alola")));
+                assert_eq!(res.double, vec!["Ground", "Bug", "Ghost", "Dark"]);
+            }
+            _ => unreachable!(),
+        };
+    }
+}
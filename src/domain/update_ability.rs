@@ -0,0 +1,81 @@
+use std::sync::Arc;
+
+use super::entities::{Ability, AbilityName, ValidationError};
+use crate::repositories::ability::{AbilityRepository, RepositoryError, UpdateError};
+
+pub struct Request {
+    pub name: String,
+    pub description: String,
+}
+
+pub struct Response {
+    pub name: String,
+    pub description: String,
+}
+
+pub enum Error {
+    BadRequest(Vec<ValidationError>),
+    NotFound,
+    Unknown(RepositoryError),
+}
+
+pub fn execute(repo: Arc<dyn AbilityRepository>, req: Request) -> Result<Response, Error> {
+    match AbilityName::try_from(req.name) {
+        Ok(name) => match repo.update(name, req.description) {
+            Ok(Ability { name, description }) => Ok(Response {
+                name: String::from(name),
+                description,
+            }),
+            Err(UpdateError::NotFound) => Err(Error::NotFound),
+            Err(UpdateError::Unknown(e)) => Err(Error::Unknown(e)),
+        },
+        Err(e) => Err(Error::BadRequest(vec![e.into()])),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repositories::ability::InMemoryAbilityRepository;
+
+    impl Request {
+        fn new(name: AbilityName, description: &str) -> Self {
+            Self {
+                name: String::from(name),
+                description: String::from(description),
+            }
+        }
+    }
+
+    #[test]
+    fn it_should_return_a_not_found_error_when_the_repo_does_not_contain_the_ability() {
+        let repo = Arc::new(InMemoryAbilityRepository::new());
+
+        let res = execute(repo, Request::new(AbilityName::blaze(), ""));
+
+        match res {
+            Err(Error::NotFound) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_replace_the_description_otherwise() {
+        let repo = Arc::new(InMemoryAbilityRepository::new());
+        repo.insert(AbilityName::blaze(), String::new()).ok();
+
+        let res = execute(
+            repo.clone(),
+            Request::new(AbilityName::blaze(), "Powers up Fire moves."),
+        );
+
+        match res {
+            Ok(res) => assert_eq!(res.description, "Powers up Fire moves."),
+            _ => unreachable!(),
+        };
+        match repo.fetch_one(AbilityName::blaze()) {
+            Ok(ability) => assert_eq!(ability.description, "Powers up Fire moves."),
+            _ => unreachable!(),
+        };
+    }
+}
@@ -0,0 +1,289 @@
+use std::sync::Arc;
+
+use super::{entities::*, Stats};
+use crate::repositories::pokemon::{FetchOneError, Repository, RepositoryError, UpdateError};
+
+pub struct Request {
+    pub number: u16,
+    /// The default form when none is given.
+    pub form: Option<String>,
+    pub name: Option<String>,
+    pub types: Option<Vec<String>>,
+    pub stats: Option<Stats>,
+}
+
+pub struct Response {
+    pub number: u16,
+    /// None for the default form.
+    pub form: Option<String>,
+    pub name: String,
+    pub types: Vec<String>,
+    pub stats: Option<Stats>,
+    pub total: Option<u16>,
+}
+
+pub enum Error {
+    BadRequest(Vec<ValidationError>),
+    NotFound,
+    Unknown(RepositoryError),
+}
+
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response, Error> {
+    let (number, form, name, types, stats) = match (
+        PokemonNumber::try_from(req.number),
+        PokemonForm::try_from(req.form.unwrap_or_default()).map_err(ValidationError::from),
+        req.name.map(PokemonName::try_from).transpose(),
+        req.types.map(PokemonTypes::try_from).transpose(),
+        req.stats
+            .map(BaseStats::try_from)
+            .transpose()
+            .map_err(ValidationError::from),
+    ) {
+        (Ok(number), Ok(form), Ok(name), Ok(types), Ok(stats)) => {
+            (number, form, name, types, stats)
+        }
+        (number, form, name, types, stats) => {
+            return Err(Error::BadRequest(validation_errors([
+                number.err(),
+                form.err(),
+                name.err(),
+                types.err(),
+                stats.err(),
+            ])))
+        }
+    };
+
+    // Stats are kept when not given, as a Pokemon cannot lose them.
+    let (name, types, stats) = match (name, types, stats) {
+        (Some(name), Some(types), Some(stats)) => (name, types, Some(stats)),
+        (name, types, stats) => match repo.fetch_one(number.clone(), form.clone()) {
+            Ok(pokemon) => (
+                name.unwrap_or(pokemon.name),
+                types.unwrap_or(pokemon.types),
+                stats.or(pokemon.stats),
+            ),
+            Err(FetchOneError::NotFound) => return Err(Error::NotFound),
+            Err(FetchOneError::Unknown(e)) => return Err(Error::Unknown(e)),
+        },
+    };
+
+    match repo.update(number, form, name, types, stats) {
+        Ok(Pokemon {
+            number,
+            form,
+            name,
+            types,
+            stats,
+        }) => Ok(Response {
+            number: u16::from(number),
+            form: form.into(),
+            name: String::from(name),
+            types: Vec::<String>::from(types),
+            stats: stats.map(Stats::from),
+            total: stats.map(|stats| stats.total()),
+        }),
+        Err(UpdateError::NotFound) => Err(Error::NotFound),
+        Err(UpdateError::Unknown(e)) => Err(Error::Unknown(e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repositories::pokemon::InMemoryRepository;
+
+    impl Request {
+        fn new(
+            number: PokemonNumber,
+            name: Option<PokemonName>,
+            types: Option<PokemonTypes>,
+        ) -> Self {
+            Self {
+                number: u16::from(number),
+                form: None,
+                name: name.map(String::from),
+                types: types.map(Vec::<String>::from),
+                stats: None,
+            }
+        }
+    }
+
+    fn repo_with_pikachu() -> Arc<InMemoryRepository> {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonForm::default(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+            Some(BaseStats::pikachu()),
+        )
+        .ok();
+        repo
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_request_is_invalid() {
+        let repo = repo_with_pikachu();
+        let req = Request::new(
+            PokemonNumber::pikachu(),
+            Some(PokemonName::bad()),
+            Some(PokemonTypes::pikachu()),
+        );
+
+        let res = execute(repo, req);
+
+        match res {
+            Err(Error::BadRequest(_)) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_not_found_error_when_the_repo_does_not_contain_the_pokemon() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = Request::new(
+            PokemonNumber::pikachu(),
+            Some(PokemonName::pikachu()),
+            Some(PokemonTypes::pikachu()),
+        );
+
+        let res = execute(repo, req);
+
+        match res {
+            Err(Error::NotFound) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_not_found_error_when_patching_a_missing_pokemon() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = Request::new(PokemonNumber::pikachu(), Some(PokemonName::pikachu()), None);
+
+        let res = execute(repo, req);
+
+        match res {
+            Err(Error::NotFound) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let repo = Arc::new(InMemoryRepository::new().with_error());
+        let req = Request::new(
+            PokemonNumber::pikachu(),
+            Some(PokemonName::pikachu()),
+            Some(PokemonTypes::pikachu()),
+        );
+
+        let res = execute(repo, req);
+
+        match res {
+            Err(Error::Unknown(_)) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_replace_the_pokemon_otherwise() {
+        let repo = repo_with_pikachu();
+        let req = Request::new(
+            PokemonNumber::pikachu(),
+            Some(PokemonName::charmander()),
+            Some(PokemonTypes::charmander()),
+        );
+
+        let res = execute(repo.clone(), req);
+
+        match res {
+            Ok(res) => {
+                assert_eq!(res.number, u16::from(PokemonNumber::pikachu()));
+                assert_eq!(res.name, String::from(PokemonName::charmander()));
+                assert_eq!(res.types, Vec::<String>::from(PokemonTypes::charmander()));
+            }
+            _ => unreachable!(),
+        };
+        match repo.fetch_one(PokemonNumber::pikachu(), PokemonForm::default()) {
+            Ok(pokemon) => assert_eq!(
+                String::from(pokemon.name),
+                String::from(PokemonName::charmander())
+            ),
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_only_patch_the_given_fields() {
+        let repo = repo_with_pikachu();
+        let req = Request::new(
+            PokemonNumber::pikachu(),
+            Some(PokemonName::charmander()),
+            None,
+        );
+
+        let res = execute(repo, req);
+
+        match res {
+            Ok(res) => {
+                assert_eq!(res.number, u16::from(PokemonNumber::pikachu()));
+                assert_eq!(res.name, String::from(PokemonName::charmander()));
+                assert_eq!(res.types, Vec::<String>::from(PokemonTypes::pikachu()));
+                assert_eq!(res.stats, Some(Stats::from(BaseStats::pikachu())));
+            }
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_replace_the_stats_when_given() {
+        let repo = repo_with_pikachu();
+        let mut req = Request::new(PokemonNumber::pikachu(), None, None);
+        req.stats = Some(Stats::from(BaseStats::charizard()));
+
+        let res = execute(repo, req);
+
+        match res {
+            Ok(res) => {
+                assert_eq!(res.stats, Some(Stats::from(BaseStats::charizard())));
+                assert_eq!(res.total, Some(534));
+            }
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_only_patch_the_requested_form() {
+        let repo = repo_with_pikachu();
+        repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonForm::alola(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+            None,
+        )
+        .ok();
+        let req = Request {
+            form: Some(String::from("alola")),
+            ..Request::new(
+                PokemonNumber::pikachu(),
+                None,
+                Some(PokemonTypes::alolan_raichu()),
+            )
+        };
+
+        let res = execute(repo.clone(), req);
+
+        match res {
+            Ok(res) => {
+                assert_eq!(res.form, Some(String::from("alola")));
+                assert_eq!(res.types, vec!["Electric", "Psychic"]);
+            }
+            _ => unreachable!(),
+        };
+        match repo.fetch_one(PokemonNumber::pikachu(), PokemonForm::default()) {
+            Ok(pokemon) => assert_eq!(Vec::<String>::from(pokemon.types), vec!["Electric"]),
+            _ => unreachable!(),
+        };
+    }
+}
@@ -1,44 +1,428 @@
 mod api;
 mod cli;
+mod config;
 mod domain;
+mod log;
 mod repositories;
-use std::sync::Arc;
+use std::{env, process, sync::Arc};
 
-use clap::{Args, Parser};
-use repositories::pokemon::{AirtableRepository, InMemoryRepository, Repository, SqliteRepository};
+use clap::{Parser, Subcommand};
+use config::{AirtableSection, Config, Mode, RepoSpec, RepositoryKind};
+use repositories::{
+    ability::{AbilityRepository, InMemoryAbilityRepository, SqliteAbilityRepository},
+    evolution::{
+        AirtableEvolutionRepository, EvolutionRepository, InMemoryEvolutionRepository,
+        SqliteEvolutionRepository,
+    },
+    moves::{InMemoryMoveRepository, MoveRepository, SqliteMoveRepository},
+    pokemon::{AirtableRepository, FakeAirtable, InMemoryRepository, Repository, SqliteRepository},
+};
 
 #[derive(Parser, Debug)]
 struct Opt {
     #[clap(long, help = "Runs in CLI mode")]
     cli: bool,
+    #[clap(long, value_name = "FILE", help = "Reads settings from a TOML file")]
+    config: Option<String>,
+    #[clap(
+        long,
+        value_name = "KIND",
+        help = "Repository to use: memory, sqlite, airtable or fake-airtable"
+    )]
+    repository: Option<RepositoryKind>,
     #[clap(long, name = "PATH", help = "Where the database file exists")]
     sqlite: Option<String>,
-    #[clap(long, value_names = &["API_KEY", "WORKSPACE_ID"], help = "Use airtable as repository")]
+    #[clap(long, value_names = &["API_KEY", "BASE_ID"], help = "Use airtable as repository")]
     airtable: Vec<String>,
+    #[clap(
+        long,
+        value_name = "TABLE",
+        help = "Airtable table holding the pokemons"
+    )]
+    airtable_table: Option<String>,
+    #[clap(long, value_name = "URL", help = "Root URL of the airtable API")]
+    airtable_url: Option<String>,
+    #[clap(
+        long,
+        value_name = "FIELD=COLUMN",
+        help = "Airtable column of a pokemon field (number, form, name, types, a stat or total)"
+    )]
+    airtable_field: Vec<String>,
+    #[clap(
+        long,
+        help = "Use airtable through an in-memory fake of its API, for development"
+    )]
+    fake_airtable: bool,
+    #[clap(long, help = "Address the API listens on [default: localhost]")]
+    bind: Option<String>,
+    #[clap(long, help = "Port the API listens on [default: 8000]")]
+    port: Option<u16>,
+    #[clap(long, help = "Threads handling API requests [default: 8 per CPU]")]
+    workers: Option<usize>,
+    #[clap(
+        long,
+        value_name = "LEVEL",
+        help = "error, warn, info or debug [default: info]"
+    )]
+    log_level: Option<log::Level>,
+    #[clap(
+        long,
+        arg_enum,
+        default_value = "table",
+        help = "How the CLI prints pokemons"
+    )]
+    output: cli::Format,
+    #[clap(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    #[clap(flatten)]
+    Cli(cli::Command),
+    #[clap(about = "Applies the pending migrations of the sqlite database")]
+    Migrate {
+        #[clap(long, help = "Only lists the migrations and whether they are applied")]
+        status: bool,
+    },
+    #[clap(
+        about = "Copies every pokemon from one repository to another, without their abilities, learnsets and evolutions"
+    )]
+    MigrateData {
+        #[clap(
+            long,
+            value_name = "REPO",
+            help = "memory, sqlite:PATH or airtable[:API_KEY:BASE_ID]"
+        )]
+        from: RepoSpec,
+        #[clap(
+            long,
+            value_name = "REPO",
+            help = "memory, sqlite:PATH or airtable[:API_KEY:BASE_ID]"
+        )]
+        to: RepoSpec,
+        #[clap(long, arg_enum, default_value = "fail")]
+        on_conflict: cli::OnConflict,
+        #[clap(long, help = "Only reports what would be copied")]
+        dry_run: bool,
+    },
+    #[clap(about = "Looks for types left without their pokemon in the sqlite database")]
+    Check {
+        #[clap(long, help = "Removes the orphaned types found")]
+        repair: bool,
+    },
 }
 
 fn main() {
-    let opt = Opt::parse();
-    let repo = build_repo(opt.sqlite, opt.airtable);
+    let mut opt = Opt::parse();
+    let config = match load_config(&opt) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("Error while reading the configuration: {}", e);
+            process::exit(2);
+        }
+    };
+    log::set_level(config.log_level());
+    let command = match opt.command.take() {
+        Some(Command::Cli(command)) => Some(command),
+        Some(Command::MigrateData {
+            from,
+            to,
+            on_conflict,
+            dry_run,
+        }) => {
+            let section = config.airtable.unwrap_or_default();
+            let (source, target) = match (open_repo(from, &section), open_repo(to, &section)) {
+                (Ok(source), Ok(target)) => (source, target),
+                (Err(e), _) | (_, Err(e)) => {
+                    eprintln!("{}", e);
+                    process::exit(2);
+                }
+            };
+            process::exit(i32::from(cli::migrate_data(
+                source,
+                target,
+                opt.output,
+                on_conflict,
+                dry_run,
+            )));
+        }
+        Some(maintenance) => {
+            let path = match config.sqlite_path() {
+                Some(path) => path,
+                None => {
+                    eprintln!("This command needs a --sqlite database");
+                    process::exit(2);
+                }
+            };
+            match maintenance {
+                Command::Migrate { status } => migrate(path, status),
+                Command::Check { repair } => check(path, repair),
+                Command::Cli(_) | Command::MigrateData { .. } => unreachable!(),
+            }
+            return;
+        }
+        None => None,
+    };
+    let kind = match config.repository() {
+        Ok(kind) => kind,
+        Err(e) => {
+            eprintln!("Error while reading the configuration: {}", e);
+            process::exit(2);
+        }
+    };
+    let fake_airtable = match kind {
+        RepositoryKind::FakeAirtable => match FakeAirtable::start() {
+            Ok(fake) => Some(fake),
+            Err(e) => {
+                eprintln!("Error while starting the fake airtable: {}", e);
+                process::exit(2);
+            }
+        },
+        _ => None,
+    };
+    if let (Some(fake), true) = (&fake_airtable, log::enabled(log::Level::Info)) {
+        eprintln!("Serving a fake airtable API on {}", fake.url());
+    }
+    let (repo, abilities, moves, evolutions) = build_repo(kind, &config, fake_airtable.as_ref());
+    if let Some(command) = command {
+        process::exit(i32::from(cli::execute(
+            repo, abilities, moves, evolutions, command, opt.output,
+        )));
+    }
+    match config.mode() {
+        Mode::Cli => cli::run(repo, abilities, moves, evolutions, opt.output),
+        Mode::Api => {
+            if let Err(e) = api::serve(&config.server(), repo, abilities, moves, evolutions) {
+                eprintln!("Error while starting the API: {}", e);
+                process::exit(1);
+            }
+        }
+    }
+}
+
+/// Layers the config file, the environment and the command line, each one
+/// overriding the previous.
+fn load_config(opt: &Opt) -> Result<Config, String> {
+    let mut config = match &opt.config {
+        Some(path) => Config::load(path)?,
+        None => Config::default(),
+    };
+    config.apply_env(env::vars())?;
+    apply_options(&mut config, opt)?;
+    Ok(config)
+}
+
+fn apply_options(config: &mut Config, opt: &Opt) -> Result<(), String> {
+    let mut chosen: Vec<(&str, RepositoryKind)> = vec![];
+    if let Some(kind) = opt.repository {
+        chosen.push(("--repository", kind));
+    }
+    if opt.sqlite.is_some() {
+        chosen.push(("--sqlite", RepositoryKind::Sqlite));
+    }
+    if !opt.airtable.is_empty() {
+        chosen.push(("--airtable", RepositoryKind::Airtable));
+    }
+    if opt.fake_airtable {
+        chosen.push(("--fake-airtable", RepositoryKind::FakeAirtable));
+    }
+    if let Some((option, kind)) = chosen.first() {
+        if let Some((other, _)) = chosen.iter().find(|(_, other)| other != kind) {
+            return Err(format!("{} and {} cannot be used together", option, other));
+        }
+        config.repository = Some(*kind);
+    }
+    if let Some(path) = &opt.sqlite {
+        config.sqlite_mut().path = Some(path.clone());
+    }
+    if let [api_key, base_id] = &opt.airtable[..] {
+        let section = config.airtable_mut();
+        section.api_key = Some(api_key.clone());
+        section.base_id = Some(base_id.clone());
+    }
+    if let Some(table) = &opt.airtable_table {
+        config.airtable_mut().table = Some(table.clone());
+    }
+    if let Some(url) = &opt.airtable_url {
+        config.airtable_mut().api_url = Some(url.clone());
+    }
+    for mapping in opt.airtable_field.iter() {
+        config.airtable_mut().set_field(mapping)?;
+    }
     if opt.cli {
-        cli::run(repo);
+        config.mode = Some(Mode::Cli);
+    }
+    if let Some(bind) = &opt.bind {
+        config.server.bind = Some(bind.clone());
+    }
+    if let Some(port) = opt.port {
+        config.server.port = Some(port);
+    }
+    if let Some(workers) = opt.workers {
+        config.server.workers = Some(workers);
+    }
+    if let Some(level) = opt.log_level {
+        config.log.level = Some(level);
+    }
+    Ok(())
+}
+
+fn migrate(path: &str, status_only: bool) {
+    if !status_only {
+        match SqliteRepository::migrate(path) {
+            Ok(applied) if applied.is_empty() => println!("The database is up to date"),
+            Ok(applied) => {
+                for migration in applied {
+                    println!(
+                        "Applied {:>4}  {}",
+                        migration.version, migration.description
+                    );
+                }
+            }
+            Err(e) => {
+                eprintln!("Error while migrating the sqlite database: {}", e.chain());
+                process::exit(1);
+            }
+        }
+        return;
+    }
+    match SqliteRepository::migration_status(path) {
+        Ok(migrations) => {
+            for migration in migrations {
+                println!(
+                    "{:>4}  {:<20}  {}",
+                    migration.version,
+                    migration.applied_at.as_deref().unwrap_or("pending"),
+                    migration.description
+                );
+            }
+        }
+        Err(e) => {
+            eprintln!("Error while reading the sqlite migrations: {}", e.chain());
+            process::exit(1);
+        }
+    }
+}
+
+fn check(path: &str, repair: bool) {
+    let repo = match SqliteRepository::try_new(path) {
+        Ok(repo) => repo,
+        Err(e) => panic!("Error while creating sqlite repo: {}", e.chain()),
+    };
+    let orphans = match repo.check_integrity() {
+        Ok(orphans) => orphans,
+        Err(e) => {
+            eprintln!("Error while checking the sqlite database: {}", e.chain());
+            process::exit(1);
+        }
+    };
+    for (number, r#type) in orphans.iter() {
+        println!("Orphaned type {} of missing pokemon {}", r#type, number);
+    }
+    if orphans.is_empty() {
+        println!("The database is consistent");
+    } else if repair {
+        match repo.repair_integrity() {
+            Ok(removed) => println!("Removed {} orphaned types", removed),
+            Err(e) => {
+                eprintln!("Error while repairing the sqlite database: {}", e.chain());
+                process::exit(1);
+            }
+        }
     } else {
-        api::serve("localhost:8000", repo);
+        process::exit(1);
     }
 }
 
-fn build_repo(sqlite_path: Option<String>, airtable_value: Vec<String>) -> Arc<dyn Repository> {
-    if let Some(sqlite_path) = sqlite_path {
-        match SqliteRepository::try_new(sqlite_path.as_str()) {
-            Ok(repo) => return Arc::new(repo),
-            _ => panic!("Error while creating sqlite repo"),
+/// The pokemons along with the repositories of what is linked to them.
+type Repositories = (
+    Arc<dyn Repository>,
+    Arc<dyn AbilityRepository>,
+    Arc<dyn MoveRepository>,
+    Arc<dyn EvolutionRepository>,
+);
+
+fn build_repo(kind: RepositoryKind, config: &Config, fake: Option<&FakeAirtable>) -> Repositories {
+    let mut section = config.airtable.clone().unwrap_or_default();
+    match kind {
+        RepositoryKind::Memory => (
+            Arc::new(InMemoryRepository::new()),
+            Arc::new(InMemoryAbilityRepository::new()),
+            Arc::new(InMemoryMoveRepository::new()),
+            Arc::new(InMemoryEvolutionRepository::new()),
+        ),
+        RepositoryKind::Sqlite => {
+            match SqliteRepository::try_new(config.sqlite_path().unwrap_or_default()) {
+                Ok(repo) => {
+                    let abilities = SqliteAbilityRepository::new(&repo);
+                    let moves = SqliteMoveRepository::new(&repo);
+                    let evolutions = SqliteEvolutionRepository::new(&repo);
+                    (
+                        Arc::new(repo),
+                        Arc::new(abilities),
+                        Arc::new(moves),
+                        Arc::new(evolutions),
+                    )
+                }
+                Err(e) => panic!("Error while creating sqlite repo: {}", e.chain()),
+            }
+        }
+        RepositoryKind::Airtable | RepositoryKind::FakeAirtable => {
+            if let Some(fake) = fake {
+                let fake = fake.config();
+                section.api_url = Some(fake.api_url);
+                section.api_key = Some(fake.api_key);
+                section.base_id = Some(fake.base_id);
+            }
+            let config = match section.into_config() {
+                Ok(config) => config,
+                Err(e) => panic!("Error while reading airtable options: {}", e),
+            };
+            if log::enabled(log::Level::Warn) {
+                eprintln!("Abilities and moves are kept in memory, airtable does not store them");
+            }
+            match AirtableRepository::try_new(config) {
+                Ok(repo) => {
+                    let repo = Arc::new(repo);
+                    let evolutions = AirtableEvolutionRepository::new(repo.clone());
+                    (
+                        repo,
+                        Arc::new(InMemoryAbilityRepository::new()),
+                        Arc::new(InMemoryMoveRepository::new()),
+                        Arc::new(evolutions),
+                    )
+                }
+                Err(e) => panic!("Error while creating airtable repo: {}", e.chain()),
+            }
         }
     }
-    if let [api_key, workspace_id] = &airtable_value[..] {
-        match AirtableRepository::try_new(api_key, workspace_id) {
-            Ok(repo) => return Arc::new(repo),
-            _ => panic!("Error while creating airtable repo"),
+}
+
+fn open_repo(spec: RepoSpec, airtable: &AirtableSection) -> Result<Arc<dyn Repository>, String> {
+    match spec {
+        RepoSpec::Memory => Ok(Arc::new(InMemoryRepository::new())),
+        RepoSpec::Sqlite(path) => match SqliteRepository::try_new(&path) {
+            Ok(repo) => Ok(Arc::new(repo)),
+            Err(e) => Err(format!(
+                "Error while opening the sqlite database: {}",
+                e.chain()
+            )),
+        },
+        RepoSpec::Airtable { api_key, base_id } => {
+            let mut section = airtable.clone();
+            if let (Some(api_key), Some(base_id)) = (api_key, base_id) {
+                section.api_key = Some(api_key);
+                section.base_id = Some(base_id);
+            }
+            let config = match section.into_config() {
+                Ok(config) => config,
+                Err(e) => return Err(format!("Error while reading airtable options: {}", e)),
+            };
+            match AirtableRepository::try_new(config) {
+                Ok(repo) => Ok(Arc::new(repo)),
+                Err(e) => Err(format!("Error while connecting to airtable: {}", e.chain())),
+            }
         }
     }
-    return Arc::new(InMemoryRepository::new());
 }
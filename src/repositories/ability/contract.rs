@@ -0,0 +1,204 @@
+//! Behaviour every `AbilityRepository` adapter must share, each check run as
+//! a test of every adapter with `contract_tests!`.
+//!
+//! Pikachu, Charmander and Charizard must exist for the adapters checking
+//! the pokemons they link abilities to.
+
+use std::sync::Arc;
+
+use crate::domain::entities::{AbilityName, PokemonAbilities, PokemonNumber};
+use crate::repositories::ability::{
+    AbilityRepository, DeleteError, FetchOneError, InsertError, SetAbilitiesError, UpdateError,
+};
+
+fn seed(repo: &Arc<dyn AbilityRepository>) {
+    for name in [
+        AbilityName::r#static(),
+        AbilityName::lightning_rod(),
+        AbilityName::blaze(),
+        AbilityName::solar_power(),
+    ] {
+        match repo.insert(name, String::from("Does something.")) {
+            Ok(_) => {}
+            _ => unreachable!(),
+        }
+    }
+}
+
+fn names(abilities: &PokemonAbilities) -> (Vec<String>, Option<String>) {
+    (
+        abilities
+            .regular()
+            .iter()
+            .cloned()
+            .map(String::from)
+            .collect(),
+        abilities.hidden().cloned().map(String::from),
+    )
+}
+
+pub fn it_should_return_the_inserted_ability(repo: Arc<dyn AbilityRepository>) {
+    let res = repo.insert(AbilityName::r#static(), String::from("May paralyze."));
+
+    match res {
+        Ok(ability) => {
+            assert_eq!(ability.name, AbilityName::r#static());
+            assert_eq!(ability.description, "May paralyze.");
+        }
+        _ => unreachable!(),
+    };
+}
+
+pub fn it_should_refuse_to_insert_an_existing_name(repo: Arc<dyn AbilityRepository>) {
+    seed(&repo);
+
+    let res = repo.insert(AbilityName::blaze(), String::new());
+
+    match res {
+        Err(InsertError::Conflict) => {}
+        _ => unreachable!(),
+    };
+}
+
+pub fn it_should_fetch_all_by_name(repo: Arc<dyn AbilityRepository>) {
+    seed(&repo);
+
+    let res = repo.fetch_all();
+
+    match res {
+        Ok(abilities) => assert_eq!(
+            abilities
+                .into_iter()
+                .map(|a| String::from(a.name))
+                .collect::<Vec<String>>(),
+            vec!["Blaze", "Lightning Rod", "Solar Power", "Static"]
+        ),
+        _ => unreachable!(),
+    };
+}
+
+pub fn it_should_update_the_description(repo: Arc<dyn AbilityRepository>) {
+    seed(&repo);
+
+    let res = repo.update(AbilityName::blaze(), String::from("Powers up Fire moves."));
+
+    assert!(res.is_ok());
+    match repo.fetch_one(AbilityName::blaze()) {
+        Ok(ability) => assert_eq!(ability.description, "Powers up Fire moves."),
+        _ => unreachable!(),
+    };
+}
+
+pub fn it_should_not_find_update_or_delete_a_missing_ability(repo: Arc<dyn AbilityRepository>) {
+    match repo.fetch_one(AbilityName::blaze()) {
+        Err(FetchOneError::NotFound) => {}
+        _ => unreachable!(),
+    };
+    match repo.update(AbilityName::blaze(), String::new()) {
+        Err(UpdateError::NotFound) => {}
+        _ => unreachable!(),
+    };
+    match repo.delete(AbilityName::blaze()) {
+        Err(DeleteError::NotFound) => {}
+        _ => unreachable!(),
+    };
+    match repo.fetch_holders(AbilityName::blaze()) {
+        Err(FetchOneError::NotFound) => {}
+        _ => unreachable!(),
+    };
+}
+
+pub fn it_should_return_no_abilities_for_a_pokemon_without_any(repo: Arc<dyn AbilityRepository>) {
+    seed(&repo);
+
+    let res = repo.fetch_pokemon_abilities(PokemonNumber::pikachu());
+
+    match res {
+        Ok(abilities) => assert!(abilities.is_empty()),
+        _ => unreachable!(),
+    };
+}
+
+pub fn it_should_replace_the_abilities_of_a_pokemon(repo: Arc<dyn AbilityRepository>) {
+    seed(&repo);
+    repo.set_pokemon_abilities(PokemonNumber::pikachu(), PokemonAbilities::charmander())
+        .ok();
+
+    let res = repo.set_pokemon_abilities(PokemonNumber::pikachu(), PokemonAbilities::pikachu());
+
+    assert!(res.is_ok());
+    match repo.fetch_pokemon_abilities(PokemonNumber::pikachu()) {
+        Ok(abilities) => assert_eq!(
+            names(&abilities),
+            (
+                vec![String::from("Static")],
+                Some(String::from("Lightning Rod"))
+            )
+        ),
+        _ => unreachable!(),
+    };
+}
+
+pub fn it_should_refuse_an_unknown_ability_and_keep_the_previous_ones(
+    repo: Arc<dyn AbilityRepository>,
+) {
+    seed(&repo);
+    repo.set_pokemon_abilities(PokemonNumber::pikachu(), PokemonAbilities::pikachu())
+        .ok();
+    let abilities =
+        PokemonAbilities::try_new(vec![String::from("Static"), String::from("Plus")], None)
+            .unwrap();
+
+    let res = repo.set_pokemon_abilities(PokemonNumber::pikachu(), abilities);
+
+    match res {
+        Err(SetAbilitiesError::UnknownAbility(name)) => {
+            assert_eq!(String::from(name), "Plus")
+        }
+        _ => unreachable!(),
+    };
+    match repo.fetch_pokemon_abilities(PokemonNumber::pikachu()) {
+        Ok(abilities) => assert_eq!(abilities, PokemonAbilities::pikachu()),
+        _ => unreachable!(),
+    };
+}
+
+pub fn it_should_find_the_pokemons_having_an_ability(repo: Arc<dyn AbilityRepository>) {
+    seed(&repo);
+    repo.set_pokemon_abilities(PokemonNumber::charizard(), PokemonAbilities::charmander())
+        .ok();
+    repo.set_pokemon_abilities(PokemonNumber::pikachu(), PokemonAbilities::pikachu())
+        .ok();
+    let hidden_blaze = PokemonAbilities::try_new(vec![], Some(String::from("Blaze")));
+    repo.set_pokemon_abilities(PokemonNumber::charmander(), hidden_blaze.unwrap())
+        .ok();
+
+    let res = repo.fetch_holders(AbilityName::blaze());
+
+    match res {
+        Ok(holders) => assert_eq!(
+            holders
+                .into_iter()
+                .map(|h| (u16::from(h.number), h.hidden))
+                .collect::<Vec<(u16, bool)>>(),
+            vec![(4, true), (6, false)]
+        ),
+        _ => unreachable!(),
+    };
+}
+
+pub fn it_should_remove_a_deleted_ability_from_the_pokemons(repo: Arc<dyn AbilityRepository>) {
+    seed(&repo);
+    repo.set_pokemon_abilities(PokemonNumber::pikachu(), PokemonAbilities::pikachu())
+        .ok();
+
+    assert!(repo.delete(AbilityName::lightning_rod()).is_ok());
+
+    match repo.fetch_pokemon_abilities(PokemonNumber::pikachu()) {
+        Ok(abilities) => {
+            assert_eq!(abilities.regular(), &[AbilityName::r#static()]);
+            assert_eq!(abilities.hidden(), None);
+        }
+        _ => unreachable!(),
+    };
+}
@@ -0,0 +1,196 @@
+use std::sync::{Mutex, MutexGuard};
+
+use crate::domain::entities::{Ability, AbilityName, PokemonAbilities, PokemonNumber};
+
+use super::{
+    AbilityHolder, AbilityRepository, DeleteError, FetchOneError, InsertError, RepositoryError,
+    SetAbilitiesError, UpdateError,
+};
+
+#[derive(Default)]
+struct State {
+    abilities: Vec<Ability>,
+    pokemons: Vec<(PokemonNumber, PokemonAbilities)>,
+}
+
+pub struct InMemoryAbilityRepository {
+    error: bool,
+    state: Mutex<State>,
+}
+
+impl InMemoryAbilityRepository {
+    pub fn new() -> Self {
+        Self {
+            error: false,
+            state: Mutex::new(State::default()),
+        }
+    }
+
+    #[cfg(test)]
+    pub fn with_error(self) -> Self {
+        Self {
+            error: true,
+            ..self
+        }
+    }
+
+    fn lock(&self) -> Result<MutexGuard<'_, State>, RepositoryError> {
+        if self.error {
+            return Err(RepositoryError::new("the repository is set to fail"));
+        }
+        match self.state.lock() {
+            Ok(lock) => Ok(lock),
+            Err(_) => Err(RepositoryError::new("the abilities lock is poisoned")),
+        }
+    }
+}
+
+impl AbilityRepository for InMemoryAbilityRepository {
+    fn insert(&self, name: AbilityName, description: String) -> Result<Ability, InsertError> {
+        let mut lock = match self.lock() {
+            Ok(lock) => lock,
+            Err(e) => return Err(InsertError::Unknown(e)),
+        };
+        if lock.abilities.iter().any(|a| a.name == name) {
+            return Err(InsertError::Conflict);
+        }
+        let ability = Ability::new(name, description);
+        lock.abilities.push(ability.clone());
+        Ok(ability)
+    }
+
+    fn fetch_all(&self) -> Result<Vec<Ability>, RepositoryError> {
+        let lock = self.lock()?;
+        let mut abilities = lock.abilities.clone();
+        abilities.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(abilities)
+    }
+
+    fn fetch_one(&self, name: AbilityName) -> Result<Ability, FetchOneError> {
+        let lock = match self.lock() {
+            Ok(lock) => lock,
+            Err(e) => return Err(FetchOneError::Unknown(e)),
+        };
+        match lock.abilities.iter().find(|a| a.name == name) {
+            Some(ability) => Ok(ability.clone()),
+            None => Err(FetchOneError::NotFound),
+        }
+    }
+
+    fn update(&self, name: AbilityName, description: String) -> Result<Ability, UpdateError> {
+        let mut lock = match self.lock() {
+            Ok(lock) => lock,
+            Err(e) => return Err(UpdateError::Unknown(e)),
+        };
+        let ability = match lock.abilities.iter_mut().find(|a| a.name == name) {
+            Some(ability) => ability,
+            None => return Err(UpdateError::NotFound),
+        };
+        *ability = Ability::new(name, description);
+        Ok(ability.clone())
+    }
+
+    fn delete(&self, name: AbilityName) -> Result<(), DeleteError> {
+        let mut lock = match self.lock() {
+            Ok(lock) => lock,
+            Err(e) => return Err(DeleteError::Unknown(e)),
+        };
+        let index = match lock.abilities.iter().position(|a| a.name == name) {
+            Some(index) => index,
+            None => return Err(DeleteError::NotFound),
+        };
+        lock.abilities.remove(index);
+        for (_, abilities) in lock.pokemons.iter_mut() {
+            *abilities = abilities.clone().without(&name);
+        }
+        Ok(())
+    }
+
+    fn fetch_pokemon_abilities(
+        &self,
+        number: PokemonNumber,
+    ) -> Result<PokemonAbilities, RepositoryError> {
+        let lock = self.lock()?;
+        Ok(lock
+            .pokemons
+            .iter()
+            .find(|(n, _)| *n == number)
+            .map(|(_, abilities)| abilities.clone())
+            .unwrap_or_default())
+    }
+
+    fn set_pokemon_abilities(
+        &self,
+        number: PokemonNumber,
+        abilities: PokemonAbilities,
+    ) -> Result<(), SetAbilitiesError> {
+        let mut lock = match self.lock() {
+            Ok(lock) => lock,
+            Err(e) => return Err(SetAbilitiesError::Unknown(e)),
+        };
+        let missing = abilities
+            .regular()
+            .iter()
+            .chain(abilities.hidden())
+            .find(|name| !lock.abilities.iter().any(|a| a.name == **name));
+        if let Some(name) = missing {
+            return Err(SetAbilitiesError::UnknownAbility(name.clone()));
+        }
+        lock.pokemons.retain(|(n, _)| *n != number);
+        lock.pokemons.push((number, abilities));
+        Ok(())
+    }
+
+    fn fetch_holders(&self, name: AbilityName) -> Result<Vec<AbilityHolder>, FetchOneError> {
+        let lock = match self.lock() {
+            Ok(lock) => lock,
+            Err(e) => return Err(FetchOneError::Unknown(e)),
+        };
+        if !lock.abilities.iter().any(|a| a.name == name) {
+            return Err(FetchOneError::NotFound);
+        }
+        let mut holders = lock
+            .pokemons
+            .iter()
+            .filter(|(_, abilities)| abilities.contains(&name))
+            .map(|(number, abilities)| AbilityHolder {
+                number: number.clone(),
+                hidden: abilities.hidden() == Some(&name),
+            })
+            .collect::<Vec<AbilityHolder>>();
+        holders.sort_by(|a, b| a.number.cmp(&b.number));
+        Ok(holders)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod contract {
+        use super::super::InMemoryAbilityRepository;
+        use crate::repositories::{
+            ability::{contract, AbilityRepository},
+            contract_tests,
+        };
+
+        fn factory() -> (std::sync::Arc<dyn AbilityRepository>, ()) {
+            (std::sync::Arc::new(InMemoryAbilityRepository::new()), ())
+        }
+
+        contract_tests!(
+            factory,
+            contract,
+            [
+                it_should_return_the_inserted_ability,
+                it_should_refuse_to_insert_an_existing_name,
+                it_should_fetch_all_by_name,
+                it_should_update_the_description,
+                it_should_not_find_update_or_delete_a_missing_ability,
+                it_should_return_no_abilities_for_a_pokemon_without_any,
+                it_should_replace_the_abilities_of_a_pokemon,
+                it_should_refuse_an_unknown_ability_and_keep_the_previous_ones,
+                it_should_find_the_pokemons_having_an_ability,
+                it_should_remove_a_deleted_ability_from_the_pokemons,
+            ]
+        );
+    }
+}
@@ -0,0 +1,162 @@
+#[cfg(test)]
+mod contract;
+mod memory;
+mod sqlite;
+pub use memory::InMemoryAbilityRepository;
+pub use sqlite::SqliteAbilityRepository;
+
+use std::{error::Error, fmt};
+
+use crate::domain::entities::{Ability, AbilityName, PokemonAbilities, PokemonNumber};
+
+pub use super::pokemon::RepositoryError;
+
+/// Stores the abilities and which ones each Pokemon has. It only knows the
+/// numbers of the Pokemon, checking that they exist is left to the caller.
+pub trait AbilityRepository: Send + Sync {
+    fn insert(&self, name: AbilityName, description: String) -> Result<Ability, InsertError>;
+    /// Every ability, sorted by name.
+    fn fetch_all(&self) -> Result<Vec<Ability>, RepositoryError>;
+    fn fetch_one(&self, name: AbilityName) -> Result<Ability, FetchOneError>;
+    fn update(&self, name: AbilityName, description: String) -> Result<Ability, UpdateError>;
+    /// Also removes the ability from the Pokemon having it.
+    fn delete(&self, name: AbilityName) -> Result<(), DeleteError>;
+    /// The abilities of a Pokemon, empty when none were set.
+    fn fetch_pokemon_abilities(
+        &self,
+        number: PokemonNumber,
+    ) -> Result<PokemonAbilities, RepositoryError>;
+    /// Replaces the abilities of a Pokemon, every one of them having to exist.
+    fn set_pokemon_abilities(
+        &self,
+        number: PokemonNumber,
+        abilities: PokemonAbilities,
+    ) -> Result<(), SetAbilitiesError>;
+    /// The Pokemon having an ability, by ascending number.
+    fn fetch_holders(&self, name: AbilityName) -> Result<Vec<AbilityHolder>, FetchOneError>;
+}
+
+/// A Pokemon having an ability, either as a regular or as its hidden ability.
+#[derive(Clone, PartialEq)]
+pub struct AbilityHolder {
+    pub number: PokemonNumber,
+    pub hidden: bool,
+}
+
+#[derive(Debug)]
+pub enum InsertError {
+    Conflict,
+    Unknown(RepositoryError),
+}
+#[derive(Debug)]
+pub enum FetchOneError {
+    NotFound,
+    Unknown(RepositoryError),
+}
+#[derive(Debug)]
+pub enum UpdateError {
+    NotFound,
+    Unknown(RepositoryError),
+}
+#[derive(Debug)]
+pub enum DeleteError {
+    NotFound,
+    Unknown(RepositoryError),
+}
+#[derive(Debug)]
+pub enum SetAbilitiesError {
+    UnknownAbility(AbilityName),
+    Unknown(RepositoryError),
+}
+
+impl fmt::Display for InsertError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Conflict => write!(f, "an ability with this name already exists"),
+            Self::Unknown(e) => e.fmt(f),
+        }
+    }
+}
+
+impl fmt::Display for FetchOneError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::NotFound => write!(f, "no ability has this name"),
+            Self::Unknown(e) => e.fmt(f),
+        }
+    }
+}
+
+impl fmt::Display for UpdateError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::NotFound => write!(f, "no ability has this name"),
+            Self::Unknown(e) => e.fmt(f),
+        }
+    }
+}
+
+impl fmt::Display for DeleteError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::NotFound => write!(f, "no ability has this name"),
+            Self::Unknown(e) => e.fmt(f),
+        }
+    }
+}
+
+impl fmt::Display for SetAbilitiesError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::UnknownAbility(name) => {
+                write!(f, "no ability is named {:?}", String::from(name.clone()))
+            }
+            Self::Unknown(e) => e.fmt(f),
+        }
+    }
+}
+
+impl Error for InsertError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Unknown(e) => Some(e),
+            Self::Conflict => None,
+        }
+    }
+}
+
+impl Error for FetchOneError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Unknown(e) => Some(e),
+            Self::NotFound => None,
+        }
+    }
+}
+
+impl Error for UpdateError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Unknown(e) => Some(e),
+            Self::NotFound => None,
+        }
+    }
+}
+
+impl Error for DeleteError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Unknown(e) => Some(e),
+            Self::NotFound => None,
+        }
+    }
+}
+
+impl Error for SetAbilitiesError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Unknown(e) => Some(e),
+            Self::UnknownAbility(_) => None,
+        }
+    }
+}
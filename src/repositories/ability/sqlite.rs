@@ -0,0 +1,440 @@
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use rusqlite::{params, Connection, Params, Row};
+
+use crate::{
+    domain::entities::{Ability, AbilityName, PokemonAbilities, PokemonNumber},
+    repositories::pokemon::SqliteRepository,
+};
+
+use super::{
+    AbilityHolder, AbilityRepository, DeleteError, FetchOneError, InsertError, RepositoryError,
+    SetAbilitiesError, UpdateError,
+};
+
+/// The slot of the hidden ability, the regular ones taking the first slots.
+const HIDDEN_SLOT: usize = PokemonAbilities::MAX_REGULAR + 1;
+
+pub struct SqliteAbilityRepository {
+    connection: Arc<Mutex<Connection>>,
+}
+
+impl SqliteAbilityRepository {
+    /// Keeps the abilities in the database of `pokemons`, sharing its
+    /// connection so that deleting a pokemon also forgets its abilities.
+    pub fn new(pokemons: &SqliteRepository) -> Self {
+        Self {
+            connection: pokemons.connection(),
+        }
+    }
+
+    fn lock(&self) -> Result<MutexGuard<'_, Connection>, RepositoryError> {
+        match self.connection.lock() {
+            Ok(lock) => Ok(lock),
+            Err(_) => Err(RepositoryError::new("the connection lock is poisoned")),
+        }
+    }
+
+    fn fetch_rows<T, P: Params>(
+        connection: &Connection,
+        sql: &str,
+        params: P,
+        read: impl FnMut(&Row<'_>) -> rusqlite::Result<T>,
+    ) -> rusqlite::Result<Vec<T>> {
+        let mut stat = connection.prepare(sql)?;
+        let rows = stat.query_map(params, read)?;
+        rows.collect()
+    }
+
+    fn fetch_abilities(
+        connection: &Connection,
+        name: Option<&AbilityName>,
+    ) -> Result<Vec<Ability>, RepositoryError> {
+        let rows = match Self::fetch_rows(
+            connection,
+            "select name, description from abilities \
+            where ?1 is null or name = ?1 order by name",
+            [name.cloned().map(String::from)],
+            |row| Ok((row.get::<usize, String>(0)?, row.get::<usize, String>(1)?)),
+        ) {
+            Ok(rows) => rows,
+            Err(e) => return Err(RepositoryError::caused_by("cannot fetch the abilities", e)),
+        };
+        let mut abilities = vec![];
+        for (name, description) in rows {
+            match AbilityName::try_from(name) {
+                Ok(name) => abilities.push(Ability::new(name, description)),
+                Err(e) => {
+                    return Err(RepositoryError::caused_by(
+                        "an ability is stored with an empty name",
+                        e,
+                    ))
+                }
+            }
+        }
+        Ok(abilities)
+    }
+
+    fn exists(connection: &Connection, name: &AbilityName) -> Result<bool, RepositoryError> {
+        match connection.query_row(
+            "select count(*) from abilities where name = ?",
+            [String::from(name.clone())],
+            |row| row.get::<usize, u32>(0),
+        ) {
+            Ok(count) => Ok(count > 0),
+            Err(e) => Err(RepositoryError::caused_by("cannot look for the ability", e)),
+        }
+    }
+}
+
+impl AbilityRepository for SqliteAbilityRepository {
+    fn insert(&self, name: AbilityName, description: String) -> Result<Ability, InsertError> {
+        let lock = match self.lock() {
+            Ok(lock) => lock,
+            Err(e) => return Err(InsertError::Unknown(e)),
+        };
+        match lock.execute(
+            "insert into abilities (name, description) values (?, ?)",
+            params![String::from(name.clone()), description],
+        ) {
+            Ok(_) => Ok(Ability::new(name, description)),
+            Err(rusqlite::Error::SqliteFailure(_, Some(msg)))
+                if msg == "UNIQUE constraint failed: abilities.name" =>
+            {
+                Err(InsertError::Conflict)
+            }
+            Err(e) => Err(InsertError::Unknown(RepositoryError::caused_by(
+                "cannot insert the ability",
+                e,
+            ))),
+        }
+    }
+
+    fn fetch_all(&self) -> Result<Vec<Ability>, RepositoryError> {
+        let lock = self.lock()?;
+        Self::fetch_abilities(&lock, None)
+    }
+
+    fn fetch_one(&self, name: AbilityName) -> Result<Ability, FetchOneError> {
+        let lock = match self.lock() {
+            Ok(lock) => lock,
+            Err(e) => return Err(FetchOneError::Unknown(e)),
+        };
+        match Self::fetch_abilities(&lock, Some(&name)) {
+            Ok(mut abilities) if !abilities.is_empty() => Ok(abilities.remove(0)),
+            Ok(_) => Err(FetchOneError::NotFound),
+            Err(e) => Err(FetchOneError::Unknown(e)),
+        }
+    }
+
+    fn update(&self, name: AbilityName, description: String) -> Result<Ability, UpdateError> {
+        let lock = match self.lock() {
+            Ok(lock) => lock,
+            Err(e) => return Err(UpdateError::Unknown(e)),
+        };
+        match lock.execute(
+            "update abilities set description = ? where name = ?",
+            params![description, String::from(name.clone())],
+        ) {
+            Ok(0) => Err(UpdateError::NotFound),
+            Ok(_) => Ok(Ability::new(name, description)),
+            Err(e) => Err(UpdateError::Unknown(RepositoryError::caused_by(
+                "cannot update the ability",
+                e,
+            ))),
+        }
+    }
+
+    fn delete(&self, name: AbilityName) -> Result<(), DeleteError> {
+        let lock = match self.lock() {
+            Ok(lock) => lock,
+            Err(e) => return Err(DeleteError::Unknown(e)),
+        };
+        match lock.execute("delete from abilities where name = ?", [String::from(name)]) {
+            Ok(0) => Err(DeleteError::NotFound),
+            Ok(_) => Ok(()),
+            Err(e) => Err(DeleteError::Unknown(RepositoryError::caused_by(
+                "cannot delete the ability",
+                e,
+            ))),
+        }
+    }
+
+    fn fetch_pokemon_abilities(
+        &self,
+        number: PokemonNumber,
+    ) -> Result<PokemonAbilities, RepositoryError> {
+        let lock = self.lock()?;
+        let rows = match Self::fetch_rows(
+            &lock,
+            "select slot, ability_name from pokemon_abilities \
+            where pokemon_number = ? order by slot",
+            [u16::from(number.clone())],
+            |row| Ok((row.get::<usize, usize>(0)?, row.get::<usize, String>(1)?)),
+        ) {
+            Ok(rows) => rows,
+            Err(e) => {
+                return Err(RepositoryError::caused_by(
+                    "cannot fetch the abilities of the pokemon",
+                    e,
+                ))
+            }
+        };
+        let mut regular = vec![];
+        let mut hidden = None;
+        for (slot, name) in rows {
+            if slot == HIDDEN_SLOT {
+                hidden = Some(name);
+            } else {
+                regular.push(name);
+            }
+        }
+        match PokemonAbilities::try_new(regular, hidden) {
+            Ok(abilities) => Ok(abilities),
+            Err(e) => Err(RepositoryError::caused_by(
+                format!(
+                    "pokemon {} is stored with invalid abilities",
+                    u16::from(number)
+                ),
+                e,
+            )),
+        }
+    }
+
+    fn set_pokemon_abilities(
+        &self,
+        number: PokemonNumber,
+        abilities: PokemonAbilities,
+    ) -> Result<(), SetAbilitiesError> {
+        let mut lock = match self.lock() {
+            Ok(lock) => lock,
+            Err(e) => return Err(SetAbilitiesError::Unknown(e)),
+        };
+        let transaction = match lock.transaction() {
+            Ok(t) => t,
+            Err(e) => {
+                return Err(SetAbilitiesError::Unknown(RepositoryError::caused_by(
+                    "cannot start a transaction",
+                    e,
+                )))
+            }
+        };
+        let number = u16::from(number);
+        if let Err(e) = transaction.execute(
+            "delete from pokemon_abilities where pokemon_number = ?",
+            [number],
+        ) {
+            return Err(SetAbilitiesError::Unknown(RepositoryError::caused_by(
+                "cannot delete the previous abilities",
+                e,
+            )));
+        }
+        let slots = abilities
+            .regular()
+            .iter()
+            .enumerate()
+            .map(|(i, name)| (i + 1, name))
+            .chain(abilities.hidden().map(|name| (HIDDEN_SLOT, name)));
+        for (slot, name) in slots {
+            match Self::exists(&transaction, name) {
+                Ok(true) => {}
+                Ok(false) => return Err(SetAbilitiesError::UnknownAbility(name.clone())),
+                Err(e) => return Err(SetAbilitiesError::Unknown(e)),
+            }
+            if let Err(e) = transaction.execute(
+                "insert into pokemon_abilities (pokemon_number, slot, ability_name) \
+                values (?, ?, ?)",
+                params![number, slot, String::from(name.clone())],
+            ) {
+                return Err(SetAbilitiesError::Unknown(RepositoryError::caused_by(
+                    "cannot insert the abilities",
+                    e,
+                )));
+            }
+        }
+        match transaction.commit() {
+            Ok(_) => Ok(()),
+            Err(e) => Err(SetAbilitiesError::Unknown(RepositoryError::caused_by(
+                "cannot commit the transaction",
+                e,
+            ))),
+        }
+    }
+
+    fn fetch_holders(&self, name: AbilityName) -> Result<Vec<AbilityHolder>, FetchOneError> {
+        let lock = match self.lock() {
+            Ok(lock) => lock,
+            Err(e) => return Err(FetchOneError::Unknown(e)),
+        };
+        match Self::exists(&lock, &name) {
+            Ok(true) => {}
+            Ok(false) => return Err(FetchOneError::NotFound),
+            Err(e) => return Err(FetchOneError::Unknown(e)),
+        }
+        let rows = match Self::fetch_rows(
+            &lock,
+            "select pokemon_number, slot from pokemon_abilities \
+            where ability_name = ? order by pokemon_number",
+            [String::from(name)],
+            |row| Ok((row.get::<usize, u16>(0)?, row.get::<usize, usize>(1)?)),
+        ) {
+            Ok(rows) => rows,
+            Err(e) => {
+                return Err(FetchOneError::Unknown(RepositoryError::caused_by(
+                    "cannot fetch the pokemons having the ability",
+                    e,
+                )))
+            }
+        };
+        let mut holders = vec![];
+        for (number, slot) in rows {
+            match PokemonNumber::try_from(number) {
+                Ok(number) => holders.push(AbilityHolder {
+                    number,
+                    hidden: slot == HIDDEN_SLOT,
+                }),
+                Err(e) => {
+                    return Err(FetchOneError::Unknown(RepositoryError::caused_by(
+                        "an ability is held by an invalid pokemon number",
+                        e,
+                    )))
+                }
+            }
+        }
+        Ok(holders)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        domain::entities::{PokemonForm, PokemonName, PokemonTypes},
+        repositories::pokemon::Repository,
+    };
+
+    #[test]
+    fn it_should_forget_the_abilities_of_a_deleted_pokemon() {
+        let pokemons = SqliteRepository::try_new(":memory:").ok().unwrap();
+        let repo = SqliteAbilityRepository::new(&pokemons);
+        pokemons
+            .insert(
+                PokemonNumber::pikachu(),
+                PokemonForm::default(),
+                PokemonName::pikachu(),
+                PokemonTypes::pikachu(),
+                None,
+            )
+            .ok();
+        repo.insert(AbilityName::r#static(), String::new()).ok();
+        repo.set_pokemon_abilities(
+            PokemonNumber::pikachu(),
+            PokemonAbilities::try_new(vec![String::from("Static")], None).unwrap(),
+        )
+        .ok();
+
+        pokemons
+            .delete(PokemonNumber::pikachu(), PokemonForm::default())
+            .ok();
+
+        match repo.fetch_holders(AbilityName::r#static()) {
+            Ok(holders) => assert!(holders.is_empty()),
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_keep_the_abilities_when_another_form_is_deleted() {
+        let pokemons = SqliteRepository::try_new(":memory:").ok().unwrap();
+        let repo = SqliteAbilityRepository::new(&pokemons);
+        for form in [PokemonForm::default(), PokemonForm::alola()] {
+            pokemons
+                .insert(
+                    PokemonNumber::pikachu(),
+                    form,
+                    PokemonName::pikachu(),
+                    PokemonTypes::pikachu(),
+                    None,
+                )
+                .ok();
+        }
+        repo.insert(AbilityName::r#static(), String::new()).ok();
+        repo.set_pokemon_abilities(
+            PokemonNumber::pikachu(),
+            PokemonAbilities::try_new(vec![String::from("Static")], None).unwrap(),
+        )
+        .ok();
+
+        pokemons
+            .delete(PokemonNumber::pikachu(), PokemonForm::alola())
+            .ok();
+
+        match repo.fetch_pokemon_abilities(PokemonNumber::pikachu()) {
+            Ok(abilities) => assert_eq!(abilities.regular().len(), 1),
+            _ => unreachable!(),
+        };
+    }
+
+    mod contract {
+        use super::{SqliteAbilityRepository, SqliteRepository};
+        use crate::domain::entities::{PokemonForm, PokemonName, PokemonNumber, PokemonTypes};
+        use crate::repositories::{
+            ability::{contract, AbilityRepository},
+            contract_tests,
+            pokemon::Repository,
+        };
+        use tempfile::TempDir;
+
+        /// The links need the pokemons to exist in the same database.
+        fn factory() -> (std::sync::Arc<dyn AbilityRepository>, TempDir) {
+            let dir = tempfile::tempdir().expect("failed to create a temporary directory");
+            let path = dir.path().join("pokedex.db");
+            let pokemons = match SqliteRepository::try_new(path.to_str().unwrap()) {
+                Ok(repo) => repo,
+                _ => unreachable!(),
+            };
+            for (number, name, types) in [
+                (
+                    PokemonNumber::pikachu(),
+                    PokemonName::pikachu(),
+                    PokemonTypes::pikachu(),
+                ),
+                (
+                    PokemonNumber::charmander(),
+                    PokemonName::charmander(),
+                    PokemonTypes::charmander(),
+                ),
+                (
+                    PokemonNumber::charizard(),
+                    PokemonName::charizard(),
+                    PokemonTypes::charizard(),
+                ),
+            ] {
+                pokemons
+                    .insert(number, PokemonForm::default(), name, types, None)
+                    .ok();
+            }
+            (
+                std::sync::Arc::new(SqliteAbilityRepository::new(&pokemons)),
+                dir,
+            )
+        }
+
+        contract_tests!(
+            factory,
+            contract,
+            [
+                it_should_return_the_inserted_ability,
+                it_should_refuse_to_insert_an_existing_name,
+                it_should_fetch_all_by_name,
+                it_should_update_the_description,
+                it_should_not_find_update_or_delete_a_missing_ability,
+                it_should_return_no_abilities_for_a_pokemon_without_any,
+                it_should_replace_the_abilities_of_a_pokemon,
+                it_should_refuse_an_unknown_ability_and_keep_the_previous_ones,
+                it_should_find_the_pokemons_having_an_ability,
+                it_should_remove_a_deleted_ability_from_the_pokemons,
+            ]
+        );
+    }
+}
@@ -0,0 +1,266 @@
+use std::sync::Arc;
+
+use ureq::serde_json::{Map, Value};
+
+use crate::{
+    domain::entities::{Evolution, EvolutionTrigger, PokemonNumber, ValidationError},
+    repositories::pokemon::{AirtableEvolutionFieldNames, AirtableRepository},
+};
+
+use super::{DeleteError, EvolutionRepository, InsertError, RepositoryError};
+
+/// A record of the evolutions table, with its id and its stored values.
+type EvolutionRecord = (String, Map<String, Value>);
+
+pub struct AirtableEvolutionRepository {
+    pokemons: Arc<AirtableRepository>,
+}
+
+impl AirtableEvolutionRepository {
+    /// Keeps the evolutions in a table of the base of `pokemons`, sending
+    /// the requests through it so that both tables share its rate limit.
+    pub fn new(pokemons: Arc<AirtableRepository>) -> Self {
+        Self { pokemons }
+    }
+
+    fn fields(&self) -> &AirtableEvolutionFieldNames {
+        self.pokemons.evolution_fields()
+    }
+
+    /// The records matching the formula, by ascending `to` number.
+    fn fetch_records(&self, formula: &str) -> Result<Vec<EvolutionRecord>, RepositoryError> {
+        let url = self.pokemons.evolutions_url();
+        let (records, _) = AirtableRepository::fetch_pages(None, false, |cursor| {
+            let mut req = self
+                .pokemons
+                .request("GET", url)
+                .query("filterByFormula", formula)
+                .query("sort[0][field]", &self.fields().to)
+                .query("sort[0][direction]", "asc");
+            if let Some(cursor) = cursor.as_deref() {
+                req = req.query("offset", cursor);
+            }
+            match self.pokemons.send(req, None) {
+                Ok(res) => AirtableRepository::read_json(res),
+                Err(e) => Err(RepositoryError::caused_by("cannot fetch the evolutions", e)),
+            }
+        })?;
+        Ok(records
+            .into_iter()
+            .map(|record| (record.id, record.fields))
+            .collect())
+    }
+
+    fn fetch_evolutions_where(
+        &self,
+        field: &str,
+        number: PokemonNumber,
+    ) -> Result<Vec<Evolution>, RepositoryError> {
+        let records = self.fetch_records(&format!("{{{}}}={}", field, u16::from(number)))?;
+        let mut evolutions = vec![];
+        for (_, fields) in records {
+            evolutions.push(self.read_evolution(&fields)?);
+        }
+        Ok(evolutions)
+    }
+
+    fn read_evolution(&self, fields: &Map<String, Value>) -> Result<Evolution, RepositoryError> {
+        let names = self.fields();
+        let number = |field: &str| {
+            fields
+                .get(field)
+                .and_then(Value::as_u64)
+                .and_then(|n| u16::try_from(n).ok())
+        };
+        let text = |field: &str| fields.get(field).and_then(Value::as_str).map(String::from);
+        let level = fields
+            .get(&names.level)
+            .and_then(Value::as_u64)
+            .map(|n| u16::try_from(n).unwrap_or(u16::MAX));
+        match (number(&names.from), number(&names.to), text(&names.trigger)) {
+            (Some(from), Some(to), Some(trigger)) => match (
+                PokemonNumber::try_from(from),
+                PokemonNumber::try_from(to),
+                EvolutionTrigger::try_new(trigger, level, text(&names.item))
+                    .map_err(ValidationError::from),
+            ) {
+                (Ok(from), Ok(to), Ok(trigger)) => Ok(Evolution { from, to, trigger }),
+                (Err(e), _, _) | (_, Err(e), _) | (_, _, Err(e)) => {
+                    Err(RepositoryError::caused_by(
+                        format!(
+                            "the evolution of pokemon {} into {} is stored with an invalid field",
+                            from, to
+                        ),
+                        e,
+                    ))
+                }
+            },
+            (None, _, _) => Err(RepositoryError::new(format!(
+                "a record has no valid {} field",
+                names.from
+            ))),
+            (_, None, _) => Err(RepositoryError::new(format!(
+                "a record has no valid {} field",
+                names.to
+            ))),
+            (_, _, None) => Err(RepositoryError::new(format!(
+                "a record has no valid {} field",
+                names.trigger
+            ))),
+        }
+    }
+}
+
+impl EvolutionRepository for AirtableEvolutionRepository {
+    fn insert(&self, evolution: Evolution) -> Result<Evolution, InsertError> {
+        let names = self.fields();
+        let to = u16::from(evolution.to.clone());
+        match self.fetch_records(&format!("{{{}}}={}", names.to, to)) {
+            Ok(records) if !records.is_empty() => return Err(InsertError::Conflict),
+            Ok(_) => {}
+            Err(e) => return Err(InsertError::Unknown(e)),
+        };
+        let mut fields = Map::new();
+        fields.insert(
+            names.from.clone(),
+            Value::from(u16::from(evolution.from.clone())),
+        );
+        fields.insert(names.to.clone(), Value::from(to));
+        fields.insert(names.trigger.clone(), Value::from(evolution.trigger.name()));
+        fields.insert(
+            names.level.clone(),
+            evolution.trigger.level().map_or(Value::Null, Value::from),
+        );
+        fields.insert(
+            names.item.clone(),
+            evolution.trigger.item().map_or(Value::Null, Value::from),
+        );
+        let body = ureq::json!({ "records": [{ "fields": fields }] });
+        let url = self.pokemons.evolutions_url();
+        match self
+            .pokemons
+            .send(self.pokemons.request("POST", url), Some(body))
+        {
+            Ok(_) => Ok(evolution),
+            Err(e) => Err(InsertError::Unknown(RepositoryError::caused_by(
+                "cannot insert the evolution",
+                e,
+            ))),
+        }
+    }
+
+    fn fetch_pre_evolution(
+        &self,
+        number: PokemonNumber,
+    ) -> Result<Option<Evolution>, RepositoryError> {
+        let mut evolutions = self.fetch_evolutions_where(&self.fields().to, number)?;
+        Ok(evolutions.pop())
+    }
+
+    fn fetch_evolutions(&self, number: PokemonNumber) -> Result<Vec<Evolution>, RepositoryError> {
+        self.fetch_evolutions_where(&self.fields().from, number)
+    }
+
+    fn delete(&self, from: PokemonNumber, to: PokemonNumber) -> Result<(), DeleteError> {
+        let formula = format!(
+            "AND({{{}}}={}, {{{}}}={})",
+            self.fields().from,
+            u16::from(from),
+            self.fields().to,
+            u16::from(to)
+        );
+        let records = match self.fetch_records(&formula) {
+            Ok(records) if records.is_empty() => return Err(DeleteError::NotFound),
+            Ok(records) => records,
+            Err(e) => return Err(DeleteError::Unknown(e)),
+        };
+        for (id, _) in records {
+            let url = format!("{}/{}", self.pokemons.evolutions_url(), id);
+            if let Err(e) = self
+                .pokemons
+                .send(self.pokemons.request("DELETE", &url), None)
+            {
+                return Err(DeleteError::Unknown(RepositoryError::caused_by(
+                    format!("cannot delete the record {}", id),
+                    e,
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repositories::pokemon::FakeAirtable;
+
+    fn repo(server: &FakeAirtable) -> AirtableEvolutionRepository {
+        AirtableEvolutionRepository::new(Arc::new(server.repo()))
+    }
+
+    #[test]
+    fn it_should_follow_offsets_to_fetch_every_evolution() {
+        let server = FakeAirtable::start().expect("failed to start the fake airtable server");
+        let repo = repo(&server);
+        for to in 2..=102 {
+            repo.insert(Evolution {
+                to: PokemonNumber::try_from(to).unwrap(),
+                ..Evolution::pikachu()
+            })
+            .ok();
+        }
+
+        match repo.fetch_evolutions(PokemonNumber::pikachu()) {
+            Ok(evolutions) => assert_eq!(evolutions.len(), 101),
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_name_the_missing_field_of_a_record() {
+        let server = FakeAirtable::start().expect("failed to start the fake airtable server");
+        let repo = repo(&server);
+        let body = ureq::json!({ "records": [{ "fields": { "to": 26, "trigger": "trade" } }] });
+        let url = repo.pokemons.evolutions_url();
+        repo.pokemons
+            .send(repo.pokemons.request("POST", url), Some(body))
+            .ok();
+
+        match repo.fetch_pre_evolution(PokemonNumber::raichu()) {
+            Err(e) => assert_eq!(e.to_string(), "a record has no valid from field"),
+            _ => unreachable!(),
+        };
+    }
+
+    mod contract {
+        use super::super::AirtableEvolutionRepository;
+        use crate::repositories::{
+            contract_tests,
+            evolution::{contract, EvolutionRepository},
+            pokemon::FakeAirtable,
+        };
+
+        fn factory() -> (std::sync::Arc<dyn EvolutionRepository>, FakeAirtable) {
+            let server = FakeAirtable::start().expect("failed to start the fake airtable server");
+            let pokemons = std::sync::Arc::new(server.repo());
+            (
+                std::sync::Arc::new(AirtableEvolutionRepository::new(pokemons)),
+                server,
+            )
+        }
+
+        contract_tests!(
+            factory,
+            contract,
+            [
+                it_should_return_the_inserted_evolution,
+                it_should_find_no_pre_evolution_for_a_base_form,
+                it_should_refuse_a_second_pre_evolution,
+                it_should_fetch_the_evolutions_by_number,
+                it_should_keep_the_trigger_of_an_evolution,
+                it_should_delete_an_evolution,
+            ]
+        );
+    }
+}
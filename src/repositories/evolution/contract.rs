@@ -0,0 +1,111 @@
+//! Behaviour every `EvolutionRepository` adapter must share, each check run
+//! as a test of every adapter with `contract_tests!`.
+//!
+//! Charmander, Charmeleon, Charizard, Pikachu and Raichu must exist for the
+//! adapters checking the pokemons they link.
+
+use std::sync::Arc;
+
+use crate::domain::entities::{Evolution, EvolutionTrigger, PokemonNumber};
+use crate::repositories::evolution::{DeleteError, EvolutionRepository, InsertError};
+
+fn numbers(evolutions: Vec<Evolution>) -> Vec<(u16, u16)> {
+    evolutions
+        .into_iter()
+        .map(|e| (u16::from(e.from), u16::from(e.to)))
+        .collect()
+}
+
+pub fn it_should_return_the_inserted_evolution(repo: Arc<dyn EvolutionRepository>) {
+    let res = repo.insert(Evolution::pikachu());
+
+    match res {
+        Ok(evolution) => assert!(evolution == Evolution::pikachu()),
+        _ => unreachable!(),
+    };
+    match repo.fetch_pre_evolution(PokemonNumber::raichu()) {
+        Ok(Some(evolution)) => assert!(evolution == Evolution::pikachu()),
+        _ => unreachable!(),
+    };
+}
+
+pub fn it_should_find_no_pre_evolution_for_a_base_form(repo: Arc<dyn EvolutionRepository>) {
+    repo.insert(Evolution::charmander()).ok();
+
+    let res = repo.fetch_pre_evolution(PokemonNumber::charmander());
+
+    match res {
+        Ok(None) => {}
+        _ => unreachable!(),
+    };
+}
+
+pub fn it_should_refuse_a_second_pre_evolution(repo: Arc<dyn EvolutionRepository>) {
+    repo.insert(Evolution::charmeleon()).ok();
+
+    let res = repo.insert(Evolution {
+        from: PokemonNumber::pikachu(),
+        to: PokemonNumber::charizard(),
+        trigger: EvolutionTrigger::Trade(None),
+    });
+
+    match res {
+        Err(InsertError::Conflict) => {}
+        _ => unreachable!(),
+    };
+}
+
+pub fn it_should_fetch_the_evolutions_by_number(repo: Arc<dyn EvolutionRepository>) {
+    repo.insert(Evolution {
+        from: PokemonNumber::charmander(),
+        to: PokemonNumber::raichu(),
+        trigger: EvolutionTrigger::Friendship,
+    })
+    .ok();
+    repo.insert(Evolution::charmander()).ok();
+    repo.insert(Evolution::charmeleon()).ok();
+
+    let res = repo.fetch_evolutions(PokemonNumber::charmander());
+
+    match res {
+        Ok(evolutions) => assert_eq!(numbers(evolutions), vec![(4, 5), (4, 26)]),
+        _ => unreachable!(),
+    };
+}
+
+pub fn it_should_keep_the_trigger_of_an_evolution(repo: Arc<dyn EvolutionRepository>) {
+    let trigger = EvolutionTrigger::try_new(
+        String::from("trade"),
+        None,
+        Some(String::from("Metal Coat")),
+    );
+    let evolution = Evolution {
+        from: PokemonNumber::charmeleon(),
+        to: PokemonNumber::charizard(),
+        trigger: trigger.unwrap(),
+    };
+    repo.insert(evolution.clone()).ok();
+
+    let res = repo.fetch_evolutions(PokemonNumber::charmeleon());
+
+    match res {
+        Ok(evolutions) => assert!(evolutions == vec![evolution]),
+        _ => unreachable!(),
+    };
+}
+
+pub fn it_should_delete_an_evolution(repo: Arc<dyn EvolutionRepository>) {
+    repo.insert(Evolution::charmander()).ok();
+
+    let res = repo.delete(PokemonNumber::charmander(), PokemonNumber::charmeleon());
+
+    assert!(res.is_ok());
+    match repo.fetch_pre_evolution(PokemonNumber::charmeleon()) {
+        Ok(None) => {}
+        _ => unreachable!(),
+    };
+    match repo.delete(PokemonNumber::charmander(), PokemonNumber::charmeleon()) {
+        Err(DeleteError::NotFound) => {}
+        _ => unreachable!(),
+    };
+}
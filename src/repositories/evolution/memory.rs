@@ -0,0 +1,112 @@
+use std::sync::{Mutex, MutexGuard};
+
+use crate::domain::entities::{Evolution, PokemonNumber};
+
+use super::{DeleteError, EvolutionRepository, InsertError, RepositoryError};
+
+pub struct InMemoryEvolutionRepository {
+    error: bool,
+    evolutions: Mutex<Vec<Evolution>>,
+}
+
+impl InMemoryEvolutionRepository {
+    pub fn new() -> Self {
+        Self {
+            error: false,
+            evolutions: Mutex::new(vec![]),
+        }
+    }
+
+    #[cfg(test)]
+    pub fn with_error(self) -> Self {
+        Self {
+            error: true,
+            ..self
+        }
+    }
+
+    fn lock(&self) -> Result<MutexGuard<'_, Vec<Evolution>>, RepositoryError> {
+        if self.error {
+            return Err(RepositoryError::new("the repository is set to fail"));
+        }
+        match self.evolutions.lock() {
+            Ok(lock) => Ok(lock),
+            Err(_) => Err(RepositoryError::new("the evolutions lock is poisoned")),
+        }
+    }
+}
+
+impl EvolutionRepository for InMemoryEvolutionRepository {
+    fn insert(&self, evolution: Evolution) -> Result<Evolution, InsertError> {
+        let mut lock = match self.lock() {
+            Ok(lock) => lock,
+            Err(e) => return Err(InsertError::Unknown(e)),
+        };
+        if lock.iter().any(|e| e.to == evolution.to) {
+            return Err(InsertError::Conflict);
+        }
+        lock.push(evolution.clone());
+        Ok(evolution)
+    }
+
+    fn fetch_pre_evolution(
+        &self,
+        number: PokemonNumber,
+    ) -> Result<Option<Evolution>, RepositoryError> {
+        let lock = self.lock()?;
+        Ok(lock.iter().find(|e| e.to == number).cloned())
+    }
+
+    fn fetch_evolutions(&self, number: PokemonNumber) -> Result<Vec<Evolution>, RepositoryError> {
+        let lock = self.lock()?;
+        let mut evolutions = lock
+            .iter()
+            .filter(|e| e.from == number)
+            .cloned()
+            .collect::<Vec<Evolution>>();
+        evolutions.sort_by(|a, b| a.to.cmp(&b.to));
+        Ok(evolutions)
+    }
+
+    fn delete(&self, from: PokemonNumber, to: PokemonNumber) -> Result<(), DeleteError> {
+        let mut lock = match self.lock() {
+            Ok(lock) => lock,
+            Err(e) => return Err(DeleteError::Unknown(e)),
+        };
+        match lock.iter().position(|e| e.from == from && e.to == to) {
+            Some(index) => {
+                lock.remove(index);
+                Ok(())
+            }
+            None => Err(DeleteError::NotFound),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod contract {
+        use super::super::InMemoryEvolutionRepository;
+        use crate::repositories::{
+            contract_tests,
+            evolution::{contract, EvolutionRepository},
+        };
+
+        fn factory() -> (std::sync::Arc<dyn EvolutionRepository>, ()) {
+            (std::sync::Arc::new(InMemoryEvolutionRepository::new()), ())
+        }
+
+        contract_tests!(
+            factory,
+            contract,
+            [
+                it_should_return_the_inserted_evolution,
+                it_should_find_no_pre_evolution_for_a_base_form,
+                it_should_refuse_a_second_pre_evolution,
+                it_should_fetch_the_evolutions_by_number,
+                it_should_keep_the_trigger_of_an_evolution,
+                it_should_delete_an_evolution,
+            ]
+        );
+    }
+}
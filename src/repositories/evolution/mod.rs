@@ -0,0 +1,77 @@
+mod airtable;
+#[cfg(test)]
+mod contract;
+mod memory;
+mod sqlite;
+pub use airtable::AirtableEvolutionRepository;
+pub use memory::InMemoryEvolutionRepository;
+pub use sqlite::SqliteEvolutionRepository;
+
+use std::{error::Error, fmt};
+
+use crate::domain::entities::{Evolution, PokemonNumber};
+
+pub use super::pokemon::RepositoryError;
+
+/// Stores which Pokemon evolves into which, and how. Like the abilities, it
+/// only knows the numbers of the Pokemon, checking that they exist and that
+/// the links make no cycle is left to the caller.
+pub trait EvolutionRepository: Send + Sync {
+    /// Refuses a Pokemon that already evolves from another one.
+    fn insert(&self, evolution: Evolution) -> Result<Evolution, InsertError>;
+    /// The evolution leading to the Pokemon, none for a base form.
+    fn fetch_pre_evolution(
+        &self,
+        number: PokemonNumber,
+    ) -> Result<Option<Evolution>, RepositoryError>;
+    /// The evolutions of the Pokemon, by ascending number.
+    fn fetch_evolutions(&self, number: PokemonNumber) -> Result<Vec<Evolution>, RepositoryError>;
+    fn delete(&self, from: PokemonNumber, to: PokemonNumber) -> Result<(), DeleteError>;
+}
+
+#[derive(Debug)]
+pub enum InsertError {
+    Conflict,
+    Unknown(RepositoryError),
+}
+#[derive(Debug)]
+pub enum DeleteError {
+    NotFound,
+    Unknown(RepositoryError),
+}
+
+impl fmt::Display for InsertError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Conflict => write!(f, "the pokemon already evolves from another one"),
+            Self::Unknown(e) => e.fmt(f),
+        }
+    }
+}
+
+impl fmt::Display for DeleteError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::NotFound => write!(f, "the pokemon does not evolve this way"),
+            Self::Unknown(e) => e.fmt(f),
+        }
+    }
+}
+
+impl Error for InsertError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Unknown(e) => Some(e),
+            Self::Conflict => None,
+        }
+    }
+}
+
+impl Error for DeleteError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Unknown(e) => Some(e),
+            Self::NotFound => None,
+        }
+    }
+}
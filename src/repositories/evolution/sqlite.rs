@@ -0,0 +1,268 @@
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use rusqlite::{params, Connection};
+
+use crate::{
+    domain::entities::{Evolution, EvolutionTrigger, PokemonNumber, ValidationError},
+    repositories::pokemon::SqliteRepository,
+};
+
+use super::{DeleteError, EvolutionRepository, InsertError, RepositoryError};
+
+/// A row of the evolutions table, as stored.
+type EvolutionRow = (u16, u16, String, Option<u16>, Option<String>);
+
+pub struct SqliteEvolutionRepository {
+    connection: Arc<Mutex<Connection>>,
+}
+
+impl SqliteEvolutionRepository {
+    /// Keeps the evolutions in the database of `pokemons`, sharing its
+    /// connection so that deleting a pokemon also forgets its evolutions.
+    pub fn new(pokemons: &SqliteRepository) -> Self {
+        Self {
+            connection: pokemons.connection(),
+        }
+    }
+
+    fn lock(&self) -> Result<MutexGuard<'_, Connection>, RepositoryError> {
+        match self.connection.lock() {
+            Ok(lock) => Ok(lock),
+            Err(_) => Err(RepositoryError::new("the connection lock is poisoned")),
+        }
+    }
+
+    fn fetch_evolutions_where(
+        &self,
+        column: &str,
+        number: PokemonNumber,
+    ) -> Result<Vec<Evolution>, RepositoryError> {
+        let lock = self.lock()?;
+        let rows = match lock
+            .prepare(&format!(
+                "select from_number, to_number, trigger, level, item from evolutions \
+                where {} = ? order by to_number",
+                column
+            ))
+            .and_then(|mut stat| {
+                let rows = stat.query_map(
+                    [u16::from(number)],
+                    |row| -> rusqlite::Result<EvolutionRow> {
+                        Ok((
+                            row.get(0)?,
+                            row.get(1)?,
+                            row.get(2)?,
+                            row.get(3)?,
+                            row.get(4)?,
+                        ))
+                    },
+                )?;
+                rows.collect::<rusqlite::Result<Vec<EvolutionRow>>>()
+            }) {
+            Ok(rows) => rows,
+            Err(e) => return Err(RepositoryError::caused_by("cannot fetch the evolutions", e)),
+        };
+        let mut evolutions = vec![];
+        for row in rows {
+            let (from, to) = (row.0, row.1);
+            match Self::read_evolution(row) {
+                Ok(evolution) => evolutions.push(evolution),
+                Err(e) => {
+                    return Err(RepositoryError::caused_by(
+                        format!(
+                            "the evolution of pokemon {} into {} is stored with invalid values",
+                            from, to
+                        ),
+                        e,
+                    ))
+                }
+            }
+        }
+        Ok(evolutions)
+    }
+
+    fn read_evolution(
+        (from, to, trigger, level, item): EvolutionRow,
+    ) -> Result<Evolution, ValidationError> {
+        Ok(Evolution {
+            from: PokemonNumber::try_from(from)?,
+            to: PokemonNumber::try_from(to)?,
+            trigger: EvolutionTrigger::try_new(trigger, level, item)?,
+        })
+    }
+}
+
+impl EvolutionRepository for SqliteEvolutionRepository {
+    fn insert(&self, evolution: Evolution) -> Result<Evolution, InsertError> {
+        let lock = match self.lock() {
+            Ok(lock) => lock,
+            Err(e) => return Err(InsertError::Unknown(e)),
+        };
+        match lock.execute(
+            "insert into evolutions (from_number, to_number, trigger, level, item) \
+            values (?, ?, ?, ?, ?)",
+            params![
+                u16::from(evolution.from.clone()),
+                u16::from(evolution.to.clone()),
+                evolution.trigger.name(),
+                evolution.trigger.level(),
+                evolution.trigger.item(),
+            ],
+        ) {
+            Ok(_) => Ok(evolution),
+            Err(rusqlite::Error::SqliteFailure(_, Some(msg)))
+                if msg == "UNIQUE constraint failed: evolutions.to_number" =>
+            {
+                Err(InsertError::Conflict)
+            }
+            Err(e) => Err(InsertError::Unknown(RepositoryError::caused_by(
+                "cannot insert the evolution",
+                e,
+            ))),
+        }
+    }
+
+    fn fetch_pre_evolution(
+        &self,
+        number: PokemonNumber,
+    ) -> Result<Option<Evolution>, RepositoryError> {
+        let mut evolutions = self.fetch_evolutions_where("to_number", number)?;
+        Ok(evolutions.pop())
+    }
+
+    fn fetch_evolutions(&self, number: PokemonNumber) -> Result<Vec<Evolution>, RepositoryError> {
+        self.fetch_evolutions_where("from_number", number)
+    }
+
+    fn delete(&self, from: PokemonNumber, to: PokemonNumber) -> Result<(), DeleteError> {
+        let lock = match self.lock() {
+            Ok(lock) => lock,
+            Err(e) => return Err(DeleteError::Unknown(e)),
+        };
+        match lock.execute(
+            "delete from evolutions where from_number = ? and to_number = ?",
+            params![u16::from(from), u16::from(to)],
+        ) {
+            Ok(0) => Err(DeleteError::NotFound),
+            Ok(_) => Ok(()),
+            Err(e) => Err(DeleteError::Unknown(RepositoryError::caused_by(
+                "cannot delete the evolution",
+                e,
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        domain::entities::{PokemonForm, PokemonName, PokemonTypes},
+        repositories::pokemon::Repository,
+    };
+
+    #[test]
+    fn it_should_forget_the_evolutions_of_a_deleted_pokemon() {
+        let pokemons = SqliteRepository::try_new(":memory:").ok().unwrap();
+        let repo = SqliteEvolutionRepository::new(&pokemons);
+        for (number, name) in [
+            (PokemonNumber::charmander(), PokemonName::charmander()),
+            (PokemonNumber::charmeleon(), PokemonName::charmeleon()),
+            (PokemonNumber::charizard(), PokemonName::charizard()),
+        ] {
+            pokemons
+                .insert(
+                    number,
+                    PokemonForm::default(),
+                    name,
+                    PokemonTypes::charmander(),
+                    None,
+                )
+                .ok();
+        }
+        repo.insert(Evolution::charmander()).ok();
+        repo.insert(Evolution::charmeleon()).ok();
+
+        pokemons
+            .delete(PokemonNumber::charmeleon(), PokemonForm::default())
+            .ok();
+
+        match repo.fetch_evolutions(PokemonNumber::charmander()) {
+            Ok(evolutions) => assert!(evolutions.is_empty()),
+            _ => unreachable!(),
+        };
+        match repo.fetch_pre_evolution(PokemonNumber::charizard()) {
+            Ok(None) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    mod contract {
+        use super::{SqliteEvolutionRepository, SqliteRepository};
+        use crate::domain::entities::{PokemonForm, PokemonName, PokemonNumber, PokemonTypes};
+        use crate::repositories::{
+            contract_tests,
+            evolution::{contract, EvolutionRepository},
+            pokemon::Repository,
+        };
+        use tempfile::TempDir;
+
+        /// The evolutions need the pokemons to exist in the same database.
+        fn factory() -> (std::sync::Arc<dyn EvolutionRepository>, TempDir) {
+            let dir = tempfile::tempdir().expect("failed to create a temporary directory");
+            let path = dir.path().join("pokedex.db");
+            let pokemons = match SqliteRepository::try_new(path.to_str().unwrap()) {
+                Ok(repo) => repo,
+                _ => unreachable!(),
+            };
+            for (number, name, types) in [
+                (
+                    PokemonNumber::charmander(),
+                    PokemonName::charmander(),
+                    PokemonTypes::charmander(),
+                ),
+                (
+                    PokemonNumber::charmeleon(),
+                    PokemonName::charmeleon(),
+                    PokemonTypes::charmander(),
+                ),
+                (
+                    PokemonNumber::charizard(),
+                    PokemonName::charizard(),
+                    PokemonTypes::charizard(),
+                ),
+                (
+                    PokemonNumber::pikachu(),
+                    PokemonName::pikachu(),
+                    PokemonTypes::pikachu(),
+                ),
+                (
+                    PokemonNumber::raichu(),
+                    PokemonName::raichu(),
+                    PokemonTypes::pikachu(),
+                ),
+            ] {
+                pokemons
+                    .insert(number, PokemonForm::default(), name, types, None)
+                    .ok();
+            }
+            (
+                std::sync::Arc::new(SqliteEvolutionRepository::new(&pokemons)),
+                dir,
+            )
+        }
+
+        contract_tests!(
+            factory,
+            contract,
+            [
+                it_should_return_the_inserted_evolution,
+                it_should_find_no_pre_evolution_for_a_base_form,
+                it_should_refuse_a_second_pre_evolution,
+                it_should_fetch_the_evolutions_by_number,
+                it_should_keep_the_trigger_of_an_evolution,
+                it_should_delete_an_evolution,
+            ]
+        );
+    }
+}
@@ -1 +1,23 @@
+pub mod ability;
+pub mod evolution;
+pub mod moves;
 pub mod pokemon;
+
+/// Runs the checks of a contract module as the tests of one adapter. The
+/// factory returns the repository along with a guard kept alive for the
+/// duration of the test, e.g. a temporary directory or a fake server.
+#[cfg(test)]
+macro_rules! contract_tests {
+    ($factory:expr, $contract:ident, [$($check:ident),+ $(,)?]) => {
+        $(
+            #[test]
+            fn $check() {
+                let (repo, _guard) = $factory();
+                $contract::$check(repo);
+            }
+        )+
+    };
+}
+
+#[cfg(test)]
+pub(crate) use contract_tests;
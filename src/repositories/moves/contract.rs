@@ -0,0 +1,165 @@
+//! Behaviour every `MoveRepository` adapter must share, each check run as a
+//! test of every adapter with `contract_tests!`.
+//!
+//! Pikachu, Charmander and Charizard must exist for the adapters checking
+//! the pokemons they store learnsets of.
+
+use std::sync::Arc;
+
+use crate::domain::entities::{
+    LearnMethod, Learnset, LearnsetEntry, Move, MoveName, PokemonNumber,
+};
+use crate::repositories::moves::{FetchOneError, InsertError, MoveRepository, SetLearnsetError};
+
+fn seed(repo: &Arc<dyn MoveRepository>) {
+    for r#move in [
+        Move::thunder_shock(),
+        Move::thunderbolt(),
+        Move::growl(),
+        Move::ember(),
+    ] {
+        match repo.insert(r#move) {
+            Ok(_) => {}
+            _ => unreachable!(),
+        }
+    }
+}
+
+pub fn it_should_return_the_inserted_move(repo: Arc<dyn MoveRepository>) {
+    let res = repo.insert(Move::growl());
+
+    match res {
+        Ok(r#move) => assert_eq!(r#move, Move::growl()),
+        _ => unreachable!(),
+    };
+    match repo.fetch_one(MoveName::growl()) {
+        Ok(r#move) => assert_eq!(r#move, Move::growl()),
+        _ => unreachable!(),
+    };
+}
+
+pub fn it_should_refuse_to_insert_an_existing_name(repo: Arc<dyn MoveRepository>) {
+    seed(&repo);
+
+    let res = repo.insert(Move::ember());
+
+    match res {
+        Err(InsertError::Conflict) => {}
+        _ => unreachable!(),
+    };
+}
+
+pub fn it_should_fetch_all_by_name(repo: Arc<dyn MoveRepository>) {
+    seed(&repo);
+
+    let res = repo.fetch_all();
+
+    match res {
+        Ok(moves) => assert_eq!(
+            moves
+                .into_iter()
+                .map(|m| String::from(m.name))
+                .collect::<Vec<String>>(),
+            vec!["Ember", "Growl", "Thunder Shock", "Thunderbolt"]
+        ),
+        _ => unreachable!(),
+    };
+}
+
+pub fn it_should_not_find_a_missing_move(repo: Arc<dyn MoveRepository>) {
+    match repo.fetch_one(MoveName::ember()) {
+        Err(FetchOneError::NotFound) => {}
+        _ => unreachable!(),
+    };
+    match repo.fetch_learners(MoveName::ember()) {
+        Err(FetchOneError::NotFound) => {}
+        _ => unreachable!(),
+    };
+}
+
+pub fn it_should_return_an_empty_learnset_for_a_pokemon_without_one(repo: Arc<dyn MoveRepository>) {
+    seed(&repo);
+
+    let res = repo.fetch_learnset(PokemonNumber::pikachu());
+
+    match res {
+        Ok(learnset) => assert!(learnset.entries().is_empty()),
+        _ => unreachable!(),
+    };
+}
+
+pub fn it_should_replace_the_learnset_of_a_pokemon(repo: Arc<dyn MoveRepository>) {
+    seed(&repo);
+    repo.set_learnset(PokemonNumber::pikachu(), Learnset::charmander())
+        .ok();
+
+    let res = repo.set_learnset(PokemonNumber::pikachu(), Learnset::pikachu());
+
+    assert!(res.is_ok());
+    match repo.fetch_learnset(PokemonNumber::pikachu()) {
+        Ok(learnset) => assert_eq!(learnset, Learnset::pikachu()),
+        _ => unreachable!(),
+    };
+}
+
+pub fn it_should_refuse_an_unknown_move_and_keep_the_previous_learnset(
+    repo: Arc<dyn MoveRepository>,
+) {
+    seed(&repo);
+    repo.set_learnset(PokemonNumber::pikachu(), Learnset::pikachu())
+        .ok();
+    let entry = LearnsetEntry::try_new(String::from("Surf"), String::from("tm"), None);
+    let learnset = Learnset::try_from(vec![entry.unwrap()]).unwrap();
+
+    let res = repo.set_learnset(PokemonNumber::pikachu(), learnset);
+
+    match res {
+        Err(SetLearnsetError::UnknownMove(name)) => assert_eq!(String::from(name), "Surf"),
+        _ => unreachable!(),
+    };
+    match repo.fetch_learnset(PokemonNumber::pikachu()) {
+        Ok(learnset) => assert_eq!(learnset, Learnset::pikachu()),
+        _ => unreachable!(),
+    };
+}
+
+pub fn it_should_find_the_pokemons_learning_a_move(repo: Arc<dyn MoveRepository>) {
+    seed(&repo);
+    repo.set_learnset(PokemonNumber::pikachu(), Learnset::pikachu())
+        .ok();
+    repo.set_learnset(PokemonNumber::charmander(), Learnset::charmander())
+        .ok();
+    let growl = |method: &str, level| {
+        LearnsetEntry::try_new(String::from("Growl"), String::from(method), level).unwrap()
+    };
+    let learnset = Learnset::try_from(vec![growl("tutor", None), growl("level-up", Some(9))]);
+    repo.set_learnset(PokemonNumber::charizard(), learnset.unwrap())
+        .ok();
+
+    let res = repo.fetch_learners(MoveName::growl());
+
+    match res {
+        Ok(learners) => assert_eq!(
+            learners
+                .into_iter()
+                .map(|l| (u16::from(l.number), l.method))
+                .collect::<Vec<(u16, LearnMethod)>>(),
+            vec![
+                (
+                    4,
+                    LearnMethod::try_new(String::from("level-up"), Some(1)).unwrap()
+                ),
+                (
+                    6,
+                    LearnMethod::try_new(String::from("level-up"), Some(9)).unwrap()
+                ),
+                (6, LearnMethod::Tutor),
+                (
+                    25,
+                    LearnMethod::try_new(String::from("level-up"), Some(1)).unwrap()
+                ),
+            ]
+        ),
+        _ => unreachable!(),
+    };
+}
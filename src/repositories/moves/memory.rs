@@ -0,0 +1,160 @@
+use std::sync::{Mutex, MutexGuard};
+
+use crate::domain::entities::{Learnset, Move, MoveName, PokemonNumber};
+
+use super::{
+    FetchOneError, InsertError, Learner, MoveRepository, RepositoryError, SetLearnsetError,
+};
+
+#[derive(Default)]
+struct State {
+    moves: Vec<Move>,
+    learnsets: Vec<(PokemonNumber, Learnset)>,
+}
+
+pub struct InMemoryMoveRepository {
+    error: bool,
+    state: Mutex<State>,
+}
+
+impl InMemoryMoveRepository {
+    pub fn new() -> Self {
+        Self {
+            error: false,
+            state: Mutex::new(State::default()),
+        }
+    }
+
+    #[cfg(test)]
+    pub fn with_error(self) -> Self {
+        Self {
+            error: true,
+            ..self
+        }
+    }
+
+    fn lock(&self) -> Result<MutexGuard<'_, State>, RepositoryError> {
+        if self.error {
+            return Err(RepositoryError::new("the repository is set to fail"));
+        }
+        match self.state.lock() {
+            Ok(lock) => Ok(lock),
+            Err(_) => Err(RepositoryError::new("the moves lock is poisoned")),
+        }
+    }
+}
+
+impl MoveRepository for InMemoryMoveRepository {
+    fn insert(&self, r#move: Move) -> Result<Move, InsertError> {
+        let mut lock = match self.lock() {
+            Ok(lock) => lock,
+            Err(e) => return Err(InsertError::Unknown(e)),
+        };
+        if lock.moves.iter().any(|m| m.name == r#move.name) {
+            return Err(InsertError::Conflict);
+        }
+        lock.moves.push(r#move.clone());
+        Ok(r#move)
+    }
+
+    fn fetch_all(&self) -> Result<Vec<Move>, RepositoryError> {
+        let lock = self.lock()?;
+        let mut moves = lock.moves.clone();
+        moves.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(moves)
+    }
+
+    fn fetch_one(&self, name: MoveName) -> Result<Move, FetchOneError> {
+        let lock = match self.lock() {
+            Ok(lock) => lock,
+            Err(e) => return Err(FetchOneError::Unknown(e)),
+        };
+        match lock.moves.iter().find(|m| m.name == name) {
+            Some(r#move) => Ok(r#move.clone()),
+            None => Err(FetchOneError::NotFound),
+        }
+    }
+
+    fn fetch_learnset(&self, number: PokemonNumber) -> Result<Learnset, RepositoryError> {
+        let lock = self.lock()?;
+        Ok(lock
+            .learnsets
+            .iter()
+            .find(|(n, _)| *n == number)
+            .map(|(_, learnset)| learnset.clone())
+            .unwrap_or_default())
+    }
+
+    fn set_learnset(
+        &self,
+        number: PokemonNumber,
+        learnset: Learnset,
+    ) -> Result<(), SetLearnsetError> {
+        let mut lock = match self.lock() {
+            Ok(lock) => lock,
+            Err(e) => return Err(SetLearnsetError::Unknown(e)),
+        };
+        let missing = learnset
+            .entries()
+            .iter()
+            .find(|e| !lock.moves.iter().any(|m| m.name == e.move_name));
+        if let Some(entry) = missing {
+            return Err(SetLearnsetError::UnknownMove(entry.move_name.clone()));
+        }
+        lock.learnsets.retain(|(n, _)| *n != number);
+        lock.learnsets.push((number, learnset));
+        Ok(())
+    }
+
+    fn fetch_learners(&self, name: MoveName) -> Result<Vec<Learner>, FetchOneError> {
+        let lock = match self.lock() {
+            Ok(lock) => lock,
+            Err(e) => return Err(FetchOneError::Unknown(e)),
+        };
+        if !lock.moves.iter().any(|m| m.name == name) {
+            return Err(FetchOneError::NotFound);
+        }
+        let mut learners = lock
+            .learnsets
+            .iter()
+            .flat_map(|(number, learnset)| {
+                learnset.methods_of(&name).map(|method| Learner {
+                    number: number.clone(),
+                    method,
+                })
+            })
+            .collect::<Vec<Learner>>();
+        learners.sort_by(|a, b| (&a.number, a.method).cmp(&(&b.number, b.method)));
+        Ok(learners)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod contract {
+        use super::super::InMemoryMoveRepository;
+        use crate::repositories::{
+            contract_tests,
+            moves::{contract, MoveRepository},
+        };
+
+        fn factory() -> (std::sync::Arc<dyn MoveRepository>, ()) {
+            (std::sync::Arc::new(InMemoryMoveRepository::new()), ())
+        }
+
+        contract_tests!(
+            factory,
+            contract,
+            [
+                it_should_return_the_inserted_move,
+                it_should_refuse_to_insert_an_existing_name,
+                it_should_fetch_all_by_name,
+                it_should_not_find_a_missing_move,
+                it_should_return_an_empty_learnset_for_a_pokemon_without_one,
+                it_should_replace_the_learnset_of_a_pokemon,
+                it_should_refuse_an_unknown_move_and_keep_the_previous_learnset,
+                it_should_find_the_pokemons_learning_a_move,
+            ]
+        );
+    }
+}
@@ -0,0 +1,111 @@
+#[cfg(test)]
+mod contract;
+mod memory;
+mod sqlite;
+pub use memory::InMemoryMoveRepository;
+pub use sqlite::SqliteMoveRepository;
+
+use std::{error::Error, fmt};
+
+use crate::domain::entities::{LearnMethod, Learnset, Move, MoveName, PokemonNumber};
+
+pub use super::pokemon::RepositoryError;
+
+/// Stores the moves and the learnset of each Pokemon. Like the abilities, it
+/// only knows the numbers of the Pokemon, checking that they exist is left to
+/// the caller.
+pub trait MoveRepository: Send + Sync {
+    fn insert(&self, r#move: Move) -> Result<Move, InsertError>;
+    /// Every move, sorted by name.
+    fn fetch_all(&self) -> Result<Vec<Move>, RepositoryError>;
+    fn fetch_one(&self, name: MoveName) -> Result<Move, FetchOneError>;
+    /// The learnset of a Pokemon, empty when none was set.
+    fn fetch_learnset(&self, number: PokemonNumber) -> Result<Learnset, RepositoryError>;
+    /// Replaces the learnset of a Pokemon, every move of it having to exist.
+    fn set_learnset(
+        &self,
+        number: PokemonNumber,
+        learnset: Learnset,
+    ) -> Result<(), SetLearnsetError>;
+    /// The Pokemon learning a move, by ascending number then method.
+    fn fetch_learners(&self, name: MoveName) -> Result<Vec<Learner>, FetchOneError>;
+}
+
+/// A Pokemon learning a move, once for each way it learns it.
+#[derive(Clone, PartialEq)]
+pub struct Learner {
+    pub number: PokemonNumber,
+    pub method: LearnMethod,
+}
+
+#[derive(Debug)]
+pub enum InsertError {
+    Conflict,
+    Unknown(RepositoryError),
+}
+#[derive(Debug)]
+pub enum FetchOneError {
+    NotFound,
+    Unknown(RepositoryError),
+}
+#[derive(Debug)]
+pub enum SetLearnsetError {
+    UnknownMove(MoveName),
+    Unknown(RepositoryError),
+}
+
+impl fmt::Display for InsertError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Conflict => write!(f, "a move with this name already exists"),
+            Self::Unknown(e) => e.fmt(f),
+        }
+    }
+}
+
+impl fmt::Display for FetchOneError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::NotFound => write!(f, "no move has this name"),
+            Self::Unknown(e) => e.fmt(f),
+        }
+    }
+}
+
+impl fmt::Display for SetLearnsetError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::UnknownMove(name) => {
+                write!(f, "no move is named {:?}", String::from(name.clone()))
+            }
+            Self::Unknown(e) => e.fmt(f),
+        }
+    }
+}
+
+impl Error for InsertError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Unknown(e) => Some(e),
+            Self::Conflict => None,
+        }
+    }
+}
+
+impl Error for FetchOneError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Unknown(e) => Some(e),
+            Self::NotFound => None,
+        }
+    }
+}
+
+impl Error for SetLearnsetError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Unknown(e) => Some(e),
+            Self::UnknownMove(_) => None,
+        }
+    }
+}
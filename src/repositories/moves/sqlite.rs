@@ -0,0 +1,418 @@
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use rusqlite::{params, Connection, Params, Row};
+
+use crate::{
+    domain::entities::{
+        Accuracy, LearnMethod, Learnset, LearnsetEntry, Move, MoveCategory, MoveName, MovePower,
+        PokemonNumber, PokemonType, PowerPoints, ValidationError,
+    },
+    repositories::pokemon::SqliteRepository,
+};
+
+use super::{
+    FetchOneError, InsertError, Learner, MoveRepository, RepositoryError, SetLearnsetError,
+};
+
+/// A row of the moves table, as stored.
+type MoveRow = (String, String, String, Option<u16>, Option<u16>, u16);
+
+pub struct SqliteMoveRepository {
+    connection: Arc<Mutex<Connection>>,
+}
+
+impl SqliteMoveRepository {
+    /// Keeps the moves in the database of `pokemons`, sharing its connection
+    /// so that deleting a pokemon also forgets its learnset.
+    pub fn new(pokemons: &SqliteRepository) -> Self {
+        Self {
+            connection: pokemons.connection(),
+        }
+    }
+
+    fn lock(&self) -> Result<MutexGuard<'_, Connection>, RepositoryError> {
+        match self.connection.lock() {
+            Ok(lock) => Ok(lock),
+            Err(_) => Err(RepositoryError::new("the connection lock is poisoned")),
+        }
+    }
+
+    fn fetch_rows<T, P: Params>(
+        connection: &Connection,
+        sql: &str,
+        params: P,
+        read: impl FnMut(&Row<'_>) -> rusqlite::Result<T>,
+    ) -> rusqlite::Result<Vec<T>> {
+        let mut stat = connection.prepare(sql)?;
+        let rows = stat.query_map(params, read)?;
+        rows.collect()
+    }
+
+    fn fetch_moves(
+        connection: &Connection,
+        name: Option<&MoveName>,
+    ) -> Result<Vec<Move>, RepositoryError> {
+        let rows = match Self::fetch_rows(
+            connection,
+            "select name, type, category, power, accuracy, pp from moves \
+            where ?1 is null or name = ?1 order by name",
+            [name.cloned().map(String::from)],
+            |row| -> rusqlite::Result<MoveRow> {
+                Ok((
+                    row.get(0)?,
+                    row.get(1)?,
+                    row.get(2)?,
+                    row.get(3)?,
+                    row.get(4)?,
+                    row.get(5)?,
+                ))
+            },
+        ) {
+            Ok(rows) => rows,
+            Err(e) => return Err(RepositoryError::caused_by("cannot fetch the moves", e)),
+        };
+        let mut moves = vec![];
+        for row in rows {
+            let name = row.0.clone();
+            match Self::read_move(row) {
+                Ok(r#move) => moves.push(r#move),
+                Err(e) => {
+                    return Err(RepositoryError::caused_by(
+                        format!("move {:?} is stored with invalid values", name),
+                        e,
+                    ))
+                }
+            }
+        }
+        Ok(moves)
+    }
+
+    fn read_move(
+        (name, r#type, category, power, accuracy, pp): MoveRow,
+    ) -> Result<Move, ValidationError> {
+        Move::try_new(
+            MoveName::try_from(name)?,
+            PokemonType::try_from(r#type)?,
+            MoveCategory::try_from(category)?,
+            power.map(MovePower::try_from).transpose()?,
+            accuracy.map(Accuracy::try_from).transpose()?,
+            PowerPoints::try_from(pp)?,
+        )
+        .map_err(ValidationError::from)
+    }
+
+    fn exists(connection: &Connection, name: &MoveName) -> Result<bool, RepositoryError> {
+        match connection.query_row(
+            "select count(*) from moves where name = ?",
+            [String::from(name.clone())],
+            |row| row.get::<usize, u32>(0),
+        ) {
+            Ok(count) => Ok(count > 0),
+            Err(e) => Err(RepositoryError::caused_by("cannot look for the move", e)),
+        }
+    }
+}
+
+impl MoveRepository for SqliteMoveRepository {
+    fn insert(&self, r#move: Move) -> Result<Move, InsertError> {
+        let lock = match self.lock() {
+            Ok(lock) => lock,
+            Err(e) => return Err(InsertError::Unknown(e)),
+        };
+        match lock.execute(
+            "insert into moves (name, type, category, power, accuracy, pp) \
+            values (?, ?, ?, ?, ?, ?)",
+            params![
+                String::from(r#move.name.clone()),
+                String::from(r#move.r#type),
+                String::from(r#move.category),
+                r#move.power.map(u16::from),
+                r#move.accuracy.map(u16::from),
+                u16::from(r#move.pp),
+            ],
+        ) {
+            Ok(_) => Ok(r#move),
+            Err(rusqlite::Error::SqliteFailure(_, Some(msg)))
+                if msg == "UNIQUE constraint failed: moves.name" =>
+            {
+                Err(InsertError::Conflict)
+            }
+            Err(e) => Err(InsertError::Unknown(RepositoryError::caused_by(
+                "cannot insert the move",
+                e,
+            ))),
+        }
+    }
+
+    fn fetch_all(&self) -> Result<Vec<Move>, RepositoryError> {
+        let lock = self.lock()?;
+        Self::fetch_moves(&lock, None)
+    }
+
+    fn fetch_one(&self, name: MoveName) -> Result<Move, FetchOneError> {
+        let lock = match self.lock() {
+            Ok(lock) => lock,
+            Err(e) => return Err(FetchOneError::Unknown(e)),
+        };
+        match Self::fetch_moves(&lock, Some(&name)) {
+            Ok(mut moves) if !moves.is_empty() => Ok(moves.remove(0)),
+            Ok(_) => Err(FetchOneError::NotFound),
+            Err(e) => Err(FetchOneError::Unknown(e)),
+        }
+    }
+
+    fn fetch_learnset(&self, number: PokemonNumber) -> Result<Learnset, RepositoryError> {
+        let lock = self.lock()?;
+        let rows = match Self::fetch_rows(
+            &lock,
+            "select move_name, method, level from learnsets where pokemon_number = ?",
+            [u16::from(number.clone())],
+            |row| {
+                Ok((
+                    row.get::<usize, String>(0)?,
+                    row.get::<usize, String>(1)?,
+                    row.get::<usize, Option<u16>>(2)?,
+                ))
+            },
+        ) {
+            Ok(rows) => rows,
+            Err(e) => {
+                return Err(RepositoryError::caused_by(
+                    "cannot fetch the learnset of the pokemon",
+                    e,
+                ))
+            }
+        };
+        let mut entries = vec![];
+        for (name, method, level) in rows {
+            match LearnsetEntry::try_new(name, method, level) {
+                Ok(entry) => entries.push(entry),
+                Err(e) => {
+                    return Err(RepositoryError::caused_by(
+                        format!(
+                            "pokemon {} is stored with an invalid learnset",
+                            u16::from(number)
+                        ),
+                        e,
+                    ))
+                }
+            }
+        }
+        match Learnset::try_from(entries) {
+            Ok(learnset) => Ok(learnset),
+            Err(e) => Err(RepositoryError::caused_by(
+                format!(
+                    "pokemon {} is stored with an invalid learnset",
+                    u16::from(number)
+                ),
+                e,
+            )),
+        }
+    }
+
+    fn set_learnset(
+        &self,
+        number: PokemonNumber,
+        learnset: Learnset,
+    ) -> Result<(), SetLearnsetError> {
+        let mut lock = match self.lock() {
+            Ok(lock) => lock,
+            Err(e) => return Err(SetLearnsetError::Unknown(e)),
+        };
+        let transaction = match lock.transaction() {
+            Ok(t) => t,
+            Err(e) => {
+                return Err(SetLearnsetError::Unknown(RepositoryError::caused_by(
+                    "cannot start a transaction",
+                    e,
+                )))
+            }
+        };
+        let number = u16::from(number);
+        if let Err(e) =
+            transaction.execute("delete from learnsets where pokemon_number = ?", [number])
+        {
+            return Err(SetLearnsetError::Unknown(RepositoryError::caused_by(
+                "cannot delete the previous learnset",
+                e,
+            )));
+        }
+        for entry in learnset.entries() {
+            match Self::exists(&transaction, &entry.move_name) {
+                Ok(true) => {}
+                Ok(false) => return Err(SetLearnsetError::UnknownMove(entry.move_name.clone())),
+                Err(e) => return Err(SetLearnsetError::Unknown(e)),
+            }
+            if let Err(e) = transaction.execute(
+                "insert into learnsets (pokemon_number, move_name, method, level) \
+                values (?, ?, ?, ?)",
+                params![
+                    number,
+                    String::from(entry.move_name.clone()),
+                    entry.method.name(),
+                    entry.method.level(),
+                ],
+            ) {
+                return Err(SetLearnsetError::Unknown(RepositoryError::caused_by(
+                    "cannot insert the learnset",
+                    e,
+                )));
+            }
+        }
+        match transaction.commit() {
+            Ok(_) => Ok(()),
+            Err(e) => Err(SetLearnsetError::Unknown(RepositoryError::caused_by(
+                "cannot commit the transaction",
+                e,
+            ))),
+        }
+    }
+
+    fn fetch_learners(&self, name: MoveName) -> Result<Vec<Learner>, FetchOneError> {
+        let lock = match self.lock() {
+            Ok(lock) => lock,
+            Err(e) => return Err(FetchOneError::Unknown(e)),
+        };
+        match Self::exists(&lock, &name) {
+            Ok(true) => {}
+            Ok(false) => return Err(FetchOneError::NotFound),
+            Err(e) => return Err(FetchOneError::Unknown(e)),
+        }
+        let rows = match Self::fetch_rows(
+            &lock,
+            "select pokemon_number, method, level from learnsets where move_name = ?",
+            [String::from(name)],
+            |row| {
+                Ok((
+                    row.get::<usize, u16>(0)?,
+                    row.get::<usize, String>(1)?,
+                    row.get::<usize, Option<u16>>(2)?,
+                ))
+            },
+        ) {
+            Ok(rows) => rows,
+            Err(e) => {
+                return Err(FetchOneError::Unknown(RepositoryError::caused_by(
+                    "cannot fetch the pokemons learning the move",
+                    e,
+                )))
+            }
+        };
+        let mut learners = vec![];
+        for (number, method, level) in rows {
+            match (
+                PokemonNumber::try_from(number),
+                LearnMethod::try_new(method, level).map_err(ValidationError::from),
+            ) {
+                (Ok(number), Ok(method)) => learners.push(Learner { number, method }),
+                (Err(e), _) | (_, Err(e)) => {
+                    return Err(FetchOneError::Unknown(RepositoryError::caused_by(
+                        "a move is learnt in an invalid way",
+                        e,
+                    )))
+                }
+            }
+        }
+        learners.sort_by(|a, b| (&a.number, a.method).cmp(&(&b.number, b.method)));
+        Ok(learners)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        domain::entities::{PokemonForm, PokemonName, PokemonTypes},
+        repositories::pokemon::Repository,
+    };
+
+    #[test]
+    fn it_should_forget_the_learnset_of_a_deleted_pokemon() {
+        let pokemons = SqliteRepository::try_new(":memory:").ok().unwrap();
+        let repo = SqliteMoveRepository::new(&pokemons);
+        pokemons
+            .insert(
+                PokemonNumber::pikachu(),
+                PokemonForm::default(),
+                PokemonName::pikachu(),
+                PokemonTypes::pikachu(),
+                None,
+            )
+            .ok();
+        for r#move in [Move::growl(), Move::thunder_shock(), Move::thunderbolt()] {
+            repo.insert(r#move).ok();
+        }
+        repo.set_learnset(PokemonNumber::pikachu(), Learnset::pikachu())
+            .ok();
+
+        pokemons
+            .delete(PokemonNumber::pikachu(), PokemonForm::default())
+            .ok();
+
+        match repo.fetch_learners(MoveName::thunderbolt()) {
+            Ok(learners) => assert!(learners.is_empty()),
+            _ => unreachable!(),
+        };
+    }
+
+    mod contract {
+        use super::{SqliteMoveRepository, SqliteRepository};
+        use crate::domain::entities::{PokemonForm, PokemonName, PokemonNumber, PokemonTypes};
+        use crate::repositories::{
+            contract_tests,
+            moves::{contract, MoveRepository},
+            pokemon::Repository,
+        };
+        use tempfile::TempDir;
+
+        /// The learnsets need the pokemons to exist in the same database.
+        fn factory() -> (std::sync::Arc<dyn MoveRepository>, TempDir) {
+            let dir = tempfile::tempdir().expect("failed to create a temporary directory");
+            let path = dir.path().join("pokedex.db");
+            let pokemons = match SqliteRepository::try_new(path.to_str().unwrap()) {
+                Ok(repo) => repo,
+                _ => unreachable!(),
+            };
+            for (number, name, types) in [
+                (
+                    PokemonNumber::pikachu(),
+                    PokemonName::pikachu(),
+                    PokemonTypes::pikachu(),
+                ),
+                (
+                    PokemonNumber::charmander(),
+                    PokemonName::charmander(),
+                    PokemonTypes::charmander(),
+                ),
+                (
+                    PokemonNumber::charizard(),
+                    PokemonName::charizard(),
+                    PokemonTypes::charizard(),
+                ),
+            ] {
+                pokemons
+                    .insert(number, PokemonForm::default(), name, types, None)
+                    .ok();
+            }
+            (
+                std::sync::Arc::new(SqliteMoveRepository::new(&pokemons)),
+                dir,
+            )
+        }
+
+        contract_tests!(
+            factory,
+            contract,
+            [
+                it_should_return_the_inserted_move,
+                it_should_refuse_to_insert_an_existing_name,
+                it_should_fetch_all_by_name,
+                it_should_not_find_a_missing_move,
+                it_should_return_an_empty_learnset_for_a_pokemon_without_one,
+                it_should_replace_the_learnset_of_a_pokemon,
+                it_should_refuse_an_unknown_move_and_keep_the_previous_learnset,
+                it_should_find_the_pokemons_learning_a_move,
+            ]
+        );
+    }
+}
@@ -1,174 +1,973 @@
+use std::{
+    sync::Mutex,
+    thread,
+    time::{Duration, Instant},
+};
+
+use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
 use serde::Deserialize;
+use ureq::serde_json::{Map, Value};
+
+use crate::domain::entities::{
+    BaseStats, Pokemon, PokemonForm, PokemonName, PokemonNumber, PokemonTypes, Stat,
+    ValidationError,
+};
+
+mod fake;
+pub use fake::FakeAirtable;
+
+use super::{
+    DeleteError, FetchAllError, FetchAllPage, FetchAllQuery, FetchOneError, InsertError,
+    Repository, RepositoryError, SortDirection, SortKey, UpdateError,
+};
+
+pub const DEFAULT_API_URL: &str = "https://api.airtable.com";
+pub const DEFAULT_TABLE: &str = "pokemons";
+pub const DEFAULT_EVOLUTIONS_TABLE: &str = "evolutions";
+
+pub struct AirtableConfig {
+    /// Root of the REST API, overridden to run against a local server.
+    pub api_url: String,
+    pub api_key: String,
+    pub base_id: String,
+    pub table: String,
+    /// Table of the evolutions, in the same base as the pokemons.
+    pub evolutions_table: String,
+    pub fields: AirtableFieldNames,
+    pub evolution_fields: AirtableEvolutionFieldNames,
+}
+
+impl AirtableConfig {
+    pub fn new(api_key: &str, base_id: &str) -> Self {
+        Self {
+            api_url: String::from(DEFAULT_API_URL),
+            api_key: String::from(api_key),
+            base_id: String::from(base_id),
+            table: String::from(DEFAULT_TABLE),
+            evolutions_table: String::from(DEFAULT_EVOLUTIONS_TABLE),
+            fields: AirtableFieldNames::default(),
+            evolution_fields: AirtableEvolutionFieldNames::default(),
+        }
+    }
+
+    fn table_url(&self, table: &str) -> String {
+        format!(
+            "{}/v0/{}/{}",
+            self.api_url.trim_end_matches('/'),
+            utf8_percent_encode(&self.base_id, NON_ALPHANUMERIC),
+            utf8_percent_encode(table, NON_ALPHANUMERIC),
+        )
+    }
+}
+
+/// Names of the table columns holding each Pokemon attribute. The stats
+/// total is written too, so that listings can be sorted by it.
+#[derive(Clone)]
+pub struct AirtableFieldNames {
+    pub number: String,
+    /// Left empty for the default form, as in the records written before forms.
+    pub form: String,
+    pub name: String,
+    pub types: String,
+    pub hp: String,
+    pub attack: String,
+    pub defense: String,
+    pub special_attack: String,
+    pub special_defense: String,
+    pub speed: String,
+    pub total: String,
+}
+
+impl AirtableFieldNames {
+    pub fn columns(&self) -> [&str; 11] {
+        [
+            &self.number,
+            &self.form,
+            &self.name,
+            &self.types,
+            &self.hp,
+            &self.attack,
+            &self.defense,
+            &self.special_attack,
+            &self.special_defense,
+            &self.speed,
+            &self.total,
+        ]
+    }
+
+    pub fn stat(&self, stat: Stat) -> &str {
+        match stat {
+            Stat::Hp => &self.hp,
+            Stat::Attack => &self.attack,
+            Stat::Defense => &self.defense,
+            Stat::SpecialAttack => &self.special_attack,
+            Stat::SpecialDefense => &self.special_defense,
+            Stat::Speed => &self.speed,
+        }
+    }
+}
+
+impl Default for AirtableFieldNames {
+    fn default() -> Self {
+        Self {
+            number: String::from("number"),
+            form: String::from("form"),
+            name: String::from("name"),
+            types: String::from("types"),
+            hp: String::from("hp"),
+            attack: String::from("attack"),
+            defense: String::from("defense"),
+            special_attack: String::from("special_attack"),
+            special_defense: String::from("special_defense"),
+            speed: String::from("speed"),
+            total: String::from("total"),
+        }
+    }
+}
+
+/// Names of the columns of the evolutions table.
+#[derive(Clone)]
+pub struct AirtableEvolutionFieldNames {
+    pub from: String,
+    pub to: String,
+    pub trigger: String,
+    pub level: String,
+    pub item: String,
+}
 
-use crate::domain::entities::{Pokemon, PokemonName, PokemonNumber, PokemonTypes};
+impl AirtableEvolutionFieldNames {
+    pub fn columns(&self) -> [&str; 5] {
+        [&self.from, &self.to, &self.trigger, &self.level, &self.item]
+    }
+}
 
-use super::{DeleteError, FetchAllError, FetchOneError, InsertError, Repository};
+impl Default for AirtableEvolutionFieldNames {
+    fn default() -> Self {
+        Self {
+            from: String::from("from"),
+            to: String::from("to"),
+            trigger: String::from("trigger"),
+            level: String::from("level"),
+            item: String::from("item"),
+        }
+    }
+}
 
 pub struct AirtableRepository {
     url: String,
+    evolutions_url: String,
     auth_header: String,
+    fields: AirtableFieldNames,
+    evolution_fields: AirtableEvolutionFieldNames,
+    rate_limit: RateLimit,
+    last_request: Mutex<Option<Instant>>,
 }
-#[derive(Deserialize)]
-struct AirtableJson {
-    records: Vec<AirtableRecord>,
+
+struct RateLimit {
+    /// Minimum delay between two requests, Airtable allows 5 requests per second.
+    interval: Duration,
+    /// Delay before retrying a throttled request, doubled on each retry.
+    backoff: Duration,
+    max_retries: u32,
+}
+
+impl Default for RateLimit {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_millis(200),
+            backoff: Duration::from_secs(30),
+            max_retries: 3,
+        }
+    }
 }
 
 #[derive(Deserialize)]
-struct AirtableRecord {
-    id: String,
-    createdTime: String,
-    fields: AirtableFields,
+pub(crate) struct AirtableJson {
+    pub(crate) records: Vec<AirtableRecord>,
+    offset: Option<String>,
 }
 
 #[derive(Deserialize)]
-struct AirtableFields {
-    number: u16,
-    name: String,
-    types: Vec<String>,
+pub(crate) struct AirtableRecord {
+    pub(crate) id: String,
+    pub(crate) fields: Map<String, Value>,
 }
 
 impl AirtableRepository {
-    pub fn try_new(api_key: &str, workspace_id: &str) -> Result<Self, ()> {
-        let url = format!("https://api.airtable.com/v0/app4rbiOzPiOCE20j/pokemons");
-        let auth_header = format!("Bearer {}", api_key);
+    pub fn try_new(config: AirtableConfig) -> Result<Self, RepositoryError> {
+        let repo = Self::new(config);
+
+        if let Err(e) = repo.send(repo.request("GET", &repo.url), None) {
+            return Err(RepositoryError::caused_by(
+                format!("cannot reach the table {}", repo.url),
+                e,
+            ));
+        }
+        Ok(repo)
+    }
 
-        let res = ureq::get(&url).set("Authorization", &auth_header).call();
-        if let Err(_) = res {
-            return Err(());
+    fn new(config: AirtableConfig) -> Self {
+        Self {
+            url: config.table_url(&config.table),
+            evolutions_url: config.table_url(&config.evolutions_table),
+            auth_header: format!("Bearer {}", config.api_key),
+            fields: config.fields,
+            evolution_fields: config.evolution_fields,
+            rate_limit: RateLimit::default(),
+            last_request: Mutex::new(None),
         }
-        Ok(Self { url, auth_header })
     }
 
-    fn fetch_pokemon_rows(&self, number: Option<u16>) -> Result<AirtableJson, ()> {
-        // code will go here
-        let url = match number {
-            Some(number) => format!("{}?filterByFormula=number%3D{}", self.url, number),
-            None => format!("{}?sort%5B0%5D%5Bfield%5D=number", self.url),
+    /// The evolutions table, reached through the same client so that both
+    /// tables share the rate limit of the base.
+    pub(crate) fn evolutions_url(&self) -> &str {
+        &self.evolutions_url
+    }
+
+    pub(crate) fn evolution_fields(&self) -> &AirtableEvolutionFieldNames {
+        &self.evolution_fields
+    }
+
+    /// Gathers the records of the pages from `cursor` on, following the
+    /// offsets to the last page unless only one page is wanted.
+    pub(crate) fn fetch_pages<E>(
+        cursor: Option<String>,
+        one_page: bool,
+        mut fetch_page: impl FnMut(Option<String>) -> Result<AirtableJson, E>,
+    ) -> Result<(Vec<AirtableRecord>, Option<String>), E> {
+        let mut cursor = cursor;
+        let mut records = vec![];
+        let next_cursor = loop {
+            let mut json = fetch_page(cursor)?;
+            records.append(&mut json.records);
+            match json.offset {
+                Some(offset) if !one_page => cursor = Some(offset),
+                offset => break offset,
+            }
         };
-        let res = match ureq::get(&url)
-            .set("Authorization", &self.auth_header)
-            .call()
-        {
+        Ok((records, next_cursor))
+    }
+
+    pub(crate) fn request(&self, method: &str, url: &str) -> ureq::Request {
+        ureq::request(method, url).set("Authorization", &self.auth_header)
+    }
+
+    /// Sends a request no faster than the rate limit allows, backing off and
+    /// retrying while Airtable answers 429 Too Many Requests.
+    #[allow(clippy::result_large_err)]
+    pub(crate) fn send(
+        &self,
+        req: ureq::Request,
+        body: Option<ureq::serde_json::Value>,
+    ) -> Result<ureq::Response, ureq::Error> {
+        let mut backoff = self.rate_limit.backoff;
+        let mut retries = 0;
+        loop {
+            self.wait_for_turn();
+            let res = match body.clone() {
+                Some(body) => req.clone().send_json(body),
+                None => req.clone().call(),
+            };
+            match res {
+                Err(ureq::Error::Status(429, _)) if retries < self.rate_limit.max_retries => {
+                    thread::sleep(backoff);
+                    backoff *= 2;
+                    retries += 1;
+                }
+                res => return res,
+            }
+        }
+    }
+
+    fn wait_for_turn(&self) {
+        let mut last_request = match self.last_request.lock() {
+            Ok(lock) => lock,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        if let Some(last_request) = *last_request {
+            let elapsed = last_request.elapsed();
+            if elapsed < self.rate_limit.interval {
+                thread::sleep(self.rate_limit.interval - elapsed);
+            }
+        }
+        *last_request = Some(Instant::now());
+    }
+
+    fn fetch_pokemon_rows(&self, number: u16) -> Result<AirtableJson, RepositoryError> {
+        let req = self.request("GET", &self.url).query(
+            "filterByFormula",
+            &format!("{{{}}}={}", self.fields.number, number),
+        );
+        let res = match self.send(req, None) {
             Ok(res) => res,
-            _ => return Err(()),
+            Err(e) => {
+                return Err(RepositoryError::caused_by(
+                    format!("cannot look up pokemon {}", number),
+                    e,
+                ))
+            }
         };
+        Self::read_json(res)
+    }
+
+    /// Picks the record of the form among the records sharing a number.
+    fn find_record(&self, json: AirtableJson, form: &PokemonForm) -> Option<AirtableRecord> {
+        let form = String::from(form.clone());
+        json.records
+            .into_iter()
+            .find(|record| self.record_form(&record.fields) == form)
+    }
+
+    fn record_form<'a>(&self, fields: &'a Map<String, Value>) -> &'a str {
+        fields
+            .get(&self.fields.form)
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn read_json(res: ureq::Response) -> Result<AirtableJson, RepositoryError> {
         match res.into_json::<AirtableJson>() {
             Ok(json) => Ok(json),
-            _ => Err(()),
+            Err(e) => Err(RepositoryError::caused_by(
+                "cannot read the records returned by airtable",
+                e,
+            )),
         }
     }
+
+    fn fetch_pokemon_page(&self, query: &FetchAllQuery) -> Result<AirtableJson, FetchAllError> {
+        let field = match query.sort {
+            SortKey::Number => &self.fields.number,
+            SortKey::Name => &self.fields.name,
+            SortKey::Stat(stat) => self.fields.stat(stat),
+            SortKey::Total => &self.fields.total,
+        };
+        let direction = match query.direction {
+            SortDirection::Ascending => "asc",
+            SortDirection::Descending => "desc",
+        };
+        let mut req = self
+            .request("GET", &self.url)
+            .query("sort[0][field]", field)
+            .query("sort[0][direction]", direction)
+            .query("sort[1][field]", &self.fields.number)
+            .query("sort[1][direction]", direction);
+        if query.all_forms {
+            req = req
+                .query("sort[2][field]", &self.fields.form)
+                .query("sort[2][direction]", direction);
+        }
+        if let Some(formula) = self.filter_formula(query) {
+            req = req.query("filterByFormula", &formula);
+        }
+        if let Some(limit) = query.limit {
+            req = req.query("pageSize", &limit.to_string());
+        }
+        if let Some(cursor) = query.cursor.as_deref() {
+            req = req.query("offset", cursor);
+        }
+        let res = match self.send(req, None) {
+            Ok(res) => res,
+            Err(ureq::Error::Status(422, _)) if query.cursor.is_some() => {
+                return Err(FetchAllError::InvalidCursor)
+            }
+            Err(e) => {
+                return Err(FetchAllError::Unknown(RepositoryError::caused_by(
+                    "cannot list the pokemons",
+                    e,
+                )))
+            }
+        };
+        Self::read_json(res).map_err(FetchAllError::Unknown)
+    }
+
+    fn filter_formula(&self, query: &FetchAllQuery) -> Option<String> {
+        let mut conditions = vec![];
+        if !query.all_forms {
+            conditions.push(format!("{{{}}} = \"\"", self.fields.form));
+        }
+        if let Some(r#type) = query.r#type {
+            conditions.push(format!(
+                "FIND({}, ARRAYJOIN({{{}}}))",
+                Self::quote(&String::from(r#type)),
+                self.fields.types
+            ));
+        }
+        if let Some(prefix) = query.name_prefix.as_deref() {
+            conditions.push(format!(
+                "LEFT({{{}}}, {}) = {}",
+                self.fields.name,
+                prefix.chars().count(),
+                Self::quote(prefix)
+            ));
+        }
+        match conditions.len() {
+            0 => None,
+            1 => conditions.pop(),
+            _ => Some(format!("AND({})", conditions.join(", "))),
+        }
+    }
+
+    fn quote(value: &str) -> String {
+        format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+    }
+
+    fn read_pokemon(&self, fields: &Map<String, Value>) -> Result<Pokemon, RepositoryError> {
+        let number = fields
+            .get(&self.fields.number)
+            .and_then(Value::as_u64)
+            .and_then(|n| u16::try_from(n).ok());
+        let name = fields
+            .get(&self.fields.name)
+            .and_then(Value::as_str)
+            .map(String::from);
+        let form = String::from(self.record_form(fields));
+        let types = fields
+            .get(&self.fields.types)
+            .and_then(Value::as_array)
+            .map(|types| {
+                types
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .map(String::from)
+                    .collect::<Vec<String>>()
+            });
+        // Records written before stats were recorded have none of the stat fields.
+        let mut stats = [0; 6];
+        let mut has_stats = true;
+        for (i, stat) in Stat::ALL.into_iter().enumerate() {
+            match fields
+                .get(self.fields.stat(stat))
+                .and_then(Value::as_u64)
+                .map(|n| u16::try_from(n).unwrap_or(u16::MAX))
+            {
+                Some(n) => stats[i] = n,
+                None => has_stats = false,
+            }
+        }
+        match (number, name, types) {
+            (Some(number), Some(name), Some(types)) => match (
+                PokemonNumber::try_from(number),
+                PokemonForm::try_from(form).map_err(ValidationError::from),
+                PokemonName::try_from(name),
+                PokemonTypes::try_from(types),
+                has_stats
+                    .then(|| BaseStats::try_from(stats))
+                    .transpose()
+                    .map_err(ValidationError::from),
+            ) {
+                (Ok(number), Ok(form), Ok(name), Ok(types), Ok(stats)) => {
+                    Ok(Pokemon::new(number, form, name, types, stats))
+                }
+                (Err(e), _, _, _, _)
+                | (_, Err(e), _, _, _)
+                | (_, _, Err(e), _, _)
+                | (_, _, _, Err(e), _)
+                | (_, _, _, _, Err(e)) => Err(RepositoryError::caused_by(
+                    format!("pokemon {} is stored with an invalid field", number),
+                    e,
+                )),
+            },
+            (None, _, _) => Err(RepositoryError::new(format!(
+                "a record has no valid {} field",
+                self.fields.number
+            ))),
+            (_, None, _) => Err(RepositoryError::new(format!(
+                "a record has no valid {} field",
+                self.fields.name
+            ))),
+            (_, _, None) => Err(RepositoryError::new(format!(
+                "a record has no valid {} field",
+                self.fields.types
+            ))),
+        }
+    }
+
+    /// The number and form are only written on creation, the default form
+    /// being left empty.
+    fn write_fields(
+        &self,
+        identity: Option<(&PokemonNumber, &PokemonForm)>,
+        name: &PokemonName,
+        types: &PokemonTypes,
+        stats: Option<&BaseStats>,
+    ) -> Value {
+        let mut fields = Map::new();
+        if let Some((number, form)) = identity {
+            fields.insert(
+                self.fields.number.clone(),
+                Value::from(u16::from(number.clone())),
+            );
+            if !form.is_default() {
+                fields.insert(
+                    self.fields.form.clone(),
+                    Value::from(String::from(form.clone())),
+                );
+            }
+        }
+        fields.insert(
+            self.fields.name.clone(),
+            Value::from(String::from(name.clone())),
+        );
+        fields.insert(
+            self.fields.types.clone(),
+            Value::from(Vec::<String>::from(types.clone())),
+        );
+        for stat in Stat::ALL {
+            fields.insert(
+                String::from(self.fields.stat(stat)),
+                stats.map_or(Value::Null, |stats| Value::from(stats.get(stat))),
+            );
+        }
+        fields.insert(
+            self.fields.total.clone(),
+            stats.map_or(Value::Null, |stats| Value::from(stats.total())),
+        );
+        Value::Object(fields)
+    }
 }
 impl Repository for AirtableRepository {
     fn insert(
         &self,
-        number: crate::domain::entities::PokemonNumber,
-        name: crate::domain::entities::PokemonName,
-        types: crate::domain::entities::PokemonTypes,
-    ) -> Result<crate::domain::entities::Pokemon, super::InsertError> {
-        let json = match self.fetch_pokemon_rows(Some(u16::from(number.clone()))) {
+        number: PokemonNumber,
+        form: PokemonForm,
+        name: PokemonName,
+        types: PokemonTypes,
+        stats: Option<BaseStats>,
+    ) -> Result<Pokemon, InsertError> {
+        let json = match self.fetch_pokemon_rows(u16::from(number.clone())) {
             Ok(json) => json,
-            _ => return Err(InsertError::Unknown),
+            Err(e) => return Err(InsertError::Unknown(e)),
         };
 
-        if !json.records.is_empty() {
+        if self.find_record(json, &form).is_some() {
             return Err(InsertError::Conflict);
         }
         let body = ureq::json!({
             "records": [{
-                "fields": {
-                    "number": u16::from(number.clone()),
-                    "name": String::from(name.clone()),
-                    "types": Vec::<String>::from(types.clone()),
-                },
+                "fields": self.write_fields(Some((&number, &form)), &name, &types, stats.as_ref()),
             }],
         });
-        if let Err(_) = ureq::post(&self.url)
-            .set("Authorization", &self.auth_header)
-            .send_json(body)
-        {
-            return Err(InsertError::Unknown);
+        if let Err(e) = self.send(self.request("POST", &self.url), Some(body)) {
+            return Err(InsertError::Unknown(RepositoryError::caused_by(
+                "cannot create the record",
+                e,
+            )));
         }
 
-        Ok(Pokemon::new(number, name, types))
+        Ok(Pokemon::new(number, form, name, types, stats))
     }
 
-    fn fetch_all(&self) -> Result<Vec<crate::domain::entities::Pokemon>, super::FetchAllError> {
-        let json = match self.fetch_pokemon_rows(None) {
-            Ok(json) => json,
-            _ => return Err(FetchAllError::Unknown),
-        };
+    fn fetch_all(&self, query: FetchAllQuery) -> Result<FetchAllPage, FetchAllError> {
+        let mut query = query;
+        // Without a limit every page is wanted, so follow the offsets to the end.
+        let one_page = query.limit.is_some();
+        let (records, next_cursor) = Self::fetch_pages(query.cursor.take(), one_page, |cursor| {
+            query.cursor = cursor;
+            self.fetch_pokemon_page(&query)
+        })?;
         let mut pokemons = vec![];
 
-        for record in json.records.into_iter() {
-            match (
-                PokemonNumber::try_from(record.fields.number),
-                PokemonName::try_from(record.fields.name),
-                PokemonTypes::try_from(record.fields.types),
-            ) {
-                (Ok(number), Ok(name), Ok(types)) => {
-                    pokemons.push(Pokemon::new(number, name, types))
-                }
-                _ => return Err(FetchAllError::Unknown),
+        for record in records.into_iter() {
+            match self.read_pokemon(&record.fields) {
+                Ok(pokemon) => pokemons.push(pokemon),
+                Err(e) => return Err(FetchAllError::Unknown(e)),
             }
         }
 
-        Ok(pokemons)
+        Ok(FetchAllPage {
+            pokemons,
+            next_cursor,
+        })
     }
 
     fn fetch_one(
         &self,
-        number: crate::domain::entities::PokemonNumber,
-    ) -> Result<crate::domain::entities::Pokemon, super::FetchOneError> {
-        let mut json = match self.fetch_pokemon_rows(Some(u16::from(number.clone()))) {
+        number: PokemonNumber,
+        form: PokemonForm,
+    ) -> Result<Pokemon, FetchOneError> {
+        let json = match self.fetch_pokemon_rows(u16::from(number.clone())) {
             Ok(json) => json,
-            _ => return Err(FetchOneError::Unknown),
+            Err(e) => return Err(FetchOneError::Unknown(e)),
         };
-        if json.records.is_empty() {
-            return Err(FetchOneError::NotFound);
-        }
-
-        let record = json.records.remove(0);
-        match (
-            PokemonNumber::try_from(record.fields.number),
-            PokemonName::try_from(record.fields.name),
-            PokemonTypes::try_from(record.fields.types),
-        ) {
-            (Ok(number), Ok(name), Ok(types)) => Ok(Pokemon::new(number, name, types)),
-            _ => Err(FetchOneError::Unknown),
+        let record = match self.find_record(json, &form) {
+            Some(record) => record,
+            None => return Err(FetchOneError::NotFound),
+        };
+        match self.read_pokemon(&record.fields) {
+            Ok(pokemon) => Ok(pokemon),
+            Err(e) => Err(FetchOneError::Unknown(e)),
         }
     }
 
-    fn delete(
+    fn update(
         &self,
-        number: crate::domain::entities::PokemonNumber,
-    ) -> Result<(), super::DeleteError> {
-        let mut json = match self.fetch_pokemon_rows(Some(u16::from(number.clone()))) {
+        number: PokemonNumber,
+        form: PokemonForm,
+        name: PokemonName,
+        types: PokemonTypes,
+        stats: Option<BaseStats>,
+    ) -> Result<Pokemon, UpdateError> {
+        let json = match self.fetch_pokemon_rows(u16::from(number.clone())) {
             Ok(json) => json,
-            _ => return Err(DeleteError::Unknown),
+            Err(e) => return Err(UpdateError::Unknown(e)),
+        };
+        let record = match self.find_record(json, &form) {
+            Some(record) => record,
+            None => return Err(UpdateError::NotFound),
         };
+        let body = ureq::json!({
+            "fields": self.write_fields(None, &name, &types, stats.as_ref()),
+        });
+        let url = format!("{}/{}", self.url, record.id);
+        match self.send(self.request("PATCH", &url), Some(body)) {
+            Ok(_) => Ok(Pokemon::new(number, form, name, types, stats)),
+            Err(e) => Err(UpdateError::Unknown(RepositoryError::caused_by(
+                format!("cannot update the record {}", record.id),
+                e,
+            ))),
+        }
+    }
 
-        if json.records.is_empty() {
+    fn delete(&self, number: PokemonNumber, form: PokemonForm) -> Result<(), DeleteError> {
+        let json = match self.fetch_pokemon_rows(u16::from(number.clone())) {
+            Ok(json) => json,
+            Err(e) => return Err(DeleteError::Unknown(e)),
+        };
+        let form = String::from(form);
+        if !json
+            .records
+            .iter()
+            .any(|record| self.record_form(&record.fields) == form)
+        {
             return Err(DeleteError::NotFound);
         }
 
-        let record = json.records.remove(0);
-        match ureq::delete(&format!("{}/{}", self.url, record.id))
-            .set("Authorization", &self.auth_header)
-            .call()
+        // The default form takes the other forms of its number along.
+        for record in json
+            .records
+            .iter()
+            .filter(|record| form.is_empty() || self.record_form(&record.fields) == form)
         {
-            Ok(_) => Ok(()),
-            _ => Err(DeleteError::Unknown),
+            let url = format!("{}/{}", self.url, record.id);
+            if let Err(e) = self.send(self.request("DELETE", &url), None) {
+                return Err(DeleteError::Unknown(RepositoryError::caused_by(
+                    format!("cannot delete the record {}", record.id),
+                    e,
+                )));
+            }
         }
+        Ok(())
     }
 }
 
-#[test]
-fn test() {
-    let api_key = "key3qwcREeUm8u8QE";
-    let workspace_id = "wsp7OQ4vUAFY6v9M5";
-    let url = format!("https://api.airtable.com/v0/app4rbiOzPiOCE20j/pokemons");
-    let auth_header = format!("Bearer {}", api_key);
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc::Sender,
+        Arc,
+    };
+
+    use super::*;
+
+    /// Stand-in for the Airtable list endpoint, serving `total` fixture records
+    /// page by page and answering 429 to the first `throttled` requests.
+    struct FixtureServer {
+        url: String,
+        hits: Arc<AtomicUsize>,
+        stop: Sender<()>,
+    }
+
+    impl FixtureServer {
+        fn start(total: u16, throttled: usize) -> Self {
+            let hits = Arc::new(AtomicUsize::new(0));
+            let counter = hits.clone();
+            let server = rouille::Server::new("localhost:0", move |req| {
+                if counter.fetch_add(1, Ordering::SeqCst) < throttled {
+                    return rouille::Response::text("").with_status_code(429);
+                }
+                let start = match req.get_param("offset") {
+                    Some(offset) => match offset.parse::<u16>() {
+                        Ok(offset) => offset,
+                        _ => return rouille::Response::text("").with_status_code(422),
+                    },
+                    None => 0,
+                };
+                let page_size = req
+                    .get_param("pageSize")
+                    .and_then(|size| size.parse::<u16>().ok())
+                    .unwrap_or(100);
+                let end = total.min(start + page_size);
+                let mut body = ureq::json!({
+                    "records": (start..end).map(|i| record(i + 1)).collect::<Vec<_>>(),
+                });
+                if end < total {
+                    body["offset"] = ureq::json!(end.to_string());
+                }
+                rouille::Response::json(&body)
+            })
+            .expect("failed to start fixture server");
+            let url = format!("http://{}", server.server_addr());
+            let (_, stop) = server.stoppable();
+            Self { url, hits, stop }
+        }
+
+        fn repo(&self, interval: Duration) -> AirtableRepository {
+            AirtableRepository {
+                rate_limit: RateLimit {
+                    interval,
+                    backoff: Duration::from_millis(10),
+                    max_retries: 3,
+                },
+                ..AirtableRepository::new(self.config())
+            }
+        }
+
+        fn config(&self) -> AirtableConfig {
+            AirtableConfig {
+                api_url: self.url.clone(),
+                ..AirtableConfig::new("key", "appBase")
+            }
+        }
+
+        fn hits(&self) -> usize {
+            self.hits.load(Ordering::SeqCst)
+        }
+    }
+
+    impl Drop for FixtureServer {
+        fn drop(&mut self) {
+            self.stop.send(()).ok();
+        }
+    }
+
+    fn record(number: u16) -> ureq::serde_json::Value {
+        ureq::json!({
+            "id": format!("rec{}", number),
+            "createdTime": "2022-04-01T00:00:00.000Z",
+            "fields": {
+                "number": number,
+                "name": format!("Pokemon {}", number),
+                "types": ["Normal"],
+            },
+        })
+    }
+
+    #[test]
+    fn it_should_follow_offsets_to_fetch_every_page() {
+        let server = FixtureServer::start(250, 0);
+        let repo = server.repo(Duration::ZERO);
+
+        let res = repo.fetch_all(FetchAllQuery::default());
+
+        match res {
+            Ok(page) => {
+                assert_eq!(page.pokemons.len(), 250);
+                assert_eq!(u16::from(page.pokemons[249].number.clone()), 250);
+                assert!(page.next_cursor.is_none());
+            }
+            _ => unreachable!(),
+        };
+        assert_eq!(server.hits(), 3);
+    }
+
+    #[test]
+    fn it_should_fetch_a_single_page_when_a_limit_is_given() {
+        let server = FixtureServer::start(250, 0);
+        let repo = server.repo(Duration::ZERO);
+
+        let res = repo.fetch_all(FetchAllQuery {
+            limit: Some(50),
+            cursor: Some(String::from("100")),
+            ..FetchAllQuery::default()
+        });
+
+        match res {
+            Ok(page) => {
+                assert_eq!(page.pokemons.len(), 50);
+                assert_eq!(u16::from(page.pokemons[0].number.clone()), 101);
+                assert_eq!(page.next_cursor, Some(String::from("150")));
+            }
+            _ => unreachable!(),
+        };
+        assert_eq!(server.hits(), 1);
+    }
+
+    #[test]
+    fn it_should_report_an_invalid_cursor() {
+        let server = FixtureServer::start(10, 0);
+        let repo = server.repo(Duration::ZERO);
+
+        let res = repo.fetch_all(FetchAllQuery {
+            cursor: Some(String::from("itr-expired")),
+            ..FetchAllQuery::default()
+        });
+
+        match res {
+            Err(FetchAllError::InvalidCursor) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_retry_after_being_rate_limited() {
+        let server = FixtureServer::start(150, 2);
+        let repo = server.repo(Duration::ZERO);
+
+        let res = repo.fetch_all(FetchAllQuery::default());
+
+        match res {
+            Ok(page) => assert_eq!(page.pokemons.len(), 150),
+            _ => unreachable!(),
+        };
+        assert_eq!(server.hits(), 4);
+    }
 
-    let res = ureq::get(&url).set("Authorization", &auth_header).call();
-    println!("{:#?}", res);
+    #[test]
+    fn it_should_give_up_when_still_rate_limited_after_retries() {
+        let server = FixtureServer::start(10, 10);
+        let repo = server.repo(Duration::ZERO);
+
+        let res = repo.fetch_all(FetchAllQuery::default());
+
+        match res {
+            Err(FetchAllError::Unknown(e)) => {
+                match std::error::Error::source(&e).and_then(|e| e.downcast_ref::<ureq::Error>()) {
+                    Some(ureq::Error::Status(429, _)) => {}
+                    _ => unreachable!(),
+                }
+            }
+            _ => unreachable!(),
+        };
+        assert_eq!(server.hits(), 4);
+    }
+
+    #[test]
+    fn it_should_space_requests_by_the_rate_limit_interval() {
+        let server = FixtureServer::start(250, 0);
+        let repo = server.repo(Duration::from_millis(50));
+        let start = Instant::now();
+
+        let res = repo.fetch_all(FetchAllQuery::default());
+
+        assert!(res.is_ok());
+        assert!(start.elapsed() >= Duration::from_millis(100));
+    }
+
+    #[test]
+    fn it_should_use_the_configured_base_table_and_field_names() {
+        let requests = Arc::new(Mutex::new(vec![]));
+        let log = requests.clone();
+        let server = rouille::Server::new("localhost:0", move |req| {
+            let mut body = String::new();
+            if let Some(mut data) = req.data() {
+                std::io::Read::read_to_string(&mut data, &mut body).ok();
+            }
+            log.lock().unwrap().push((
+                req.method().to_string(),
+                req.raw_url().to_string(),
+                req.header("Authorization").map(String::from),
+                body,
+            ));
+            rouille::Response::json(&ureq::json!({
+                "records": [{
+                    "id": "rec1",
+                    "fields": { "No": 25, "Name": "Pikachu", "Types": ["Electric"] },
+                }],
+            }))
+        })
+        .expect("failed to start mock server");
+        let config = AirtableConfig {
+            api_url: format!("http://{}/", server.server_addr()),
+            table: String::from("My Pokedex"),
+            fields: AirtableFieldNames {
+                number: String::from("No"),
+                name: String::from("Name"),
+                types: String::from("Types"),
+                ..AirtableFieldNames::default()
+            },
+            ..AirtableConfig::new("secret", "appStaging")
+        };
+        let (_, stop) = server.stoppable();
+        let repo = AirtableRepository::new(config);
+
+        let fetched = repo.fetch_one(PokemonNumber::pikachu(), PokemonForm::default());
+        let updated = repo.update(
+            PokemonNumber::pikachu(),
+            PokemonForm::default(),
+            PokemonName::charmander(),
+            PokemonTypes::charmander(),
+            None,
+        );
+        stop.send(()).ok();
+
+        match fetched {
+            Ok(pokemon) => assert_eq!(String::from(pokemon.name), "Pikachu"),
+            _ => unreachable!(),
+        };
+        assert!(updated.is_ok());
+        let requests = requests.lock().unwrap();
+        let (method, url, auth, _) = &requests[0];
+        assert_eq!(method, "GET");
+        assert_eq!(
+            url,
+            "/v0/appStaging/My%20Pokedex?filterByFormula=%7BNo%7D%3D25"
+        );
+        assert_eq!(auth.as_deref(), Some("Bearer secret"));
+        let (method, url, _, body) = &requests[2];
+        assert_eq!(method, "PATCH");
+        assert_eq!(url, "/v0/appStaging/My%20Pokedex/rec1");
+        assert_eq!(
+            ureq::serde_json::from_str::<Value>(body).ok(),
+            Some(ureq::json!({ "fields": {
+                "Name": "Charmander",
+                "Types": ["Fire"],
+                "hp": null,
+                "attack": null,
+                "defense": null,
+                "special_attack": null,
+                "special_defense": null,
+                "speed": null,
+                "total": null,
+            } }))
+        );
+    }
+
+    mod contract {
+        use super::fake::FakeAirtable;
+        use crate::repositories::{
+            contract_tests,
+            pokemon::{contract, Repository},
+        };
+
+        fn factory() -> (std::sync::Arc<dyn Repository>, FakeAirtable) {
+            let server = FakeAirtable::start().expect("failed to start the fake airtable server");
+            (std::sync::Arc::new(server.repo()), server)
+        }
+
+        contract_tests!(
+            factory,
+            contract,
+            [
+                it_should_return_the_inserted_pokemon,
+                it_should_refuse_to_insert_an_existing_number,
+                it_should_fetch_an_inserted_pokemon_with_all_its_fields,
+                it_should_not_find_a_missing_pokemon,
+                it_should_update_the_name_and_types,
+                it_should_not_update_a_missing_pokemon,
+                it_should_delete_a_pokemon_and_free_its_number,
+                it_should_not_delete_a_missing_pokemon,
+                it_should_keep_a_form_apart_from_the_default_one,
+                it_should_only_list_the_other_forms_when_asked,
+                it_should_delete_the_other_forms_along_with_the_default_one,
+                it_should_fetch_all_by_ascending_number_by_default,
+                it_should_sort_by_name_or_by_descending_number,
+                it_should_filter_by_type_and_name_prefix,
+                it_should_page_through_every_pokemon_exactly_once,
+                it_should_reject_an_unknown_cursor,
+                it_should_reject_a_cursor_too_large_for_its_page,
+            ]
+        );
+    }
 }
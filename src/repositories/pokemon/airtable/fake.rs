@@ -0,0 +1,736 @@
+//! In-memory stand-in for the part of the Airtable REST API that
+//! `AirtableRepository` talks to: listing with `filterByFormula`, sorting
+//! and pagination, creating, updating and deleting records, all behind
+//! bearer authentication. Used by the tests and by `--fake-airtable`.
+
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, HashMap},
+    io::Read,
+    sync::{mpsc::Sender, Mutex},
+};
+
+use ureq::serde_json::{Map, Value};
+
+use super::AirtableConfig;
+#[cfg(test)]
+use super::{AirtableRepository, RateLimit};
+
+const MAX_PAGE_SIZE: usize = 100;
+pub const FAKE_API_KEY: &str = "keyFake";
+pub const FAKE_BASE_ID: &str = "appFake";
+
+pub struct FakeAirtable {
+    url: String,
+    stop: Sender<()>,
+}
+
+/// Tables are created on first use, keyed by base id and table name.
+type Tables = HashMap<(String, String), Table>;
+
+struct Record {
+    id: String,
+    fields: Map<String, Value>,
+}
+
+#[derive(Default)]
+struct Table {
+    next_id: u64,
+    records: Vec<Record>,
+}
+
+impl FakeAirtable {
+    /// Serves on a free local port until dropped.
+    pub fn start() -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        let tables = Mutex::new(Tables::new());
+        let auth_header = format!("Bearer {}", FAKE_API_KEY);
+        let server = rouille::Server::new("localhost:0", move |req| {
+            if req.header("Authorization") != Some(auth_header.as_str()) {
+                return error(401, "AUTHENTICATION_REQUIRED");
+            }
+            let mut tables = match tables.lock() {
+                Ok(lock) => lock,
+                Err(poisoned) => poisoned.into_inner(),
+            };
+            handle(&mut tables, req)
+        })?;
+        let url = format!("http://{}", server.server_addr());
+        let (_, stop) = server.stoppable();
+        Ok(Self { url, stop })
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn config(&self) -> AirtableConfig {
+        AirtableConfig {
+            api_url: self.url.clone(),
+            ..AirtableConfig::new(FAKE_API_KEY, FAKE_BASE_ID)
+        }
+    }
+
+    /// A repository on this server, sending its requests without delay.
+    #[cfg(test)]
+    pub fn repo(&self) -> AirtableRepository {
+        AirtableRepository {
+            rate_limit: RateLimit {
+                interval: std::time::Duration::ZERO,
+                ..RateLimit::default()
+            },
+            ..AirtableRepository::new(self.config())
+        }
+    }
+}
+
+impl Drop for FakeAirtable {
+    fn drop(&mut self) {
+        self.stop.send(()).ok();
+    }
+}
+
+fn handle(tables: &mut Tables, req: &rouille::Request) -> rouille::Response {
+    let url = req.url();
+    let segments = url.trim_matches('/').split('/').collect::<Vec<&str>>();
+    let (base, name, id) = match &segments[..] {
+        ["v0", base, name] => (base, name, None),
+        ["v0", base, name, id] => (base, name, Some(*id)),
+        _ => return error(404, "NOT_FOUND"),
+    };
+    let table = tables
+        .entry((base.to_string(), name.to_string()))
+        .or_default();
+    match (req.method(), id) {
+        ("GET", None) => list(table, req),
+        ("POST", None) => create(table, req),
+        ("PATCH", Some(id)) => update(table, id, req),
+        ("DELETE", Some(id)) => delete(table, id),
+        _ => error(404, "NOT_FOUND"),
+    }
+}
+
+fn list(table: &Table, req: &rouille::Request) -> rouille::Response {
+    let params = form_urlencoded::parse(req.raw_query_string().as_bytes())
+        .into_owned()
+        .collect::<Vec<(String, String)>>();
+    let param = |name: &str| {
+        params
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.clone())
+    };
+
+    let formula = match param("filterByFormula").map(|f| formula::parse(&f)) {
+        Some(Ok(formula)) => Some(formula),
+        Some(Err(())) => return error(422, "INVALID_FILTER_BY_FORMULA"),
+        None => None,
+    };
+    let page_size = match param("pageSize").map(|size| size.parse::<usize>()) {
+        Some(Ok(size)) if (1..=MAX_PAGE_SIZE).contains(&size) => size,
+        Some(_) => return error(422, "INVALID_PAGE_SIZE"),
+        None => MAX_PAGE_SIZE,
+    };
+    let start = match param("offset").map(|offset| read_offset(&offset)) {
+        Some(Some(start)) => start,
+        Some(None) => return error(422, "LIST_RECORDS_ITERATOR_NOT_AVAILABLE"),
+        None => 0,
+    };
+
+    let mut records = table
+        .records
+        .iter()
+        .filter(|record| match &formula {
+            Some(formula) => formula::eval(formula, &record.fields).is_truthy(),
+            None => true,
+        })
+        .collect::<Vec<&Record>>();
+    let sorts = sorts(&params);
+    records.sort_by(|a, b| {
+        sorts
+            .iter()
+            .map(|(field, descending)| {
+                let ordering =
+                    formula::field(&a.fields, field).cmp(&formula::field(&b.fields, field));
+                if *descending {
+                    ordering.reverse()
+                } else {
+                    ordering
+                }
+            })
+            .find(|ordering| *ordering != Ordering::Equal)
+            .unwrap_or(Ordering::Equal)
+    });
+
+    let end = records.len().min(start.saturating_add(page_size));
+    let mut body = ureq::json!({
+        "records": records
+            .get(start..end)
+            .unwrap_or_default()
+            .iter()
+            .map(|record| json(record))
+            .collect::<Vec<Value>>(),
+    });
+    if end < records.len() {
+        body["offset"] = Value::from(format!("itr{}", end));
+    }
+    rouille::Response::json(&body)
+}
+
+fn read_offset(offset: &str) -> Option<usize> {
+    offset.strip_prefix("itr").and_then(|n| n.parse().ok())
+}
+
+/// Reads the `sort[i][field]` and `sort[i][direction]` parameters, in order.
+fn sorts(params: &[(String, String)]) -> Vec<(String, bool)> {
+    let mut sorts = BTreeMap::<usize, (String, bool)>::new();
+    for (key, value) in params {
+        let rest = match key.strip_prefix("sort[") {
+            Some(rest) => rest,
+            None => continue,
+        };
+        let (index, attribute) = match rest.split_once("][") {
+            Some((index, attribute)) => (index, attribute.trim_end_matches(']')),
+            None => continue,
+        };
+        let index = match index.parse::<usize>() {
+            Ok(index) => index,
+            Err(_) => continue,
+        };
+        let sort = sorts.entry(index).or_default();
+        match attribute {
+            "field" => sort.0 = value.clone(),
+            "direction" => sort.1 = value == "desc",
+            _ => {}
+        }
+    }
+    sorts.into_values().collect()
+}
+
+fn create(table: &mut Table, req: &rouille::Request) -> rouille::Response {
+    let body = match read_json(req) {
+        Some(body) => body,
+        None => return error(422, "INVALID_REQUEST_BODY"),
+    };
+    let records = match body["records"].as_array() {
+        Some(records) if !records.is_empty() && records.len() <= 10 => records.clone(),
+        _ => return error(422, "INVALID_RECORDS"),
+    };
+    let mut created = vec![];
+    for record in records {
+        let mut fields = match record["fields"].as_object() {
+            Some(fields) => fields.clone(),
+            None => return error(422, "INVALID_RECORDS"),
+        };
+        // Like Airtable, an empty cell is not returned at all.
+        fields.retain(|_, value| !value.is_null());
+        table.next_id += 1;
+        let record = Record {
+            id: format!("rec{:014}", table.next_id),
+            fields,
+        };
+        created.push(json(&record));
+        table.records.push(record);
+    }
+    rouille::Response::json(&ureq::json!({ "records": created }))
+}
+
+fn update(table: &mut Table, id: &str, req: &rouille::Request) -> rouille::Response {
+    let fields = match read_json(req).and_then(|body| body["fields"].as_object().cloned()) {
+        Some(fields) => fields,
+        None => return error(422, "INVALID_REQUEST_BODY"),
+    };
+    match table.records.iter_mut().find(|record| record.id == id) {
+        Some(record) => {
+            record.fields.extend(fields);
+            record.fields.retain(|_, value| !value.is_null());
+            rouille::Response::json(&json(record))
+        }
+        None => error(404, "NOT_FOUND"),
+    }
+}
+
+fn delete(table: &mut Table, id: &str) -> rouille::Response {
+    match table.records.iter().position(|record| record.id == id) {
+        Some(index) => {
+            table.records.remove(index);
+            rouille::Response::json(&ureq::json!({ "id": id, "deleted": true }))
+        }
+        None => error(404, "NOT_FOUND"),
+    }
+}
+
+fn read_json(req: &rouille::Request) -> Option<Value> {
+    let mut body = String::new();
+    req.data()?.read_to_string(&mut body).ok()?;
+    ureq::serde_json::from_str(&body).ok()
+}
+
+fn json(record: &Record) -> Value {
+    ureq::json!({
+        "id": record.id,
+        "createdTime": "2022-04-01T00:00:00.000Z",
+        "fields": record.fields,
+    })
+}
+
+fn error(status: u16, r#type: &str) -> rouille::Response {
+    rouille::Response::json(&ureq::json!({ "error": { "type": r#type } })).with_status_code(status)
+}
+
+/// Just enough of the Airtable formula language for the filters the adapter
+/// builds: field references, text and number literals, `=`/`!=` and the
+/// `AND`, `OR`, `NOT`, `FIND`, `ARRAYJOIN` and `LEFT` functions.
+mod formula {
+    use std::cmp::Ordering;
+
+    use ureq::serde_json::{Map, Value};
+
+    pub enum Expr {
+        Field(String),
+        Text(String),
+        Number(f64),
+        Call(String, Vec<Expr>),
+        Equals(Box<Expr>, Box<Expr>, bool),
+    }
+
+    #[derive(Debug, PartialEq)]
+    pub enum Data {
+        Blank,
+        Bool(bool),
+        Number(f64),
+        Text(String),
+        List(Vec<Data>),
+    }
+
+    impl Data {
+        pub fn is_truthy(&self) -> bool {
+            match self {
+                Data::Blank => false,
+                Data::Bool(b) => *b,
+                Data::Number(n) => *n != 0.0,
+                Data::Text(t) => !t.is_empty(),
+                Data::List(l) => !l.is_empty(),
+            }
+        }
+
+        fn text(&self) -> String {
+            match self {
+                Data::Blank => String::new(),
+                Data::Bool(b) => String::from(if *b { "1" } else { "0" }),
+                Data::Number(n) if n.fract() == 0.0 => format!("{}", *n as i64),
+                Data::Number(n) => n.to_string(),
+                Data::Text(t) => t.clone(),
+                Data::List(l) => l.iter().map(Data::text).collect::<Vec<_>>().join(", "),
+            }
+        }
+
+        fn number(&self) -> Option<f64> {
+            match self {
+                Data::Number(n) => Some(*n),
+                Data::Bool(b) => Some(if *b { 1.0 } else { 0.0 }),
+                Data::Text(t) => t.trim().parse().ok(),
+                _ => None,
+            }
+        }
+
+        pub fn cmp(&self, other: &Data) -> Ordering {
+            match (self, other) {
+                (Data::Blank, Data::Blank) => Ordering::Equal,
+                (Data::Blank, _) => Ordering::Less,
+                (_, Data::Blank) => Ordering::Greater,
+                (Data::Number(a), Data::Number(b)) => a.partial_cmp(b).unwrap_or(Ordering::Equal),
+                (a, b) => a.text().cmp(&b.text()),
+            }
+        }
+    }
+
+    pub fn field(fields: &Map<String, Value>, name: &str) -> Data {
+        match fields.get(name) {
+            Some(value) => data(value),
+            None => Data::Blank,
+        }
+    }
+
+    fn data(value: &Value) -> Data {
+        match value {
+            Value::Null => Data::Blank,
+            Value::Bool(b) => Data::Bool(*b),
+            Value::Number(n) => Data::Number(n.as_f64().unwrap_or_default()),
+            Value::String(s) => Data::Text(s.clone()),
+            Value::Array(values) => Data::List(values.iter().map(data).collect()),
+            Value::Object(_) => Data::Blank,
+        }
+    }
+
+    pub fn eval(expr: &Expr, fields: &Map<String, Value>) -> Data {
+        match expr {
+            Expr::Field(name) => field(fields, name),
+            Expr::Text(text) => Data::Text(text.clone()),
+            Expr::Number(n) => Data::Number(*n),
+            Expr::Equals(a, b, negated) => {
+                let (a, b) = (eval(a, fields), eval(b, fields));
+                let equal = match (a.number(), b.number()) {
+                    (Some(x), Some(y))
+                        if !matches!(a, Data::Text(_)) || !matches!(b, Data::Text(_)) =>
+                    {
+                        x == y
+                    }
+                    _ => a.text() == b.text(),
+                };
+                Data::Bool(equal != *negated)
+            }
+            Expr::Call(name, args) => {
+                let args = args
+                    .iter()
+                    .map(|arg| eval(arg, fields))
+                    .collect::<Vec<Data>>();
+                call(name, args)
+            }
+        }
+    }
+
+    fn call(name: &str, args: Vec<Data>) -> Data {
+        match (name, &args[..]) {
+            ("AND", args) => Data::Bool(args.iter().all(Data::is_truthy)),
+            ("OR", args) => Data::Bool(args.iter().any(Data::is_truthy)),
+            ("NOT", [arg]) => Data::Bool(!arg.is_truthy()),
+            ("FIND", [needle, haystack]) => {
+                let (needle, haystack) = (needle.text(), haystack.text());
+                match haystack.find(&needle) {
+                    Some(index) => Data::Number((haystack[..index].chars().count() + 1) as f64),
+                    None => Data::Number(0.0),
+                }
+            }
+            ("ARRAYJOIN", [list]) => Data::Text(list.text()),
+            ("ARRAYJOIN", [Data::List(list), separator]) => Data::Text(
+                list.iter()
+                    .map(Data::text)
+                    .collect::<Vec<String>>()
+                    .join(&separator.text()),
+            ),
+            ("LEFT", [text, count]) => Data::Text(
+                text.text()
+                    .chars()
+                    .take(count.number().unwrap_or_default() as usize)
+                    .collect(),
+            ),
+            _ => Data::Blank,
+        }
+    }
+
+    pub fn parse(formula: &str) -> Result<Expr, ()> {
+        let mut parser = Parser {
+            chars: formula.chars().collect(),
+            position: 0,
+        };
+        let expr = parser.expr()?;
+        parser.skip_spaces();
+        if parser.position == parser.chars.len() {
+            Ok(expr)
+        } else {
+            Err(())
+        }
+    }
+
+    struct Parser {
+        chars: Vec<char>,
+        position: usize,
+    }
+
+    impl Parser {
+        fn peek(&self) -> Option<char> {
+            self.chars.get(self.position).copied()
+        }
+
+        fn skip_spaces(&mut self) {
+            while self.peek().is_some_and(char::is_whitespace) {
+                self.position += 1;
+            }
+        }
+
+        fn eat(&mut self, c: char) -> bool {
+            self.skip_spaces();
+            if self.peek() == Some(c) {
+                self.position += 1;
+                true
+            } else {
+                false
+            }
+        }
+
+        fn expr(&mut self) -> Result<Expr, ()> {
+            let left = self.primary()?;
+            if self.eat('!') {
+                if !self.eat('=') {
+                    return Err(());
+                }
+                return Ok(Expr::Equals(
+                    Box::new(left),
+                    Box::new(self.primary()?),
+                    true,
+                ));
+            }
+            if self.eat('=') {
+                return Ok(Expr::Equals(
+                    Box::new(left),
+                    Box::new(self.primary()?),
+                    false,
+                ));
+            }
+            Ok(left)
+        }
+
+        fn primary(&mut self) -> Result<Expr, ()> {
+            self.skip_spaces();
+            match self.peek() {
+                Some('{') => {
+                    self.position += 1;
+                    let name = self.take_while(|c| c != '}');
+                    if !self.eat('}') {
+                        return Err(());
+                    }
+                    Ok(Expr::Field(name))
+                }
+                Some(quote @ ('"' | '\'')) => {
+                    self.position += 1;
+                    let mut text = String::new();
+                    loop {
+                        match self.peek() {
+                            Some('\\') => {
+                                self.position += 1;
+                                text.extend(self.peek());
+                            }
+                            Some(c) if c == quote => break,
+                            Some(c) => text.push(c),
+                            None => return Err(()),
+                        }
+                        self.position += 1;
+                    }
+                    self.position += 1;
+                    Ok(Expr::Text(text))
+                }
+                Some('(') => {
+                    self.position += 1;
+                    let expr = self.expr()?;
+                    if self.eat(')') {
+                        Ok(expr)
+                    } else {
+                        Err(())
+                    }
+                }
+                Some(c) if c.is_ascii_digit() || c == '-' => {
+                    let number = self.take_while(|c| c.is_ascii_digit() || c == '.' || c == '-');
+                    number.parse().map(Expr::Number).map_err(|_| ())
+                }
+                Some(c) if c.is_ascii_alphabetic() => {
+                    let name = self
+                        .take_while(|c| c.is_ascii_alphanumeric())
+                        .to_uppercase();
+                    if !self.eat('(') {
+                        return Err(());
+                    }
+                    let mut args = vec![];
+                    if !self.eat(')') {
+                        loop {
+                            args.push(self.expr()?);
+                            if self.eat(')') {
+                                break;
+                            }
+                            if !self.eat(',') {
+                                return Err(());
+                            }
+                        }
+                    }
+                    Ok(Expr::Call(name, args))
+                }
+                _ => Err(()),
+            }
+        }
+
+        fn take_while(&mut self, predicate: impl Fn(char) -> bool) -> String {
+            let start = self.position;
+            while self.peek().is_some_and(&predicate) {
+                self.position += 1;
+            }
+            self.chars[start..self.position].iter().collect()
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn matches(formula: &str) -> bool {
+            let fields =
+                ureq::json!({ "number": 6, "name": "Charizard", "types": ["Fire", "Flying"] });
+            eval(&parse(formula).ok().unwrap(), fields.as_object().unwrap()).is_truthy()
+        }
+
+        #[test]
+        fn it_should_evaluate_the_formulas_built_by_the_adapter() {
+            assert!(matches("{number}=6"));
+            assert!(!matches("{number}=25"));
+            assert!(matches(r#"FIND("Flying", ARRAYJOIN({types}))"#));
+            assert!(!matches(r#"FIND("Water", ARRAYJOIN({types}))"#));
+            assert!(matches(r#"LEFT({name}, 4) = "Char""#));
+            assert!(matches(
+                r#"AND(FIND("Fire", ARRAYJOIN({types})), LEFT({name}, 5) = "Chari")"#
+            ));
+            assert!(!matches(r#"AND({number}=6, NOT({name}="Charizard"))"#));
+            assert!(matches(r#"{name} != "Pikachu""#));
+        }
+
+        #[test]
+        fn it_should_unescape_quoted_text() {
+            let fields = ureq::json!({ "name": "Farfetch\"d" });
+
+            let res = eval(
+                &parse(r#"{name} = "Farfetch\"d""#).ok().unwrap(),
+                fields.as_object().unwrap(),
+            );
+
+            assert_eq!(res, Data::Bool(true));
+        }
+
+        #[test]
+        fn it_should_reject_malformed_formulas() {
+            assert!(parse("AND(").is_err());
+            assert!(parse("{number").is_err());
+            assert!(parse("{number}=6)").is_err());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn send(
+        server: &FakeAirtable,
+        method: &str,
+        path: &str,
+        key: &str,
+        body: Option<Value>,
+    ) -> (u16, Option<Value>) {
+        let req = ureq::request(
+            method,
+            &format!("{}/v0/{}/pokemons{}", server.url(), FAKE_BASE_ID, path),
+        )
+        .set("Authorization", &format!("Bearer {}", key));
+        let res = match body {
+            Some(body) => req.send_json(body),
+            None => req.call(),
+        };
+        match res {
+            Ok(res) => (res.status(), res.into_json().ok()),
+            Err(ureq::Error::Status(status, res)) => (status, res.into_json().ok()),
+            Err(_) => unreachable!(),
+        }
+    }
+
+    fn create(server: &FakeAirtable, number: u16, name: &str) -> String {
+        let (_, body) = send(
+            server,
+            "POST",
+            "",
+            FAKE_API_KEY,
+            Some(ureq::json!({ "records": [{ "fields": { "number": number, "name": name } }] })),
+        );
+        String::from(body.unwrap()["records"][0]["id"].as_str().unwrap())
+    }
+
+    #[test]
+    fn it_should_require_the_api_key() {
+        let server = FakeAirtable::start().expect("failed to start the fake airtable server");
+
+        let (status, body) = send(&server, "GET", "", "keyWrong", None);
+
+        assert_eq!(status, 401);
+        assert_eq!(body.unwrap()["error"]["type"], "AUTHENTICATION_REQUIRED");
+    }
+
+    #[test]
+    fn it_should_list_the_created_records_with_a_formula_and_a_sort() {
+        let server = FakeAirtable::start().expect("failed to start the fake airtable server");
+        create(&server, 25, "Pikachu");
+        create(&server, 4, "Charmander");
+        create(&server, 6, "Charizard");
+
+        let (status, body) = send(
+            &server,
+            "GET",
+            "?filterByFormula=LEFT(%7Bname%7D%2C%204)%20%3D%20%22Char%22&sort%5B0%5D%5Bfield%5D=number&sort%5B0%5D%5Bdirection%5D=desc",
+            FAKE_API_KEY,
+            None,
+        );
+
+        assert_eq!(status, 200);
+        let body = body.unwrap();
+        assert_eq!(body["records"][0]["fields"]["number"], 6);
+        assert_eq!(body["records"][1]["fields"]["number"], 4);
+        assert_eq!(body["records"].as_array().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn it_should_paginate_with_an_offset() {
+        let server = FakeAirtable::start().expect("failed to start the fake airtable server");
+        for number in 1..=3 {
+            create(&server, number, "Pokemon");
+        }
+
+        let (_, first) = send(&server, "GET", "?pageSize=2", FAKE_API_KEY, None);
+        let offset = String::from(first.unwrap()["offset"].as_str().unwrap());
+        let (_, second) = send(
+            &server,
+            "GET",
+            &format!("?pageSize=2&offset={}", offset),
+            FAKE_API_KEY,
+            None,
+        );
+
+        let second = second.unwrap();
+        assert_eq!(second["records"].as_array().unwrap().len(), 1);
+        assert!(second.get("offset").is_none());
+    }
+
+    #[test]
+    fn it_should_return_an_empty_page_past_the_last_record() {
+        let server = FakeAirtable::start().expect("failed to start the fake airtable server");
+        create(&server, 25, "Pikachu");
+
+        let (status, body) = send(
+            &server,
+            "GET",
+            &format!("?pageSize=2&offset=itr{}", usize::MAX),
+            FAKE_API_KEY,
+            None,
+        );
+
+        assert_eq!(status, 200);
+        assert_eq!(body.unwrap()["records"], ureq::json!([]));
+    }
+
+    #[test]
+    fn it_should_delete_a_record_by_id() {
+        let server = FakeAirtable::start().expect("failed to start the fake airtable server");
+        let id = create(&server, 25, "Pikachu");
+
+        let (status, body) = send(&server, "DELETE", &format!("/{}", id), FAKE_API_KEY, None);
+        let (missing, _) = send(&server, "DELETE", &format!("/{}", id), FAKE_API_KEY, None);
+        let (_, list) = send(&server, "GET", "", FAKE_API_KEY, None);
+
+        assert_eq!(status, 200);
+        assert_eq!(body.unwrap()["deleted"], true);
+        assert_eq!(missing, 404);
+        assert_eq!(list.unwrap()["records"], ureq::json!([]));
+    }
+
+    #[test]
+    fn it_should_reject_an_invalid_formula() {
+        let server = FakeAirtable::start().expect("failed to start the fake airtable server");
+
+        let (status, _) = send(&server, "GET", "?filterByFormula=AND(", FAKE_API_KEY, None);
+
+        assert_eq!(status, 422);
+    }
+}
@@ -0,0 +1,397 @@
+//! Behaviour every `Repository` adapter must share, each check run as a test
+//! of every adapter with `contract_tests!`.
+
+use std::sync::Arc;
+
+use crate::domain::entities::{
+    Pokemon, PokemonForm, PokemonName, PokemonNumber, PokemonType, PokemonTypes,
+};
+use crate::repositories::pokemon::{
+    DeleteError, FetchAllError, FetchAllQuery, FetchOneError, InsertError, Repository,
+    SortDirection, SortKey, UpdateError,
+};
+
+fn seed(repo: &Arc<dyn Repository>) {
+    for (number, name, types) in [
+        (25, "Pikachu", vec!["Electric"]),
+        (4, "Charmander", vec!["Fire"]),
+        (6, "Charizard", vec!["Fire", "Flying"]),
+        (1, "Bulbasaur", vec!["Grass", "Poison"]),
+    ] {
+        match repo.insert(
+            PokemonNumber::try_from(number).ok().unwrap(),
+            PokemonForm::default(),
+            PokemonName::try_from(String::from(name)).ok().unwrap(),
+            PokemonTypes::try_from(types.into_iter().map(String::from).collect::<Vec<_>>())
+                .ok()
+                .unwrap(),
+            None,
+        ) {
+            Ok(_) => {}
+            _ => unreachable!(),
+        }
+    }
+}
+
+fn numbers(pokemons: Vec<Pokemon>) -> Vec<u16> {
+    pokemons.into_iter().map(|p| u16::from(p.number)).collect()
+}
+
+fn fetch_all(repo: &Arc<dyn Repository>, query: FetchAllQuery) -> Vec<u16> {
+    match repo.fetch_all(query) {
+        Ok(page) => numbers(page.pokemons),
+        _ => unreachable!(),
+    }
+}
+
+pub fn it_should_return_the_inserted_pokemon(repo: Arc<dyn Repository>) {
+    let res = repo.insert(
+        PokemonNumber::pikachu(),
+        PokemonForm::default(),
+        PokemonName::pikachu(),
+        PokemonTypes::pikachu(),
+        None,
+    );
+
+    match res {
+        Ok(pokemon) => {
+            assert!(pokemon.number == PokemonNumber::pikachu());
+            assert!(pokemon.name == PokemonName::pikachu());
+        }
+        _ => unreachable!(),
+    };
+}
+
+pub fn it_should_refuse_to_insert_an_existing_number(repo: Arc<dyn Repository>) {
+    seed(&repo);
+
+    let res = repo.insert(
+        PokemonNumber::pikachu(),
+        PokemonForm::default(),
+        PokemonName::charmander(),
+        PokemonTypes::charmander(),
+        None,
+    );
+
+    match res {
+        Err(InsertError::Conflict) => {}
+        _ => unreachable!(),
+    };
+}
+
+pub fn it_should_fetch_an_inserted_pokemon_with_all_its_fields(repo: Arc<dyn Repository>) {
+    seed(&repo);
+
+    let res = repo.fetch_one(PokemonNumber::charizard(), PokemonForm::default());
+
+    match res {
+        Ok(pokemon) => {
+            assert_eq!(u16::from(pokemon.number), 6);
+            assert_eq!(String::from(pokemon.name), "Charizard");
+            assert_eq!(Vec::<String>::from(pokemon.types), vec!["Fire", "Flying"]);
+        }
+        _ => unreachable!(),
+    };
+}
+
+pub fn it_should_not_find_a_missing_pokemon(repo: Arc<dyn Repository>) {
+    let res = repo.fetch_one(PokemonNumber::pikachu(), PokemonForm::default());
+
+    match res {
+        Err(FetchOneError::NotFound) => {}
+        _ => unreachable!(),
+    };
+}
+
+pub fn it_should_update_the_name_and_types(repo: Arc<dyn Repository>) {
+    seed(&repo);
+
+    let res = repo.update(
+        PokemonNumber::pikachu(),
+        PokemonForm::default(),
+        PokemonName::charmander(),
+        PokemonTypes::charizard(),
+        None,
+    );
+
+    assert!(res.is_ok());
+    match repo.fetch_one(PokemonNumber::pikachu(), PokemonForm::default()) {
+        Ok(pokemon) => {
+            assert!(pokemon.name == PokemonName::charmander());
+            assert_eq!(Vec::<String>::from(pokemon.types), vec!["Fire", "Flying"]);
+        }
+        _ => unreachable!(),
+    };
+}
+
+pub fn it_should_not_update_a_missing_pokemon(repo: Arc<dyn Repository>) {
+    let res = repo.update(
+        PokemonNumber::pikachu(),
+        PokemonForm::default(),
+        PokemonName::pikachu(),
+        PokemonTypes::pikachu(),
+        None,
+    );
+
+    match res {
+        Err(UpdateError::NotFound) => {}
+        _ => unreachable!(),
+    };
+}
+
+pub fn it_should_delete_a_pokemon_and_free_its_number(repo: Arc<dyn Repository>) {
+    seed(&repo);
+
+    let res = repo.delete(PokemonNumber::pikachu(), PokemonForm::default());
+
+    assert!(res.is_ok());
+    match repo.fetch_one(PokemonNumber::pikachu(), PokemonForm::default()) {
+        Err(FetchOneError::NotFound) => {}
+        _ => unreachable!(),
+    };
+    assert!(repo
+        .insert(
+            PokemonNumber::pikachu(),
+            PokemonForm::default(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+            None
+        )
+        .is_ok());
+}
+
+pub fn it_should_not_delete_a_missing_pokemon(repo: Arc<dyn Repository>) {
+    let res = repo.delete(PokemonNumber::pikachu(), PokemonForm::default());
+
+    match res {
+        Err(DeleteError::NotFound) => {}
+        _ => unreachable!(),
+    };
+}
+
+pub fn it_should_keep_a_form_apart_from_the_default_one(repo: Arc<dyn Repository>) {
+    seed(&repo);
+
+    let res = repo.insert(
+        PokemonNumber::pikachu(),
+        PokemonForm::alola(),
+        PokemonName::pikachu(),
+        PokemonTypes::alolan_raichu(),
+        None,
+    );
+
+    assert!(res.is_ok());
+    match repo.fetch_one(PokemonNumber::pikachu(), PokemonForm::alola()) {
+        Ok(pokemon) => {
+            assert!(pokemon.form == PokemonForm::alola());
+            assert_eq!(
+                Vec::<String>::from(pokemon.types),
+                vec!["Electric", "Psychic"]
+            );
+        }
+        _ => unreachable!(),
+    };
+    match repo.fetch_one(PokemonNumber::pikachu(), PokemonForm::default()) {
+        Ok(pokemon) => {
+            assert!(pokemon.form.is_default());
+            assert_eq!(Vec::<String>::from(pokemon.types), vec!["Electric"]);
+        }
+        _ => unreachable!(),
+    };
+    match repo.insert(
+        PokemonNumber::pikachu(),
+        PokemonForm::alola(),
+        PokemonName::pikachu(),
+        PokemonTypes::pikachu(),
+        None,
+    ) {
+        Err(InsertError::Conflict) => {}
+        _ => unreachable!(),
+    };
+}
+
+pub fn it_should_only_list_the_other_forms_when_asked(repo: Arc<dyn Repository>) {
+    seed(&repo);
+    repo.insert(
+        PokemonNumber::charmander(),
+        PokemonForm::alola(),
+        PokemonName::charmander(),
+        PokemonTypes::charmander(),
+        None,
+    )
+    .ok();
+
+    assert_eq!(
+        fetch_all(&repo, FetchAllQuery::default()),
+        vec![1, 4, 6, 25]
+    );
+    match repo.fetch_all(FetchAllQuery {
+        all_forms: true,
+        ..FetchAllQuery::default()
+    }) {
+        Ok(page) => {
+            let forms = page
+                .pokemons
+                .into_iter()
+                .map(|p| (u16::from(p.number), String::from(p.form)))
+                .collect::<Vec<(u16, String)>>();
+            assert_eq!(
+                forms,
+                vec![
+                    (1, String::new()),
+                    (4, String::new()),
+                    (4, String::from("alola")),
+                    (6, String::new()),
+                    (25, String::new()),
+                ]
+            );
+        }
+        _ => unreachable!(),
+    };
+}
+
+pub fn it_should_delete_the_other_forms_along_with_the_default_one(repo: Arc<dyn Repository>) {
+    seed(&repo);
+    for number in [PokemonNumber::pikachu(), PokemonNumber::charmander()] {
+        repo.insert(
+            number,
+            PokemonForm::alola(),
+            PokemonName::pikachu(),
+            PokemonTypes::alolan_raichu(),
+            None,
+        )
+        .ok();
+    }
+
+    assert!(repo
+        .delete(PokemonNumber::charmander(), PokemonForm::alola())
+        .is_ok());
+    assert!(repo
+        .delete(PokemonNumber::pikachu(), PokemonForm::default())
+        .is_ok());
+
+    match repo.fetch_one(PokemonNumber::charmander(), PokemonForm::default()) {
+        Ok(_) => {}
+        _ => unreachable!(),
+    };
+    match repo.fetch_one(PokemonNumber::pikachu(), PokemonForm::alola()) {
+        Err(FetchOneError::NotFound) => {}
+        _ => unreachable!(),
+    };
+}
+
+pub fn it_should_fetch_all_by_ascending_number_by_default(repo: Arc<dyn Repository>) {
+    seed(&repo);
+
+    assert_eq!(
+        fetch_all(&repo, FetchAllQuery::default()),
+        vec![1, 4, 6, 25]
+    );
+}
+
+pub fn it_should_sort_by_name_or_by_descending_number(repo: Arc<dyn Repository>) {
+    seed(&repo);
+
+    assert_eq!(
+        fetch_all(
+            &repo,
+            FetchAllQuery {
+                sort: SortKey::Name,
+                ..FetchAllQuery::default()
+            }
+        ),
+        vec![1, 6, 4, 25]
+    );
+    assert_eq!(
+        fetch_all(
+            &repo,
+            FetchAllQuery {
+                sort: SortKey::Number,
+                direction: SortDirection::Descending,
+                ..FetchAllQuery::default()
+            }
+        ),
+        vec![25, 6, 4, 1]
+    );
+}
+
+pub fn it_should_filter_by_type_and_name_prefix(repo: Arc<dyn Repository>) {
+    seed(&repo);
+
+    assert_eq!(
+        fetch_all(
+            &repo,
+            FetchAllQuery {
+                r#type: Some(PokemonType::Fire),
+                ..FetchAllQuery::default()
+            }
+        ),
+        vec![4, 6]
+    );
+    assert_eq!(
+        fetch_all(
+            &repo,
+            FetchAllQuery {
+                r#type: Some(PokemonType::Fire),
+                name_prefix: Some(String::from("Charm")),
+                ..FetchAllQuery::default()
+            }
+        ),
+        vec![4]
+    );
+}
+
+pub fn it_should_page_through_every_pokemon_exactly_once(repo: Arc<dyn Repository>) {
+    seed(&repo);
+    let mut seen = vec![];
+    let mut cursor = None;
+
+    loop {
+        let page = match repo.fetch_all(FetchAllQuery {
+            cursor,
+            limit: Some(3),
+            ..FetchAllQuery::default()
+        }) {
+            Ok(page) => page,
+            _ => unreachable!(),
+        };
+        assert!(page.pokemons.len() <= 3);
+        seen.append(&mut numbers(page.pokemons));
+        match page.next_cursor {
+            Some(next) => cursor = Some(next),
+            None => break,
+        }
+    }
+
+    assert_eq!(seen, vec![1, 4, 6, 25]);
+}
+
+pub fn it_should_reject_an_unknown_cursor(repo: Arc<dyn Repository>) {
+    seed(&repo);
+
+    let res = repo.fetch_all(FetchAllQuery {
+        cursor: Some(String::from("not-a-cursor")),
+        limit: Some(2),
+        ..FetchAllQuery::default()
+    });
+
+    match res {
+        Err(FetchAllError::InvalidCursor) => {}
+        _ => unreachable!(),
+    };
+}
+
+pub fn it_should_reject_a_cursor_too_large_for_its_page(repo: Arc<dyn Repository>) {
+    seed(&repo);
+
+    let res = repo.fetch_all(FetchAllQuery {
+        cursor: Some(usize::MAX.to_string()),
+        limit: Some(2),
+        ..FetchAllQuery::default()
+    });
+
+    match res {
+        Err(FetchAllError::InvalidCursor) => {}
+        _ => unreachable!(),
+    };
+}
@@ -1,8 +1,13 @@
 use std::sync::Mutex;
 
-use crate::domain::entities::{Pokemon, PokemonName, PokemonNumber, PokemonTypes};
+use crate::domain::entities::{
+    BaseStats, Pokemon, PokemonForm, PokemonName, PokemonNumber, PokemonTypes,
+};
 
-use super::{DeleteError, FetchAllError, FetchOneError, InsertError, Repository};
+use super::{
+    DeleteError, FetchAllError, FetchAllPage, FetchAllQuery, FetchOneError, InsertError,
+    Repository, RepositoryError, SortDirection, SortKey, UpdateError,
+};
 
 pub struct InMemoryRepository {
     error: bool,
@@ -31,67 +36,218 @@ impl Repository for InMemoryRepository {
     fn insert(
         &self,
         number: PokemonNumber,
+        form: PokemonForm,
         name: PokemonName,
         types: PokemonTypes,
+        stats: Option<BaseStats>,
     ) -> Result<Pokemon, InsertError> {
         if self.error {
-            return Err(InsertError::Unknown);
+            return Err(InsertError::Unknown(RepositoryError::new(
+                "the repository is set to fail",
+            )));
         }
         let mut lock = match self.pokemons.lock() {
             Ok(lock) => lock,
-            _ => return Err(InsertError::Unknown),
+            _ => {
+                return Err(InsertError::Unknown(RepositoryError::new(
+                    "the pokemons lock is poisoned",
+                )))
+            }
         };
-        if lock.iter().any(|pokemon| pokemon.number == number) {
+        if lock
+            .iter()
+            .any(|pokemon| pokemon.number == number && pokemon.form == form)
+        {
             return Err(InsertError::Conflict);
         }
 
-        let pokemon = Pokemon::new(number, name, types);
+        let pokemon = Pokemon::new(number, form, name, types, stats);
         lock.push(pokemon.clone());
         Ok(pokemon)
     }
 
-    fn fetch_all(&self) -> Result<Vec<Pokemon>, FetchAllError> {
+    fn fetch_all(&self, query: FetchAllQuery) -> Result<FetchAllPage, FetchAllError> {
         if self.error {
-            return Err(FetchAllError::Unknown);
+            return Err(FetchAllError::Unknown(RepositoryError::new(
+                "the repository is set to fail",
+            )));
         }
+        let offset = query.offset()?;
         let lock = match self.pokemons.lock() {
             Ok(lock) => lock,
-            _ => return Err(FetchAllError::Unknown),
+            _ => {
+                return Err(FetchAllError::Unknown(RepositoryError::new(
+                    "the pokemons lock is poisoned",
+                )))
+            }
         };
-        let mut pokemons = lock.to_vec();
-        pokemons.sort_by(|a, b| a.number.cmp(&b.number));
-        Ok(pokemons)
+        let mut pokemons = lock
+            .iter()
+            .filter(|p| query.all_forms || p.form.is_default())
+            .filter(|p| match query.r#type {
+                Some(t) => p.types.contains(&t),
+                None => true,
+            })
+            .filter(|p| match query.name_prefix.as_deref() {
+                Some(prefix) => p.name.starts_with(prefix),
+                None => true,
+            })
+            .cloned()
+            .collect::<Vec<Pokemon>>();
+        pokemons.sort_by(|a, b| {
+            let identity = a.number.cmp(&b.number).then(a.form.cmp(&b.form));
+            let ordering = match query.sort {
+                SortKey::Number => identity,
+                SortKey::Name => a.name.cmp(&b.name).then(identity),
+                SortKey::Stat(stat) => {
+                    let stat = |p: &Pokemon| p.stats.map(|stats| stats.get(stat));
+                    stat(a).cmp(&stat(b)).then(identity)
+                }
+                SortKey::Total => {
+                    let total = |p: &Pokemon| p.stats.map(|stats| stats.total());
+                    total(a).cmp(&total(b)).then(identity)
+                }
+            };
+            match query.direction {
+                SortDirection::Ascending => ordering,
+                SortDirection::Descending => ordering.reverse(),
+            }
+        });
+
+        let total = pokemons.len();
+        let mut pokemons = pokemons.into_iter().skip(offset).collect::<Vec<Pokemon>>();
+        let next_cursor = match query.limit {
+            Some(limit) if offset + limit < total => {
+                pokemons.truncate(limit);
+                Some((offset + limit).to_string())
+            }
+            _ => None,
+        };
+        Ok(FetchAllPage {
+            pokemons,
+            next_cursor,
+        })
     }
 
-    fn fetch_one(&self, number: PokemonNumber) -> Result<Pokemon, FetchOneError> {
+    fn fetch_one(
+        &self,
+        number: PokemonNumber,
+        form: PokemonForm,
+    ) -> Result<Pokemon, FetchOneError> {
         if self.error {
-            return Err(FetchOneError::Unknown);
+            return Err(FetchOneError::Unknown(RepositoryError::new(
+                "the repository is set to fail",
+            )));
         }
         let lock = match self.pokemons.lock() {
             Ok(lock) => lock,
-            _ => return Err(FetchOneError::Unknown),
+            _ => {
+                return Err(FetchOneError::Unknown(RepositoryError::new(
+                    "the pokemons lock is poisoned",
+                )))
+            }
         };
-        match lock.iter().find(|p| p.number == number) {
+        match lock.iter().find(|p| p.number == number && p.form == form) {
             Some(pokemon) => Ok(pokemon.clone()),
             None => Err(FetchOneError::NotFound),
         }
     }
-    fn delete(&self, number: PokemonNumber) -> Result<(), DeleteError> {
+
+    fn update(
+        &self,
+        number: PokemonNumber,
+        form: PokemonForm,
+        name: PokemonName,
+        types: PokemonTypes,
+        stats: Option<BaseStats>,
+    ) -> Result<Pokemon, UpdateError> {
         if self.error {
-            return Err(DeleteError::Unknown);
+            return Err(UpdateError::Unknown(RepositoryError::new(
+                "the repository is set to fail",
+            )));
         }
 
         let mut lock = match self.pokemons.lock() {
             Ok(lock) => lock,
-            _ => return Err(DeleteError::Unknown),
+            _ => {
+                return Err(UpdateError::Unknown(RepositoryError::new(
+                    "the pokemons lock is poisoned",
+                )))
+            }
         };
 
-        let index = match lock.iter().position(|p| p.number == number) {
-            Some(index) => index,
-            None => return Err(DeleteError::NotFound),
+        let pokemon = match lock
+            .iter_mut()
+            .find(|p| p.number == number && p.form == form)
+        {
+            Some(pokemon) => pokemon,
+            None => return Err(UpdateError::NotFound),
+        };
+
+        *pokemon = Pokemon::new(number, form, name, types, stats);
+        Ok(pokemon.clone())
+    }
+
+    fn delete(&self, number: PokemonNumber, form: PokemonForm) -> Result<(), DeleteError> {
+        if self.error {
+            return Err(DeleteError::Unknown(RepositoryError::new(
+                "the repository is set to fail",
+            )));
+        }
+
+        let mut lock = match self.pokemons.lock() {
+            Ok(lock) => lock,
+            _ => {
+                return Err(DeleteError::Unknown(RepositoryError::new(
+                    "the pokemons lock is poisoned",
+                )))
+            }
         };
 
-        lock.remove(index);
+        if !lock.iter().any(|p| p.number == number && p.form == form) {
+            return Err(DeleteError::NotFound);
+        }
+
+        lock.retain(|p| p.number != number || !(form.is_default() || p.form == form));
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    mod contract {
+        use super::super::InMemoryRepository;
+        use crate::repositories::{
+            contract_tests,
+            pokemon::{contract, Repository},
+        };
+
+        fn factory() -> (std::sync::Arc<dyn Repository>, ()) {
+            (std::sync::Arc::new(InMemoryRepository::new()), ())
+        }
+
+        contract_tests!(
+            factory,
+            contract,
+            [
+                it_should_return_the_inserted_pokemon,
+                it_should_refuse_to_insert_an_existing_number,
+                it_should_fetch_an_inserted_pokemon_with_all_its_fields,
+                it_should_not_find_a_missing_pokemon,
+                it_should_update_the_name_and_types,
+                it_should_not_update_a_missing_pokemon,
+                it_should_delete_a_pokemon_and_free_its_number,
+                it_should_not_delete_a_missing_pokemon,
+                it_should_keep_a_form_apart_from_the_default_one,
+                it_should_only_list_the_other_forms_when_asked,
+                it_should_delete_the_other_forms_along_with_the_default_one,
+                it_should_fetch_all_by_ascending_number_by_default,
+                it_should_sort_by_name_or_by_descending_number,
+                it_should_filter_by_type_and_name_prefix,
+                it_should_page_through_every_pokemon_exactly_once,
+                it_should_reject_an_unknown_cursor,
+                it_should_reject_a_cursor_too_large_for_its_page,
+            ]
+        );
+    }
+}
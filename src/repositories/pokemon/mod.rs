@@ -1,36 +1,275 @@
 mod airtable;
+#[cfg(test)]
+mod contract;
 mod memory;
 mod sqlite;
-pub use airtable::AirtableRepository;
+pub use airtable::{
+    AirtableConfig, AirtableEvolutionFieldNames, AirtableFieldNames, AirtableRepository,
+    FakeAirtable,
+};
 pub use memory::InMemoryRepository;
 pub use sqlite::SqliteRepository;
-use std::sync::Mutex;
 
-use crate::domain::entities::{Pokemon, PokemonName, PokemonNumber, PokemonTypes};
+use std::{error::Error, fmt};
+
+use crate::domain::entities::{
+    BaseStats, Pokemon, PokemonForm, PokemonName, PokemonNumber, PokemonType, PokemonTypes, Stat,
+};
+
 pub trait Repository: Send + Sync {
     fn insert(
         &self,
         number: PokemonNumber,
+        form: PokemonForm,
         name: PokemonName,
         types: PokemonTypes,
+        stats: Option<BaseStats>,
     ) -> Result<Pokemon, InsertError>;
-    fn fetch_all(&self) -> Result<Vec<Pokemon>, FetchAllError>;
-    fn fetch_one(&self, number: PokemonNumber) -> Result<Pokemon, FetchOneError>;
-    fn delete(&self, number: PokemonNumber) -> Result<(), DeleteError>;
+    fn fetch_all(&self, query: FetchAllQuery) -> Result<FetchAllPage, FetchAllError>;
+    fn fetch_one(&self, number: PokemonNumber, form: PokemonForm)
+        -> Result<Pokemon, FetchOneError>;
+    fn update(
+        &self,
+        number: PokemonNumber,
+        form: PokemonForm,
+        name: PokemonName,
+        types: PokemonTypes,
+        stats: Option<BaseStats>,
+    ) -> Result<Pokemon, UpdateError>;
+    /// Deleting the default form deletes every form sharing its number.
+    fn delete(&self, number: PokemonNumber, form: PokemonForm) -> Result<(), DeleteError>;
+}
+
+/// An unexpected failure of the storage, keeping the error that caused it,
+/// e.g. a `rusqlite::Error` or a `ureq::Error`.
+#[derive(Debug)]
+pub struct RepositoryError {
+    message: String,
+    cause: Option<Box<dyn Error + Send + Sync>>,
+}
+
+impl RepositoryError {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            cause: None,
+        }
+    }
+
+    pub fn caused_by(
+        message: impl Into<String>,
+        cause: impl Error + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            message: message.into(),
+            cause: Some(Box::new(cause)),
+        }
+    }
+
+    /// The message followed by each cause, for the logs and the CLI.
+    pub fn chain(&self) -> String {
+        let mut out = self.to_string();
+        let mut source = self.source();
+        while let Some(cause) = source {
+            out += &format!(": {}", cause);
+            source = cause.source();
+        }
+        out
+    }
+}
+
+impl fmt::Display for RepositoryError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl Error for RepositoryError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match &self.cause {
+            Some(cause) => Some(cause.as_ref()),
+            None => None,
+        }
+    }
 }
+
+#[derive(Debug)]
 pub enum InsertError {
     Conflict,
-    Unknown,
+    Unknown(RepositoryError),
 }
 
+#[derive(Clone)]
+pub struct FetchAllQuery {
+    /// Opaque position returned as `next_cursor` by a previous page.
+    pub cursor: Option<String>,
+    pub limit: Option<usize>,
+    pub sort: SortKey,
+    pub direction: SortDirection,
+    pub r#type: Option<PokemonType>,
+    pub name_prefix: Option<String>,
+    /// Lists the alternate forms too, not only the default ones.
+    pub all_forms: bool,
+}
+
+impl Default for FetchAllQuery {
+    fn default() -> Self {
+        Self {
+            cursor: None,
+            limit: None,
+            sort: SortKey::Number,
+            direction: SortDirection::Ascending,
+            r#type: None,
+            name_prefix: None,
+            all_forms: false,
+        }
+    }
+}
+
+/// Pokemon without stats come first in ascending order when sorting by a stat.
+/// Ties are broken by number, then by form.
+#[derive(Clone, Copy)]
+pub enum SortKey {
+    Number,
+    Name,
+    Stat(Stat),
+    Total,
+}
+
+#[derive(Clone, Copy)]
+pub enum SortDirection {
+    Ascending,
+    Descending,
+}
+
+impl FetchAllQuery {
+    /// Reads the cursor as a row offset, for repositories paginating by offset.
+    /// An offset whose page would end past `usize::MAX` is invalid too.
+    fn offset(&self) -> Result<usize, FetchAllError> {
+        let offset = match self.cursor.as_deref().map(str::parse::<usize>) {
+            Some(Ok(offset)) => offset,
+            Some(Err(_)) => return Err(FetchAllError::InvalidCursor),
+            None => 0,
+        };
+        match self.limit.map(|limit| offset.checked_add(limit)) {
+            Some(None) => Err(FetchAllError::InvalidCursor),
+            _ => Ok(offset),
+        }
+    }
+}
+
+pub struct FetchAllPage {
+    pub pokemons: Vec<Pokemon>,
+    pub next_cursor: Option<String>,
+}
+
+#[derive(Debug)]
 pub enum FetchAllError {
-    Unknown,
+    InvalidCursor,
+    Unknown(RepositoryError),
 }
+#[derive(Debug)]
 pub enum FetchOneError {
-    Unknown,
+    Unknown(RepositoryError),
     NotFound,
 }
+#[derive(Debug)]
+pub enum UpdateError {
+    NotFound,
+    Unknown(RepositoryError),
+}
+#[derive(Debug)]
 pub enum DeleteError {
     NotFound,
-    Unknown,
+    Unknown(RepositoryError),
+}
+
+impl fmt::Display for InsertError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Conflict => write!(f, "a pokemon with this number and form already exists"),
+            Self::Unknown(e) => e.fmt(f),
+        }
+    }
+}
+
+impl fmt::Display for FetchAllError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidCursor => write!(f, "the cursor does not point into this listing"),
+            Self::Unknown(e) => e.fmt(f),
+        }
+    }
+}
+
+impl fmt::Display for FetchOneError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::NotFound => write!(f, "no pokemon has this number and form"),
+            Self::Unknown(e) => e.fmt(f),
+        }
+    }
+}
+
+impl fmt::Display for UpdateError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::NotFound => write!(f, "no pokemon has this number and form"),
+            Self::Unknown(e) => e.fmt(f),
+        }
+    }
+}
+
+impl fmt::Display for DeleteError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::NotFound => write!(f, "no pokemon has this number and form"),
+            Self::Unknown(e) => e.fmt(f),
+        }
+    }
+}
+
+impl Error for InsertError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Unknown(e) => Some(e),
+            Self::Conflict => None,
+        }
+    }
+}
+
+impl Error for FetchAllError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Unknown(e) => Some(e),
+            Self::InvalidCursor => None,
+        }
+    }
+}
+
+impl Error for FetchOneError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Unknown(e) => Some(e),
+            Self::NotFound => None,
+        }
+    }
+}
+
+impl Error for UpdateError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Unknown(e) => Some(e),
+            Self::NotFound => None,
+        }
+    }
+}
+
+impl Error for DeleteError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Unknown(e) => Some(e),
+            Self::NotFound => None,
+        }
+    }
 }
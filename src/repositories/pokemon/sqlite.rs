@@ -1,197 +1,767 @@
-use std::sync::{Mutex, MutexGuard};
+mod migrations;
 
-use rusqlite::{params, params_from_iter, Connection};
+use std::{
+    path::Path,
+    sync::{Arc, Mutex, MutexGuard},
+};
 
-use crate::domain::entities::{Pokemon, PokemonName, PokemonNumber, PokemonTypes};
+use rusqlite::{params, Connection, OpenFlags, Row, Rows};
 
-use super::{DeleteError, FetchAllError, FetchOneError, InsertError, Repository};
+use crate::domain::entities::{
+    BaseStats, Pokemon, PokemonForm, PokemonName, PokemonNumber, PokemonTypes, Stat,
+    ValidationError,
+};
+
+use super::{
+    DeleteError, FetchAllError, FetchAllPage, FetchAllQuery, FetchOneError, InsertError,
+    Repository, RepositoryError, SortDirection, SortKey, UpdateError,
+};
+
+pub use migrations::{Migration, MigrationStatus};
+
+/// Columns of a pokemon row, its stats being null when unknown.
+const POKEMON_COLUMNS: &str = "p.number, p.form, p.name, \
+    s.hp, s.attack, s.defense, s.special_attack, s.special_defense, s.speed \
+    from pokemons p left join stats s on s.pokemon_number = p.number and s.pokemon_form = p.form";
+
+struct PokemonRow {
+    number: u16,
+    form: String,
+    name: String,
+    stats: Option<[u16; 6]>,
+}
 
 pub struct SqliteRepository {
-    connection: Mutex<Connection>,
+    connection: Arc<Mutex<Connection>>,
 }
 
 impl SqliteRepository {
-    pub fn try_new(path: &str) -> Result<Self, ()> {
-        let connection = match rusqlite::Connection::open_with_flags(
+    /// Opens the database, creating it if needed, and brings its schema up to date.
+    pub fn try_new(path: &str) -> Result<Self, RepositoryError> {
+        let mut connection = Self::open(path)?;
+        migrations::run(&mut connection)?;
+        // SQLite ignores unknown pragmas, so make sure cascading deletes are really on.
+        match connection
+            .execute_batch("pragma foreign_keys = on")
+            .and_then(|_| connection.query_row("pragma foreign_keys", [], |row| row.get(0)))
+        {
+            Ok(1) => Ok(Self {
+                connection: Arc::new(Mutex::new(connection)),
+            }),
+            Ok(_) => Err(RepositoryError::new("foreign keys cannot be enforced")),
+            Err(e) => Err(RepositoryError::caused_by("cannot enforce foreign keys", e)),
+        }
+    }
+
+    fn open(path: &str) -> Result<Connection, RepositoryError> {
+        match Connection::open_with_flags(
             path,
-            rusqlite::OpenFlags::SQLITE_OPEN_READ_WRITE,
+            OpenFlags::SQLITE_OPEN_READ_WRITE | OpenFlags::SQLITE_OPEN_CREATE,
+        ) {
+            Ok(connection) => Ok(connection),
+            Err(e) => Err(RepositoryError::caused_by(
+                format!("cannot open the database {}", path),
+                e,
+            )),
+        }
+    }
+
+    /// The connection, for the repositories storing what refers to these pokemons.
+    pub(in crate::repositories) fn connection(&self) -> Arc<Mutex<Connection>> {
+        self.connection.clone()
+    }
+
+    fn lock(&self) -> Result<MutexGuard<'_, Connection>, RepositoryError> {
+        match self.connection.lock() {
+            Ok(lock) => Ok(lock),
+            Err(_) => Err(RepositoryError::new("the connection lock is poisoned")),
+        }
+    }
+
+    /// Lists the type rows whose pokemon no longer exists.
+    pub fn check_integrity(&self) -> Result<Vec<(u16, String)>, RepositoryError> {
+        let lock = self.lock()?;
+        let mut stat = match lock.prepare(
+            "select pokemon_number, name from types t \
+            where not exists (select 1 from pokemons p \
+            where p.number = t.pokemon_number and p.form = t.pokemon_form) \
+            order by pokemon_number, pokemon_form, name",
+        ) {
+            Ok(stat) => stat,
+            Err(e) => {
+                return Err(RepositoryError::caused_by(
+                    "cannot look for orphaned types",
+                    e,
+                ))
+            }
+        };
+        let mut rows = match stat.query([]) {
+            Ok(rows) => rows,
+            Err(e) => {
+                return Err(RepositoryError::caused_by(
+                    "cannot look for orphaned types",
+                    e,
+                ))
+            }
+        };
+        let mut orphans = vec![];
+        while let Some(row) = Self::next_row(&mut rows)? {
+            match (row.get::<usize, u16>(0), row.get::<usize, String>(1)) {
+                (Ok(number), Ok(r#type)) => orphans.push((number, r#type)),
+                (Err(e), _) | (_, Err(e)) => {
+                    return Err(RepositoryError::caused_by("cannot read a type row", e))
+                }
+            }
+        }
+        Ok(orphans)
+    }
+
+    /// Removes the type rows whose pokemon no longer exists and returns how many were removed.
+    pub fn repair_integrity(&self) -> Result<usize, RepositoryError> {
+        let lock = self.lock()?;
+        match lock.execute(
+            "delete from types where not exists (select 1 from pokemons p \
+            where p.number = types.pokemon_number and p.form = types.pokemon_form)",
+            [],
         ) {
-            Ok(connection) => connection,
-            Err(_) => return Err(()),
+            Ok(removed) => Ok(removed),
+            Err(e) => Err(RepositoryError::caused_by(
+                "cannot remove orphaned types",
+                e,
+            )),
+        }
+    }
+
+    /// Applies the pending migrations of the database and returns them.
+    pub fn migrate(path: &str) -> Result<Vec<&'static Migration>, RepositoryError> {
+        let mut connection = Self::open(path)?;
+        migrations::run(&mut connection)
+    }
+
+    /// Lists every known migration and when it was applied, without touching the database.
+    pub fn migration_status(path: &str) -> Result<Vec<MigrationStatus>, RepositoryError> {
+        let connection = if Path::new(path).exists() {
+            Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+        } else {
+            Connection::open_in_memory()
         };
-        match connection.execute("pragma foreign_key = 1", []) {
-            Ok(_) => Ok(Self {
-                connection: Mutex::new(connection),
-            }),
-            Err(_) => return Err(()),
+        match connection {
+            Ok(connection) => migrations::status(&connection),
+            Err(e) => Err(RepositoryError::caused_by(
+                format!("cannot open the database {}", path),
+                e,
+            )),
         }
     }
 
     fn fetch_pokemon_rows(
         lock: &MutexGuard<'_, Connection>,
-        number: Option<u16>,
-    ) -> Result<Vec<(u16, String)>, ()> {
-        let (query, params) = match number {
-            Some(n) => (
-                "select number, name from pokemons where number = ?",
-                vec![n],
+        number: u16,
+        form: &str,
+    ) -> Result<Vec<PokemonRow>, RepositoryError> {
+        let sql = format!(
+            "select {} where p.number = ? and p.form = ?",
+            POKEMON_COLUMNS
+        );
+        let mut stat = match lock.prepare(&sql) {
+            Ok(stat) => stat,
+            Err(e) => return Err(RepositoryError::caused_by("cannot fetch the pokemon", e)),
+        };
+        let rows = match stat.query(params![number, form]) {
+            Ok(rows) => rows,
+            Err(e) => return Err(RepositoryError::caused_by("cannot fetch the pokemon", e)),
+        };
+        Self::read_pokemon_rows(rows)
+    }
+
+    fn query_pokemon_rows(
+        lock: &MutexGuard<'_, Connection>,
+        query: &FetchAllQuery,
+        offset: i64,
+        limit: i64,
+    ) -> Result<Vec<PokemonRow>, RepositoryError> {
+        let column = match query.sort {
+            SortKey::Number => String::from("p.number"),
+            SortKey::Name => String::from("p.name"),
+            SortKey::Stat(stat) => format!("s.{}", String::from(stat)),
+            SortKey::Total => format!(
+                "({})",
+                Stat::ALL
+                    .iter()
+                    .map(|stat| format!("s.{}", String::from(*stat)))
+                    .collect::<Vec<String>>()
+                    .join(" + ")
             ),
-            _ => ("select number, name from pokemons", vec![]),
         };
-        let mut stat = match lock.prepare(query) {
+        let direction = match query.direction {
+            SortDirection::Ascending => "asc",
+            SortDirection::Descending => "desc",
+        };
+        let sql = format!(
+            "select {POKEMON_COLUMNS} \
+            where (?1 is null or exists (select 1 from types t where t.pokemon_number = p.number \
+            and t.pokemon_form = p.form and t.name = ?1)) \
+            and (?2 is null or substr(p.name, 1, length(?2)) = ?2) \
+            and (?5 or p.form = '') \
+            order by {column} {direction}, p.number {direction}, p.form {direction} \
+            limit ?3 offset ?4"
+        );
+        let mut stat = match lock.prepare(&sql) {
             Ok(stat) => stat,
-            _ => return Err(()),
+            Err(e) => return Err(RepositoryError::caused_by("cannot fetch the pokemons", e)),
         };
-        let rows = stat.query(params_from_iter(params));
-        if rows.is_err() {
-            return Err(());
-        }
-        let mut rows = rows.unwrap();
+        let rows = match stat.query(params![
+            query.r#type.map(String::from),
+            query.name_prefix,
+            limit,
+            offset,
+            query.all_forms,
+        ]) {
+            Ok(rows) => rows,
+            Err(e) => return Err(RepositoryError::caused_by("cannot fetch the pokemons", e)),
+        };
+        Self::read_pokemon_rows(rows)
+    }
+
+    fn read_pokemon_rows(mut rows: Rows<'_>) -> Result<Vec<PokemonRow>, RepositoryError> {
         let mut pokemon_rows = vec![];
-        while let Ok(Some(row)) = rows.next() {
-            match (row.get::<usize, u16>(0), row.get::<usize, String>(1)) {
-                (Ok(number), Ok(name)) => pokemon_rows.push((number, name)),
-                _ => return Err(()),
+        while let Some(row) = Self::next_row(&mut rows)? {
+            let failed = |e| RepositoryError::caused_by("cannot read a pokemon row", e);
+            let mut stats = [0; 6];
+            for (i, stat) in stats.iter_mut().enumerate() {
+                match row.get::<usize, Option<u16>>(i + 3) {
+                    Ok(value) => *stat = value.unwrap_or_default(),
+                    Err(e) => return Err(failed(e)),
+                }
+            }
+            match (
+                row.get::<usize, u16>(0),
+                row.get::<usize, String>(1),
+                row.get::<usize, String>(2),
+                row.get::<usize, Option<u16>>(3),
+            ) {
+                (Ok(number), Ok(form), Ok(name), Ok(hp)) => pokemon_rows.push(PokemonRow {
+                    number,
+                    form,
+                    name,
+                    stats: hp.map(|_| stats),
+                }),
+                (Err(e), _, _, _) | (_, Err(e), _, _) | (_, _, Err(e), _) | (_, _, _, Err(e)) => {
+                    return Err(failed(e))
+                }
             };
         }
         Ok(pokemon_rows)
     }
 
-    fn fetch_type_rows(lock: &MutexGuard<'_, Connection>, number: u16) -> Result<Vec<String>, ()> {
-        let mut stat = match lock.prepare("select * from types where pokemon_number = ?") {
+    fn fetch_type_rows(
+        lock: &MutexGuard<'_, Connection>,
+        number: u16,
+        form: &str,
+    ) -> Result<Vec<String>, RepositoryError> {
+        let mut stat = match lock
+            .prepare("select name from types where pokemon_number = ? and pokemon_form = ?")
+        {
             Ok(stat) => stat,
-            _ => return Err(()),
+            Err(e) => return Err(RepositoryError::caused_by("cannot fetch the types", e)),
         };
-        let mut rows = match stat.query([number]) {
+        let mut rows = match stat.query(params![number, form]) {
             Ok(rows) => rows,
-            _ => return Err(()),
+            Err(e) => return Err(RepositoryError::caused_by("cannot fetch the types", e)),
         };
         let mut type_rows = vec![];
-        while let Ok(Some(row)) = rows.next() {
-            match row.get::<usize, String>(1) {
+        while let Some(row) = Self::next_row(&mut rows)? {
+            match row.get::<usize, String>(0) {
                 Ok(r#type) => type_rows.push(r#type),
-                _ => return Err(()),
+                Err(e) => return Err(RepositoryError::caused_by("cannot read a type row", e)),
             }
         }
         Ok(type_rows)
     }
+
+    fn next_row<'a>(rows: &'a mut Rows<'_>) -> Result<Option<&'a Row<'a>>, RepositoryError> {
+        match rows.next() {
+            Ok(row) => Ok(row),
+            Err(e) => Err(RepositoryError::caused_by("cannot read the next row", e)),
+        }
+    }
+
+    /// Turns stored rows back into a pokemon, refusing rows the domain would not accept.
+    fn pokemon(row: PokemonRow, types: Vec<String>) -> Result<Pokemon, RepositoryError> {
+        match (
+            PokemonNumber::try_from(row.number),
+            PokemonForm::try_from(row.form).map_err(ValidationError::from),
+            PokemonName::try_from(row.name),
+            PokemonTypes::try_from(types),
+            row.stats
+                .map(BaseStats::try_from)
+                .transpose()
+                .map_err(ValidationError::from),
+        ) {
+            (Ok(number), Ok(form), Ok(name), Ok(types), Ok(stats)) => {
+                Ok(Pokemon::new(number, form, name, types, stats))
+            }
+            (Err(e), _, _, _, _)
+            | (_, Err(e), _, _, _)
+            | (_, _, Err(e), _, _)
+            | (_, _, _, Err(e), _)
+            | (_, _, _, _, Err(e)) => Err(RepositoryError::caused_by(
+                format!("pokemon {} is stored with an invalid field", row.number),
+                e,
+            )),
+        }
+    }
+
+    fn insert_stats(
+        connection: &Connection,
+        number: u16,
+        form: &str,
+        stats: Option<BaseStats>,
+    ) -> Result<(), RepositoryError> {
+        let stats = match stats {
+            Some(stats) => <[u16; 6]>::from(stats),
+            None => return Ok(()),
+        };
+        match connection.execute(
+            "insert into stats (pokemon_number, pokemon_form, hp, attack, defense, \
+            special_attack, special_defense, speed) values (?, ?, ?, ?, ?, ?, ?, ?)",
+            params![number, form, stats[0], stats[1], stats[2], stats[3], stats[4], stats[5]],
+        ) {
+            Ok(_) => Ok(()),
+            Err(e) => Err(RepositoryError::caused_by("cannot insert the stats", e)),
+        }
+    }
 }
 
 impl Repository for SqliteRepository {
     fn insert(
         &self,
-        number: crate::domain::entities::PokemonNumber,
-        name: crate::domain::entities::PokemonName,
-        types: crate::domain::entities::PokemonTypes,
-    ) -> Result<crate::domain::entities::Pokemon, super::InsertError> {
-        let mut lock = match self.connection.lock() {
+        number: PokemonNumber,
+        form: PokemonForm,
+        name: PokemonName,
+        types: PokemonTypes,
+        stats: Option<BaseStats>,
+    ) -> Result<Pokemon, InsertError> {
+        let mut lock = match self.lock() {
             Ok(lock) => lock,
-            _ => return Err(InsertError::Unknown),
+            Err(e) => return Err(InsertError::Unknown(e)),
         };
         let transaction = match lock.transaction() {
             Ok(t) => t,
-            _ => return Err(InsertError::Unknown),
+            Err(e) => {
+                return Err(InsertError::Unknown(RepositoryError::caused_by(
+                    "cannot start a transaction",
+                    e,
+                )))
+            }
         };
+        let stored_form = String::from(form.clone());
         match transaction.execute(
-            "insert into pokemons(number, name) values (?, ?)",
-            params![u16::from(number.clone()), String::from(name.clone())],
+            "insert into pokemons(number, form, name) values (?, ?, ?)",
+            params![
+                u16::from(number.clone()),
+                stored_form,
+                String::from(name.clone())
+            ],
         ) {
             Ok(_) => {}
             Err(rusqlite::Error::SqliteFailure(_, Some(msg)))
-                if msg == "UNIQUE constraint failed: pokemons.number" =>
+                if msg == "UNIQUE constraint failed: pokemons.number, pokemons.form" =>
             {
                 return Err(InsertError::Conflict)
             }
 
-            Err(_) => return Err(InsertError::Unknown),
+            Err(e) => {
+                return Err(InsertError::Unknown(RepositoryError::caused_by(
+                    "cannot insert the pokemon",
+                    e,
+                )))
+            }
         }
         for r#type in Vec::<String>::from(types.clone()) {
-            if let Err(_) = transaction.execute(
-                "insert into types (pokemon_number, name) values (?, ?)",
-                params![u16::from(number.clone()), r#type],
+            if let Err(e) = transaction.execute(
+                "insert into types (pokemon_number, pokemon_form, name) values (?, ?, ?)",
+                params![u16::from(number.clone()), stored_form, r#type],
             ) {
-                return Err(InsertError::Unknown);
+                return Err(InsertError::Unknown(RepositoryError::caused_by(
+                    "cannot insert the types",
+                    e,
+                )));
             }
         }
+        if let Err(e) =
+            Self::insert_stats(&transaction, u16::from(number.clone()), &stored_form, stats)
+        {
+            return Err(InsertError::Unknown(e));
+        }
         match transaction.commit() {
-            Ok(_) => Ok(Pokemon::new(number, name, types)),
-            _ => Err(InsertError::Unknown),
+            Ok(_) => Ok(Pokemon::new(number, form, name, types, stats)),
+            Err(e) => Err(InsertError::Unknown(RepositoryError::caused_by(
+                "cannot commit the transaction",
+                e,
+            ))),
         }
     }
 
-    fn fetch_all(&self) -> Result<Vec<crate::domain::entities::Pokemon>, super::FetchAllError> {
-        let lock = match self.connection.lock() {
+    fn fetch_all(&self, query: FetchAllQuery) -> Result<FetchAllPage, FetchAllError> {
+        let offset = query.offset()?;
+        // SQLite binds the bounds as signed integers, -1 meaning no limit.
+        // One extra row tells whether there is a next page.
+        let (sql_offset, sql_limit) = match (
+            i64::try_from(offset),
+            query
+                .limit
+                .map(|limit| i64::try_from(limit).map(|limit| limit.saturating_add(1))),
+        ) {
+            (Ok(offset), None) => (offset, -1),
+            (Ok(offset), Some(Ok(limit))) => (offset, limit),
+            _ => return Err(FetchAllError::InvalidCursor),
+        };
+        let lock = match self.lock() {
             Ok(lock) => lock,
-            _ => return Err(FetchAllError::Unknown),
+            Err(e) => return Err(FetchAllError::Unknown(e)),
         };
-        let rows = match Self::fetch_pokemon_rows(&lock, None) {
+        let mut rows = match Self::query_pokemon_rows(&lock, &query, sql_offset, sql_limit) {
             Ok(rows) => rows,
-            _ => return Err(FetchAllError::Unknown),
+            Err(e) => return Err(FetchAllError::Unknown(e)),
+        };
+        let next_cursor = match query.limit {
+            Some(limit) if rows.len() > limit => {
+                rows.truncate(limit);
+                Some((offset + limit).to_string())
+            }
+            _ => None,
         };
         let mut pokemons = vec![];
         for row in rows {
-            let types = match Self::fetch_type_rows(&lock, row.0) {
-                Ok(types) => types,
-                _ => return Err(FetchAllError::Unknown),
-            };
-            let pokemon = match (
-                PokemonNumber::try_from(row.0),
-                PokemonName::try_from(row.1),
-                PokemonTypes::try_from(types),
-            ) {
-                (Ok(number), Ok(name), Ok(types)) => Pokemon::new(number, name, types),
-                _ => return Err(FetchAllError::Unknown),
+            let pokemon = match Self::fetch_type_rows(&lock, row.number, &row.form)
+                .and_then(|types| Self::pokemon(row, types))
+            {
+                Ok(pokemon) => pokemon,
+                Err(e) => return Err(FetchAllError::Unknown(e)),
             };
             pokemons.push(pokemon);
         }
-        Ok(pokemons)
+        Ok(FetchAllPage {
+            pokemons,
+            next_cursor,
+        })
     }
 
     fn fetch_one(
         &self,
-        number: crate::domain::entities::PokemonNumber,
-    ) -> Result<crate::domain::entities::Pokemon, super::FetchOneError> {
-        let lock = match self.connection.lock() {
+        number: PokemonNumber,
+        form: PokemonForm,
+    ) -> Result<Pokemon, FetchOneError> {
+        let lock = match self.lock() {
             Ok(lock) => lock,
-            _ => return Err(FetchOneError::Unknown),
+            Err(e) => return Err(FetchOneError::Unknown(e)),
         };
-        let mut rows = match Self::fetch_pokemon_rows(&lock, Some(u16::from(number.clone()))) {
+        let form = String::from(form);
+        let mut rows = match Self::fetch_pokemon_rows(&lock, u16::from(number.clone()), &form) {
             Ok(rows) => rows,
-            _ => return Err(FetchOneError::Unknown),
+            Err(e) => return Err(FetchOneError::Unknown(e)),
         };
         if rows.is_empty() {
             return Err(FetchOneError::NotFound);
         }
-        let types = match Self::fetch_type_rows(&lock, u16::from(number)) {
+        let types = match Self::fetch_type_rows(&lock, u16::from(number), &form) {
             Ok(types) => types,
-            _ => return Err(FetchOneError::Unknown),
+            Err(e) => return Err(FetchOneError::Unknown(e)),
         };
-        let row = rows.remove(0);
-        match (
-            PokemonNumber::try_from(row.0),
-            PokemonName::try_from(row.1),
-            PokemonTypes::try_from(types),
-        ) {
-            (Ok(number), Ok(name), Ok(types)) => Ok(Pokemon::new(number, name, types)),
-            _ => Err(FetchOneError::Unknown),
+        match Self::pokemon(rows.remove(0), types) {
+            Ok(pokemon) => Ok(pokemon),
+            Err(e) => Err(FetchOneError::Unknown(e)),
         }
     }
 
-    fn delete(
+    fn update(
         &self,
-        number: crate::domain::entities::PokemonNumber,
-    ) -> Result<(), super::DeleteError> {
-        let lock = match self.connection.lock() {
+        number: PokemonNumber,
+        form: PokemonForm,
+        name: PokemonName,
+        types: PokemonTypes,
+        stats: Option<BaseStats>,
+    ) -> Result<Pokemon, UpdateError> {
+        let mut lock = match self.lock() {
             Ok(lock) => lock,
-            _ => return Err(DeleteError::Unknown),
+            Err(e) => return Err(UpdateError::Unknown(e)),
+        };
+        let transaction = match lock.transaction() {
+            Ok(t) => t,
+            Err(e) => {
+                return Err(UpdateError::Unknown(RepositoryError::caused_by(
+                    "cannot start a transaction",
+                    e,
+                )))
+            }
+        };
+        let stored_form = String::from(form.clone());
+        match transaction.execute(
+            "update pokemons set name = ? where number = ? and form = ?",
+            params![
+                String::from(name.clone()),
+                u16::from(number.clone()),
+                stored_form
+            ],
+        ) {
+            Ok(0) => return Err(UpdateError::NotFound),
+            Ok(_) => {}
+            Err(e) => {
+                return Err(UpdateError::Unknown(RepositoryError::caused_by(
+                    "cannot update the pokemon",
+                    e,
+                )))
+            }
+        }
+        if let Err(e) = transaction.execute(
+            "delete from types where pokemon_number = ? and pokemon_form = ?",
+            params![u16::from(number.clone()), stored_form],
+        ) {
+            return Err(UpdateError::Unknown(RepositoryError::caused_by(
+                "cannot delete the previous types",
+                e,
+            )));
+        }
+        for r#type in Vec::<String>::from(types.clone()) {
+            if let Err(e) = transaction.execute(
+                "insert into types (pokemon_number, pokemon_form, name) values (?, ?, ?)",
+                params![u16::from(number.clone()), stored_form, r#type],
+            ) {
+                return Err(UpdateError::Unknown(RepositoryError::caused_by(
+                    "cannot insert the types",
+                    e,
+                )));
+            }
+        }
+        if let Err(e) = transaction.execute(
+            "delete from stats where pokemon_number = ? and pokemon_form = ?",
+            params![u16::from(number.clone()), stored_form],
+        ) {
+            return Err(UpdateError::Unknown(RepositoryError::caused_by(
+                "cannot delete the previous stats",
+                e,
+            )));
+        }
+        if let Err(e) =
+            Self::insert_stats(&transaction, u16::from(number.clone()), &stored_form, stats)
+        {
+            return Err(UpdateError::Unknown(e));
+        }
+        match transaction.commit() {
+            Ok(_) => Ok(Pokemon::new(number, form, name, types, stats)),
+            Err(e) => Err(UpdateError::Unknown(RepositoryError::caused_by(
+                "cannot commit the transaction",
+                e,
+            ))),
+        }
+    }
+
+    fn delete(&self, number: PokemonNumber, form: PokemonForm) -> Result<(), DeleteError> {
+        let lock = match self.lock() {
+            Ok(lock) => lock,
+            Err(e) => return Err(DeleteError::Unknown(e)),
         };
         match lock.execute(
-            "delete from pokemons where number = ?",
-            params![u16::from(number)],
+            "delete from pokemons where number = ?1 and (form = ?2 or ?2 = '' \
+            and exists (select 1 from pokemons where number = ?1 and form = ''))",
+            params![u16::from(number), String::from(form)],
         ) {
             Ok(0) => Err(DeleteError::NotFound),
             Ok(_) => Ok(()),
-            _ => Err(DeleteError::Unknown),
+            Err(e) => Err(DeleteError::Unknown(RepositoryError::caused_by(
+                "cannot delete the pokemon",
+                e,
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn repo() -> SqliteRepository {
+        match SqliteRepository::try_new(":memory:") {
+            Ok(repo) => repo,
+            _ => unreachable!(),
         }
     }
+
+    fn insert_orphaned_type(repo: &SqliteRepository) {
+        let lock = repo.connection.lock().unwrap();
+        lock.execute_batch(
+            "pragma foreign_keys = off;
+            insert into types (pokemon_number, pokemon_form, name) values (25, '', 'Fire');
+            pragma foreign_keys = on;",
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn it_should_enforce_foreign_keys() {
+        let repo = repo();
+        let lock = repo.connection.lock().unwrap();
+
+        let res = lock.execute(
+            "insert into types (pokemon_number, name) values (25, 'Electric')",
+            [],
+        );
+
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn it_should_delete_the_types_of_a_deleted_pokemon() {
+        let repo = repo();
+        repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonForm::default(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+            None,
+        )
+        .ok();
+
+        assert!(repo
+            .delete(PokemonNumber::pikachu(), PokemonForm::default())
+            .is_ok());
+
+        let lock = repo.connection.lock().unwrap();
+        assert!(
+            matches!(SqliteRepository::fetch_type_rows(&lock, 25, ""), Ok(types) if types.is_empty())
+        );
+    }
+
+    #[test]
+    fn it_should_not_inherit_stale_types_when_reinserting_a_deleted_number() {
+        let repo = repo();
+        repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonForm::default(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+            None,
+        )
+        .ok();
+        repo.delete(PokemonNumber::pikachu(), PokemonForm::default())
+            .ok();
+
+        let res = repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonForm::default(),
+            PokemonName::charmander(),
+            PokemonTypes::charmander(),
+            None,
+        );
+
+        assert!(res.is_ok());
+        match repo.fetch_one(PokemonNumber::pikachu(), PokemonForm::default()) {
+            Ok(pokemon) => assert_eq!(
+                Vec::<String>::from(pokemon.types),
+                Vec::<String>::from(PokemonTypes::charmander())
+            ),
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_find_and_remove_orphaned_types() {
+        let repo = repo();
+        repo.insert(
+            PokemonNumber::charmander(),
+            PokemonForm::default(),
+            PokemonName::charmander(),
+            PokemonTypes::charmander(),
+            None,
+        )
+        .ok();
+        insert_orphaned_type(&repo);
+
+        assert_eq!(
+            repo.check_integrity().ok(),
+            Some(vec![(25, String::from("Fire"))])
+        );
+        assert_eq!(repo.repair_integrity().ok(), Some(1));
+        assert_eq!(repo.check_integrity().ok(), Some(vec![]));
+        assert!(repo
+            .fetch_one(PokemonNumber::charmander(), PokemonForm::default())
+            .is_ok());
+    }
+
+    #[test]
+    fn it_should_reject_an_offset_sqlite_cannot_bind() {
+        let repo = repo();
+
+        let res = repo.fetch_all(FetchAllQuery {
+            cursor: Some((i64::MAX as u64 + 1).to_string()),
+            ..FetchAllQuery::default()
+        });
+
+        match res {
+            Err(FetchAllError::InvalidCursor) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_keep_the_sqlite_error_as_the_cause() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("missing").join("pokedex.db");
+
+        let res = SqliteRepository::try_new(path.to_str().unwrap());
+
+        match res {
+            Err(e) => {
+                let cause = std::error::Error::source(&e).unwrap();
+                assert!(cause.downcast_ref::<rusqlite::Error>().is_some());
+                let message = format!("cannot open the database {}", path.display());
+                assert_eq!(e.to_string(), message);
+                assert!(e.chain().starts_with(&format!("{}: {}", message, cause)));
+            }
+            _ => unreachable!(),
+        };
+    }
+
+    mod contract {
+        use super::SqliteRepository;
+        use crate::repositories::{
+            contract_tests,
+            pokemon::{contract, Repository},
+        };
+        use tempfile::TempDir;
+
+        fn factory() -> (std::sync::Arc<dyn Repository>, TempDir) {
+            let dir = tempfile::tempdir().expect("failed to create a temporary directory");
+            let path = dir.path().join("pokedex.db");
+            match SqliteRepository::try_new(path.to_str().unwrap()) {
+                Ok(repo) => (std::sync::Arc::new(repo), dir),
+                _ => unreachable!(),
+            }
+        }
+
+        contract_tests!(
+            factory,
+            contract,
+            [
+                it_should_return_the_inserted_pokemon,
+                it_should_refuse_to_insert_an_existing_number,
+                it_should_fetch_an_inserted_pokemon_with_all_its_fields,
+                it_should_not_find_a_missing_pokemon,
+                it_should_update_the_name_and_types,
+                it_should_not_update_a_missing_pokemon,
+                it_should_delete_a_pokemon_and_free_its_number,
+                it_should_not_delete_a_missing_pokemon,
+                it_should_keep_a_form_apart_from_the_default_one,
+                it_should_only_list_the_other_forms_when_asked,
+                it_should_delete_the_other_forms_along_with_the_default_one,
+                it_should_fetch_all_by_ascending_number_by_default,
+                it_should_sort_by_name_or_by_descending_number,
+                it_should_filter_by_type_and_name_prefix,
+                it_should_page_through_every_pokemon_exactly_once,
+                it_should_reject_an_unknown_cursor,
+                it_should_reject_a_cursor_too_large_for_its_page,
+            ]
+        );
+    }
 }
@@ -0,0 +1,501 @@
+use rusqlite::{params, Connection, OptionalExtension};
+
+use crate::repositories::pokemon::RepositoryError;
+
+pub struct Migration {
+    pub version: u32,
+    pub description: &'static str,
+    sql: &'static str,
+}
+
+/// Every schema change, in order. Applied migrations must never be edited,
+/// append a new one instead.
+const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        description: "Create pokemons and types tables",
+        sql: "
+            create table if not exists pokemons (
+                number integer primary key,
+                name text
+            );
+            create table if not exists types (
+                pokemon_number integer,
+                name text,
+                foreign key (pokemon_number) references pokemons (number) on delete cascade,
+                primary key (pokemon_number, name)
+            );
+        ",
+    },
+    Migration {
+        version: 2,
+        description: "Remove types left behind while foreign keys were not enforced",
+        sql: "
+            delete from types where pokemon_number not in (select number from pokemons);
+        ",
+    },
+    Migration {
+        version: 3,
+        description: "Create stats table",
+        sql: "
+            create table if not exists stats (
+                pokemon_number integer primary key,
+                hp integer not null,
+                attack integer not null,
+                defense integer not null,
+                special_attack integer not null,
+                special_defense integer not null,
+                speed integer not null,
+                foreign key (pokemon_number) references pokemons (number) on delete cascade
+            );
+        ",
+    },
+    Migration {
+        version: 4,
+        description: "Create abilities tables",
+        sql: "
+            create table if not exists abilities (
+                name text primary key,
+                description text not null
+            );
+            create table if not exists pokemon_abilities (
+                pokemon_number integer not null,
+                slot integer not null,
+                ability_name text not null,
+                foreign key (pokemon_number) references pokemons (number) on delete cascade,
+                foreign key (ability_name) references abilities (name) on delete cascade,
+                primary key (pokemon_number, slot)
+            );
+        ",
+    },
+    Migration {
+        version: 5,
+        description: "Create moves and learnsets tables",
+        sql: "
+            create table if not exists moves (
+                name text primary key,
+                type text not null,
+                category text not null,
+                power integer,
+                accuracy integer,
+                pp integer not null
+            );
+            create table if not exists learnsets (
+                pokemon_number integer not null,
+                move_name text not null,
+                method text not null,
+                level integer,
+                foreign key (pokemon_number) references pokemons (number) on delete cascade,
+                foreign key (move_name) references moves (name) on delete cascade
+            );
+            create index if not exists learnsets_move_name on learnsets (move_name);
+        ",
+    },
+    Migration {
+        version: 6,
+        description: "Create evolutions table",
+        sql: "
+            create table if not exists evolutions (
+                from_number integer not null,
+                to_number integer primary key,
+                trigger text not null,
+                level integer,
+                item text,
+                foreign key (from_number) references pokemons (number) on delete cascade,
+                foreign key (to_number) references pokemons (number) on delete cascade
+            );
+            create index if not exists evolutions_from_number on evolutions (from_number);
+        ",
+    },
+    Migration {
+        version: 7,
+        description: "Identify pokemons by number and form",
+        // SQLite cannot change a primary key in place: every table referring
+        // to the pokemons is rebuilt and renamed once the previous ones are
+        // dropped. Types and stats belong to a form, the default one being
+        // ''. Abilities, learnsets and evolutions belong to the species: no
+        // foreign key can point at a number alone any more, so a trigger
+        // forgets them along with the default form.
+        sql: "
+            create table pokemons_new (
+                number integer not null,
+                form text not null default '',
+                name text,
+                primary key (number, form)
+            );
+            insert into pokemons_new (number, name) select number, name from pokemons;
+            create table types_new (
+                pokemon_number integer,
+                pokemon_form text not null default '',
+                name text,
+                foreign key (pokemon_number, pokemon_form)
+                    references pokemons (number, form) on delete cascade,
+                primary key (pokemon_number, pokemon_form, name)
+            );
+            insert into types_new (pokemon_number, name)
+                select pokemon_number, name from types order by rowid;
+            create table stats_new (
+                pokemon_number integer not null,
+                pokemon_form text not null default '',
+                hp integer not null,
+                attack integer not null,
+                defense integer not null,
+                special_attack integer not null,
+                special_defense integer not null,
+                speed integer not null,
+                foreign key (pokemon_number, pokemon_form)
+                    references pokemons (number, form) on delete cascade,
+                primary key (pokemon_number, pokemon_form)
+            );
+            insert into stats_new (pokemon_number, hp, attack, defense, special_attack,
+                special_defense, speed)
+                select pokemon_number, hp, attack, defense, special_attack, special_defense, speed
+                from stats;
+            create table pokemon_abilities_new (
+                pokemon_number integer not null,
+                slot integer not null,
+                ability_name text not null,
+                foreign key (ability_name) references abilities (name) on delete cascade,
+                primary key (pokemon_number, slot)
+            );
+            insert into pokemon_abilities_new (pokemon_number, slot, ability_name)
+                select pokemon_number, slot, ability_name from pokemon_abilities;
+            create table learnsets_new (
+                pokemon_number integer not null,
+                move_name text not null,
+                method text not null,
+                level integer,
+                foreign key (move_name) references moves (name) on delete cascade
+            );
+            insert into learnsets_new (pokemon_number, move_name, method, level)
+                select pokemon_number, move_name, method, level from learnsets order by rowid;
+            create table evolutions_new (
+                from_number integer not null,
+                to_number integer primary key,
+                trigger text not null,
+                level integer,
+                item text
+            );
+            insert into evolutions_new (from_number, to_number, trigger, level, item)
+                select from_number, to_number, trigger, level, item from evolutions;
+            drop table evolutions;
+            drop table learnsets;
+            drop table pokemon_abilities;
+            drop table stats;
+            drop table types;
+            drop table pokemons;
+            alter table pokemons_new rename to pokemons;
+            alter table types_new rename to types;
+            alter table stats_new rename to stats;
+            alter table pokemon_abilities_new rename to pokemon_abilities;
+            alter table learnsets_new rename to learnsets;
+            alter table evolutions_new rename to evolutions;
+            create index learnsets_move_name on learnsets (move_name);
+            create index evolutions_from_number on evolutions (from_number);
+            create trigger pokemons_species_deleted after delete on pokemons
+                when old.form = ''
+            begin
+                delete from pokemon_abilities where pokemon_number = old.number;
+                delete from learnsets where pokemon_number = old.number;
+                delete from evolutions where from_number = old.number or to_number = old.number;
+            end;
+        ",
+    },
+];
+
+pub struct MigrationStatus {
+    pub version: u32,
+    pub description: &'static str,
+    pub applied_at: Option<String>,
+}
+
+pub fn latest_version() -> u32 {
+    MIGRATIONS.last().map_or(0, |m| m.version)
+}
+
+/// Applies every pending migration, each in its own transaction, and returns
+/// the ones that were applied.
+pub fn run(connection: &mut Connection) -> Result<Vec<&'static Migration>, RepositoryError> {
+    if let Err(e) = connection.execute(
+        "create table if not exists schema_version (
+                version integer primary key,
+                description text not null,
+                applied_at text not null default current_timestamp
+            )",
+        [],
+    ) {
+        return Err(RepositoryError::caused_by(
+            "cannot create the schema_version table",
+            e,
+        ));
+    }
+    let current = current_version(connection)?;
+    if current > latest_version() {
+        return Err(RepositoryError::new(format!(
+            "the database schema version {} is newer than the supported version {}",
+            current,
+            latest_version()
+        )));
+    }
+
+    let mut applied = vec![];
+    for migration in MIGRATIONS.iter().filter(|m| m.version > current) {
+        let failed = |e| {
+            RepositoryError::caused_by(format!("cannot apply migration {}", migration.version), e)
+        };
+        let transaction = match connection.transaction() {
+            Ok(t) => t,
+            Err(e) => return Err(failed(e)),
+        };
+        if let Err(e) = transaction.execute_batch(migration.sql) {
+            return Err(failed(e));
+        }
+        if let Err(e) = transaction.execute(
+            "insert into schema_version (version, description) values (?, ?)",
+            params![migration.version, migration.description],
+        ) {
+            return Err(failed(e));
+        }
+        if let Err(e) = transaction.commit() {
+            return Err(failed(e));
+        }
+        applied.push(migration);
+    }
+    Ok(applied)
+}
+
+pub fn status(connection: &Connection) -> Result<Vec<MigrationStatus>, RepositoryError> {
+    let mut applied = vec![];
+    if has_version_table(connection)? {
+        let failed = |e| RepositoryError::caused_by("cannot read the applied migrations", e);
+        let mut stat = match connection.prepare("select version, applied_at from schema_version") {
+            Ok(stat) => stat,
+            Err(e) => return Err(failed(e)),
+        };
+        let mut rows = match stat.query([]) {
+            Ok(rows) => rows,
+            Err(e) => return Err(failed(e)),
+        };
+        loop {
+            let row = match rows.next() {
+                Ok(Some(row)) => row,
+                Ok(None) => break,
+                Err(e) => return Err(failed(e)),
+            };
+            match (row.get::<usize, u32>(0), row.get::<usize, String>(1)) {
+                (Ok(version), Ok(applied_at)) => applied.push((version, applied_at)),
+                (Err(e), _) | (_, Err(e)) => return Err(failed(e)),
+            }
+        }
+    }
+    Ok(MIGRATIONS
+        .iter()
+        .map(|m| MigrationStatus {
+            version: m.version,
+            description: m.description,
+            applied_at: applied
+                .iter()
+                .find(|(version, _)| *version == m.version)
+                .map(|(_, applied_at)| applied_at.clone()),
+        })
+        .collect())
+}
+
+fn current_version(connection: &Connection) -> Result<u32, RepositoryError> {
+    match connection.query_row("select max(version) from schema_version", [], |row| {
+        row.get::<usize, Option<u32>>(0)
+    }) {
+        Ok(version) => Ok(version.unwrap_or(0)),
+        Err(e) => Err(RepositoryError::caused_by(
+            "cannot read the schema version",
+            e,
+        )),
+    }
+}
+
+fn has_version_table(connection: &Connection) -> Result<bool, RepositoryError> {
+    match connection
+        .query_row(
+            "select name from sqlite_master where type = 'table' and name = 'schema_version'",
+            [],
+            |row| row.get::<usize, String>(0),
+        )
+        .optional()
+    {
+        Ok(table) => Ok(table.is_some()),
+        Err(e) => Err(RepositoryError::caused_by(
+            "cannot look for the schema_version table",
+            e,
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tables(connection: &Connection) -> Vec<String> {
+        let mut stat = connection
+            .prepare("select name from sqlite_master where type = 'table' order by name")
+            .unwrap();
+        let rows = stat
+            .query_map([], |row| row.get::<usize, String>(0))
+            .unwrap();
+        rows.map(|r| r.unwrap()).collect()
+    }
+
+    #[test]
+    fn it_should_create_the_schema_of_an_empty_database() {
+        let mut connection = Connection::open_in_memory().unwrap();
+
+        let applied = run(&mut connection);
+
+        match applied {
+            Ok(applied) => assert_eq!(applied.len(), MIGRATIONS.len()),
+            _ => unreachable!(),
+        };
+        assert_eq!(
+            tables(&connection),
+            vec![
+                "abilities",
+                "evolutions",
+                "learnsets",
+                "moves",
+                "pokemon_abilities",
+                "pokemons",
+                "schema_version",
+                "stats",
+                "types"
+            ]
+        );
+        assert_eq!(current_version(&connection).ok(), Some(latest_version()));
+    }
+
+    #[test]
+    fn it_should_not_apply_migrations_twice() {
+        let mut connection = Connection::open_in_memory().unwrap();
+        run(&mut connection).ok();
+
+        let applied = run(&mut connection);
+
+        match applied {
+            Ok(applied) => assert!(applied.is_empty()),
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_upgrade_a_database_created_before_versioning() {
+        let mut connection = Connection::open_in_memory().unwrap();
+        connection
+            .execute_batch(
+                "create table pokemons (number integer primary key, name text);
+                create table types (pokemon_number integer, name text, primary key (pokemon_number, name));
+                insert into pokemons values (25, 'Pikachu');",
+            )
+            .unwrap();
+
+        assert!(run(&mut connection).is_ok());
+
+        let count = connection
+            .query_row("select count(*) from pokemons", [], |row| {
+                row.get::<usize, u32>(0)
+            })
+            .unwrap();
+        assert_eq!(count, 1);
+        assert_eq!(current_version(&connection).ok(), Some(latest_version()));
+    }
+
+    #[test]
+    fn it_should_keep_the_pokemons_as_default_forms_when_adding_forms() {
+        let mut connection = Connection::open_in_memory().unwrap();
+        let before_forms = MIGRATIONS
+            .iter()
+            .take_while(|m| m.version < 7)
+            .map(|m| m.sql)
+            .collect::<String>();
+        connection
+            .execute_batch(&format!(
+                "{}
+                insert into pokemons values (25, 'Pikachu');
+                insert into types values (25, 'Electric');
+                insert into stats values (25, 35, 55, 40, 50, 50, 90);
+                insert into abilities values ('Static', 'May paralyze on contact.');
+                insert into pokemon_abilities values (25, 0, 'Static');
+                create table schema_version (
+                    version integer primary key,
+                    description text not null,
+                    applied_at text not null default current_timestamp
+                );
+                insert into schema_version (version, description) values (6, 'before forms');",
+                before_forms
+            ))
+            .unwrap();
+
+        let applied = run(&mut connection);
+
+        match applied {
+            Ok(applied) => assert_eq!(applied.len(), 1),
+            _ => unreachable!(),
+        };
+        let row = connection
+            .query_row(
+                "select p.form, t.name, s.hp, a.ability_name from pokemons p \
+                join types t on t.pokemon_number = p.number and t.pokemon_form = p.form \
+                join stats s on s.pokemon_number = p.number and s.pokemon_form = p.form \
+                join pokemon_abilities a on a.pokemon_number = p.number \
+                where p.number = 25",
+                [],
+                |row| {
+                    Ok((
+                        row.get::<usize, String>(0)?,
+                        row.get::<usize, String>(1)?,
+                        row.get::<usize, u16>(2)?,
+                        row.get::<usize, String>(3)?,
+                    ))
+                },
+            )
+            .unwrap();
+        assert_eq!(
+            row,
+            (
+                String::new(),
+                String::from("Electric"),
+                35,
+                String::from("Static")
+            )
+        );
+        connection
+            .execute(
+                "insert into pokemons (number, form, name) values (25, 'alola', 'Pikachu')",
+                [],
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn it_should_refuse_a_database_from_a_newer_version() {
+        let mut connection = Connection::open_in_memory().unwrap();
+        run(&mut connection).ok();
+        connection
+            .execute(
+                "insert into schema_version (version, description) values (?, 'future')",
+                [latest_version() + 1],
+            )
+            .unwrap();
+
+        assert!(run(&mut connection).is_err());
+    }
+
+    #[test]
+    fn it_should_report_pending_and_applied_migrations() {
+        let mut connection = Connection::open_in_memory().unwrap();
+
+        let before = status(&connection).unwrap();
+        run(&mut connection).ok();
+        let after = status(&connection).unwrap();
+
+        assert!(before.iter().all(|m| m.applied_at.is_none()));
+        assert!(after.iter().all(|m| m.applied_at.is_some()));
+    }
+}